@@ -0,0 +1,13 @@
+CREATE TABLE IF NOT EXISTS watchlists
+(
+    id      INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    name    TEXT NOT NULL UNIQUE
+);
+
+CREATE TABLE IF NOT EXISTS watchlist_coins
+(
+    watchlist_id    INTEGER NOT NULL REFERENCES watchlists (id) ON DELETE CASCADE,
+    coin_id         INTEGER NOT NULL REFERENCES coins (id) ON DELETE CASCADE,
+    position        INTEGER NOT NULL,
+    PRIMARY KEY (watchlist_id, coin_id)
+);
\ No newline at end of file
@@ -0,0 +1,19 @@
+CREATE TABLE IF NOT EXISTS transactions
+(
+    id              INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    exchange        TEXT NOT NULL,
+    trade_id        TEXT NOT NULL,
+    executed_at     TEXT NOT NULL,
+    side            TEXT NOT NULL,
+    base_coin_id    INTEGER NOT NULL,
+    base_symbol     TEXT NOT NULL,
+    quote_coin_id   INTEGER,
+    quote_symbol    TEXT NOT NULL,
+    quantity        REAL NOT NULL,
+    price           REAL NOT NULL,
+    fee             REAL NOT NULL,
+    fee_asset       TEXT NOT NULL,
+    UNIQUE (exchange, trade_id)
+);
+
+CREATE INDEX IF NOT EXISTS transactions_base_coin_id ON transactions (base_coin_id);
\ No newline at end of file
@@ -0,0 +1,12 @@
+CREATE TABLE IF NOT EXISTS candles
+(
+    coin_id     INTEGER NOT NULL REFERENCES coins (id) ON DELETE CASCADE,
+    interval    TEXT NOT NULL,
+    open_time   INTEGER NOT NULL,
+    open        REAL NOT NULL,
+    high        REAL NOT NULL,
+    low         REAL NOT NULL,
+    close       REAL NOT NULL,
+    volume      REAL NOT NULL,
+    PRIMARY KEY (coin_id, interval, open_time)
+);
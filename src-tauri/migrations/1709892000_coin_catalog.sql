@@ -0,0 +1,10 @@
+CREATE TABLE IF NOT EXISTS coin_catalog
+(
+    id      INTEGER PRIMARY KEY NOT NULL,
+    slug    TEXT NOT NULL,
+    symbol  TEXT NOT NULL,
+    name    TEXT NOT NULL,
+    rank    INTEGER
+);
+
+CREATE INDEX IF NOT EXISTS coin_catalog_rank ON coin_catalog (rank);
@@ -0,0 +1,34 @@
+CREATE TABLE IF NOT EXISTS coin_metadata
+(
+    coin_id         INTEGER PRIMARY KEY NOT NULL REFERENCES coins (id) ON DELETE CASCADE,
+    description     TEXT NOT NULL,
+    logo_url        TEXT,
+    logo            BLOB,
+    logo_type       TEXT,
+    fetched_at      TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS coin_links
+(
+    coin_id     INTEGER NOT NULL REFERENCES coins (id) ON DELETE CASCADE,
+    kind        TEXT NOT NULL,
+    url         TEXT NOT NULL,
+    position    INTEGER NOT NULL,
+    PRIMARY KEY (coin_id, kind, url)
+);
+
+CREATE TABLE IF NOT EXISTS coin_tags
+(
+    coin_id     INTEGER NOT NULL REFERENCES coins (id) ON DELETE CASCADE,
+    tag         TEXT NOT NULL,
+    position    INTEGER NOT NULL,
+    PRIMARY KEY (coin_id, tag)
+);
+
+CREATE TABLE IF NOT EXISTS coin_contracts
+(
+    coin_id     INTEGER NOT NULL REFERENCES coins (id) ON DELETE CASCADE,
+    platform    TEXT NOT NULL,
+    address     TEXT NOT NULL,
+    PRIMARY KEY (coin_id, platform, address)
+);
@@ -0,0 +1,13 @@
+CREATE TABLE IF NOT EXISTS provider_cache
+(
+    key         TEXT PRIMARY KEY NOT NULL,
+    body        TEXT NOT NULL,
+    fetched_at  INTEGER NOT NULL,
+    expires_at  INTEGER NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS provider_credits
+(
+    day     TEXT PRIMARY KEY NOT NULL,
+    credits INTEGER NOT NULL DEFAULT 0
+);
@@ -0,0 +1,24 @@
+CREATE TABLE IF NOT EXISTS provider_calls
+(
+    id          INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    called_at   INTEGER NOT NULL,
+    endpoint    TEXT NOT NULL,
+    status      INTEGER,
+    latency_ms  INTEGER NOT NULL,
+    credits     INTEGER NOT NULL DEFAULT 0,
+    error_code  INTEGER
+);
+
+CREATE INDEX IF NOT EXISTS provider_calls_called_at ON provider_calls (called_at);
+
+CREATE TABLE IF NOT EXISTS provider_plan
+(
+    id                      INTEGER PRIMARY KEY NOT NULL CHECK (id = 1),
+    credit_limit_daily      INTEGER,
+    credit_limit_monthly    INTEGER,
+    rate_limit_minute       INTEGER,
+    credits_used_day        INTEGER NOT NULL,
+    credits_used_month      INTEGER NOT NULL,
+    monthly_reset           TEXT,
+    fetched_at              TEXT NOT NULL
+);
@@ -0,0 +1 @@
+ALTER TABLE coins ADD COLUMN sources TEXT NOT NULL DEFAULT '';
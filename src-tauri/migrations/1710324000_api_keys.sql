@@ -0,0 +1,17 @@
+CREATE TABLE IF NOT EXISTS api_keys
+(
+    id              INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    label           TEXT NOT NULL UNIQUE,
+    key             TEXT NOT NULL UNIQUE,
+    plan            TEXT NOT NULL DEFAULT '',
+    monthly_credits INTEGER,
+    priority        INTEGER NOT NULL DEFAULT 0,
+    enabled         INTEGER NOT NULL DEFAULT 1,
+    status          TEXT NOT NULL DEFAULT 'active',
+    status_message  TEXT,
+    available_at    INTEGER,
+    credits_month   TEXT NOT NULL DEFAULT '',
+    credits_used    INTEGER NOT NULL DEFAULT 0,
+    last_used_at    INTEGER,
+    created_at      INTEGER NOT NULL
+);
@@ -0,0 +1,4 @@
+ALTER TABLE coins ADD COLUMN deleted_at INTEGER;
+ALTER TABLE coins ADD COLUMN deletion_id INTEGER;
+
+CREATE INDEX IF NOT EXISTS coins_deleted_at ON coins (deleted_at);
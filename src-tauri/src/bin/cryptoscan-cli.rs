@@ -0,0 +1,3 @@
+fn main() {
+    std::process::exit(cryptoscan_lib::run_cli())
+}
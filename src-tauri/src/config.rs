@@ -1,28 +1,926 @@
-use crate::driven::repository::sqlite::{SQLITE_FILE, SQLITE_LOCAL_PATH};
+pub(crate) mod profile;
+
+use std::{collections::BTreeMap, path::Path, str::FromStr, time::Duration};
+
+use log::LevelFilter;
+use serde::Serialize;
+use thiserror::Error;
+use ts_rs::TS;
+
+use crate::{
+    domain::provider::{
+        aggregation::{parse_sources, AggregationMethod, QuoteSource},
+        api_key::KeySelection,
+    },
+    driven::repository::sqlite::{SQLITE_FILE, SQLITE_LOCAL_PATH},
+};
+
+use self::profile::{validate_name, ProfileConfig, DEFAULT_PROFILE, PROFILES_DIR};
+
+pub(crate) const APP_IDENTIFIER: &str = "net.serhiidubetskyi.cryptoscan";
+
+pub(crate) const CONFIG_FILE: &str = "cryptoscan.toml";
+pub(crate) const CONFIG_FILE_ENV: &str = "CRYPTOSCAN_CONFIG";
+pub(crate) const CONFIG_FILE_FLAG: &str = "--config";
+
+pub(crate) const DEFAULT_PROVIDER_BASE_URL: &str = "https://pro-api.coinmarketcap.com";
+pub(crate) const DEFAULT_PROVIDER_CACHE_TTL_SECS: u64 = 60;
+pub(crate) const DEFAULT_KEY_SELECTION: &str = "priority";
+pub(crate) const DEFAULT_AGGREGATION_METHOD: &str = "median";
+pub(crate) const DEFAULT_AGGREGATION_MAX_DEVIATION_PERCENT: f64 = 5.0;
+pub(crate) const DEFAULT_REFRESH_INTERVAL_SECS: u64 = 300;
+pub(crate) const DEFAULT_OFFLINE_PROBE_INTERVAL_SECS: u64 = 30;
+pub(crate) const DEFAULT_BINANCE_STREAM_URL: &str = "wss://stream.binance.com:9443/ws";
+pub(crate) const DEFAULT_KRAKEN_STREAM_URL: &str = "wss://ws.kraken.com/v2";
+pub(crate) const DEFAULT_STREAM_WRITE_INTERVAL_SECS: u64 = 10;
+pub(crate) const DEFAULT_TRASH_RETENTION_DAYS: u64 = 30;
+pub(crate) const DEFAULT_HTTP_CONNECT_TIMEOUT_SECS: u64 = 10;
+pub(crate) const DEFAULT_HTTP_REQUEST_TIMEOUT_SECS: u64 = 30;
+pub(crate) const DEFAULT_LOG_LEVEL: &str = "info";
+pub(crate) const DEFAULT_SERVER_PORT: u16 = 8420;
+
+/// A configuration value that can be set in the config file (`key`, dotted for tables),
+/// through an environment variable (`env`) or with a command-line flag (`flag`).
+struct ConfigKey {
+    key: &'static str,
+    env: &'static str,
+    flag: &'static str,
+}
+
+const DB_PATH: ConfigKey = ConfigKey {
+    key: "db_path",
+    env: "CRYPTOSCAN_DB_PATH",
+    flag: "--db-path",
+};
+const PROFILE: ConfigKey = ConfigKey {
+    key: "profile",
+    env: "CRYPTOSCAN_PROFILE",
+    flag: "--profile",
+};
+const PROVIDER: ConfigKey = ConfigKey {
+    key: "provider.name",
+    env: "CRYPTOSCAN_PROVIDER",
+    flag: "--provider",
+};
+const PROVIDER_BASE_URL: ConfigKey = ConfigKey {
+    key: "provider.base_url",
+    env: "CRYPTOSCAN_PROVIDER_BASE_URL",
+    flag: "--provider-base-url",
+};
+const PROVIDER_CACHE_TTL: ConfigKey = ConfigKey {
+    key: "provider.cache_ttl",
+    env: "CRYPTOSCAN_PROVIDER_CACHE_TTL",
+    flag: "--provider-cache-ttl",
+};
+const PROVIDER_DAILY_CREDITS: ConfigKey = ConfigKey {
+    key: "provider.daily_credits",
+    env: "CRYPTOSCAN_PROVIDER_DAILY_CREDITS",
+    flag: "--provider-daily-credits",
+};
+const PROVIDER_KEY_SELECTION: ConfigKey = ConfigKey {
+    key: "provider.key_selection",
+    env: "CRYPTOSCAN_PROVIDER_KEY_SELECTION",
+    flag: "--provider-key-selection",
+};
+const AGGREGATION_SOURCES: ConfigKey = ConfigKey {
+    key: "aggregation.sources",
+    env: "CRYPTOSCAN_AGGREGATION_SOURCES",
+    flag: "--aggregation-sources",
+};
+const AGGREGATION_METHOD: ConfigKey = ConfigKey {
+    key: "aggregation.method",
+    env: "CRYPTOSCAN_AGGREGATION_METHOD",
+    flag: "--aggregation-method",
+};
+const AGGREGATION_MAX_DEVIATION: ConfigKey = ConfigKey {
+    key: "aggregation.max_deviation",
+    env: "CRYPTOSCAN_AGGREGATION_MAX_DEVIATION",
+    flag: "--aggregation-max-deviation",
+};
+const REFRESH_INTERVAL: ConfigKey = ConfigKey {
+    key: "refresh_interval",
+    env: "CRYPTOSCAN_REFRESH_INTERVAL",
+    flag: "--refresh-interval",
+};
+const OFFLINE_PROBE_INTERVAL: ConfigKey = ConfigKey {
+    key: "offline.probe_interval",
+    env: "CRYPTOSCAN_OFFLINE_PROBE_INTERVAL",
+    flag: "--offline-probe-interval",
+};
+const STREAM_ENABLED: ConfigKey = ConfigKey {
+    key: "stream.enabled",
+    env: "CRYPTOSCAN_STREAM_ENABLED",
+    flag: "--stream-enabled",
+};
+const STREAM_EXCHANGE: ConfigKey = ConfigKey {
+    key: "stream.exchange",
+    env: "CRYPTOSCAN_STREAM_EXCHANGE",
+    flag: "--stream-exchange",
+};
+const STREAM_URL: ConfigKey = ConfigKey {
+    key: "stream.url",
+    env: "CRYPTOSCAN_STREAM_URL",
+    flag: "--stream-url",
+};
+const STREAM_WRITE_INTERVAL: ConfigKey = ConfigKey {
+    key: "stream.write_interval",
+    env: "CRYPTOSCAN_STREAM_WRITE_INTERVAL",
+    flag: "--stream-write-interval",
+};
+const TRASH_RETENTION_DAYS: ConfigKey = ConfigKey {
+    key: "trash.retention_days",
+    env: "CRYPTOSCAN_TRASH_RETENTION_DAYS",
+    flag: "--trash-retention-days",
+};
+const HTTP_CONNECT_TIMEOUT: ConfigKey = ConfigKey {
+    key: "http.connect_timeout",
+    env: "CRYPTOSCAN_HTTP_CONNECT_TIMEOUT",
+    flag: "--http-connect-timeout",
+};
+const HTTP_REQUEST_TIMEOUT: ConfigKey = ConfigKey {
+    key: "http.request_timeout",
+    env: "CRYPTOSCAN_HTTP_REQUEST_TIMEOUT",
+    flag: "--http-request-timeout",
+};
+const LOG_LEVEL: ConfigKey = ConfigKey {
+    key: "log_level",
+    env: "CRYPTOSCAN_LOG_LEVEL",
+    flag: "--log-level",
+};
+
+const SERVER_ENABLED: ConfigKey = ConfigKey {
+    key: "server.enabled",
+    env: "CRYPTOSCAN_SERVER_ENABLED",
+    flag: "--server-enabled",
+};
+const SERVER_PORT: ConfigKey = ConfigKey {
+    key: "server.port",
+    env: "CRYPTOSCAN_SERVER_PORT",
+    flag: "--server-port",
+};
+const SERVER_TOKEN: ConfigKey = ConfigKey {
+    key: "server.token",
+    env: "CRYPTOSCAN_SERVER_TOKEN",
+    flag: "--server-token",
+};
+
+const CONFIG_KEYS: [&ConfigKey; 23] = [
+    &DB_PATH,
+    &PROFILE,
+    &PROVIDER,
+    &PROVIDER_BASE_URL,
+    &PROVIDER_CACHE_TTL,
+    &PROVIDER_DAILY_CREDITS,
+    &PROVIDER_KEY_SELECTION,
+    &AGGREGATION_SOURCES,
+    &AGGREGATION_METHOD,
+    &AGGREGATION_MAX_DEVIATION,
+    &REFRESH_INTERVAL,
+    &OFFLINE_PROBE_INTERVAL,
+    &STREAM_ENABLED,
+    &STREAM_EXCHANGE,
+    &STREAM_URL,
+    &STREAM_WRITE_INTERVAL,
+    &TRASH_RETENTION_DAYS,
+    &HTTP_CONNECT_TIMEOUT,
+    &HTTP_REQUEST_TIMEOUT,
+    &LOG_LEVEL,
+    &SERVER_ENABLED,
+    &SERVER_PORT,
+    &SERVER_TOKEN,
+];
+
+#[derive(Debug, Error)]
+pub enum ConfigError {
+    #[error("Unable to read config file {0}: {1}")]
+    ReadFile(String, String),
+    #[error("Unable to parse config file {0}: {1}")]
+    ParseFile(String, String),
+    #[error("Invalid value for {0}: {1}")]
+    InvalidValue(String, String),
+    #[error("Missing value for flag {0}")]
+    MissingFlagValue(String),
+    #[error("Unable to resolve the {0} directory")]
+    UnknownDirectory(String),
+}
+
+/// Where the effective value of a configuration key came from, in increasing precedence.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, TS)]
+#[ts(export, export_to = "../src/bindings/")]
+#[serde(rename_all = "snake_case")]
+pub enum ConfigSource {
+    Default,
+    File,
+    Environment,
+    CommandLine,
+}
+
+#[derive(Clone, Debug, Serialize, TS)]
+#[ts(export, export_to = "../src/bindings/")]
+pub struct ConfigEntry {
+    pub key: String,
+    pub value: String,
+    pub source: ConfigSource,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Provider {
+    CoinMarketCap,
+}
+
+impl FromStr for Provider {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_lowercase().as_str() {
+            "coinmarketcap" | "cmc" => Ok(Provider::CoinMarketCap),
+            _ => Err(format!("unknown provider '{}'", value)),
+        }
+    }
+}
+
+/// Exchange whose public WebSocket tickers are streamed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StreamExchange {
+    Binance,
+    Kraken,
+}
+
+impl StreamExchange {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            StreamExchange::Binance => "binance",
+            StreamExchange::Kraken => "kraken",
+        }
+    }
+
+    pub fn default_url(&self) -> &'static str {
+        match self {
+            StreamExchange::Binance => DEFAULT_BINANCE_STREAM_URL,
+            StreamExchange::Kraken => DEFAULT_KRAKEN_STREAM_URL,
+        }
+    }
+}
+
+impl FromStr for StreamExchange {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_lowercase().as_str() {
+            "binance" => Ok(StreamExchange::Binance),
+            "kraken" => Ok(StreamExchange::Kraken),
+            _ => Err(format!("unknown exchange '{}'", value)),
+        }
+    }
+}
 
 pub struct Config {
     pub app_config_dir: String,
+    pub config_file: String,
     pub sqlite: SqtliteConfig,
+    pub profiles: ProfileConfig,
+    /// The profile whose database `sqlite` points to
+    pub profile: String,
+    pub provider: ProviderConfig,
+    pub aggregation: AggregationConfig,
+    pub http: HttpConfig,
+    /// Time between automatic refreshes of the tracked coins; zero disables them
+    pub refresh_interval: Duration,
+    pub offline: OfflineConfig,
+    pub stream: StreamConfig,
+    pub trash: TrashConfig,
+    pub log_level: LevelFilter,
+    pub server: ServerConfig,
+    entries: BTreeMap<&'static str, ConfigEntry>,
 }
 
 pub struct SqtliteConfig {
     pub db_path: String,
 }
 
-impl SqtliteConfig {
-    pub fn from(app_config_dir: String) -> Self {
-        let db_path = format!("{}/{}/{}", app_config_dir, SQLITE_LOCAL_PATH, SQLITE_FILE);
-        Self { db_path }
-    }
+pub struct ProviderConfig {
+    pub provider: Provider,
+    pub base_url: String,
+    /// How long provider responses are reused; zero disables the cache
+    pub cache_ttl: Duration,
+    /// Credits that may be spent per UTC day, unlimited when `None`
+    pub daily_credits: Option<u32>,
+    /// Order in which the keys of the pool are tried
+    pub key_selection: KeySelection,
+}
+
+/// Secondary price sources queried alongside the provider on refresh. Their quotes and the
+/// provider's are combined with `method` after dropping the ones further than
+/// `max_deviation` from the median.
+pub struct AggregationConfig {
+    /// Empty when only the provider is used
+    pub sources: Vec<QuoteSource>,
+    pub method: AggregationMethod,
+    /// A fraction, e.g. `0.05` for the configured `5` percent
+    pub max_deviation: f64,
+}
+
+/// Offline mode: while the provider is unreachable refreshes serve the stored coins and
+/// are queued until a probe finds it reachable again.
+pub struct OfflineConfig {
+    /// Time between reachability probes while offline; zero disables them
+    pub probe_interval: Duration,
+}
+
+/// Live prices from an exchange's WebSocket tickers for the tracked coins' symbols.
+pub struct StreamConfig {
+    pub enabled: bool,
+    pub exchange: StreamExchange,
+    /// The exchange's public endpoint unless configured otherwise
+    pub url: String,
+    /// Least time between two writes of a coin's streamed price to the database
+    pub write_interval: Duration,
+}
+
+/// Deleted coins stay in the trash, restorable, until they are purged.
+pub struct TrashConfig {
+    /// How long a deleted coin is kept; zero purges it on the next purge
+    pub retention: Duration,
+}
+
+pub struct HttpConfig {
+    pub connect_timeout: Duration,
+    pub request_timeout: Duration,
+}
+
+/// The embedded HTTP server, which always binds to localhost.
+pub struct ServerConfig {
+    pub enabled: bool,
+    pub port: u16,
+    pub token: String,
 }
 
 impl Config {
-    pub fn from(app_config_dir: String) -> Self {
-        let sqlite = SqtliteConfig::from(app_config_dir.clone());
+    /// Builds the effective configuration by layering, from lowest to highest precedence,
+    /// the built-in defaults, the TOML config file, `CRYPTOSCAN_*` environment variables
+    /// and command-line flags.
+    pub fn load(app_data_dir: String, app_config_dir: String) -> Result<Self, ConfigError> {
+        let args = std::env::args().skip(1).collect::<Vec<String>>();
 
-        Self {
+        Self::load_layers(app_data_dir, app_config_dir, &args, env_value)
+    }
+
+    /// `load` with the command-line arguments and the environment lookup given.
+    fn load_layers(
+        app_data_dir: String,
+        app_config_dir: String,
+        args: &[String],
+        env_value: impl Fn(&str) -> Option<String>,
+    ) -> Result<Self, ConfigError> {
+        let config_file = flag_value(args, CONFIG_FILE_FLAG)?
+            .or_else(|| env_value(CONFIG_FILE_ENV))
+            .unwrap_or_else(|| join_path(&app_config_dir, &[CONFIG_FILE]));
+
+        let mut entries = BTreeMap::new();
+
+        for (key, value) in defaults(&app_data_dir) {
+            set_entry(&mut entries, key, value, ConfigSource::Default);
+        }
+
+        for (key, value) in read_file(&config_file)? {
+            set_entry(&mut entries, key, value, ConfigSource::File);
+        }
+
+        for config_key in CONFIG_KEYS {
+            if let Some(value) = env_value(config_key.env) {
+                set_entry(
+                    &mut entries,
+                    config_key.key,
+                    value,
+                    ConfigSource::Environment,
+                );
+            }
+        }
+
+        for config_key in CONFIG_KEYS {
+            if let Some(value) = flag_value(args, config_key.flag)? {
+                set_entry(
+                    &mut entries,
+                    config_key.key,
+                    value,
+                    ConfigSource::CommandLine,
+                );
+            }
+        }
+
+        let value = |config_key: &ConfigKey| entries[config_key.key].value.clone();
+
+        let profiles = ProfileConfig {
+            dir: join_path(&app_data_dir, &[PROFILES_DIR]),
+            default_db_path: value(&DB_PATH),
+        };
+        // Without one configured, the profile last switched to is used
+        let profile = match value(&PROFILE).trim() {
+            "" => profiles
+                .remembered()
+                .unwrap_or_else(|| DEFAULT_PROFILE.to_string()),
+            name => {
+                validate_name(name)
+                    .map_err(|e| ConfigError::InvalidValue(PROFILE.key.to_string(), e))?;
+                name.to_string()
+            }
+        };
+
+        Ok(Self {
             app_config_dir,
-            sqlite,
+            config_file,
+            sqlite: SqtliteConfig {
+                db_path: profiles.db_path(&profile),
+            },
+            profiles,
+            profile,
+            provider: ProviderConfig {
+                provider: parse(&PROVIDER, &value(&PROVIDER))?,
+                base_url: value(&PROVIDER_BASE_URL).trim_end_matches('/').to_string(),
+                cache_ttl: Duration::from_secs(parse(
+                    &PROVIDER_CACHE_TTL,
+                    &value(&PROVIDER_CACHE_TTL),
+                )?),
+                daily_credits: match parse(
+                    &PROVIDER_DAILY_CREDITS,
+                    &value(&PROVIDER_DAILY_CREDITS),
+                )? {
+                    0 => None,
+                    credits => Some(credits),
+                },
+                key_selection: parse(&PROVIDER_KEY_SELECTION, &value(&PROVIDER_KEY_SELECTION))?,
+            },
+            aggregation: AggregationConfig {
+                sources: parse_sources(&value(&AGGREGATION_SOURCES))
+                    .map_err(|e| ConfigError::InvalidValue(AGGREGATION_SOURCES.key.to_string(), e))?
+                    .into_iter()
+                    .filter(|source| *source != QuoteSource::CoinMarketCap)
+                    .collect(),
+                method: parse(&AGGREGATION_METHOD, &value(&AGGREGATION_METHOD))?,
+                max_deviation: match parse::<f64>(
+                    &AGGREGATION_MAX_DEVIATION,
+                    &value(&AGGREGATION_MAX_DEVIATION),
+                )? {
+                    percent if percent.is_finite() && percent > 0.0 => percent / 100.0,
+                    _ => {
+                        return Err(ConfigError::InvalidValue(
+                            AGGREGATION_MAX_DEVIATION.key.to_string(),
+                            value(&AGGREGATION_MAX_DEVIATION),
+                        ))
+                    }
+                },
+            },
+            stream: {
+                let exchange: StreamExchange = parse(&STREAM_EXCHANGE, &value(&STREAM_EXCHANGE))?;
+                let url = value(&STREAM_URL);
+
+                StreamConfig {
+                    enabled: parse(&STREAM_ENABLED, &value(&STREAM_ENABLED))?,
+                    exchange,
+                    url: if url.is_empty() {
+                        exchange.default_url().to_string()
+                    } else {
+                        url
+                    },
+                    write_interval: Duration::from_secs(parse(
+                        &STREAM_WRITE_INTERVAL,
+                        &value(&STREAM_WRITE_INTERVAL),
+                    )?),
+                }
+            },
+            trash: TrashConfig {
+                retention: Duration::from_secs(
+                    parse::<u64>(&TRASH_RETENTION_DAYS, &value(&TRASH_RETENTION_DAYS))?
+                        .saturating_mul(24 * 60 * 60),
+                ),
+            },
+            http: HttpConfig {
+                connect_timeout: Duration::from_secs(parse(
+                    &HTTP_CONNECT_TIMEOUT,
+                    &value(&HTTP_CONNECT_TIMEOUT),
+                )?),
+                request_timeout: Duration::from_secs(parse(
+                    &HTTP_REQUEST_TIMEOUT,
+                    &value(&HTTP_REQUEST_TIMEOUT),
+                )?),
+            },
+            refresh_interval: Duration::from_secs(parse(
+                &REFRESH_INTERVAL,
+                &value(&REFRESH_INTERVAL),
+            )?),
+            offline: OfflineConfig {
+                probe_interval: Duration::from_secs(parse(
+                    &OFFLINE_PROBE_INTERVAL,
+                    &value(&OFFLINE_PROBE_INTERVAL),
+                )?),
+            },
+            log_level: parse(&LOG_LEVEL, &value(&LOG_LEVEL))?,
+            server: ServerConfig {
+                enabled: parse(&SERVER_ENABLED, &value(&SERVER_ENABLED))?,
+                port: parse(&SERVER_PORT, &value(&SERVER_PORT))?,
+                token: value(&SERVER_TOKEN),
+            },
+            entries,
+        })
+    }
+
+    /// Loads the configuration outside of a Tauri app, resolving the same app data and
+    /// app config directories the GUI uses so both operate on the same files.
+    pub fn load_standalone() -> Result<Self, ConfigError> {
+        let app_data_dir = dirs::data_dir()
+            .ok_or(ConfigError::UnknownDirectory("data".to_string()))?
+            .join(APP_IDENTIFIER);
+        let app_config_dir = dirs::config_dir()
+            .ok_or(ConfigError::UnknownDirectory("config".to_string()))?
+            .join(APP_IDENTIFIER);
+
+        Self::load(
+            app_data_dir.to_string_lossy().into_owned(),
+            app_config_dir.to_string_lossy().into_owned(),
+        )
+    }
+
+    /// Every configuration key with its effective value and the layer it came from.
+    /// Secrets are masked.
+    pub fn entries(&self) -> Vec<ConfigEntry> {
+        self.entries
+            .values()
+            .cloned()
+            .map(|mut entry| {
+                if entry.key == SERVER_TOKEN.key && !entry.value.is_empty() {
+                    entry.value = "********".to_string();
+                }
+                entry
+            })
+            .collect()
+    }
+}
+
+fn defaults(app_data_dir: &str) -> Vec<(&'static str, String)> {
+    vec![
+        (
+            DB_PATH.key,
+            join_path(app_data_dir, &[SQLITE_LOCAL_PATH, SQLITE_FILE]),
+        ),
+        (PROFILE.key, String::new()),
+        (PROVIDER.key, "coinmarketcap".to_string()),
+        (PROVIDER_BASE_URL.key, DEFAULT_PROVIDER_BASE_URL.to_string()),
+        (
+            PROVIDER_CACHE_TTL.key,
+            DEFAULT_PROVIDER_CACHE_TTL_SECS.to_string(),
+        ),
+        (PROVIDER_DAILY_CREDITS.key, 0.to_string()),
+        (
+            PROVIDER_KEY_SELECTION.key,
+            DEFAULT_KEY_SELECTION.to_string(),
+        ),
+        (AGGREGATION_SOURCES.key, String::new()),
+        (
+            AGGREGATION_METHOD.key,
+            DEFAULT_AGGREGATION_METHOD.to_string(),
+        ),
+        (
+            AGGREGATION_MAX_DEVIATION.key,
+            DEFAULT_AGGREGATION_MAX_DEVIATION_PERCENT.to_string(),
+        ),
+        (
+            REFRESH_INTERVAL.key,
+            DEFAULT_REFRESH_INTERVAL_SECS.to_string(),
+        ),
+        (
+            OFFLINE_PROBE_INTERVAL.key,
+            DEFAULT_OFFLINE_PROBE_INTERVAL_SECS.to_string(),
+        ),
+        (STREAM_ENABLED.key, false.to_string()),
+        (STREAM_EXCHANGE.key, "binance".to_string()),
+        (STREAM_URL.key, String::new()),
+        (
+            STREAM_WRITE_INTERVAL.key,
+            DEFAULT_STREAM_WRITE_INTERVAL_SECS.to_string(),
+        ),
+        (
+            TRASH_RETENTION_DAYS.key,
+            DEFAULT_TRASH_RETENTION_DAYS.to_string(),
+        ),
+        (
+            HTTP_CONNECT_TIMEOUT.key,
+            DEFAULT_HTTP_CONNECT_TIMEOUT_SECS.to_string(),
+        ),
+        (
+            HTTP_REQUEST_TIMEOUT.key,
+            DEFAULT_HTTP_REQUEST_TIMEOUT_SECS.to_string(),
+        ),
+        (LOG_LEVEL.key, DEFAULT_LOG_LEVEL.to_string()),
+        (SERVER_ENABLED.key, false.to_string()),
+        (SERVER_PORT.key, DEFAULT_SERVER_PORT.to_string()),
+        (SERVER_TOKEN.key, String::new()),
+    ]
+}
+
+/// Reads the known keys from the TOML config file. A missing file is not an error.
+fn read_file(path: &str) -> Result<Vec<(&'static str, String)>, ConfigError> {
+    if !Path::new(path).is_file() {
+        return Ok(vec![]);
+    }
+
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| ConfigError::ReadFile(path.to_string(), e.to_string()))?;
+    let table = content
+        .parse::<toml::Table>()
+        .map_err(|e| ConfigError::ParseFile(path.to_string(), e.to_string()))?;
+
+    let mut values = vec![];
+
+    for config_key in CONFIG_KEYS {
+        let mut parts = config_key.key.split('.');
+        let mut value = parts.next().and_then(|part| table.get(part));
+
+        for part in parts {
+            value = value.and_then(|value| value.get(part));
+        }
+
+        let value = match value {
+            None => continue,
+            Some(toml::Value::String(value)) => value.clone(),
+            Some(toml::Value::Integer(value)) => value.to_string(),
+            Some(toml::Value::Float(value)) => value.to_string(),
+            Some(toml::Value::Boolean(value)) => value.to_string(),
+            Some(value) => {
+                return Err(ConfigError::InvalidValue(
+                    config_key.key.to_string(),
+                    value.to_string(),
+                ))
+            }
+        };
+
+        values.push((config_key.key, value));
+    }
+
+    Ok(values)
+}
+
+fn env_value(name: &str) -> Option<String> {
+    std::env::var(name).ok().filter(|value| !value.is_empty())
+}
+
+/// Looks up `--flag <value>` or `--flag=<value>`; the last occurrence wins.
+fn flag_value(args: &[String], flag: &str) -> Result<Option<String>, ConfigError> {
+    let mut result = None;
+    let mut args = args.iter();
+
+    while let Some(arg) = args.next() {
+        if arg == flag {
+            match args.next() {
+                Some(value) if !value.starts_with("--") => result = Some(value.clone()),
+                _ => return Err(ConfigError::MissingFlagValue(flag.to_string())),
+            }
+        } else if let Some(value) = arg
+            .strip_prefix(flag)
+            .and_then(|value| value.strip_prefix('='))
+        {
+            result = Some(value.to_string());
+        }
+    }
+
+    Ok(result)
+}
+
+fn set_entry(
+    entries: &mut BTreeMap<&'static str, ConfigEntry>,
+    key: &'static str,
+    value: String,
+    source: ConfigSource,
+) {
+    entries.insert(
+        key,
+        ConfigEntry {
+            key: key.to_string(),
+            value,
+            source,
+        },
+    );
+}
+
+fn parse<T: FromStr>(config_key: &ConfigKey, value: &str) -> Result<T, ConfigError> {
+    value
+        .trim()
+        .parse()
+        .map_err(|_| ConfigError::InvalidValue(config_key.key.to_string(), value.to_string()))
+}
+
+fn join_path(base: &str, parts: &[&str]) -> String {
+    parts
+        .iter()
+        .fold(Path::new(base).to_path_buf(), |path, part| path.join(part))
+        .to_string_lossy()
+        .into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A fresh directory per test, so tests neither share nor pick up a config file.
+    fn temp_dir(name: &str) -> String {
+        let dir =
+            std::env::temp_dir().join(format!("cryptoscan-config-{}-{}", std::process::id(), name));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+
+        dir.to_string_lossy().into_owned()
+    }
+
+    fn args(values: &[&str]) -> Vec<String> {
+        values.iter().map(|value| value.to_string()).collect()
+    }
+
+    fn load(
+        name: &str,
+        file: Option<&str>,
+        args: &[String],
+        env: &[(&str, &str)],
+    ) -> Result<Config, ConfigError> {
+        let app_data_dir = temp_dir(&format!("{}-data", name));
+        let app_config_dir = temp_dir(&format!("{}-config", name));
+
+        if let Some(content) = file {
+            std::fs::write(join_path(&app_config_dir, &[CONFIG_FILE]), content).unwrap();
+        }
+
+        let env = env
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect::<BTreeMap<String, String>>();
+
+        Config::load_layers(app_data_dir, app_config_dir, args, |name| {
+            env.get(name).cloned()
+        })
+    }
+
+    fn source(config: &Config, key: &str) -> ConfigSource {
+        config.entries[key].source
+    }
+
+    #[test]
+    fn defaults_apply_without_other_layers() {
+        let config = load("defaults", None, &[], &[]).unwrap();
+
+        assert!(config.app_config_dir.ends_with("defaults-config"));
+        assert_eq!(
+            config.config_file,
+            join_path(&config.app_config_dir, &[CONFIG_FILE])
+        );
+        assert_eq!(
+            config.refresh_interval,
+            Duration::from_secs(DEFAULT_REFRESH_INTERVAL_SECS)
+        );
+        assert_eq!(config.server.port, DEFAULT_SERVER_PORT);
+        assert_eq!(config.profile, DEFAULT_PROFILE);
+        assert_eq!(config.log_level, LevelFilter::Info);
+        assert!(config
+            .entries()
+            .iter()
+            .all(|entry| entry.source == ConfigSource::Default));
+    }
+
+    #[test]
+    fn layers_override_in_order() {
+        let file = r#"
+refresh_interval = 60
+log_level = "debug"
+
+[http]
+connect_timeout = 5
+request_timeout = 15
+"#;
+        let config = load(
+            "layers",
+            Some(file),
+            &args(&["--http-request-timeout", "25"]),
+            &[
+                ("CRYPTOSCAN_REFRESH_INTERVAL", "120"),
+                ("CRYPTOSCAN_HTTP_REQUEST_TIMEOUT", "20"),
+            ],
+        )
+        .unwrap();
+
+        assert_eq!(config.http.connect_timeout, Duration::from_secs(5));
+        assert_eq!(
+            source(&config, HTTP_CONNECT_TIMEOUT.key),
+            ConfigSource::File
+        );
+        assert_eq!(config.log_level, LevelFilter::Debug);
+        assert_eq!(source(&config, LOG_LEVEL.key), ConfigSource::File);
+        assert_eq!(config.refresh_interval, Duration::from_secs(120));
+        assert_eq!(
+            source(&config, REFRESH_INTERVAL.key),
+            ConfigSource::Environment
+        );
+        assert_eq!(config.http.request_timeout, Duration::from_secs(25));
+        assert_eq!(
+            source(&config, HTTP_REQUEST_TIMEOUT.key),
+            ConfigSource::CommandLine
+        );
+        assert_eq!(config.provider.base_url, DEFAULT_PROVIDER_BASE_URL);
+        assert_eq!(
+            source(&config, PROVIDER_BASE_URL.key),
+            ConfigSource::Default
+        );
+    }
+
+    #[test]
+    fn config_file_can_be_moved() {
+        let dir = temp_dir("moved");
+        let path = join_path(&dir, &["other.toml"]);
+        std::fs::write(&path, "refresh_interval = 42").unwrap();
+
+        let config = load("moved", None, &args(&["--config", &path]), &[]).unwrap();
+
+        assert_eq!(config.config_file, path);
+        assert_eq!(config.refresh_interval, Duration::from_secs(42));
+    }
+
+    #[test]
+    fn flag_values_are_found() {
+        let values = args(&["--log-level=warn", "--other", "x", "--log-level", "debug"]);
+
+        assert_eq!(
+            flag_value(&values, "--log-level").unwrap(),
+            Some("debug".to_string())
+        );
+        assert_eq!(flag_value(&values, "--log").unwrap(), None);
+        assert_eq!(flag_value(&values, "--db-path").unwrap(), None);
+        assert!(matches!(
+            flag_value(&args(&["--log-level"]), "--log-level"),
+            Err(ConfigError::MissingFlagValue(flag)) if flag == "--log-level"
+        ));
+        assert!(matches!(
+            flag_value(
+                &args(&["--log-level", "--refresh-interval", "60"]),
+                "--log-level"
+            ),
+            Err(ConfigError::MissingFlagValue(_))
+        ));
+    }
+
+    #[test]
+    fn bad_flag_values_are_rejected() {
+        for (flag, value, key) in [
+            ("--refresh-interval", "-1", REFRESH_INTERVAL.key),
+            ("--log-level", "loud", LOG_LEVEL.key),
+            ("--provider", "coingecko", PROVIDER.key),
+            ("--server-port", "http", SERVER_PORT.key),
+            (
+                "--aggregation-max-deviation",
+                "0",
+                AGGREGATION_MAX_DEVIATION.key,
+            ),
+            ("--stream-exchange", "bitstamp", STREAM_EXCHANGE.key),
+            ("--profile", "../other", PROFILE.key),
+        ] {
+            let result = load("bad-flags", None, &args(&[flag, value]), &[]);
+
+            assert!(
+                matches!(&result, Err(ConfigError::InvalidValue(k, _)) if k == key),
+                "{} {}",
+                flag,
+                value
+            );
         }
     }
+
+    #[test]
+    fn bad_file_values_are_rejected() {
+        assert!(matches!(
+            load("bad-file-type", Some("refresh_interval = [1, 2]"), &[], &[]),
+            Err(ConfigError::InvalidValue(key, _)) if key == REFRESH_INTERVAL.key
+        ));
+        assert!(matches!(
+            load("bad-file-value", Some("log_level = \"loud\""), &[], &[]),
+            Err(ConfigError::InvalidValue(key, value)) if key == LOG_LEVEL.key && value == "loud"
+        ));
+        assert!(matches!(
+            load("bad-file-toml", Some("refresh_interval = "), &[], &[]),
+            Err(ConfigError::ParseFile(_, _))
+        ));
+    }
+
+    #[test]
+    fn server_token_is_masked() {
+        let config = load("token", None, &args(&["--server-token", "secret"]), &[]).unwrap();
+        let token = |config: &Config| {
+            config
+                .entries()
+                .into_iter()
+                .find(|entry| entry.key == SERVER_TOKEN.key)
+                .unwrap()
+                .value
+        };
+
+        assert_eq!(config.server.token, "secret");
+        assert_eq!(token(&config), "********");
+        assert_eq!(token(&load("no-token", None, &[], &[]).unwrap()), "");
+    }
 }
@@ -0,0 +1,127 @@
+use std::{
+    path::Path,
+    sync::{Arc, Mutex},
+};
+
+use crate::driven::repository::sqlite::SQLITE_FILE;
+
+pub(crate) const DEFAULT_PROFILE: &str = "default";
+pub(crate) const PROFILES_DIR: &str = "profiles";
+const ACTIVE_PROFILE_FILE: &str = "active";
+const MAX_PROFILE_NAME_LEN: usize = 32;
+
+/// Where the databases of the profiles live. Every profile has its own database, and with
+/// it its own coins, settings and API keys. The `default` profile uses `db_path`, the
+/// others `<dir>/<name>/data.db`.
+pub struct ProfileConfig {
+    pub dir: String,
+    pub default_db_path: String,
+}
+
+impl ProfileConfig {
+    pub fn db_path(&self, name: &str) -> String {
+        if name == DEFAULT_PROFILE {
+            return self.default_db_path.clone();
+        }
+
+        Path::new(&self.dir)
+            .join(name)
+            .join(SQLITE_FILE)
+            .to_string_lossy()
+            .into_owned()
+    }
+
+    /// The default profile first, then the others by name.
+    pub fn names(&self) -> std::io::Result<Vec<String>> {
+        let mut names = match std::fs::read_dir(&self.dir) {
+            Ok(entries) => entries
+                .filter_map(|entry| entry.ok())
+                .filter(|entry| entry.path().is_dir())
+                .filter_map(|entry| entry.file_name().to_str().map(|name| name.to_string()))
+                .filter(|name| validate_name(name).is_ok() && name != DEFAULT_PROFILE)
+                .collect::<Vec<String>>(),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => vec![],
+            Err(e) => return Err(e),
+        };
+        names.sort();
+        names.insert(0, DEFAULT_PROFILE.to_string());
+
+        Ok(names)
+    }
+
+    pub fn exists(&self, name: &str) -> bool {
+        name == DEFAULT_PROFILE || Path::new(&self.dir).join(name).is_dir()
+    }
+
+    /// Creates the directory of a profile; its database is created when it is first opened.
+    pub fn create(&self, name: &str) -> std::io::Result<()> {
+        std::fs::create_dir_all(Path::new(&self.dir).join(name))
+    }
+
+    /// Deletes a profile together with its database.
+    pub fn delete(&self, name: &str) -> std::io::Result<()> {
+        std::fs::remove_dir_all(Path::new(&self.dir).join(name))
+    }
+
+    /// The profile that was active when the app was last used, if it still exists.
+    pub fn remembered(&self) -> Option<String> {
+        std::fs::read_to_string(Path::new(&self.dir).join(ACTIVE_PROFILE_FILE))
+            .ok()
+            .map(|name| name.trim().to_string())
+            .filter(|name| validate_name(name).is_ok() && self.exists(name))
+    }
+
+    pub fn remember(&self, name: &str) -> std::io::Result<()> {
+        std::fs::create_dir_all(&self.dir)?;
+        std::fs::write(Path::new(&self.dir).join(ACTIVE_PROFILE_FILE), name)
+    }
+}
+
+/// Profile names are used as directory names, so they are limited to lowercase letters,
+/// digits, `-` and `_`.
+pub fn validate_name(name: &str) -> Result<(), String> {
+    let valid = !name.is_empty()
+        && name.len() <= MAX_PROFILE_NAME_LEN
+        && name != ACTIVE_PROFILE_FILE
+        && name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_');
+
+    if valid {
+        Ok(())
+    } else {
+        Err(format!(
+            "profile name '{}' must be 1 to {} lowercase letters, digits, '-' or '_'",
+            name, MAX_PROFILE_NAME_LEN
+        ))
+    }
+}
+
+/// The profile in use, replaced when switching. Cloning shares the same state.
+#[derive(Clone)]
+pub struct ActiveProfile {
+    name: Arc<Mutex<String>>,
+}
+
+impl ActiveProfile {
+    pub fn new(name: String) -> Self {
+        Self {
+            name: Arc::new(Mutex::new(name)),
+        }
+    }
+
+    pub fn name(&self) -> String {
+        self.lock().clone()
+    }
+
+    pub fn set(&self, name: String) {
+        *self.lock() = name;
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, String> {
+        // A name is replaced in one step, so it stays consistent even if a holder panicked
+        self.name
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
@@ -0,0 +1,253 @@
+use std::{collections::BTreeSet, sync::Arc};
+
+use tokio::sync::Mutex;
+
+use crate::{
+    domain::candle::candle::{Candle, CandleInterval, CandleRange},
+    driven::repository::{CandleRepository, RepoGetAllError},
+};
+
+use super::statistics;
+
+/// CoinMarketCap id of Bitcoin, the benchmark for betas
+pub const BTC_ID: u32 = 1;
+pub const DEFAULT_CONFIDENCE: f64 = 0.95;
+/// Daily candles in a year; crypto markets trade every day
+pub const DAYS_PER_YEAR: f64 = 365.0;
+/// Most coins one request may cover
+pub const MAX_COINS: usize = 50;
+/// Returns needed for any statistic
+pub const MIN_OBSERVATIONS: usize = 2;
+
+/// A coin of a portfolio and its share of the portfolio value.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Holding {
+    pub coin_id: u32,
+    pub weight: f64,
+}
+
+/// Pairwise correlations of daily returns; `matrix[i][j]` pairs `coin_ids[i]` and
+/// `coin_ids[j]` and is `None` when either coin's price did not move.
+#[derive(Clone, Debug)]
+pub struct CorrelationMatrix {
+    pub coin_ids: Vec<u32>,
+    pub range: CandleRange,
+    /// Daily returns each correlation is computed from
+    pub observations: usize,
+    pub matrix: Vec<Vec<Option<f64>>>,
+}
+
+#[derive(Clone, Debug)]
+pub struct CoinRisk {
+    pub coin_id: u32,
+    /// Weight normalized so that the portfolio sums to 1
+    pub weight: f64,
+    pub volatility: Option<f64>,
+    pub beta: Option<f64>,
+}
+
+/// Risk figures of a portfolio rebalanced daily to its weights. Volatility and the Sharpe
+/// ratio are annualized; Value-at-Risk is a one-day loss as a fraction of the portfolio.
+#[derive(Clone, Debug)]
+pub struct PortfolioRisk {
+    pub range: CandleRange,
+    pub observations: usize,
+    pub confidence: f64,
+    pub risk_free_rate: f64,
+    pub volatility: Option<f64>,
+    /// Beta to BTC; `None` without stored BTC candles
+    pub beta: Option<f64>,
+    pub historical_var: Option<f64>,
+    pub parametric_var: Option<f64>,
+    pub sharpe_ratio: Option<f64>,
+    pub coins: Vec<CoinRisk>,
+}
+
+/// Stored daily candles of every coin over the range, in the order of `coin_ids`.
+pub async fn daily_candles<R: CandleRepository>(
+    repository: Arc<Mutex<R>>,
+    coin_ids: &[u32],
+    range: CandleRange,
+) -> Result<Vec<Vec<Candle>>, RepoGetAllError> {
+    let mut repository = repository.lock().await;
+    let mut series = vec![];
+
+    for coin_id in coin_ids {
+        series.push(
+            repository
+                .get_candles(*coin_id, CandleInterval::Daily, range.start, range.end)
+                .await?,
+        );
+    }
+
+    Ok(series)
+}
+
+/// Closes of every series on the open times all of them have a candle, oldest first. A
+/// return between two such closes may span a day missing from one of the series.
+pub fn align_closes(series: &[Vec<Candle>]) -> (Vec<i64>, Vec<Vec<f64>>) {
+    let Some((first, rest)) = series.split_first() else {
+        return (vec![], vec![]);
+    };
+
+    let common = rest.iter().fold(
+        first
+            .iter()
+            .map(Candle::open_time)
+            .collect::<BTreeSet<i64>>(),
+        |common, candles| {
+            let times = candles
+                .iter()
+                .map(Candle::open_time)
+                .collect::<BTreeSet<i64>>();
+
+            common.intersection(&times).copied().collect()
+        },
+    );
+
+    let closes = series
+        .iter()
+        .map(|candles| {
+            candles
+                .iter()
+                .filter(|candle| common.contains(&candle.open_time()))
+                .map(|candle| candle.ohlcv().close)
+                .collect()
+        })
+        .collect();
+
+    (common.into_iter().collect(), closes)
+}
+
+/// Checks the weights and scales them to sum to 1.
+pub fn normalize_weights(holdings: &[Holding]) -> Result<Vec<Holding>, &'static str> {
+    if holdings
+        .iter()
+        .any(|holding| !holding.weight.is_finite() || holding.weight <= 0.0)
+    {
+        return Err("weights must be greater than 0");
+    }
+
+    let total = holdings.iter().map(|holding| holding.weight).sum::<f64>();
+
+    Ok(holdings
+        .iter()
+        .map(|holding| Holding {
+            coin_id: holding.coin_id,
+            weight: holding.weight / total,
+        })
+        .collect())
+}
+
+/// Checks a list of coin ids: between `min` and `MAX_COINS` distinct, non-zero ids.
+pub fn validate_coin_ids(coin_ids: &[u32], min: usize) -> Result<(), String> {
+    if coin_ids.len() < min || coin_ids.len() > MAX_COINS {
+        return Err(format!(
+            "between {} and {} coins are needed",
+            min, MAX_COINS
+        ));
+    }
+
+    if coin_ids.contains(&0) {
+        return Err("coin ids must be greater than 0".to_string());
+    }
+
+    if coin_ids.iter().collect::<BTreeSet<&u32>>().len() != coin_ids.len() {
+        return Err("coin ids must be distinct".to_string());
+    }
+
+    Ok(())
+}
+
+/// Daily returns of aligned closes, or an error when there are too few to compare.
+pub fn aligned_returns(closes: &[Vec<f64>]) -> Result<Vec<Vec<f64>>, String> {
+    let returns = closes
+        .iter()
+        .map(|closes| statistics::simple_returns(closes))
+        .collect::<Vec<Vec<f64>>>();
+
+    match returns.first() {
+        Some(first) if first.len() >= MIN_OBSERVATIONS => Ok(returns),
+        _ => Err(format!(
+            "at least {} days with a stored candle for every coin are needed",
+            MIN_OBSERVATIONS + 1
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::candle::candle::Ohlcv;
+
+    use super::*;
+
+    const DAY: i64 = 24 * 60 * 60;
+
+    fn candles(coin_id: u32, closes: &[(i64, f64)]) -> Vec<Candle> {
+        closes
+            .iter()
+            .map(|&(day, close)| {
+                let ohlcv = Ohlcv {
+                    open: close,
+                    high: close,
+                    low: close,
+                    close,
+                    volume: 0.0,
+                };
+
+                Candle::new(coin_id, CandleInterval::Daily, day * DAY, ohlcv).unwrap()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn align_closes_keeps_the_common_days() {
+        let (times, closes) = align_closes(&[
+            candles(1, &[(0, 10.0), (1, 11.0), (2, 12.0), (3, 13.0)]),
+            candles(2, &[(1, 21.0), (3, 23.0), (4, 24.0)]),
+        ]);
+
+        assert_eq!(times, vec![DAY, 3 * DAY]);
+        assert_eq!(closes, vec![vec![11.0, 13.0], vec![21.0, 23.0]]);
+    }
+
+    #[test]
+    fn normalize_weights_scales_to_one() {
+        let holdings = normalize_weights(&[
+            Holding {
+                coin_id: 1,
+                weight: 3.0,
+            },
+            Holding {
+                coin_id: 2,
+                weight: 1.0,
+            },
+        ])
+        .unwrap();
+
+        assert_eq!(holdings[0].weight, 0.75);
+        assert_eq!(holdings[1].weight, 0.25);
+        assert!(normalize_weights(&[Holding {
+            coin_id: 1,
+            weight: 0.0
+        }])
+        .is_err());
+    }
+
+    #[test]
+    fn validate_coin_ids_rejects_duplicates() {
+        assert!(validate_coin_ids(&[1, 1027], 2).is_ok());
+        assert!(validate_coin_ids(&[1], 2).is_err());
+        assert!(validate_coin_ids(&[1, 1], 2).is_err());
+        assert!(validate_coin_ids(&[0, 1], 2).is_err());
+    }
+
+    #[test]
+    fn aligned_returns_needs_two_returns() {
+        assert!(aligned_returns(&[vec![1.0, 2.0]]).is_err());
+        assert_eq!(
+            aligned_returns(&[vec![1.0, 2.0, 1.0]]).unwrap(),
+            vec![vec![1.0, -0.5]]
+        );
+    }
+}
@@ -0,0 +1,50 @@
+use std::sync::Arc;
+
+use thiserror::Error;
+use tokio::sync::Mutex;
+
+use crate::{
+    domain::candle::candle::CandleRange,
+    driven::repository::{CandleRepository, RepoGetAllError},
+};
+
+use super::{
+    analytics::{
+        align_closes, aligned_returns, daily_candles, validate_coin_ids, CorrelationMatrix,
+    },
+    statistics::correlation_matrix,
+};
+
+#[derive(Debug, Error)]
+pub enum GetCorrelationsError {
+    #[error("Invalid data: {0}")]
+    InvalidData(String),
+    #[error("Unknown error: {0}")]
+    Unknown(String),
+}
+
+/// Correlates the daily returns of the coins over the days in `range` every coin has a
+/// stored candle for.
+pub async fn get_correlations<R: CandleRepository>(
+    repository: Arc<Mutex<R>>,
+    coin_ids: Vec<u32>,
+    range: CandleRange,
+) -> Result<CorrelationMatrix, GetCorrelationsError> {
+    validate_coin_ids(&coin_ids, 2).map_err(GetCorrelationsError::InvalidData)?;
+
+    let series = daily_candles(repository, &coin_ids, range)
+        .await
+        .map_err(|e| match e {
+            RepoGetAllError::Unknown(e) => GetCorrelationsError::Unknown(e),
+        })?;
+
+    let (_, closes) = align_closes(&series);
+    let returns = aligned_returns(&closes).map_err(GetCorrelationsError::InvalidData)?;
+
+    Ok(CorrelationMatrix {
+        coin_ids,
+        range,
+        observations: returns[0].len(),
+        matrix: correlation_matrix(&returns),
+    })
+}
@@ -0,0 +1,116 @@
+use std::sync::Arc;
+
+use thiserror::Error;
+use tokio::sync::Mutex;
+
+use crate::{
+    domain::candle::candle::CandleRange,
+    driven::repository::{CandleRepository, RepoGetAllError},
+};
+
+use super::{
+    analytics::{
+        align_closes, aligned_returns, daily_candles, normalize_weights, validate_coin_ids,
+        CoinRisk, Holding, PortfolioRisk, BTC_ID, DAYS_PER_YEAR,
+    },
+    statistics,
+};
+
+#[derive(Debug, Error)]
+pub enum GetPortfolioRiskError {
+    #[error("Invalid data: {0}")]
+    InvalidData(String),
+    #[error("Unknown error: {0}")]
+    Unknown(String),
+}
+
+/// Computes the risk of a portfolio from the stored daily candles of its coins. When BTC
+/// candles are stored, only days that have one are used so that betas compare the same days.
+pub async fn get_portfolio_risk<R: CandleRepository>(
+    repository: Arc<Mutex<R>>,
+    holdings: Vec<Holding>,
+    range: CandleRange,
+    confidence: f64,
+    risk_free_rate: f64,
+) -> Result<PortfolioRisk, GetPortfolioRiskError> {
+    let coin_ids = holdings
+        .iter()
+        .map(|holding| holding.coin_id)
+        .collect::<Vec<u32>>();
+    validate_coin_ids(&coin_ids, 1).map_err(GetPortfolioRiskError::InvalidData)?;
+
+    if !(0.5..1.0).contains(&confidence) {
+        return Err(GetPortfolioRiskError::InvalidData(
+            "confidence must be at least 0.5 and less than 1".to_string(),
+        ));
+    }
+
+    if !risk_free_rate.is_finite() {
+        return Err(GetPortfolioRiskError::InvalidData(
+            "risk_free_rate must be a number".to_string(),
+        ));
+    }
+
+    let holdings = normalize_weights(&holdings)
+        .map_err(|e| GetPortfolioRiskError::InvalidData(e.to_string()))?;
+
+    let mut series = daily_candles(repository.clone(), &coin_ids, range)
+        .await
+        .map_err(|e| match e {
+            RepoGetAllError::Unknown(e) => GetPortfolioRiskError::Unknown(e),
+        })?;
+
+    // BTC is the benchmark even when it is not held
+    let benchmark = match coin_ids.iter().position(|id| *id == BTC_ID) {
+        Some(index) => Some(index),
+        None => {
+            let btc = daily_candles(repository, &[BTC_ID], range)
+                .await
+                .map_err(|e| match e {
+                    RepoGetAllError::Unknown(e) => GetPortfolioRiskError::Unknown(e),
+                })?
+                .remove(0);
+
+            if btc.is_empty() {
+                None
+            } else {
+                series.push(btc);
+                Some(series.len() - 1)
+            }
+        }
+    };
+
+    let (_, closes) = align_closes(&series);
+    let returns = aligned_returns(&closes).map_err(GetPortfolioRiskError::InvalidData)?;
+    let benchmark = benchmark.map(|index| &returns[index]);
+
+    let weights = holdings
+        .iter()
+        .map(|holding| holding.weight)
+        .collect::<Vec<f64>>();
+    let portfolio = statistics::portfolio_returns(&returns[..holdings.len()], &weights);
+
+    let coins = holdings
+        .iter()
+        .zip(&returns)
+        .map(|(holding, returns)| CoinRisk {
+            coin_id: holding.coin_id,
+            weight: holding.weight,
+            volatility: statistics::annualized_volatility(returns, DAYS_PER_YEAR),
+            beta: benchmark.and_then(|benchmark| statistics::beta(returns, benchmark)),
+        })
+        .collect();
+
+    Ok(PortfolioRisk {
+        range,
+        observations: portfolio.len(),
+        confidence,
+        risk_free_rate,
+        volatility: statistics::annualized_volatility(&portfolio, DAYS_PER_YEAR),
+        beta: benchmark.and_then(|benchmark| statistics::beta(&portfolio, benchmark)),
+        historical_var: statistics::historical_var(&portfolio, confidence),
+        parametric_var: statistics::parametric_var(&portfolio, confidence),
+        sharpe_ratio: statistics::sharpe_ratio(&portfolio, risk_free_rate, DAYS_PER_YEAR),
+        coins,
+    })
+}
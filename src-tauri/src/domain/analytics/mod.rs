@@ -0,0 +1,4 @@
+pub(crate) mod analytics;
+pub(crate) mod get_correlations;
+pub(crate) mod get_portfolio_risk;
+pub(crate) mod statistics;
@@ -0,0 +1,302 @@
+//! Return statistics used by the risk analytics. Returns are simple period returns, e.g.
+//! `0.01` for a 1% gain, and series compared with each other must be aligned.
+
+/// Deviations below this are treated as a constant series, whose mean is rarely exact.
+const MIN_DEVIATION: f64 = 1e-12;
+
+/// Simple return of each value over the previous one; one shorter than `values`.
+pub fn simple_returns(values: &[f64]) -> Vec<f64> {
+    values
+        .windows(2)
+        .map(|pair| pair[1] / pair[0] - 1.0)
+        .collect()
+}
+
+pub fn mean(values: &[f64]) -> Option<f64> {
+    if values.is_empty() {
+        return None;
+    }
+
+    Some(values.iter().sum::<f64>() / values.len() as f64)
+}
+
+/// Sample covariance; `None` with fewer than two pairs.
+pub fn covariance(a: &[f64], b: &[f64]) -> Option<f64> {
+    let len = a.len().min(b.len());
+
+    if len < 2 {
+        return None;
+    }
+
+    let (a, b) = (&a[..len], &b[..len]);
+    let (mean_a, mean_b) = (mean(a)?, mean(b)?);
+
+    Some(
+        a.iter()
+            .zip(b)
+            .map(|(a, b)| (a - mean_a) * (b - mean_b))
+            .sum::<f64>()
+            / (len - 1) as f64,
+    )
+}
+
+/// Sample standard deviation; `None` with fewer than two values.
+pub fn std_dev(values: &[f64]) -> Option<f64> {
+    covariance(values, values).map(f64::sqrt)
+}
+
+/// Pearson correlation; `None` when either series is constant.
+pub fn correlation(a: &[f64], b: &[f64]) -> Option<f64> {
+    let (deviation_a, deviation_b) = (std_dev(a)?, std_dev(b)?);
+
+    if deviation_a < MIN_DEVIATION || deviation_b < MIN_DEVIATION {
+        return None;
+    }
+
+    Some((covariance(a, b)? / (deviation_a * deviation_b)).clamp(-1.0, 1.0))
+}
+
+/// Correlation of every pair of series; the diagonal is 1 unless a series is constant.
+pub fn correlation_matrix(series: &[Vec<f64>]) -> Vec<Vec<Option<f64>>> {
+    series
+        .iter()
+        .map(|a| series.iter().map(|b| correlation(a, b)).collect())
+        .collect()
+}
+
+/// Returns of a portfolio rebalanced to `weights` every period.
+pub fn portfolio_returns(series: &[Vec<f64>], weights: &[f64]) -> Vec<f64> {
+    let len = series.iter().map(Vec::len).min().unwrap_or(0);
+
+    (0..len)
+        .map(|index| {
+            series
+                .iter()
+                .zip(weights)
+                .map(|(returns, weight)| returns[index] * weight)
+                .sum()
+        })
+        .collect()
+}
+
+/// Standard deviation of the returns scaled by the square root of `periods_per_year`.
+pub fn annualized_volatility(returns: &[f64], periods_per_year: f64) -> Option<f64> {
+    Some(std_dev(returns)? * periods_per_year.sqrt())
+}
+
+/// Sensitivity of `returns` to `benchmark`: their covariance over the benchmark variance.
+pub fn beta(returns: &[f64], benchmark: &[f64]) -> Option<f64> {
+    let variance = covariance(benchmark, benchmark)?;
+
+    if variance < MIN_DEVIATION * MIN_DEVIATION {
+        return None;
+    }
+
+    Some(covariance(returns, benchmark)? / variance)
+}
+
+/// One-period Value-at-Risk from the empirical distribution: the loss, as a positive
+/// fraction, that was exceeded in at most `1 - confidence` of the periods.
+pub fn historical_var(returns: &[f64], confidence: f64) -> Option<f64> {
+    if returns.is_empty() {
+        return None;
+    }
+
+    let mut sorted = returns.to_vec();
+    sorted.sort_by(f64::total_cmp);
+
+    // Nudged so that e.g. 10% of 10 returns is one despite rounding in `1.0 - 0.9`
+    let index = ((1.0 - confidence) * sorted.len() as f64 + 1e-9).floor() as usize;
+
+    Some(-sorted[index.min(sorted.len() - 1)])
+}
+
+/// One-period Value-at-Risk assuming normally distributed returns.
+pub fn parametric_var(returns: &[f64], confidence: f64) -> Option<f64> {
+    Some(-(mean(returns)? + normal_quantile(1.0 - confidence) * std_dev(returns)?))
+}
+
+/// Annualized Sharpe ratio of the returns over an annual `risk_free_rate`; `None` when the
+/// returns do not vary.
+pub fn sharpe_ratio(returns: &[f64], risk_free_rate: f64, periods_per_year: f64) -> Option<f64> {
+    let deviation = std_dev(returns)?;
+
+    if deviation < MIN_DEVIATION {
+        return None;
+    }
+
+    let excess = mean(returns)? - risk_free_rate / periods_per_year;
+
+    Some(excess / deviation * periods_per_year.sqrt())
+}
+
+/// Inverse of the standard normal distribution function, for `0 < p < 1`, with Acklam's
+/// rational approximation (relative error below 1.15e-9).
+pub fn normal_quantile(p: f64) -> f64 {
+    const A: [f64; 6] = [
+        -3.969683028665376e1,
+        2.209460984245205e2,
+        -2.759285104469687e2,
+        1.38357751867269e2,
+        -3.066479806614716e1,
+        2.506628277459239,
+    ];
+    const B: [f64; 5] = [
+        -5.447609879822406e1,
+        1.615858368580409e2,
+        -1.556989798598866e2,
+        6.680131188771972e1,
+        -1.328068155288572e1,
+    ];
+    const C: [f64; 6] = [
+        -7.784894002430293e-3,
+        -3.223964580411365e-1,
+        -2.400758277161838,
+        -2.549732539343734,
+        4.374664141464968,
+        2.938163982698783,
+    ];
+    const D: [f64; 4] = [
+        7.784695709041462e-3,
+        3.224671290700398e-1,
+        2.445134137142996,
+        3.754408661907416,
+    ];
+    const LOW: f64 = 0.02425;
+
+    let tail = |q: f64| {
+        (((((C[0] * q + C[1]) * q + C[2]) * q + C[3]) * q + C[4]) * q + C[5])
+            / ((((D[0] * q + D[1]) * q + D[2]) * q + D[3]) * q + 1.0)
+    };
+
+    if p < LOW {
+        tail((-2.0 * p.ln()).sqrt())
+    } else if p > 1.0 - LOW {
+        -tail((-2.0 * (1.0 - p).ln()).sqrt())
+    } else {
+        let q = p - 0.5;
+        let r = q * q;
+
+        (((((A[0] * r + A[1]) * r + A[2]) * r + A[3]) * r + A[4]) * r + A[5]) * q
+            / (((((B[0] * r + B[1]) * r + B[2]) * r + B[3]) * r + B[4]) * r + 1.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::test_support::assert_close;
+
+    use super::*;
+
+    const EPSILON: f64 = 1e-9;
+
+    /// Daily returns of two made-up coins over ten days
+    const RETURNS_A: [f64; 10] = [
+        0.02, -0.01, 0.03, -0.02, 0.01, 0.00, -0.03, 0.04, -0.01, 0.02,
+    ];
+    const RETURNS_B: [f64; 10] = [
+        0.01, -0.02, 0.02, -0.01, 0.00, 0.01, -0.02, 0.03, -0.02, 0.01,
+    ];
+
+    #[test]
+    fn simple_returns_are_relative_changes() {
+        let returns = simple_returns(&[100.0, 110.0, 99.0]);
+
+        assert_close(Some(returns[0]), 0.1, EPSILON);
+        assert_close(Some(returns[1]), -0.1, EPSILON);
+    }
+
+    #[test]
+    fn std_dev_uses_the_sample_estimator() {
+        assert_close(
+            std_dev(&[2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0]),
+            2.138089935,
+            1e-9,
+        );
+        assert_eq!(std_dev(&[1.0]), None);
+    }
+
+    #[test]
+    fn correlation_matrix_is_symmetric_with_a_unit_diagonal() {
+        let matrix = correlation_matrix(&[RETURNS_A.to_vec(), RETURNS_B.to_vec()]);
+
+        assert_close(matrix[0][0], 1.0, EPSILON);
+        assert_close(matrix[1][1], 1.0, EPSILON);
+        assert_close(matrix[0][1], 0.913_838_412, 1e-6);
+        assert_eq!(matrix[0][1], matrix[1][0]);
+    }
+
+    #[test]
+    fn correlation_of_opposite_and_constant_series() {
+        let negated = RETURNS_A.iter().map(|r| -r).collect::<Vec<f64>>();
+
+        assert_close(correlation(&RETURNS_A, &negated), -1.0, EPSILON);
+        assert_eq!(correlation(&RETURNS_A, &[0.01; 10]), None);
+    }
+
+    #[test]
+    fn portfolio_returns_are_weighted_sums() {
+        let returns = portfolio_returns(&[RETURNS_A.to_vec(), RETURNS_B.to_vec()], &[0.5, 0.5]);
+
+        assert_close(Some(returns[0]), 0.015, EPSILON);
+        assert_close(Some(returns[1]), -0.015, EPSILON);
+        assert_eq!(returns.len(), 10);
+    }
+
+    #[test]
+    fn volatility_is_annualized() {
+        let daily = std_dev(&RETURNS_A).unwrap();
+
+        assert_close(
+            annualized_volatility(&RETURNS_A, 365.0),
+            daily * 365.0_f64.sqrt(),
+            EPSILON,
+        );
+    }
+
+    #[test]
+    fn beta_of_a_leveraged_series_is_its_leverage() {
+        let leveraged = RETURNS_B
+            .iter()
+            .map(|r| 2.0 * r + 0.001)
+            .collect::<Vec<f64>>();
+
+        assert_close(beta(&leveraged, &RETURNS_B), 2.0, EPSILON);
+        assert_close(beta(&RETURNS_B, &RETURNS_B), 1.0, EPSILON);
+    }
+
+    #[test]
+    fn historical_var_takes_the_empirical_quantile() {
+        let returns = (1..=100)
+            .map(|i| i as f64 / 1000.0 - 0.05)
+            .collect::<Vec<f64>>();
+
+        // The 6th lowest of -0.049..0.05
+        assert_close(historical_var(&returns, 0.95), 0.044, EPSILON);
+        assert_close(historical_var(&RETURNS_A, 0.9), 0.02, EPSILON);
+    }
+
+    #[test]
+    fn parametric_var_uses_the_normal_quantile() {
+        let expected = -(mean(&RETURNS_A).unwrap() - 1.644_853_627 * std_dev(&RETURNS_A).unwrap());
+
+        assert_close(parametric_var(&RETURNS_A, 0.95), expected, 1e-8);
+    }
+
+    #[test]
+    fn normal_quantile_matches_tables() {
+        assert_close(Some(normal_quantile(0.5)), 0.0, EPSILON);
+        assert_close(Some(normal_quantile(0.05)), -1.644_853_627, 1e-8);
+        assert_close(Some(normal_quantile(0.01)), -2.326_347_874, 1e-8);
+        assert_close(Some(normal_quantile(0.975)), 1.959_963_985, 1e-8);
+    }
+
+    #[test]
+    fn sharpe_ratio_subtracts_the_risk_free_rate() {
+        let expected = mean(&RETURNS_A).unwrap() / std_dev(&RETURNS_A).unwrap() * 365.0_f64.sqrt();
+
+        assert_close(sharpe_ratio(&RETURNS_A, 0.0, 365.0), expected, EPSILON);
+        assert!(sharpe_ratio(&RETURNS_A, 0.05, 365.0).unwrap() < expected);
+        assert_eq!(sharpe_ratio(&[0.01; 5], 0.0, 365.0), None);
+    }
+}
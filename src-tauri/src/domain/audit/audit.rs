@@ -0,0 +1,298 @@
+use std::{fmt, str::FromStr};
+
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use ts_rs::TS;
+use utoipa::ToSchema;
+
+use crate::{
+    domain::{
+        coin::coin::Coin, provider::api_key::mask_key, settings::settings::Setting,
+        transfer::export_data::SECRET_SETTINGS, Value,
+    },
+    driven::repository::AuditRepository,
+};
+
+/// Records returned by a query of the audit log unless a limit is given.
+pub const DEFAULT_AUDIT_LIMIT: u32 = 100;
+pub const MAX_AUDIT_LIMIT: u32 = 1000;
+
+/// The adapter a change came through.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize, ToSchema, TS)]
+#[ts(export, export_to = "../src/bindings/")]
+#[serde(rename_all = "snake_case")]
+pub enum Origin {
+    /// A Tauri command of the desktop app
+    Desktop,
+    Http,
+    Cli,
+    /// Work the app does on its own, e.g. resuming refreshes once the provider is reachable
+    Background,
+}
+
+impl Origin {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Origin::Desktop => "desktop",
+            Origin::Http => "http",
+            Origin::Cli => "cli",
+            Origin::Background => "background",
+        }
+    }
+}
+
+impl fmt::Display for Origin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Origin {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_lowercase().as_str() {
+            "desktop" => Ok(Origin::Desktop),
+            "http" => Ok(Origin::Http),
+            "cli" => Ok(Origin::Cli),
+            "background" => Ok(Origin::Background),
+            _ => Err(format!("Unknown origin {}", value)),
+        }
+    }
+}
+
+/// The kind of entity an audit record is about.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize, ToSchema, TS)]
+#[ts(export, export_to = "../src/bindings/")]
+#[serde(rename_all = "snake_case")]
+pub enum AuditEntity {
+    Coin,
+    Setting,
+}
+
+impl AuditEntity {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AuditEntity::Coin => "coin",
+            AuditEntity::Setting => "setting",
+        }
+    }
+}
+
+impl fmt::Display for AuditEntity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for AuditEntity {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_lowercase().as_str() {
+            "coin" => Ok(AuditEntity::Coin),
+            "setting" => Ok(AuditEntity::Setting),
+            _ => Err(format!("Unknown audit entity {}", value)),
+        }
+    }
+}
+
+/// One change of a coin or setting. `before` and `after` are JSON snapshots of the entity,
+/// missing when it did not exist before or no longer exists after the change.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AuditRecord {
+    pub id: u32,
+    /// Unix seconds
+    pub recorded_at: i64,
+    /// The use case that made the change, e.g. `delete_coin`
+    pub operation: String,
+    pub entity: AuditEntity,
+    pub entity_id: String,
+    pub before: Option<String>,
+    pub after: Option<String>,
+    pub origin: Origin,
+}
+
+impl AuditRecord {
+    pub fn new(
+        origin: Origin,
+        operation: &str,
+        entity: AuditEntity,
+        entity_id: String,
+        before: Option<String>,
+        after: Option<String>,
+    ) -> Self {
+        Self {
+            id: 0,
+            recorded_at: chrono::Utc::now().timestamp(),
+            operation: operation.to_string(),
+            entity,
+            entity_id,
+            before,
+            after,
+            origin,
+        }
+    }
+
+    pub fn coin(
+        origin: Origin,
+        operation: &str,
+        before: Option<&Coin>,
+        after: Option<&Coin>,
+    ) -> Self {
+        let id = before
+            .or(after)
+            .map(|coin| *coin.id().value())
+            .unwrap_or_default();
+
+        Self::new(
+            origin,
+            operation,
+            AuditEntity::Coin,
+            id.to_string(),
+            before.map(coin_snapshot),
+            after.map(coin_snapshot),
+        )
+    }
+
+    pub fn setting(
+        origin: Origin,
+        operation: &str,
+        before: Option<&Setting>,
+        after: Option<&Setting>,
+    ) -> Self {
+        let key = before
+            .or(after)
+            .map(|setting| setting.key().value().clone())
+            .unwrap_or_default();
+
+        Self::new(
+            origin,
+            operation,
+            AuditEntity::Setting,
+            key,
+            before.map(setting_snapshot),
+            after.map(setting_snapshot),
+        )
+    }
+}
+
+/// Which records to return, newest first.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AuditFilter {
+    pub entity: Option<AuditEntity>,
+    pub entity_id: Option<String>,
+    /// Unix seconds, inclusive
+    pub start: Option<i64>,
+    /// Unix seconds, inclusive
+    pub end: Option<i64>,
+    pub limit: u32,
+}
+
+/// The stored fields of a coin; derived ones like its age are left out.
+fn coin_snapshot(coin: &Coin) -> String {
+    json!({
+        "id": coin.id().value(),
+        "name": coin.name().value(),
+        "symbol": coin.symbol().value(),
+        "price": coin.price().value(),
+        "volume_24h": coin.volume_24h().value(),
+        "percent_change_1h": coin.percent_change_1h().value(),
+        "percent_change_24h": coin.percent_change_24h().value(),
+        "percent_change_7d": coin.percent_change_7d().value(),
+        "market_cap": coin.market_cap().value(),
+        "last_updated": coin.last_updated().value(),
+        "sources": coin.sources().to_string(),
+    })
+    .to_string()
+}
+
+/// Secret settings like the API key are masked, the log is not meant to hold them.
+fn setting_snapshot(setting: &Setting) -> String {
+    let key = setting.key().value();
+    let value = if SECRET_SETTINGS.contains(&key.as_str()) {
+        mask_key(setting.value().value())
+    } else {
+        setting.value().value().clone()
+    };
+
+    json!({ "key": key, "value": value }).to_string()
+}
+
+/// Appends the record to the audit log. A failure is logged rather than returned, the
+/// change it describes is already stored by then.
+pub(crate) async fn append<R: AuditRepository>(repository: &mut R, record: AuditRecord) {
+    if let Err(e) = repository.append_audit(record.clone()).await {
+        log::warn!(
+            "Could not audit {} of {} {}: {}",
+            record.operation,
+            record.entity,
+            record.entity_id,
+            e
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn masks_secret_settings() {
+        let key = Setting::new("api_key".to_string(), "abcdef123456".to_string()).unwrap();
+        let theme = Setting::new("theme".to_string(), "dark".to_string()).unwrap();
+
+        let record = AuditRecord::setting(Origin::Cli, "create_setting", None, Some(&key));
+        assert_eq!(record.entity_id, "api_key");
+        assert_eq!(record.before, None);
+        assert_eq!(
+            record.after.as_deref(),
+            Some(r#"{"key":"api_key","value":"****3456"}"#)
+        );
+
+        let record = AuditRecord::setting(Origin::Http, "create_setting", Some(&theme), None);
+        assert_eq!(
+            record.before.as_deref(),
+            Some(r#"{"key":"theme","value":"dark"}"#)
+        );
+    }
+
+    #[test]
+    fn takes_the_id_of_either_snapshot() {
+        let coin = Coin::new(
+            1,
+            "Bitcoin".to_string(),
+            "BTC".to_string(),
+            100.0,
+            10.0,
+            0.1,
+            0.2,
+            0.3,
+            1000.0,
+            "2024-03-01T00:00:00Z".to_string(),
+        )
+        .unwrap();
+
+        let deleted = AuditRecord::coin(Origin::Desktop, "delete_coin", Some(&coin), None);
+        assert_eq!(deleted.entity, AuditEntity::Coin);
+        assert_eq!(deleted.entity_id, "1");
+        assert!(deleted.before.unwrap().contains(r#""symbol":"BTC""#));
+        assert_eq!(deleted.after, None);
+    }
+
+    #[test]
+    fn parses_what_it_stores() {
+        for origin in [
+            Origin::Desktop,
+            Origin::Http,
+            Origin::Cli,
+            Origin::Background,
+        ] {
+            assert_eq!(origin.as_str().parse::<Origin>(), Ok(origin));
+        }
+        for entity in [AuditEntity::Coin, AuditEntity::Setting] {
+            assert_eq!(entity.as_str().parse::<AuditEntity>(), Ok(entity));
+        }
+        assert!("system".parse::<Origin>().is_err());
+    }
+}
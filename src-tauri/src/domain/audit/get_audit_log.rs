@@ -0,0 +1,46 @@
+use std::sync::Arc;
+
+use thiserror::Error;
+use tokio::sync::Mutex;
+
+use crate::driven::repository::{AuditRepository, RepoGetAllError};
+
+use super::audit::{AuditFilter, AuditRecord, MAX_AUDIT_LIMIT};
+
+#[derive(Debug, Error)]
+pub enum GetAuditLogError {
+    #[error("Invalid data: {0}")]
+    InvalidData(String),
+    #[error("Unknown error: {0}")]
+    Unknown(String),
+}
+
+/// Returns the changes matching `filter`, newest first.
+pub async fn get_audit_log<R: AuditRepository>(
+    repository: Arc<Mutex<R>>,
+    filter: AuditFilter,
+) -> Result<Vec<AuditRecord>, GetAuditLogError> {
+    if filter.limit == 0 || filter.limit > MAX_AUDIT_LIMIT {
+        return Err(GetAuditLogError::InvalidData(format!(
+            "limit must be between 1 and {}",
+            MAX_AUDIT_LIMIT
+        )));
+    }
+
+    if let (Some(start), Some(end)) = (filter.start, filter.end) {
+        if start > end {
+            return Err(GetAuditLogError::InvalidData(
+                "start must not be after end".to_string(),
+            ));
+        }
+    }
+
+    repository
+        .lock()
+        .await
+        .find_audit(filter)
+        .await
+        .map_err(|e| match e {
+            RepoGetAllError::Unknown(e) => GetAuditLogError::Unknown(e),
+        })
+}
@@ -0,0 +1,2 @@
+pub(crate) mod audit;
+pub(crate) mod get_audit_log;
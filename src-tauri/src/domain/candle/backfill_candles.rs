@@ -0,0 +1,96 @@
+use std::sync::Arc;
+
+use thiserror::Error;
+use tokio::sync::Mutex;
+
+use crate::{
+    config::Config,
+    domain::coin::coin::Coin,
+    driven::repository::{
+        CandleRepository, ProviderRepository, RepoCreateError, RepoFindOneError, Repository,
+    },
+    fetch::{coinmarketcap::fetch_ohlcv, FetchError},
+};
+
+use super::{
+    candle::{CandleInterval, CandleRange, CandleSeries},
+    get_candles::{get_candles, GetCandlesError},
+};
+
+#[derive(Debug, Error)]
+pub enum BackfillError {
+    #[error("Fetch error: {0}")]
+    Fetch(#[from] FetchError),
+    #[error("Invalid data: {0}")]
+    InvalidData(String),
+    #[error("Coin not found")]
+    CoinNotFound,
+    #[error("Unknown error: {0}")]
+    Unknown(String),
+}
+
+impl From<GetCandlesError> for BackfillError {
+    fn from(e: GetCandlesError) -> Self {
+        match e {
+            GetCandlesError::Unknown(e) => BackfillError::Unknown(e),
+        }
+    }
+}
+
+/// Fetches the candles missing over `range` from the provider, one request per gap, and
+/// returns the series as stored afterwards. Ranges the provider has no data for, e.g.
+/// before a coin was listed, stay in `CandleSeries::gaps` and are requested again on the
+/// next backfill.
+pub async fn backfill_candles<R>(
+    repository: Arc<Mutex<R>>,
+    config: &Config,
+    token: String,
+    coin_id: u32,
+    interval: CandleInterval,
+    range: CandleRange,
+) -> Result<CandleSeries, BackfillError>
+where
+    R: Repository<Coin, u32> + CandleRepository + ProviderRepository,
+{
+    repository
+        .lock()
+        .await
+        .find_one(coin_id)
+        .await
+        .map_err(|e| match e {
+            RepoFindOneError::NotFound => BackfillError::CoinNotFound,
+            RepoFindOneError::Unknown(e) => BackfillError::Unknown(e),
+        })?;
+
+    let series = get_candles(repository.clone(), coin_id, interval, range).await?;
+
+    for gap in series.gaps {
+        let fetched = fetch_ohlcv(
+            repository.clone(),
+            config,
+            coin_id,
+            interval,
+            gap,
+            token.clone(),
+        )
+        .await;
+
+        let candles = match fetched {
+            Ok(candles) => candles,
+            Err(FetchError::NotFound) => continue,
+            Err(e) => return Err(BackfillError::Fetch(e)),
+        };
+
+        repository
+            .lock()
+            .await
+            .upsert_candles(candles)
+            .await
+            .map_err(|e| match e {
+                RepoCreateError::InvalidData(e) => BackfillError::InvalidData(e),
+                RepoCreateError::Unknown(e) => BackfillError::Unknown(e),
+            })?;
+    }
+
+    Ok(get_candles(repository, coin_id, interval, range).await?)
+}
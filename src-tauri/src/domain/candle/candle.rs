@@ -0,0 +1,364 @@
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+use utoipa::ToSchema;
+
+use crate::domain::{coin::coin::CoinId, Entity, Value};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize, ToSchema, TS)]
+#[ts(export, export_to = "../src/bindings/")]
+#[serde(rename_all = "snake_case")]
+pub enum CandleInterval {
+    Hourly,
+    Daily,
+}
+
+impl CandleInterval {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CandleInterval::Hourly => "hourly",
+            CandleInterval::Daily => "daily",
+        }
+    }
+
+    /// Length of one candle in seconds
+    pub fn seconds(&self) -> i64 {
+        match self {
+            CandleInterval::Hourly => 60 * 60,
+            CandleInterval::Daily => 24 * 60 * 60,
+        }
+    }
+
+    /// Start of the candle containing `time`
+    pub fn align(&self, time: i64) -> i64 {
+        time - time.rem_euclid(self.seconds())
+    }
+}
+
+impl FromStr for CandleInterval {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_lowercase().as_str() {
+            "hourly" | "1h" => Ok(CandleInterval::Hourly),
+            "daily" | "1d" => Ok(CandleInterval::Daily),
+            _ => Err(format!("Unknown candle interval {}", value)),
+        }
+    }
+}
+
+/// An inclusive range of candle open times, in Unix seconds.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, ToSchema, TS)]
+#[ts(export, export_to = "../src/bindings/")]
+pub struct CandleRange {
+    #[ts(type = "number")]
+    pub start: i64,
+    #[ts(type = "number")]
+    pub end: i64,
+}
+
+/// Prices of one candle in USD, and the traded volume.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Ohlcv {
+    pub open: f64,
+    pub high: f64,
+    pub low: f64,
+    pub close: f64,
+    pub volume: f64,
+}
+
+/// One OHLCV candle of a coin. `open_time` is in Unix seconds and aligned to the interval.
+#[derive(Clone, Debug)]
+pub struct Candle {
+    coin_id: CoinId,
+    interval: CandleInterval,
+    open_time: i64,
+    ohlcv: Ohlcv,
+}
+
+impl Entity for Candle {}
+
+impl Candle {
+    pub fn new(
+        coin_id: u32,
+        interval: CandleInterval,
+        open_time: i64,
+        ohlcv: Ohlcv,
+    ) -> Result<Self, &'static str> {
+        if interval.align(open_time) != open_time {
+            return Err("Candle open time must be aligned to its interval");
+        }
+
+        let Ohlcv {
+            open,
+            high,
+            low,
+            close,
+            volume,
+        } = ohlcv;
+
+        if [open, high, low, close, volume]
+            .iter()
+            .any(|value| !value.is_finite() || *value < 0.0)
+        {
+            return Err("Candle prices and volume must be greater than or equal to 0");
+        }
+
+        if low > high {
+            return Err("Candle low must not be greater than high");
+        }
+
+        Ok(Self {
+            coin_id: CoinId::try_from(coin_id)?,
+            interval,
+            open_time,
+            ohlcv,
+        })
+    }
+
+    pub fn coin_id(&self) -> u32 {
+        *self.coin_id.value()
+    }
+
+    pub fn interval(&self) -> CandleInterval {
+        self.interval
+    }
+
+    pub fn open_time(&self) -> i64 {
+        self.open_time
+    }
+
+    pub fn ohlcv(&self) -> &Ohlcv {
+        &self.ohlcv
+    }
+}
+
+/// Returns the ranges of `range` not covered by `open_times`, which must be sorted.
+pub fn gaps(open_times: &[i64], interval: CandleInterval, range: CandleRange) -> Vec<CandleRange> {
+    let step = interval.seconds();
+    let mut gaps = vec![];
+    let mut expected = range.start;
+
+    for &open_time in open_times
+        .iter()
+        .filter(|&&time| time >= range.start && time <= range.end)
+    {
+        if open_time > expected {
+            gaps.push(CandleRange {
+                start: expected,
+                end: open_time - step,
+            });
+        }
+
+        expected = expected.max(open_time + step);
+    }
+
+    if expected <= range.end {
+        gaps.push(CandleRange {
+            start: expected,
+            end: range.end,
+        });
+    }
+
+    gaps
+}
+
+/// Candles returned when a request gives no start
+pub(crate) const DEFAULT_CANDLES: i64 = 90;
+/// Most candles one request may cover
+pub(crate) const MAX_CANDLES: i64 = 10_000;
+
+/// Stored candles of a coin over a range, with the parts of the range that have none.
+#[derive(Clone, Debug)]
+pub struct CandleSeries {
+    pub coin_id: u32,
+    pub interval: CandleInterval,
+    pub range: CandleRange,
+    pub candles: Vec<Candle>,
+    pub gaps: Vec<CandleRange>,
+}
+
+impl CandleSeries {
+    pub fn new(
+        coin_id: u32,
+        interval: CandleInterval,
+        range: CandleRange,
+        candles: Vec<Candle>,
+    ) -> Self {
+        let open_times = candles
+            .iter()
+            .map(|candle| candle.open_time())
+            .collect::<Vec<i64>>();
+
+        Self {
+            coin_id,
+            interval,
+            range,
+            gaps: gaps(&open_times, interval, range),
+            candles,
+        }
+    }
+}
+
+/// Aligns a requested range to the interval and clamps it to the last closed candle before
+/// `now`. Without `end` the range ends at that candle; without `start` it covers
+/// `DEFAULT_CANDLES` candles.
+pub fn resolve_range(
+    interval: CandleInterval,
+    start: Option<i64>,
+    end: Option<i64>,
+    now: i64,
+) -> Result<CandleRange, &'static str> {
+    let step = interval.seconds();
+    let last_closed = interval.align(now) - step;
+
+    let end = end
+        .map(|end| interval.align(end))
+        .unwrap_or(last_closed)
+        .min(last_closed);
+    let start = start
+        .map(|start| interval.align(start + step - 1))
+        .unwrap_or(end - (DEFAULT_CANDLES - 1) * step);
+
+    if start > end {
+        return Err("start must be before end and the last closed candle");
+    }
+
+    if (end - start) / step + 1 > MAX_CANDLES {
+        return Err("range must not cover more than 10000 candles");
+    }
+
+    Ok(CandleRange { start, end })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HOUR: i64 = 60 * 60;
+    const DAY: i64 = 24 * HOUR;
+    /// 2024-03-09 16:00 UTC, on an hour boundary
+    const T: i64 = 1_710_000_000;
+
+    fn range(start: i64, end: i64) -> CandleRange {
+        CandleRange { start, end }
+    }
+
+    fn hourly_gaps(open_times: &[i64]) -> Vec<CandleRange> {
+        gaps(open_times, CandleInterval::Hourly, range(T, T + 5 * HOUR))
+    }
+
+    #[test]
+    fn covered_ranges_have_no_gaps() {
+        let open_times = (0..6).map(|i| T + i * HOUR).collect::<Vec<i64>>();
+
+        assert!(hourly_gaps(&open_times).is_empty());
+    }
+
+    #[test]
+    fn gaps_are_found() {
+        assert_eq!(
+            hourly_gaps(&[T, T + HOUR, T + 4 * HOUR, T + 5 * HOUR]),
+            vec![range(T + 2 * HOUR, T + 3 * HOUR)]
+        );
+        assert_eq!(
+            hourly_gaps(&[T + 2 * HOUR, T + 3 * HOUR, T + 4 * HOUR, T + 5 * HOUR]),
+            vec![range(T, T + HOUR)]
+        );
+        assert_eq!(
+            hourly_gaps(&[T, T + HOUR]),
+            vec![range(T + 2 * HOUR, T + 5 * HOUR)]
+        );
+        assert_eq!(
+            hourly_gaps(&[T + HOUR, T + 3 * HOUR]),
+            vec![
+                range(T, T),
+                range(T + 2 * HOUR, T + 2 * HOUR),
+                range(T + 4 * HOUR, T + 5 * HOUR),
+            ]
+        );
+    }
+
+    #[test]
+    fn candles_outside_the_range_are_ignored() {
+        assert_eq!(hourly_gaps(&[]), vec![range(T, T + 5 * HOUR)]);
+        assert_eq!(
+            hourly_gaps(&[T - HOUR, T + 6 * HOUR]),
+            vec![range(T, T + 5 * HOUR)]
+        );
+    }
+
+    #[test]
+    fn ranges_are_aligned_to_the_interval() {
+        let now = T + 30 * 60;
+
+        // The start rounds up to the next candle, the end down to the one it falls in
+        assert_eq!(
+            resolve_range(
+                CandleInterval::Hourly,
+                Some(T - 10 * HOUR + 1),
+                Some(T - 3 * HOUR + 59 * 60),
+                now
+            ),
+            Ok(range(T - 9 * HOUR, T - 3 * HOUR))
+        );
+        assert_eq!(
+            resolve_range(CandleInterval::Hourly, Some(T - 9 * HOUR), None, now),
+            Ok(range(T - 9 * HOUR, T - HOUR))
+        );
+    }
+
+    #[test]
+    fn ranges_end_at_the_last_closed_candle() {
+        let now = T + 30 * 60;
+
+        assert_eq!(
+            resolve_range(
+                CandleInterval::Hourly,
+                Some(T - 2 * HOUR),
+                Some(T + 5 * HOUR),
+                now
+            ),
+            Ok(range(T - 2 * HOUR, T - HOUR))
+        );
+        assert_eq!(
+            resolve_range(CandleInterval::Hourly, None, None, now),
+            Ok(range(T - DEFAULT_CANDLES * HOUR, T - HOUR))
+        );
+
+        let today = CandleInterval::Daily.align(T);
+        assert_eq!(
+            resolve_range(CandleInterval::Daily, None, None, T),
+            Ok(range(today - DEFAULT_CANDLES * DAY, today - DAY))
+        );
+    }
+
+    #[test]
+    fn empty_and_oversized_ranges_are_rejected() {
+        let now = T + 30 * 60;
+
+        assert!(resolve_range(CandleInterval::Hourly, Some(T), None, now).is_err());
+        assert!(resolve_range(
+            CandleInterval::Hourly,
+            Some(T - 2 * HOUR),
+            Some(T - 3 * HOUR),
+            now
+        )
+        .is_err());
+        assert!(resolve_range(
+            CandleInterval::Hourly,
+            Some(T - (MAX_CANDLES + 1) * HOUR),
+            None,
+            now
+        )
+        .is_err());
+        assert!(resolve_range(
+            CandleInterval::Hourly,
+            Some(T - MAX_CANDLES * HOUR),
+            None,
+            now
+        )
+        .is_ok());
+    }
+}
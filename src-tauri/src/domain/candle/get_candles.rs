@@ -0,0 +1,34 @@
+use std::sync::Arc;
+
+use thiserror::Error;
+use tokio::sync::Mutex;
+
+use crate::driven::repository::{CandleRepository, RepoGetAllError};
+
+use super::candle::{CandleInterval, CandleRange, CandleSeries};
+
+#[derive(Debug, Error)]
+pub enum GetCandlesError {
+    #[error("Unknown error: {0}")]
+    Unknown(String),
+}
+
+/// Returns the stored candles over `range` without calling the provider; missing candles
+/// are listed in `CandleSeries::gaps`.
+pub async fn get_candles<R: CandleRepository>(
+    repository: Arc<Mutex<R>>,
+    coin_id: u32,
+    interval: CandleInterval,
+    range: CandleRange,
+) -> Result<CandleSeries, GetCandlesError> {
+    let candles = repository
+        .lock()
+        .await
+        .get_candles(coin_id, interval, range.start, range.end)
+        .await
+        .map_err(|e| match e {
+            RepoGetAllError::Unknown(e) => GetCandlesError::Unknown(e),
+        })?;
+
+    Ok(CandleSeries::new(coin_id, interval, range, candles))
+}
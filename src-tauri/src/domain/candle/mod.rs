@@ -0,0 +1,3 @@
+pub(crate) mod backfill_candles;
+pub(crate) mod candle;
+pub(crate) mod get_candles;
@@ -0,0 +1,85 @@
+use std::sync::Arc;
+
+use thiserror::Error;
+use tokio::sync::Mutex;
+
+use crate::{
+    config::Config,
+    domain::{
+        audit::audit::Origin,
+        coin::{
+            coin::Coin,
+            create_coin::{create_coin, CreateError},
+        },
+        events::EventBus,
+    },
+    driven::repository::{
+        AuditRepository, CatalogRepository, ProviderRepository, RepoCreateError, Repository,
+    },
+    driving::tauri::coins::CreateCoinRequest,
+    fetch::{coinmarketcap::fetch_listings, FetchError},
+};
+
+use super::catalog::MAX_TOP_COINS;
+
+#[derive(Debug, Error)]
+pub enum AddTopCoinsError {
+    #[error("Fetch error: {0}")]
+    Fetch(#[from] FetchError),
+    #[error("Invalid data: {0}")]
+    InvalidData(String),
+    #[error("Create error: {0}")]
+    Create(#[from] CreateError),
+    #[error("Unknown error: {0}")]
+    Unknown(String),
+}
+
+/// Starts tracking the top `limit` coins by market cap with their latest quotes. Coins
+/// already tracked get their quotes updated; the listed coins are added to the catalog too.
+pub async fn add_top_coins<R>(
+    repository: Arc<Mutex<R>>,
+    events: &EventBus,
+    origin: Origin,
+    config: &Config,
+    token: String,
+    limit: u32,
+) -> Result<Vec<Coin>, AddTopCoinsError>
+where
+    R: Repository<Coin, u32> + CatalogRepository + ProviderRepository + AuditRepository,
+{
+    if limit == 0 || limit > MAX_TOP_COINS {
+        return Err(AddTopCoinsError::InvalidData(format!(
+            "limit must be between 1 and {}",
+            MAX_TOP_COINS
+        )));
+    }
+
+    let listings = fetch_listings(repository.clone(), config, limit, token).await?;
+
+    repository
+        .lock()
+        .await
+        .upsert_catalog(listings.iter().map(|(entry, _)| entry.clone()).collect())
+        .await
+        .map_err(|e| match e {
+            RepoCreateError::InvalidData(e) | RepoCreateError::Unknown(e) => {
+                AddTopCoinsError::Unknown(e)
+            }
+        })?;
+
+    let mut coins = vec![];
+
+    for (_, quote) in listings {
+        coins.push(
+            create_coin(
+                repository.clone(),
+                events,
+                origin,
+                CreateCoinRequest::from(quote),
+            )
+            .await?,
+        );
+    }
+
+    Ok(coins)
+}
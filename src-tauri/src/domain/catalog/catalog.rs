@@ -0,0 +1,68 @@
+use crate::domain::{coin::coin::CoinId, Entity, Value};
+
+/// A coin listed by the provider, tracked or not. `rank` is the provider's market cap rank
+/// and is missing for coins without one.
+#[derive(Clone, Debug)]
+pub struct CatalogEntry {
+    id: CoinId,
+    slug: String,
+    symbol: String,
+    name: String,
+    rank: Option<u32>,
+}
+
+impl Entity for CatalogEntry {}
+
+impl CatalogEntry {
+    pub fn new(
+        id: u32,
+        slug: String,
+        symbol: String,
+        name: String,
+        rank: Option<u32>,
+    ) -> Result<Self, &'static str> {
+        if symbol.trim().is_empty() || name.trim().is_empty() {
+            return Err("Catalog entries must have a symbol and a name");
+        }
+
+        Ok(Self {
+            id: CoinId::try_from(id)?,
+            slug,
+            symbol,
+            name,
+            rank: rank.filter(|rank| *rank > 0),
+        })
+    }
+
+    pub fn id(&self) -> u32 {
+        *self.id.value()
+    }
+
+    pub fn slug(&self) -> &str {
+        &self.slug
+    }
+
+    pub fn symbol(&self) -> &str {
+        &self.symbol
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn rank(&self) -> Option<u32> {
+        self.rank
+    }
+}
+
+/// A catalog search result and whether the coin is already tracked.
+#[derive(Clone, Debug)]
+pub struct CatalogMatch {
+    pub entry: CatalogEntry,
+    pub tracked: bool,
+}
+
+/// Results returned when a search gives no limit
+pub(crate) const DEFAULT_SEARCH_LIMIT: u32 = 20;
+/// Most coins `add_top_coins` adds at once, one page of the provider's listings
+pub(crate) const MAX_TOP_COINS: u32 = 5000;
@@ -0,0 +1,4 @@
+pub(crate) mod add_top_coins;
+pub(crate) mod catalog;
+pub(crate) mod refresh_catalog;
+pub(crate) mod search_catalog;
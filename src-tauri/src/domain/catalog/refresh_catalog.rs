@@ -0,0 +1,42 @@
+use std::sync::Arc;
+
+use thiserror::Error;
+use tokio::sync::Mutex;
+
+use crate::{
+    config::Config,
+    driven::repository::{CatalogRepository, ProviderRepository, RepoCreateError},
+    fetch::{coinmarketcap::fetch_map, FetchError},
+};
+
+#[derive(Debug, Error)]
+pub enum RefreshCatalogError {
+    #[error("Fetch error: {0}")]
+    Fetch(#[from] FetchError),
+    #[error("Unknown error: {0}")]
+    Unknown(String),
+}
+
+/// Replaces the catalog with the provider's current list of active coins and returns the
+/// number of coins stored.
+pub async fn refresh_catalog<R: CatalogRepository + ProviderRepository>(
+    repository: Arc<Mutex<R>>,
+    config: &Config,
+    token: String,
+) -> Result<usize, RefreshCatalogError> {
+    let entries = fetch_map(repository.clone(), config, token).await?;
+    let count = entries.len();
+
+    repository
+        .lock()
+        .await
+        .replace_catalog(entries)
+        .await
+        .map_err(|e| match e {
+            RepoCreateError::InvalidData(e) | RepoCreateError::Unknown(e) => {
+                RefreshCatalogError::Unknown(e)
+            }
+        })?;
+
+    Ok(count)
+}
@@ -0,0 +1,39 @@
+use std::sync::Arc;
+
+use thiserror::Error;
+use tokio::sync::Mutex;
+
+use crate::driven::repository::{CatalogRepository, RepoGetAllError};
+
+use super::catalog::CatalogMatch;
+
+#[derive(Debug, Error)]
+pub enum SearchCatalogError {
+    #[error("Invalid data: {0}")]
+    InvalidData(String),
+    #[error("Unknown error: {0}")]
+    Unknown(String),
+}
+
+/// Searches the stored catalog by symbol, name or slug without calling the provider. The
+/// catalog is empty until `refresh_catalog` or `add_top_coins` has run.
+pub async fn search_catalog<R: CatalogRepository>(
+    repository: Arc<Mutex<R>>,
+    query: String,
+    limit: u32,
+) -> Result<Vec<CatalogMatch>, SearchCatalogError> {
+    if query.trim().is_empty() {
+        return Err(SearchCatalogError::InvalidData(
+            "query must not be empty".to_string(),
+        ));
+    }
+
+    repository
+        .lock()
+        .await
+        .search_catalog(query, limit)
+        .await
+        .map_err(|e| match e {
+            RepoGetAllError::Unknown(e) => SearchCatalogError::Unknown(e),
+        })
+}
@@ -3,10 +3,17 @@ use core::fmt;
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    domain::{Entity, Value},
+    domain::{
+        provider::aggregation::{join_sources, QuoteSource},
+        Entity, Value,
+    },
     driving::tauri::coins::{CoinResponse, CreateCoinRequest},
 };
 
+/// Quotes older than this are flagged stale; at the default refresh interval that is a few
+/// missed refreshes.
+pub const STALE_AFTER_SECS: u32 = 15 * 60;
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct CoinId(u32);
 
@@ -249,6 +256,56 @@ impl TryFrom<String> for CoinLastUpdated {
     }
 }
 
+/// The sources whose prices went into the quote; empty until the coin is first refreshed.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CoinSources(Vec<QuoteSource>);
+
+impl Value for CoinSources {
+    type ValueType = Vec<QuoteSource>;
+
+    fn value(&self) -> &Self::ValueType {
+        &self.0
+    }
+}
+
+impl From<Vec<QuoteSource>> for CoinSources {
+    fn from(value: Vec<QuoteSource>) -> Self {
+        Self(value)
+    }
+}
+
+impl CoinSources {
+    /// Reads the comma-separated form, skipping sources this version does not know.
+    pub fn parse(value: &str) -> Self {
+        Self(
+            value
+                .split(',')
+                .filter_map(|source| source.parse().ok())
+                .collect(),
+        )
+    }
+}
+
+impl fmt::Display for CoinSources {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&join_sources(&self.0))
+    }
+}
+
+/// Seconds between a quote's `last_updated` and `now`, `None` when it is not an RFC 3339
+/// timestamp. Quotes from the future, i.e. clock skew, are 0 seconds old.
+pub fn quote_age(last_updated: &str, now: i64) -> Option<u32> {
+    let updated = chrono::DateTime::parse_from_rfc3339(last_updated).ok()?;
+
+    Some(u32::try_from((now - updated.timestamp()).max(0)).unwrap_or(u32::MAX))
+}
+
+/// Whether a quote of `age` seconds is stale; an unknown age counts as stale.
+pub fn is_stale(age: Option<u32>) -> bool {
+    age.is_none_or(|age| age > STALE_AFTER_SECS)
+}
+
+#[derive(Clone)]
 pub struct Coin {
     id: CoinId,
     name: CoinName,
@@ -260,6 +317,7 @@ pub struct Coin {
     percent_change_7d: CoinPercentChange7d,
     market_cap: CoinMarketCap,
     last_updated: CoinLastUpdated,
+    sources: CoinSources,
 }
 
 impl Entity for Coin {}
@@ -288,9 +346,23 @@ impl Coin {
             percent_change_7d: CoinPercentChange7d::try_from(percent_change_7d)?,
             market_cap: CoinMarketCap::try_from(market_cap)?,
             last_updated: CoinLastUpdated::try_from(last_updated)?,
+            sources: CoinSources::default(),
         })
     }
 
+    pub fn with_sources(mut self, sources: CoinSources) -> Self {
+        self.sources = sources;
+        self
+    }
+
+    /// The coin at a newer price, e.g. a streamed one; the other quote fields are kept until
+    /// the next refresh.
+    pub fn with_price(mut self, price: f64, last_updated: String) -> Result<Self, &'static str> {
+        self.price = CoinPrice::try_from(price)?;
+        self.last_updated = CoinLastUpdated::try_from(last_updated)?;
+        Ok(self)
+    }
+
     pub fn id(&self) -> &CoinId {
         &self.id
     }
@@ -330,6 +402,10 @@ impl Coin {
     pub fn last_updated(&self) -> &CoinLastUpdated {
         &self.last_updated
     }
+
+    pub fn sources(&self) -> &CoinSources {
+        &self.sources
+    }
 }
 
 impl fmt::Display for Coin {
@@ -344,6 +420,16 @@ impl fmt::Display for Coin {
     }
 }
 
+/// A coin in the trash: deleted, no longer tracked, but restorable until it is purged.
+#[derive(Clone)]
+pub struct DeletedCoin {
+    pub coin: Coin,
+    /// Unix seconds
+    pub deleted_at: i64,
+    /// Increases with every delete; coins deleted together share it
+    pub deletion_id: i64,
+}
+
 impl From<CreateCoinRequest> for Coin {
     fn from(coin: CreateCoinRequest) -> Self {
         Coin {
@@ -366,6 +452,7 @@ impl From<CreateCoinRequest> for Coin {
             .unwrap(),
             market_cap: CoinMarketCap::try_from(coin.market_cap.unwrap_or_default()).unwrap(),
             last_updated: CoinLastUpdated::try_from(coin.last_updated).unwrap(),
+            sources: CoinSources::default(),
         }
     }
 }
@@ -383,6 +470,7 @@ impl From<CoinResponse> for Coin {
             percent_change_7d: CoinPercentChange7d::try_from(coin.percent_change_7d).unwrap(),
             market_cap: CoinMarketCap::try_from(coin.market_cap).unwrap(),
             last_updated: CoinLastUpdated::try_from(coin.last_updated).unwrap(),
+            sources: CoinSources::parse(&coin.sources),
         }
     }
 }
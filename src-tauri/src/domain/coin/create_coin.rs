@@ -4,8 +4,13 @@ use thiserror::Error;
 use tokio::sync::Mutex;
 
 use crate::{
-    driven::repository::{RepoCreateError, Repository},
-    driving::tauri::coins::CreateCoinRequest,
+    domain::{
+        audit::audit::{self, AuditRecord, Origin},
+        events::{DomainEvent, EventBus},
+        Value,
+    },
+    driven::repository::{AuditRepository, RepoCreateError, RepoFindOneError, Repository},
+    driving::tauri::coins::{CoinResponse, CreateCoinRequest},
 };
 
 use super::coin::Coin;
@@ -18,19 +23,34 @@ pub enum CreateError {
     Unknown(String),
 }
 
-pub async fn create_coin<R: Repository<Coin, u32>>(
+/// Stores the coin, replacing the tracked one with the same id.
+pub async fn create_coin<R: Repository<Coin, u32> + AuditRepository>(
     repository: Arc<Mutex<R>>,
+    events: &EventBus,
+    origin: Origin,
     coin: CreateCoinRequest,
 ) -> Result<Coin, CreateError> {
     let coin = Coin::from(coin);
+    let mut repository = repository.lock().await;
 
-    repository
-        .lock()
-        .await
-        .create(coin)
-        .await
-        .map_err(|e| match e {
-            RepoCreateError::InvalidData(e) => CreateError::InvalidData(e),
-            RepoCreateError::Unknown(e) => CreateError::Unknown(e),
-        })
+    let before = match repository.find_one(*coin.id().value()).await {
+        Ok(coin) => Some(coin),
+        Err(RepoFindOneError::NotFound) => None,
+        Err(RepoFindOneError::Unknown(e)) => return Err(CreateError::Unknown(e)),
+    };
+
+    let coin = repository.create(coin).await.map_err(|e| match e {
+        RepoCreateError::InvalidData(e) => CreateError::InvalidData(e),
+        RepoCreateError::Unknown(e) => CreateError::Unknown(e),
+    })?;
+
+    audit::append(
+        &mut *repository,
+        AuditRecord::coin(origin, "create_coin", before.as_ref(), Some(&coin)),
+    )
+    .await;
+
+    events.publish(DomainEvent::CoinCreated(CoinResponse::from(coin.clone())));
+
+    Ok(coin)
 }
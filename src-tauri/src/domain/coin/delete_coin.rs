@@ -3,7 +3,16 @@ use std::sync::Arc;
 use thiserror::Error;
 use tokio::sync::Mutex;
 
-use crate::driven::repository::{RepoDeleteError, Repository};
+use crate::{
+    domain::{
+        audit::audit::{self, AuditRecord, Origin},
+        events::{CoinDeletedEvent, DomainEvent, EventBus},
+        Value,
+    },
+    driven::repository::{
+        AuditRepository, RepoDeleteError, RepoFindOneError, RepoGetAllError, Repository,
+    },
+};
 
 use super::coin::Coin;
 
@@ -17,33 +26,69 @@ pub enum DeleteError {
     NotFound,
 }
 
-pub async fn delete_coin<R: Repository<Coin, u32>>(
+/// Moves the coin to the trash, from where it can be restored until it is purged.
+pub async fn delete_coin<R: Repository<Coin, u32> + AuditRepository>(
     repository: Arc<Mutex<R>>,
+    events: &EventBus,
+    origin: Origin,
     id: u32,
 ) -> Result<(), DeleteError> {
-    repository
-        .lock()
-        .await
-        .delete(id)
-        .await
-        .map_err(|e| match e {
-            RepoDeleteError::InvalidData(e) => DeleteError::InvalidData(e),
-            RepoDeleteError::Unknown(e) => DeleteError::Unknown(e),
-            RepoDeleteError::NotFound => DeleteError::NotFound,
-        })
+    let mut repository = repository.lock().await;
+
+    let before = repository.find_one(id).await.map_err(|e| match e {
+        RepoFindOneError::NotFound => DeleteError::NotFound,
+        RepoFindOneError::Unknown(e) => DeleteError::Unknown(e),
+    })?;
+
+    repository.delete(id).await.map_err(|e| match e {
+        RepoDeleteError::InvalidData(e) => DeleteError::InvalidData(e),
+        RepoDeleteError::Unknown(e) => DeleteError::Unknown(e),
+        RepoDeleteError::NotFound => DeleteError::NotFound,
+    })?;
+
+    audit::append(
+        &mut *repository,
+        AuditRecord::coin(origin, "delete_coin", Some(&before), None),
+    )
+    .await;
+
+    events.publish(DomainEvent::CoinDeleted(CoinDeletedEvent { id }));
+
+    Ok(())
 }
 
-pub async fn delete_all_coins<R: Repository<Coin, u32>>(
+/// Moves every tracked coin to the trash, auditing each one. They share one deletion, so a
+/// single undo restores them all.
+pub async fn delete_all_coins<R: Repository<Coin, u32> + AuditRepository>(
     repository: Arc<Mutex<R>>,
+    events: &EventBus,
+    origin: Origin,
 ) -> Result<(), DeleteError> {
-    repository
-        .lock()
-        .await
-        .delete_all()
-        .await
-        .map_err(|e| match e {
-            RepoDeleteError::InvalidData(e) => DeleteError::InvalidData(e),
-            RepoDeleteError::Unknown(e) => DeleteError::Unknown(e),
-            RepoDeleteError::NotFound => DeleteError::NotFound,
-        })
+    let mut repository = repository.lock().await;
+
+    let coins = repository.get_all().await.map_err(|e| match e {
+        RepoGetAllError::Unknown(e) => DeleteError::Unknown(e),
+    })?;
+
+    repository.delete_all().await.map_err(|e| match e {
+        RepoDeleteError::InvalidData(e) => DeleteError::InvalidData(e),
+        RepoDeleteError::Unknown(e) => DeleteError::Unknown(e),
+        RepoDeleteError::NotFound => DeleteError::NotFound,
+    })?;
+
+    for coin in &coins {
+        audit::append(
+            &mut *repository,
+            AuditRecord::coin(origin, "delete_all_coins", Some(coin), None),
+        )
+        .await;
+    }
+
+    for coin in coins {
+        events.publish(DomainEvent::CoinDeleted(CoinDeletedEvent {
+            id: *coin.id().value(),
+        }));
+    }
+
+    Ok(())
 }
@@ -3,7 +3,9 @@ use std::sync::Arc;
 use thiserror::Error;
 use tokio::sync::Mutex;
 
-use crate::driven::repository::{RepoGetAllError, Repository};
+use crate::driven::repository::{
+    RepoFindOneError, RepoGetAllError, Repository, WatchlistRepository,
+};
 
 use super::coin::Coin;
 
@@ -11,17 +13,27 @@ use super::coin::Coin;
 pub enum GetAllCoinsError {
     #[error("Unknown error: {0}")]
     Unknown(String),
+    #[error("Watchlist not found")]
+    WatchlistNotFound,
 }
 
-pub async fn get_all_coins<R: Repository<Coin, u32>>(
+/// Returns every tracked coin, or only the coins of `watchlist_id` in watchlist order.
+pub async fn get_all_coins<R: Repository<Coin, u32> + WatchlistRepository>(
     repository: Arc<Mutex<R>>,
+    watchlist_id: Option<u32>,
 ) -> Result<Vec<Coin>, GetAllCoinsError> {
-    repository
-        .lock()
-        .await
-        .get_all()
-        .await
-        .map_err(|e| match e {
+    let mut repository = repository.lock().await;
+
+    match watchlist_id {
+        Some(watchlist_id) => repository
+            .get_coins(watchlist_id)
+            .await
+            .map_err(|e| match e {
+                RepoFindOneError::NotFound => GetAllCoinsError::WatchlistNotFound,
+                RepoFindOneError::Unknown(e) => GetAllCoinsError::Unknown(e),
+            }),
+        None => repository.get_all().await.map_err(|e| match e {
             RepoGetAllError::Unknown(e) => GetAllCoinsError::Unknown(e),
-        })
+        }),
+    }
 }
@@ -0,0 +1,105 @@
+use std::sync::Arc;
+
+use thiserror::Error;
+use tokio::sync::Mutex;
+
+use crate::{
+    config::Config,
+    driven::repository::{
+        CoinMetadataRepository, ProviderRepository, RepoCreateError, RepoFindOneError, Repository,
+    },
+    fetch::{
+        coinmarketcap::{fetch_info, fetch_logo},
+        FetchError,
+    },
+};
+
+use super::{coin::Coin, metadata::CoinMetadata};
+
+#[derive(Debug, Error)]
+pub enum GetCoinDetailsError {
+    #[error("Fetch error: {0}")]
+    Fetch(#[from] FetchError),
+    #[error("Coin not found")]
+    CoinNotFound,
+    #[error("CoinMarketCap token not specified")]
+    TokenNotSpecified,
+    #[error("Unknown error: {0}")]
+    Unknown(String),
+}
+
+#[derive(Clone)]
+pub struct CoinDetails {
+    pub coin: Coin,
+    pub metadata: CoinMetadata,
+}
+
+/// Returns a tracked coin with its metadata. The metadata is fetched from the provider the
+/// first time or when `refresh` is set and served from the database otherwise, so `token`
+/// is only needed then. A logo that cannot be downloaded leaves the metadata without one.
+pub async fn get_coin_details<R>(
+    repository: Arc<Mutex<R>>,
+    config: &Config,
+    token: Option<String>,
+    coin_id: u32,
+    refresh: bool,
+) -> Result<CoinDetails, GetCoinDetailsError>
+where
+    R: Repository<Coin, u32> + CoinMetadataRepository + ProviderRepository,
+{
+    let coin = repository
+        .lock()
+        .await
+        .find_one(coin_id)
+        .await
+        .map_err(|e| match e {
+            RepoFindOneError::NotFound => GetCoinDetailsError::CoinNotFound,
+            RepoFindOneError::Unknown(e) => GetCoinDetailsError::Unknown(e),
+        })?;
+
+    let stored = match repository.lock().await.find_metadata(coin_id).await {
+        Ok(metadata) => Some(metadata),
+        Err(RepoFindOneError::NotFound) => None,
+        Err(RepoFindOneError::Unknown(e)) => return Err(GetCoinDetailsError::Unknown(e)),
+    };
+
+    if let (Some(metadata), false) = (&stored, refresh) {
+        return Ok(CoinDetails {
+            coin,
+            metadata: metadata.clone(),
+        });
+    }
+
+    let token = token.ok_or(GetCoinDetailsError::TokenNotSpecified)?;
+    let mut metadata = fetch_info(repository.clone(), config, coin_id, token).await?;
+
+    // Logos rarely change, keep the cached image while the provider's URL stays the same
+    metadata.logo = match (stored, &metadata.logo_url) {
+        (Some(stored), Some(url))
+            if stored.logo_url.as_ref() == Some(url) && stored.logo.is_some() =>
+        {
+            stored.logo
+        }
+        (_, Some(url)) => match fetch_logo(config, url).await {
+            Ok(logo) => Some(logo),
+            Err(e) => {
+                log::warn!("Could not download the logo of coin {}: {}", coin_id, e);
+                None
+            }
+        },
+        (_, None) => None,
+    };
+
+    repository
+        .lock()
+        .await
+        .upsert_metadata(metadata.clone())
+        .await
+        .map_err(|e| match e {
+            RepoCreateError::InvalidData(e) | RepoCreateError::Unknown(e) => {
+                GetCoinDetailsError::Unknown(e)
+            }
+        })?;
+
+    Ok(CoinDetails { coin, metadata })
+}
@@ -0,0 +1,25 @@
+use std::sync::Arc;
+
+use thiserror::Error;
+use tokio::sync::Mutex;
+
+use crate::driven::repository::{CoinTrashRepository, RepoGetAllError};
+
+use super::coin::DeletedCoin;
+
+#[derive(Debug, Error)]
+pub enum GetDeletedCoinsError {
+    #[error("Unknown error: {0}")]
+    Unknown(String),
+}
+
+/// Returns the coins in the trash, most recently deleted first.
+pub async fn get_deleted_coins<R: CoinTrashRepository>(
+    repository: Arc<Mutex<R>>,
+) -> Result<Vec<DeletedCoin>, GetDeletedCoinsError> {
+    let mut repository = repository.lock().await;
+
+    repository.get_deleted_coins().await.map_err(|e| match e {
+        RepoGetAllError::Unknown(e) => GetDeletedCoinsError::Unknown(e),
+    })
+}
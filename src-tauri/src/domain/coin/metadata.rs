@@ -0,0 +1,99 @@
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+use utoipa::ToSchema;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize, ToSchema, TS)]
+#[ts(export, export_to = "../src/bindings/")]
+#[serde(rename_all = "snake_case")]
+pub enum LinkKind {
+    Website,
+    Explorer,
+    SourceCode,
+    TechnicalDoc,
+    Announcement,
+    MessageBoard,
+    Chat,
+    Twitter,
+    Reddit,
+    Facebook,
+}
+
+impl LinkKind {
+    pub const ALL: [LinkKind; 10] = [
+        LinkKind::Website,
+        LinkKind::Explorer,
+        LinkKind::SourceCode,
+        LinkKind::TechnicalDoc,
+        LinkKind::Announcement,
+        LinkKind::MessageBoard,
+        LinkKind::Chat,
+        LinkKind::Twitter,
+        LinkKind::Reddit,
+        LinkKind::Facebook,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LinkKind::Website => "website",
+            LinkKind::Explorer => "explorer",
+            LinkKind::SourceCode => "source_code",
+            LinkKind::TechnicalDoc => "technical_doc",
+            LinkKind::Announcement => "announcement",
+            LinkKind::MessageBoard => "message_board",
+            LinkKind::Chat => "chat",
+            LinkKind::Twitter => "twitter",
+            LinkKind::Reddit => "reddit",
+            LinkKind::Facebook => "facebook",
+        }
+    }
+}
+
+impl FromStr for LinkKind {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        LinkKind::ALL
+            .into_iter()
+            .find(|kind| kind.as_str() == value)
+            .ok_or_else(|| format!("Unknown link kind {}", value))
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, ToSchema, TS)]
+#[ts(export, export_to = "../src/bindings/")]
+pub struct CoinLink {
+    pub kind: LinkKind,
+    pub url: String,
+}
+
+/// Address of a token on one chain, e.g. USDT on Ethereum.
+#[derive(Clone, Debug, PartialEq, Serialize, ToSchema, TS)]
+#[ts(export, export_to = "../src/bindings/")]
+pub struct TokenContract {
+    pub platform: String,
+    pub address: String,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Logo {
+    pub content_type: String,
+    pub bytes: Vec<u8>,
+}
+
+/// Descriptive data of a tracked coin, which changes rarely compared to its quote. Links
+/// and tags keep the provider's order.
+#[derive(Clone, Debug)]
+pub struct CoinMetadata {
+    pub coin_id: u32,
+    pub description: String,
+    pub logo_url: Option<String>,
+    /// Cached copy of the image at `logo_url`, missing when it could not be downloaded
+    pub logo: Option<Logo>,
+    pub links: Vec<CoinLink>,
+    pub tags: Vec<String>,
+    pub contracts: Vec<TokenContract>,
+    /// RFC 3339 time the metadata was fetched
+    pub fetched_at: String,
+}
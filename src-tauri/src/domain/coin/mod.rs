@@ -3,4 +3,10 @@ pub(crate) mod create_coin;
 pub(crate) mod delete_coin;
 pub(crate) mod find_coin;
 pub(crate) mod get_all_coins;
+pub(crate) mod get_coin_details;
+pub(crate) mod get_deleted_coins;
+pub(crate) mod metadata;
+pub(crate) mod purge_deleted_coins;
+pub(crate) mod refresh_coins;
+pub(crate) mod restore_coin;
 pub(crate) mod update_coin;
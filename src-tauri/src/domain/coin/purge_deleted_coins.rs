@@ -0,0 +1,59 @@
+use std::{sync::Arc, time::Duration};
+
+use thiserror::Error;
+use tokio::sync::Mutex;
+
+use crate::{
+    domain::audit::audit::{self, AuditRecord, Origin},
+    driven::repository::{AuditRepository, CoinTrashRepository, RepoDeleteError, RepoGetAllError},
+};
+
+#[derive(Debug, Error)]
+pub enum PurgeError {
+    #[error("Unknown error: {0}")]
+    Unknown(String),
+}
+
+/// Permanently deletes the coins that have been in the trash for longer than `retention`,
+/// together with their candles, metadata and watchlist memberships. Returns how many were
+/// purged.
+pub async fn purge_deleted_coins<R: CoinTrashRepository + AuditRepository>(
+    repository: Arc<Mutex<R>>,
+    origin: Origin,
+    retention: Duration,
+) -> Result<usize, PurgeError> {
+    let before = chrono::Utc::now().timestamp() - retention.as_secs().min(i64::MAX as u64) as i64;
+    let mut repository = repository.lock().await;
+
+    let expired = repository
+        .get_deleted_coins()
+        .await
+        .map_err(|e| match e {
+            RepoGetAllError::Unknown(e) => PurgeError::Unknown(e),
+        })?
+        .into_iter()
+        .filter(|coin| coin.deleted_at < before)
+        .collect::<Vec<_>>();
+
+    if expired.is_empty() {
+        return Ok(0);
+    }
+
+    repository
+        .purge_deleted_coins(before)
+        .await
+        .map_err(|e| match e {
+            RepoDeleteError::NotFound => PurgeError::Unknown("Coin not found".to_string()),
+            RepoDeleteError::InvalidData(e) | RepoDeleteError::Unknown(e) => PurgeError::Unknown(e),
+        })?;
+
+    for coin in &expired {
+        audit::append(
+            &mut *repository,
+            AuditRecord::coin(origin, "purge_coin", Some(&coin.coin), None),
+        )
+        .await;
+    }
+
+    Ok(expired.len())
+}
@@ -0,0 +1,218 @@
+use std::sync::Arc;
+
+use thiserror::Error;
+use tokio::sync::Mutex;
+
+use crate::{
+    config::Config,
+    domain::{
+        audit::audit::Origin,
+        events::{CoinsRefreshedEvent, DomainEvent, EventBus, RefreshFailedEvent},
+        provider::connectivity::Connectivity,
+        Value,
+    },
+    driven::repository::{
+        AuditRepository, ProviderRepository, RepoFindOneError, Repository, WatchlistRepository,
+    },
+    driving::tauri::{coins::CoinResponse, connectivity::ConnectivityResponse},
+    fetch::{aggregated::fetch_ids, FetchError},
+};
+
+use super::{
+    coin::Coin,
+    get_all_coins::{get_all_coins, GetAllCoinsError},
+    update_coin::{update_coin, UpdateError},
+};
+
+#[derive(Debug, Error)]
+pub enum RefreshError {
+    #[error("Fetch error: {0}")]
+    Fetch(#[from] FetchError),
+    #[error("Update error: {0}")]
+    Update(#[from] UpdateError),
+    #[error("{0}")]
+    Coins(#[from] GetAllCoinsError),
+    #[error("Unknown error: {0}")]
+    Unknown(String),
+}
+
+/// Fetches the latest quotes for `ids` and stores them, publishing `coins-refreshed` on
+/// success and `refresh-failed` otherwise.
+///
+/// While the provider is unreachable the refresh is queued on `connectivity` and the
+/// stored coins are returned instead; their responses are marked stale once old enough.
+pub async fn refresh_coins<R: Repository<Coin, u32> + ProviderRepository + AuditRepository>(
+    repository: Arc<Mutex<R>>,
+    events: &EventBus,
+    origin: Origin,
+    config: &Config,
+    connectivity: &Connectivity,
+    ids: Vec<u32>,
+    token: String,
+) -> Result<Vec<Coin>, RefreshError> {
+    let result = fetch_and_update(
+        repository.clone(),
+        events,
+        origin,
+        config,
+        ids.clone(),
+        token,
+    )
+    .await;
+    let now = chrono::Utc::now().timestamp();
+
+    match &result {
+        Ok(coins) => {
+            if connectivity.went_online(now) {
+                publish_connectivity(events, connectivity);
+            }
+
+            events.publish(DomainEvent::CoinsRefreshed(CoinsRefreshedEvent {
+                coins: coins.iter().cloned().map(CoinResponse::from).collect(),
+            }))
+        }
+        Err(e) => {
+            if matches!(e, RefreshError::Fetch(FetchError::Unreachable(_)))
+                && connectivity.went_offline(&ids, now)
+            {
+                publish_connectivity(events, connectivity);
+            }
+
+            events.publish(DomainEvent::RefreshFailed(RefreshFailedEvent {
+                ids: ids.clone(),
+                message: e.to_string(),
+            }))
+        }
+    }
+
+    match result {
+        Err(RefreshError::Fetch(FetchError::Unreachable(e))) => {
+            log::warn!("Provider unreachable, serving stored coins: {}", e);
+            stored_coins(repository, ids).await
+        }
+        result => result,
+    }
+}
+
+/// Marks the provider reachable again and refreshes the coins queued while it was not. If
+/// it turns out unreachable after all they are queued again.
+pub async fn resume_refreshes<R: Repository<Coin, u32> + ProviderRepository + AuditRepository>(
+    repository: Arc<Mutex<R>>,
+    events: &EventBus,
+    origin: Origin,
+    config: &Config,
+    connectivity: &Connectivity,
+    token: String,
+) -> Result<Vec<Coin>, RefreshError> {
+    if connectivity.went_online(chrono::Utc::now().timestamp()) {
+        publish_connectivity(events, connectivity);
+    }
+
+    // Coins deleted while offline are no longer refreshed
+    let ids = stored_coins(repository.clone(), connectivity.take_queued())
+        .await?
+        .iter()
+        .map(|coin| *coin.id().value())
+        .collect::<Vec<u32>>();
+
+    if ids.is_empty() {
+        return Ok(vec![]);
+    }
+
+    let result = refresh_coins(
+        repository,
+        events,
+        origin,
+        config,
+        connectivity,
+        ids.clone(),
+        token,
+    )
+    .await;
+
+    if let Err(e) = &result {
+        log::warn!("Could not resume the refresh of {:?}: {}", ids, e);
+    }
+
+    result
+}
+
+/// Refreshes the coins of one watchlist. An empty watchlist is not an error and makes no
+/// provider call.
+pub async fn refresh_watchlist<R>(
+    repository: Arc<Mutex<R>>,
+    events: &EventBus,
+    origin: Origin,
+    config: &Config,
+    connectivity: &Connectivity,
+    watchlist_id: u32,
+    token: String,
+) -> Result<Vec<Coin>, RefreshError>
+where
+    R: Repository<Coin, u32> + WatchlistRepository + ProviderRepository + AuditRepository,
+{
+    let ids = get_all_coins(repository.clone(), Some(watchlist_id))
+        .await?
+        .iter()
+        .map(|coin| *coin.id().value())
+        .collect::<Vec<u32>>();
+
+    if ids.is_empty() {
+        return Ok(vec![]);
+    }
+
+    refresh_coins(repository, events, origin, config, connectivity, ids, token).await
+}
+
+async fn fetch_and_update<R: Repository<Coin, u32> + ProviderRepository + AuditRepository>(
+    repository: Arc<Mutex<R>>,
+    events: &EventBus,
+    origin: Origin,
+    config: &Config,
+    ids: Vec<u32>,
+    token: String,
+) -> Result<Vec<Coin>, RefreshError> {
+    // Secondary sources are asked for the stored symbols while the provider is queried
+    let symbols = if config.aggregation.sources.is_empty() {
+        vec![]
+    } else {
+        stored_coins(repository.clone(), ids.clone())
+            .await?
+            .iter()
+            .map(|coin| coin.symbol().value().clone())
+            .collect()
+    };
+    let fetched = fetch_ids(repository.clone(), config, ids, symbols, token).await?;
+    let mut coins = vec![];
+
+    for coin in fetched.coins {
+        coins.push(update_coin(repository.clone(), events, origin, coin).await?);
+    }
+
+    Ok(coins)
+}
+
+/// The stored coins among `ids`, skipping the ones that are not tracked.
+async fn stored_coins<R: Repository<Coin, u32>>(
+    repository: Arc<Mutex<R>>,
+    ids: Vec<u32>,
+) -> Result<Vec<Coin>, RefreshError> {
+    let mut repository = repository.lock().await;
+    let mut coins = vec![];
+
+    for id in ids {
+        match repository.find_one(id).await {
+            Ok(coin) => coins.push(coin),
+            Err(RepoFindOneError::NotFound) => continue,
+            Err(RepoFindOneError::Unknown(e)) => return Err(RefreshError::Unknown(e)),
+        }
+    }
+
+    Ok(coins)
+}
+
+fn publish_connectivity(events: &EventBus, connectivity: &Connectivity) {
+    events.publish(DomainEvent::ConnectivityChanged(
+        ConnectivityResponse::from(connectivity.status()),
+    ));
+}
@@ -0,0 +1,85 @@
+use std::sync::Arc;
+
+use thiserror::Error;
+use tokio::sync::Mutex;
+
+use crate::{
+    domain::{
+        audit::audit::{self, AuditRecord, Origin},
+        events::{DomainEvent, EventBus},
+        Value,
+    },
+    driven::repository::{AuditRepository, CoinTrashRepository, RepoGetAllError, RepoUpdateError},
+    driving::tauri::coins::CoinResponse,
+};
+
+use super::coin::Coin;
+
+#[derive(Debug, Error)]
+pub enum RestoreError {
+    #[error("Coin not found in the trash")]
+    NotFound,
+    #[error("Unknown error: {0}")]
+    Unknown(String),
+}
+
+/// Takes the coin out of the trash, tracking it again along with its watchlist memberships.
+pub async fn restore_coin<R: CoinTrashRepository + AuditRepository>(
+    repository: Arc<Mutex<R>>,
+    events: &EventBus,
+    origin: Origin,
+    id: u32,
+) -> Result<Coin, RestoreError> {
+    let mut repository = repository.lock().await;
+
+    restore(&mut *repository, events, origin, "restore_coin", id).await
+}
+
+/// Restores the coins of the most recent deletion, all of them when it was a delete of every
+/// coin. Returns nothing when the trash is empty.
+pub async fn undo_delete<R: CoinTrashRepository + AuditRepository>(
+    repository: Arc<Mutex<R>>,
+    events: &EventBus,
+    origin: Origin,
+) -> Result<Vec<Coin>, RestoreError> {
+    let mut repository = repository.lock().await;
+
+    let deleted = repository.get_deleted_coins().await.map_err(|e| match e {
+        RepoGetAllError::Unknown(e) => RestoreError::Unknown(e),
+    })?;
+
+    let Some(last) = deleted.first().map(|coin| coin.deletion_id) else {
+        return Ok(vec![]);
+    };
+
+    let mut restored = vec![];
+    for coin in deleted.iter().take_while(|coin| coin.deletion_id == last) {
+        let id = *coin.coin.id().value();
+        restored.push(restore(&mut *repository, events, origin, "undo_delete", id).await?);
+    }
+
+    Ok(restored)
+}
+
+async fn restore<R: CoinTrashRepository + AuditRepository>(
+    repository: &mut R,
+    events: &EventBus,
+    origin: Origin,
+    operation: &str,
+    id: u32,
+) -> Result<Coin, RestoreError> {
+    let coin = repository.restore_coin(id).await.map_err(|e| match e {
+        RepoUpdateError::NotFound => RestoreError::NotFound,
+        RepoUpdateError::InvalidData(e) | RepoUpdateError::Unknown(e) => RestoreError::Unknown(e),
+    })?;
+
+    audit::append(
+        repository,
+        AuditRecord::coin(origin, operation, None, Some(&coin)),
+    )
+    .await;
+
+    events.publish(DomainEvent::CoinCreated(CoinResponse::from(coin.clone())));
+
+    Ok(coin)
+}
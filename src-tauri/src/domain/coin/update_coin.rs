@@ -4,7 +4,12 @@ use thiserror::Error;
 use tokio::sync::Mutex;
 
 use crate::{
-    driven::repository::{RepoUpdateError, Repository},
+    domain::{
+        audit::audit::{self, AuditRecord, Origin},
+        events::{DomainEvent, EventBus},
+        Value,
+    },
+    driven::repository::{AuditRepository, RepoFindOneError, RepoUpdateError, Repository},
     driving::tauri::coins::CoinResponse,
 };
 
@@ -20,20 +25,36 @@ pub enum UpdateError {
     NotFound,
 }
 
-pub async fn update_coin<R: Repository<Coin, u32>>(
+pub async fn update_coin<R: Repository<Coin, u32> + AuditRepository>(
     repository: Arc<Mutex<R>>,
+    events: &EventBus,
+    origin: Origin,
     coin: CoinResponse,
 ) -> Result<Coin, UpdateError> {
     let coin = Coin::from(coin);
+    let mut repository = repository.lock().await;
 
-    repository
-        .lock()
-        .await
-        .update(coin)
+    let before = repository
+        .find_one(*coin.id().value())
         .await
         .map_err(|e| match e {
-            RepoUpdateError::InvalidData(e) => UpdateError::InvalidData(e),
-            RepoUpdateError::Unknown(e) => UpdateError::Unknown(e),
-            RepoUpdateError::NotFound => UpdateError::NotFound,
-        })
+            RepoFindOneError::NotFound => UpdateError::NotFound,
+            RepoFindOneError::Unknown(e) => UpdateError::Unknown(e),
+        })?;
+
+    let coin = repository.update(coin).await.map_err(|e| match e {
+        RepoUpdateError::InvalidData(e) => UpdateError::InvalidData(e),
+        RepoUpdateError::Unknown(e) => UpdateError::Unknown(e),
+        RepoUpdateError::NotFound => UpdateError::NotFound,
+    })?;
+
+    audit::append(
+        &mut *repository,
+        AuditRecord::coin(origin, "update_coin", Some(&before), Some(&coin)),
+    )
+    .await;
+
+    events.publish(DomainEvent::CoinUpdated(CoinResponse::from(coin.clone())));
+
+    Ok(coin)
 }
@@ -0,0 +1,98 @@
+use serde::Serialize;
+use tokio::sync::broadcast;
+use ts_rs::TS;
+
+use crate::driving::tauri::{
+    coins::CoinResponse, connectivity::ConnectivityResponse, profiles::ProfileResponse,
+    stream::LivePriceResponse, watchlists::WatchlistResponse,
+};
+
+const EVENT_BUS_CAPACITY: usize = 256;
+
+#[derive(Clone, Debug, Serialize, TS)]
+#[ts(export, export_to = "../src/bindings/")]
+pub struct CoinDeletedEvent {
+    pub id: u32,
+}
+
+#[derive(Clone, Debug, Serialize, TS)]
+#[ts(export, export_to = "../src/bindings/")]
+pub struct CoinsRefreshedEvent {
+    pub coins: Vec<CoinResponse>,
+}
+
+#[derive(Clone, Debug, Serialize, TS)]
+#[ts(export, export_to = "../src/bindings/")]
+pub struct RefreshFailedEvent {
+    pub ids: Vec<u32>,
+    pub message: String,
+}
+
+#[derive(Clone, Debug, Serialize, TS)]
+#[ts(export, export_to = "../src/bindings/")]
+pub struct WatchlistDeletedEvent {
+    pub id: u32,
+}
+
+/// Something that happened to the tracked coins, watchlists, the provider, the price stream or the profile, published by the use cases
+/// in `domain` regardless of which adapter triggered them.
+#[derive(Clone, Debug)]
+pub enum DomainEvent {
+    CoinCreated(CoinResponse),
+    CoinUpdated(CoinResponse),
+    CoinDeleted(CoinDeletedEvent),
+    CoinsRefreshed(CoinsRefreshedEvent),
+    RefreshFailed(RefreshFailedEvent),
+    WatchlistUpdated(WatchlistResponse),
+    WatchlistDeleted(WatchlistDeletedEvent),
+    ConnectivityChanged(ConnectivityResponse),
+    PriceTicked(LivePriceResponse),
+    /// Another profile's database is in use; everything read before is outdated
+    ProfileSwitched(ProfileResponse),
+}
+
+impl DomainEvent {
+    /// The event name as seen by subscribers, e.g. the Tauri frontend.
+    pub fn name(&self) -> &'static str {
+        match self {
+            DomainEvent::CoinCreated(_) => "coin-created",
+            DomainEvent::CoinUpdated(_) => "coin-updated",
+            DomainEvent::CoinDeleted(_) => "coin-deleted",
+            DomainEvent::CoinsRefreshed(_) => "coins-refreshed",
+            DomainEvent::RefreshFailed(_) => "refresh-failed",
+            DomainEvent::WatchlistUpdated(_) => "watchlist-updated",
+            DomainEvent::WatchlistDeleted(_) => "watchlist-deleted",
+            DomainEvent::ConnectivityChanged(_) => "connectivity-changed",
+            DomainEvent::PriceTicked(_) => "price-ticked",
+            DomainEvent::ProfileSwitched(_) => "profile-switched",
+        }
+    }
+}
+
+/// In-process broadcast of domain events. Cloning shares the same channel; publishing
+/// without subscribers is a no-op.
+#[derive(Clone)]
+pub struct EventBus {
+    sender: broadcast::Sender<DomainEvent>,
+}
+
+impl EventBus {
+    pub fn new() -> Self {
+        let (sender, _) = broadcast::channel(EVENT_BUS_CAPACITY);
+        Self { sender }
+    }
+
+    pub fn publish(&self, event: DomainEvent) {
+        let _ = self.sender.send(event);
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<DomainEvent> {
+        self.sender.subscribe()
+    }
+}
+
+impl Default for EventBus {
+    fn default() -> Self {
+        Self::new()
+    }
+}
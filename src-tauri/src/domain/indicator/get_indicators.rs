@@ -0,0 +1,90 @@
+use std::sync::Arc;
+
+use thiserror::Error;
+use tokio::sync::Mutex;
+
+use crate::{
+    domain::candle::{
+        candle::{CandleInterval, CandleRange, CandleSeries},
+        get_candles::{get_candles, GetCandlesError},
+    },
+    driven::repository::CandleRepository,
+};
+
+use super::indicator::{IndicatorLines, IndicatorSpec};
+
+#[derive(Debug, Error)]
+pub enum GetIndicatorsError {
+    #[error("Invalid data: {0}")]
+    InvalidData(String),
+    #[error("Unknown error: {0}")]
+    Unknown(String),
+}
+
+impl From<GetCandlesError> for GetIndicatorsError {
+    fn from(e: GetCandlesError) -> Self {
+        match e {
+            GetCandlesError::Unknown(e) => GetIndicatorsError::Unknown(e),
+        }
+    }
+}
+
+/// Indicator lines aligned with `open_times`: the value at index `i` belongs to the candle
+/// opened at `open_times[i]`.
+#[derive(Clone, Debug)]
+pub struct IndicatorSet {
+    pub series: CandleSeries,
+    pub indicators: Vec<(IndicatorSpec, IndicatorLines)>,
+}
+
+/// Computes the indicators over the stored closes of `range`. Candles before the range are
+/// loaded for the warmup, so values are defined from the start of the range whenever enough
+/// history is stored.
+pub async fn get_indicators<R: CandleRepository>(
+    repository: Arc<Mutex<R>>,
+    coin_id: u32,
+    interval: CandleInterval,
+    range: CandleRange,
+    specs: Vec<IndicatorSpec>,
+) -> Result<IndicatorSet, GetIndicatorsError> {
+    for spec in &specs {
+        spec.validate()
+            .map_err(|e| GetIndicatorsError::InvalidData(format!("{}: {}", spec, e)))?;
+    }
+
+    let warmup = specs.iter().map(IndicatorSpec::warmup).max().unwrap_or(0) as i64;
+    let history = CandleRange {
+        start: range.start - warmup * interval.seconds(),
+        end: range.end,
+    };
+
+    let candles = get_candles(repository, coin_id, interval, history)
+        .await?
+        .candles;
+    let closes = candles
+        .iter()
+        .map(|candle| candle.ohlcv().close)
+        .collect::<Vec<f64>>();
+    let first = candles
+        .iter()
+        .position(|candle| candle.open_time() >= range.start)
+        .unwrap_or(candles.len());
+
+    let indicators = specs
+        .into_iter()
+        .map(|spec| {
+            let lines = spec
+                .compute(&closes, interval)
+                .into_iter()
+                .map(|(name, values)| (name, values[first..].to_vec()))
+                .collect();
+
+            (spec, lines)
+        })
+        .collect();
+
+    Ok(IndicatorSet {
+        series: CandleSeries::new(coin_id, interval, range, candles[first..].to_vec()),
+        indicators,
+    })
+}
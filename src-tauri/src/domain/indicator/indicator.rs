@@ -0,0 +1,201 @@
+use std::{collections::BTreeMap, fmt, str::FromStr};
+
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+use utoipa::ToSchema;
+
+use crate::domain::candle::candle::CandleInterval;
+
+use super::indicators;
+
+/// Named lines of an indicator, e.g. `upper`, `middle` and `lower` for Bollinger bands.
+pub type IndicatorLines = BTreeMap<String, Vec<Option<f64>>>;
+
+/// An indicator and its parameters; periods count candles.
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize, Serialize, ToSchema, TS)]
+#[ts(export, export_to = "../src/bindings/")]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum IndicatorSpec {
+    Sma {
+        period: usize,
+    },
+    Ema {
+        period: usize,
+    },
+    Rsi {
+        period: usize,
+    },
+    Macd {
+        fast: usize,
+        slow: usize,
+        signal: usize,
+    },
+    Bollinger {
+        period: usize,
+        k: f64,
+    },
+    /// Annualized volatility of log returns
+    RealizedVolatility {
+        period: usize,
+    },
+    MaxDrawdown,
+}
+
+impl IndicatorSpec {
+    pub fn validate(&self) -> Result<(), &'static str> {
+        match *self {
+            IndicatorSpec::Sma { period }
+            | IndicatorSpec::Ema { period }
+            | IndicatorSpec::Rsi { period }
+                if period == 0 =>
+            {
+                Err("period must be greater than 0")
+            }
+            IndicatorSpec::Macd { fast, slow, signal }
+                if fast == 0 || signal == 0 || fast >= slow =>
+            {
+                Err("MACD periods must be greater than 0 and fast must be less than slow")
+            }
+            IndicatorSpec::Bollinger { period, k } if period == 0 || !k.is_finite() || k <= 0.0 => {
+                Err("Bollinger period and k must be greater than 0")
+            }
+            IndicatorSpec::RealizedVolatility { period } if period < 2 => {
+                Err("volatility period must be at least 2")
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Candles needed before the first one with a defined value.
+    pub fn warmup(&self) -> usize {
+        match *self {
+            IndicatorSpec::Sma { period }
+            | IndicatorSpec::Ema { period }
+            | IndicatorSpec::Bollinger { period, .. } => period - 1,
+            IndicatorSpec::Rsi { period } | IndicatorSpec::RealizedVolatility { period } => period,
+            IndicatorSpec::Macd { slow, signal, .. } => slow + signal - 2,
+            IndicatorSpec::MaxDrawdown => 0,
+        }
+    }
+
+    /// Computes the indicator over `closes`, one value per close in every line.
+    pub fn compute(&self, closes: &[f64], interval: CandleInterval) -> IndicatorLines {
+        let lines: Vec<(&str, Vec<Option<f64>>)> = match *self {
+            IndicatorSpec::Sma { period } => vec![("value", indicators::sma(closes, period))],
+            IndicatorSpec::Ema { period } => vec![("value", indicators::ema(closes, period))],
+            IndicatorSpec::Rsi { period } => vec![("value", indicators::rsi(closes, period))],
+            IndicatorSpec::Macd { fast, slow, signal } => {
+                let macd = indicators::macd(closes, fast, slow, signal);
+                vec![
+                    ("macd", macd.macd),
+                    ("signal", macd.signal),
+                    ("histogram", macd.histogram),
+                ]
+            }
+            IndicatorSpec::Bollinger { period, k } => {
+                let bands = indicators::bollinger(closes, period, k);
+                vec![
+                    ("upper", bands.upper),
+                    ("middle", bands.middle),
+                    ("lower", bands.lower),
+                ]
+            }
+            IndicatorSpec::RealizedVolatility { period } => vec![(
+                "value",
+                indicators::realized_volatility(closes, period, periods_per_year(interval)),
+            )],
+            IndicatorSpec::MaxDrawdown => {
+                let drawdown = indicators::drawdown(closes);
+                vec![
+                    ("drawdown", drawdown.drawdown),
+                    ("max_drawdown", drawdown.max_drawdown),
+                ]
+            }
+        };
+
+        lines
+            .into_iter()
+            .map(|(name, values)| (name.to_string(), values))
+            .collect()
+    }
+}
+
+/// Crypto markets trade around the clock, so a year has 365 daily candles.
+pub fn periods_per_year(interval: CandleInterval) -> f64 {
+    match interval {
+        CandleInterval::Hourly => 365.0 * 24.0,
+        CandleInterval::Daily => 365.0,
+    }
+}
+
+impl fmt::Display for IndicatorSpec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IndicatorSpec::Sma { period } => write!(f, "sma({})", period),
+            IndicatorSpec::Ema { period } => write!(f, "ema({})", period),
+            IndicatorSpec::Rsi { period } => write!(f, "rsi({})", period),
+            IndicatorSpec::Macd { fast, slow, signal } => {
+                write!(f, "macd({},{},{})", fast, slow, signal)
+            }
+            IndicatorSpec::Bollinger { period, k } => write!(f, "bollinger({},{})", period, k),
+            IndicatorSpec::RealizedVolatility { period } => write!(f, "volatility({})", period),
+            IndicatorSpec::MaxDrawdown => write!(f, "drawdown"),
+        }
+    }
+}
+
+/// Parses `name[:param[:param...]]`, e.g. `sma:20`, `macd:12:26:9` or `drawdown`. Missing
+/// parameters take the usual defaults.
+impl FromStr for IndicatorSpec {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let mut parts = value.split(':');
+        let name = parts.next().unwrap_or_default().to_lowercase();
+        let params = parts.collect::<Vec<&str>>();
+
+        let period = |index: usize, default: usize| -> Result<usize, String> {
+            params
+                .get(index)
+                .map(|param| {
+                    param
+                        .parse::<usize>()
+                        .map_err(|_| format!("invalid period '{}'", param))
+                })
+                .unwrap_or(Ok(default))
+        };
+
+        let spec = match name.as_str() {
+            "sma" => IndicatorSpec::Sma {
+                period: period(0, 20)?,
+            },
+            "ema" => IndicatorSpec::Ema {
+                period: period(0, 20)?,
+            },
+            "rsi" => IndicatorSpec::Rsi {
+                period: period(0, 14)?,
+            },
+            "macd" => IndicatorSpec::Macd {
+                fast: period(0, 12)?,
+                slow: period(1, 26)?,
+                signal: period(2, 9)?,
+            },
+            "bollinger" | "bb" => IndicatorSpec::Bollinger {
+                period: period(0, 20)?,
+                k: params
+                    .get(1)
+                    .map(|k| k.parse::<f64>().map_err(|_| format!("invalid k '{}'", k)))
+                    .unwrap_or(Ok(2.0))?,
+            },
+            "volatility" | "vol" => IndicatorSpec::RealizedVolatility {
+                period: period(0, 30)?,
+            },
+            "drawdown" | "max_drawdown" => IndicatorSpec::MaxDrawdown,
+            _ => return Err(format!("unknown indicator '{}'", value)),
+        };
+
+        spec.validate()?;
+
+        Ok(spec)
+    }
+}
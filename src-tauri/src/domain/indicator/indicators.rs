@@ -0,0 +1,336 @@
+//! Technical indicators over a series of closes, oldest first. Every function returns one
+//! value per input value, `None` while the indicator is still warming up.
+
+/// Simple moving average of the last `period` values.
+pub fn sma(values: &[f64], period: usize) -> Vec<Option<f64>> {
+    let mut result = vec![None; values.len()];
+
+    if period == 0 {
+        return result;
+    }
+
+    let mut sum = 0.0;
+
+    for (index, value) in values.iter().enumerate() {
+        sum += value;
+
+        if index >= period {
+            sum -= values[index - period];
+        }
+
+        if index + 1 >= period {
+            result[index] = Some(sum / period as f64);
+        }
+    }
+
+    result
+}
+
+/// Exponential moving average with `alpha = 2 / (period + 1)`, seeded with the simple
+/// average of the first `period` values.
+pub fn ema(values: &[f64], period: usize) -> Vec<Option<f64>> {
+    let mut result = vec![None; values.len()];
+
+    if period == 0 || values.len() < period {
+        return result;
+    }
+
+    let alpha = 2.0 / (period as f64 + 1.0);
+    let mut current = values[..period].iter().sum::<f64>() / period as f64;
+    result[period - 1] = Some(current);
+
+    for (index, value) in values.iter().enumerate().skip(period) {
+        current = alpha * value + (1.0 - alpha) * current;
+        result[index] = Some(current);
+    }
+
+    result
+}
+
+/// Relative strength index with Wilder's smoothing, between 0 and 100.
+pub fn rsi(values: &[f64], period: usize) -> Vec<Option<f64>> {
+    let mut result = vec![None; values.len()];
+
+    if period == 0 || values.len() <= period {
+        return result;
+    }
+
+    let changes = values
+        .windows(2)
+        .map(|pair| pair[1] - pair[0])
+        .collect::<Vec<f64>>();
+
+    let mut gain = changes[..period].iter().map(|c| c.max(0.0)).sum::<f64>() / period as f64;
+    let mut loss = changes[..period].iter().map(|c| (-c).max(0.0)).sum::<f64>() / period as f64;
+    result[period] = Some(strength_index(gain, loss));
+
+    for (index, change) in changes.iter().enumerate().skip(period) {
+        gain = (gain * (period - 1) as f64 + change.max(0.0)) / period as f64;
+        loss = (loss * (period - 1) as f64 + (-change).max(0.0)) / period as f64;
+        result[index + 1] = Some(strength_index(gain, loss));
+    }
+
+    result
+}
+
+fn strength_index(gain: f64, loss: f64) -> f64 {
+    if loss > 0.0 {
+        100.0 - 100.0 / (1.0 + gain / loss)
+    } else if gain > 0.0 {
+        100.0
+    } else {
+        50.0
+    }
+}
+
+pub struct Macd {
+    pub macd: Vec<Option<f64>>,
+    pub signal: Vec<Option<f64>>,
+    pub histogram: Vec<Option<f64>>,
+}
+
+/// MACD line (fast EMA minus slow EMA), its signal line (EMA of the MACD line) and their
+/// difference.
+pub fn macd(values: &[f64], fast: usize, slow: usize, signal: usize) -> Macd {
+    let macd = ema(values, fast)
+        .into_iter()
+        .zip(ema(values, slow))
+        .map(|(fast, slow)| Some(fast? - slow?))
+        .collect::<Vec<Option<f64>>>();
+
+    // The signal line only starts once the MACD line is defined
+    let start = macd.iter().position(Option::is_some).unwrap_or(macd.len());
+    let defined = macd[start..]
+        .iter()
+        .flatten()
+        .copied()
+        .collect::<Vec<f64>>();
+    let mut signal_line = vec![None; start];
+    signal_line.extend(ema(&defined, signal));
+
+    let histogram = macd
+        .iter()
+        .zip(&signal_line)
+        .map(|(macd, signal)| Some((*macd)? - (*signal)?))
+        .collect();
+
+    Macd {
+        macd,
+        signal: signal_line,
+        histogram,
+    }
+}
+
+pub struct Bollinger {
+    pub upper: Vec<Option<f64>>,
+    pub middle: Vec<Option<f64>>,
+    pub lower: Vec<Option<f64>>,
+}
+
+/// Bollinger bands: the simple moving average plus and minus `k` population standard
+/// deviations over the same window.
+pub fn bollinger(values: &[f64], period: usize, k: f64) -> Bollinger {
+    let middle = sma(values, period);
+    let mut upper = vec![None; values.len()];
+    let mut lower = vec![None; values.len()];
+
+    for (index, mean) in middle.iter().enumerate() {
+        let Some(mean) = mean else {
+            continue;
+        };
+
+        let window = &values[index + 1 - period..=index];
+        let variance = window.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / period as f64;
+        let deviation = variance.sqrt();
+
+        upper[index] = Some(mean + k * deviation);
+        lower[index] = Some(mean - k * deviation);
+    }
+
+    Bollinger {
+        upper,
+        middle,
+        lower,
+    }
+}
+
+/// Sample standard deviation of the last `period` log returns, scaled by the square root of
+/// `periods_per_year` to an annual figure.
+pub fn realized_volatility(
+    values: &[f64],
+    period: usize,
+    periods_per_year: f64,
+) -> Vec<Option<f64>> {
+    let mut result = vec![None; values.len()];
+
+    if period < 2 {
+        return result;
+    }
+
+    let returns = log_returns(values);
+
+    for index in period..values.len() {
+        let window = &returns[index - period..index];
+        let mean = window.iter().sum::<f64>() / period as f64;
+        let variance = window.iter().map(|r| (r - mean).powi(2)).sum::<f64>() / (period - 1) as f64;
+
+        result[index] = Some(variance.sqrt() * periods_per_year.sqrt());
+    }
+
+    result
+}
+
+/// Natural log of each value divided by the previous one; one shorter than `values`.
+pub fn log_returns(values: &[f64]) -> Vec<f64> {
+    values
+        .windows(2)
+        .map(|pair| (pair[1] / pair[0]).ln())
+        .collect()
+}
+
+pub struct Drawdown {
+    /// Fall from the highest value so far, as a fraction
+    pub drawdown: Vec<Option<f64>>,
+    /// Largest drawdown so far
+    pub max_drawdown: Vec<Option<f64>>,
+}
+
+pub fn drawdown(values: &[f64]) -> Drawdown {
+    let mut peak = f64::MIN;
+    let mut max = 0.0_f64;
+    let mut drawdown = vec![];
+    let mut max_drawdown = vec![];
+
+    for value in values {
+        peak = peak.max(*value);
+
+        let current = if peak > 0.0 {
+            (peak - value) / peak
+        } else {
+            0.0
+        };
+        max = max.max(current);
+
+        drawdown.push(Some(current));
+        max_drawdown.push(Some(max));
+    }
+
+    Drawdown {
+        drawdown,
+        max_drawdown,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::test_support::assert_close;
+
+    use super::*;
+
+    const EPSILON: f64 = 1e-6;
+
+    /// Closes of the StockCharts RSI example; the expected values are those of its
+    /// spreadsheet, which does not round the averages like the article's table.
+    const RSI_CLOSES: [f64; 20] = [
+        44.34, 44.09, 44.15, 43.61, 44.33, 44.83, 45.10, 45.42, 45.84, 46.08, 45.89, 46.03, 45.61,
+        46.28, 46.28, 46.00, 46.03, 46.41, 46.22, 45.64,
+    ];
+
+    #[test]
+    fn sma_averages_the_window() {
+        let result = sma(&[1.0, 2.0, 3.0, 4.0, 5.0], 3);
+
+        assert_eq!(result[..2], [None, None]);
+        assert_close(result[2], 2.0, EPSILON);
+        assert_close(result[3], 3.0, EPSILON);
+        assert_close(result[4], 4.0, EPSILON);
+    }
+
+    #[test]
+    fn ema_is_seeded_with_the_sma() {
+        let result = ema(&[22.27, 22.19, 22.08, 22.17, 22.18, 22.13], 5);
+
+        assert_eq!(result[..4], [None, None, None, None]);
+        assert_close(result[4], 22.178, EPSILON);
+        assert_close(result[5], 22.162, 1e-3);
+    }
+
+    #[test]
+    fn ema_lags_a_linear_series_by_half_its_period() {
+        let values = (1..=10).map(f64::from).collect::<Vec<f64>>();
+
+        for (index, value) in ema(&values, 5).into_iter().enumerate().skip(4) {
+            assert_close(value, values[index] - 2.0, EPSILON);
+        }
+    }
+
+    #[test]
+    fn rsi_matches_the_reference_spreadsheet() {
+        let result = rsi(&RSI_CLOSES, 14);
+
+        assert!(result[..14].iter().all(Option::is_none));
+        assert_close(result[14], 70.46, 0.01);
+        assert_close(result[15], 66.25, 0.01);
+        assert_close(result[16], 66.48, 0.01);
+        assert_close(result[17], 69.35, 0.01);
+        assert_close(result[18], 66.29, 0.01);
+        assert_close(result[19], 57.92, 0.01);
+    }
+
+    #[test]
+    fn rsi_is_100_without_losses() {
+        let result = rsi(&[1.0, 2.0, 3.0, 4.0], 3);
+
+        assert_close(result[3], 100.0, EPSILON);
+    }
+
+    #[test]
+    fn macd_of_a_linear_series_is_constant() {
+        let values = (1..=12).map(f64::from).collect::<Vec<f64>>();
+        let result = macd(&values, 3, 5, 2);
+
+        assert!(result.macd[..4].iter().all(Option::is_none));
+        assert!(result.signal[..5].iter().all(Option::is_none));
+
+        for index in 5..values.len() {
+            assert_close(result.macd[index], 1.0, EPSILON);
+            assert_close(result.signal[index], 1.0, EPSILON);
+            assert_close(result.histogram[index], 0.0, EPSILON);
+        }
+    }
+
+    #[test]
+    fn bollinger_uses_the_population_deviation() {
+        let result = bollinger(&[1.0, 2.0, 3.0, 4.0, 5.0], 5, 2.0);
+
+        assert_close(result.middle[4], 3.0, EPSILON);
+        assert_close(result.upper[4], 3.0 + 2.0 * 2.0_f64.sqrt(), EPSILON);
+        assert_close(result.lower[4], 3.0 - 2.0 * 2.0_f64.sqrt(), EPSILON);
+        assert_eq!(result.upper[3], None);
+    }
+
+    #[test]
+    fn realized_volatility_annualizes_the_sample_deviation() {
+        let e = std::f64::consts::E;
+        // Log returns of 1, -1, 1
+        let values = [1.0, e, 1.0, e];
+        let result = realized_volatility(&values, 3, 365.0);
+
+        assert_eq!(result[..3], [None, None, None]);
+        assert_close(
+            result[3],
+            (4.0_f64 / 3.0).sqrt() * 365.0_f64.sqrt(),
+            EPSILON,
+        );
+    }
+
+    #[test]
+    fn drawdown_tracks_the_largest_fall_from_a_peak() {
+        let result = drawdown(&[100.0, 120.0, 90.0, 110.0, 60.0, 130.0]);
+
+        assert_close(result.drawdown[2], 0.25, EPSILON);
+        assert_close(result.drawdown[4], 0.5, EPSILON);
+        assert_close(result.drawdown[5], 0.0, EPSILON);
+        assert_close(result.max_drawdown[5], 0.5, EPSILON);
+    }
+}
@@ -0,0 +1,3 @@
+pub(crate) mod get_indicators;
+pub(crate) mod indicator;
+pub(crate) mod indicators;
@@ -1,5 +1,19 @@
 pub(crate) mod settings;
+pub(crate) mod analytics;
+pub(crate) mod audit;
+pub(crate) mod candle;
+pub(crate) mod catalog;
 pub(crate) mod coin;
+pub(crate) mod events;
+pub(crate) mod indicator;
+pub(crate) mod profile;
+pub(crate) mod provider;
+pub(crate) mod stream;
+#[cfg(test)]
+pub(crate) mod test_support;
+pub(crate) mod transaction;
+pub(crate) mod transfer;
+pub(crate) mod watchlist;
 
 pub trait Entity {}
 
@@ -0,0 +1,151 @@
+use std::sync::Arc;
+
+use thiserror::Error;
+use tokio::sync::Mutex;
+
+use crate::{
+    config::profile::{validate_name, ActiveProfile, ProfileConfig, DEFAULT_PROFILE},
+    domain::{
+        events::{DomainEvent, EventBus},
+        provider::connectivity::Connectivity,
+    },
+    driven::repository::ProfileRepository,
+    driving::tauri::profiles::ProfileResponse,
+};
+
+#[derive(Debug, Error)]
+pub enum ProfileError {
+    #[error("Invalid data: {0}")]
+    InvalidData(String),
+    #[error("Profile not found")]
+    NotFound,
+    #[error("Unknown error: {0}")]
+    Unknown(String),
+}
+
+/// A named profile and the database holding its coins, settings and API keys.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Profile {
+    pub name: String,
+    pub active: bool,
+    pub db_path: String,
+}
+
+fn profile(profiles: &ProfileConfig, active: &ActiveProfile, name: String) -> Profile {
+    Profile {
+        active: name == active.name(),
+        db_path: profiles.db_path(&name),
+        name,
+    }
+}
+
+fn valid_name(name: String) -> Result<String, ProfileError> {
+    let name = name.trim().to_string();
+    validate_name(&name).map_err(ProfileError::InvalidData)?;
+
+    Ok(name)
+}
+
+/// The default profile first, then the others by name.
+pub fn get_profiles(
+    profiles: &ProfileConfig,
+    active: &ActiveProfile,
+) -> Result<Vec<Profile>, ProfileError> {
+    Ok(profiles
+        .names()
+        .map_err(|e| ProfileError::Unknown(e.to_string()))?
+        .into_iter()
+        .map(|name| profile(profiles, active, name))
+        .collect())
+}
+
+/// Creates an empty profile without switching to it.
+pub fn create_profile(
+    profiles: &ProfileConfig,
+    active: &ActiveProfile,
+    name: String,
+) -> Result<Profile, ProfileError> {
+    let name = valid_name(name)?;
+
+    if profiles.exists(&name) {
+        return Err(ProfileError::InvalidData(format!(
+            "profile '{}' already exists",
+            name
+        )));
+    }
+
+    profiles
+        .create(&name)
+        .map_err(|e| ProfileError::Unknown(e.to_string()))?;
+
+    Ok(profile(profiles, active, name))
+}
+
+/// Opens the database of another profile in place of the current one and remembers the
+/// choice for the next launch. The repository is swapped under its lock, so operations in
+/// progress finish on the previous database and every later one uses the new one.
+pub async fn switch_profile<R: ProfileRepository>(
+    repository: Arc<Mutex<R>>,
+    events: &EventBus,
+    connectivity: &Connectivity,
+    profiles: &ProfileConfig,
+    active: &ActiveProfile,
+    name: String,
+) -> Result<Profile, ProfileError> {
+    let name = valid_name(name)?;
+
+    if !profiles.exists(&name) {
+        return Err(ProfileError::NotFound);
+    }
+
+    repository
+        .lock()
+        .await
+        .open(profiles.db_path(&name))
+        .await
+        .map_err(|e| ProfileError::Unknown(e.to_string()))?;
+
+    active.set(name.clone());
+    // Queued refreshes are for coins of the previous profile
+    connectivity.take_queued();
+
+    if let Err(e) = profiles.remember(&name) {
+        log::warn!("Could not remember the active profile: {}", e);
+    }
+
+    let profile = profile(profiles, active, name);
+    events.publish(DomainEvent::ProfileSwitched(ProfileResponse::from(
+        profile.clone(),
+    )));
+
+    Ok(profile)
+}
+
+/// Deletes a profile and its database. The default profile and the active one are kept.
+pub fn delete_profile(
+    profiles: &ProfileConfig,
+    active: &ActiveProfile,
+    name: String,
+) -> Result<(), ProfileError> {
+    let name = valid_name(name)?;
+
+    if name == DEFAULT_PROFILE {
+        return Err(ProfileError::InvalidData(
+            "the default profile cannot be deleted".to_string(),
+        ));
+    }
+
+    if name == active.name() {
+        return Err(ProfileError::InvalidData(
+            "switch to another profile before deleting this one".to_string(),
+        ));
+    }
+
+    if !profiles.exists(&name) {
+        return Err(ProfileError::NotFound);
+    }
+
+    profiles
+        .delete(&name)
+        .map_err(|e| ProfileError::Unknown(e.to_string()))
+}
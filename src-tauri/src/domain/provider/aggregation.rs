@@ -0,0 +1,269 @@
+use std::{fmt, str::FromStr};
+
+/// Where a price came from. CoinMarketCap is the primary provider and always queried; the
+/// others are optional secondary sources or the exchange of the price stream.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum QuoteSource {
+    CoinMarketCap,
+    CoinGecko,
+    Binance,
+    /// Only streams prices, it is not a secondary source
+    Kraken,
+}
+
+impl QuoteSource {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            QuoteSource::CoinMarketCap => "coinmarketcap",
+            QuoteSource::CoinGecko => "coingecko",
+            QuoteSource::Binance => "binance",
+            QuoteSource::Kraken => "kraken",
+        }
+    }
+}
+
+impl fmt::Display for QuoteSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for QuoteSource {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_lowercase().as_str() {
+            "coinmarketcap" | "cmc" => Ok(QuoteSource::CoinMarketCap),
+            "coingecko" => Ok(QuoteSource::CoinGecko),
+            "binance" => Ok(QuoteSource::Binance),
+            "kraken" => Ok(QuoteSource::Kraken),
+            _ => Err(format!("unknown quote source '{}'", value)),
+        }
+    }
+}
+
+/// Parses a comma-separated list of sources, e.g. `coingecko,binance`; blanks are skipped.
+pub fn parse_sources(value: &str) -> Result<Vec<QuoteSource>, String> {
+    let mut sources = value
+        .split(',')
+        .filter(|source| !source.trim().is_empty())
+        .map(QuoteSource::from_str)
+        .collect::<Result<Vec<QuoteSource>, String>>()?;
+
+    if sources.contains(&QuoteSource::Kraken) {
+        return Err("kraken only streams prices and cannot be queried".to_string());
+    }
+
+    sources.sort();
+    sources.dedup();
+
+    Ok(sources)
+}
+
+/// Formats sources the way `parse_sources` reads them.
+pub fn join_sources(sources: &[QuoteSource]) -> String {
+    sources
+        .iter()
+        .map(|source| source.as_str())
+        .collect::<Vec<&str>>()
+        .join(",")
+}
+
+/// How the prices that survive outlier rejection are combined.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AggregationMethod {
+    Median,
+    /// Weighted by 24h volume; falls back to the median when no source reports volume
+    VolumeWeighted,
+}
+
+impl FromStr for AggregationMethod {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_lowercase().as_str() {
+            "median" => Ok(AggregationMethod::Median),
+            "volume_weighted" | "vwap" => Ok(AggregationMethod::VolumeWeighted),
+            _ => Err(format!("unknown aggregation method '{}'", value)),
+        }
+    }
+}
+
+/// The USD price of a coin according to one source.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SourceQuote {
+    pub source: QuoteSource,
+    pub price: f64,
+    pub volume_24h: Option<f64>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct AggregatedQuote {
+    pub price: f64,
+    /// Sources whose price went into `price`
+    pub sources: Vec<QuoteSource>,
+    /// Sources dropped as outliers
+    pub rejected: Vec<QuoteSource>,
+}
+
+/// Combines the quotes of several sources into one price. Quotes further than
+/// `max_deviation` (a fraction, e.g. `0.05`) from the median of all quotes are dropped as
+/// outliers. When no two quotes agree there is no consensus and the first quote, the primary
+/// provider's, is kept alone. Returns `None` without a single usable price.
+pub fn aggregate(
+    quotes: &[SourceQuote],
+    method: AggregationMethod,
+    max_deviation: f64,
+) -> Option<AggregatedQuote> {
+    let quotes = quotes
+        .iter()
+        .filter(|quote| quote.price.is_finite() && quote.price > 0.0)
+        .collect::<Vec<&SourceQuote>>();
+
+    let reference = median(&quotes.iter().map(|quote| quote.price).collect::<Vec<f64>>())?;
+
+    let (mut accepted, mut rejected): (Vec<&SourceQuote>, Vec<&SourceQuote>) = quotes
+        .iter()
+        .partition(|quote| (quote.price - reference).abs() / reference <= max_deviation);
+
+    if accepted.is_empty() {
+        accepted = vec![quotes[0]];
+        rejected = quotes[1..].to_vec();
+    }
+
+    let prices = accepted
+        .iter()
+        .map(|quote| quote.price)
+        .collect::<Vec<f64>>();
+
+    let price = match method {
+        AggregationMethod::Median => median(&prices)?,
+        AggregationMethod::VolumeWeighted => {
+            volume_weighted(&accepted).or_else(|| median(&prices))?
+        }
+    };
+
+    Some(AggregatedQuote {
+        price,
+        sources: accepted.iter().map(|quote| quote.source).collect(),
+        rejected: rejected.iter().map(|quote| quote.source).collect(),
+    })
+}
+
+fn median(values: &[f64]) -> Option<f64> {
+    let mut values = values.to_vec();
+    values.sort_by(|a, b| a.total_cmp(b));
+
+    let middle = values.len() / 2;
+
+    match values.len() {
+        0 => None,
+        len if len % 2 == 0 => Some((values[middle - 1] + values[middle]) / 2.0),
+        _ => Some(values[middle]),
+    }
+}
+
+fn volume_weighted(quotes: &[&SourceQuote]) -> Option<f64> {
+    let (total, volume) = quotes
+        .iter()
+        .filter_map(|quote| {
+            quote
+                .volume_24h
+                .filter(|volume| volume.is_finite() && *volume > 0.0)
+                .map(|volume| (quote.price * volume, volume))
+        })
+        .fold((0.0, 0.0), |(total, sum), (value, volume)| {
+            (total + value, sum + volume)
+        });
+
+    (volume > 0.0).then_some(total / volume)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn quote(source: QuoteSource, price: f64, volume_24h: Option<f64>) -> SourceQuote {
+        SourceQuote {
+            source,
+            price,
+            volume_24h,
+        }
+    }
+
+    #[test]
+    fn outliers_beyond_the_deviation_are_dropped() {
+        let quotes = [
+            quote(QuoteSource::CoinMarketCap, 100.0, None),
+            quote(QuoteSource::CoinGecko, 101.0, None),
+            quote(QuoteSource::Binance, 150.0, None),
+        ];
+
+        let aggregated = aggregate(&quotes, AggregationMethod::Median, 0.05).unwrap();
+
+        assert_eq!(aggregated.price, 100.5);
+        assert_eq!(
+            aggregated.sources,
+            vec![QuoteSource::CoinMarketCap, QuoteSource::CoinGecko]
+        );
+        assert_eq!(aggregated.rejected, vec![QuoteSource::Binance]);
+    }
+
+    #[test]
+    fn volume_weighted_price_favours_liquid_sources() {
+        let quotes = [
+            quote(QuoteSource::CoinMarketCap, 100.0, Some(3.0)),
+            quote(QuoteSource::Binance, 104.0, Some(1.0)),
+        ];
+
+        let aggregated = aggregate(&quotes, AggregationMethod::VolumeWeighted, 0.05).unwrap();
+
+        assert_eq!(aggregated.price, 101.0);
+    }
+
+    #[test]
+    fn volume_weighted_price_without_volume_is_the_median() {
+        let quotes = [
+            quote(QuoteSource::CoinMarketCap, 100.0, None),
+            quote(QuoteSource::CoinGecko, 102.0, Some(0.0)),
+        ];
+
+        let aggregated = aggregate(&quotes, AggregationMethod::VolumeWeighted, 0.05).unwrap();
+
+        assert_eq!(aggregated.price, 101.0);
+    }
+
+    #[test]
+    fn without_consensus_the_primary_quote_is_kept() {
+        let quotes = [
+            quote(QuoteSource::CoinMarketCap, 100.0, None),
+            quote(QuoteSource::Binance, 200.0, None),
+        ];
+
+        let aggregated = aggregate(&quotes, AggregationMethod::Median, 0.05).unwrap();
+
+        assert_eq!(aggregated.price, 100.0);
+        assert_eq!(aggregated.sources, vec![QuoteSource::CoinMarketCap]);
+        assert_eq!(aggregated.rejected, vec![QuoteSource::Binance]);
+    }
+
+    #[test]
+    fn unusable_prices_are_ignored() {
+        let quotes = [
+            quote(QuoteSource::CoinMarketCap, 0.0, None),
+            quote(QuoteSource::CoinGecko, f64::NAN, None),
+        ];
+
+        assert_eq!(aggregate(&quotes, AggregationMethod::Median, 0.05), None);
+    }
+
+    #[test]
+    fn sources_round_trip_through_their_text_form() {
+        let sources = parse_sources("binance, coingecko,,binance").unwrap();
+
+        assert_eq!(sources, vec![QuoteSource::CoinGecko, QuoteSource::Binance]);
+        assert_eq!(join_sources(&sources), "coingecko,binance");
+        assert!(parse_sources("kraken").is_err());
+        assert!(parse_sources("bitstamp").is_err());
+    }
+}
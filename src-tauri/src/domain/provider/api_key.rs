@@ -0,0 +1,381 @@
+use std::{fmt, str::FromStr};
+
+use chrono::{DateTime, Datelike, NaiveDate};
+
+/// Seconds a key that hit the provider's per-minute rate limit is skipped.
+pub const MINUTE_LIMIT_COOLDOWN_SECS: i64 = 60;
+
+/// How the next key of the pool is chosen.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum KeySelection {
+    /// Lowest `priority` first; the others only when it fails
+    Priority,
+    /// Least recently used first, spreading the calls over every key
+    RoundRobin,
+}
+
+impl FromStr for KeySelection {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_lowercase().as_str() {
+            "priority" => Ok(KeySelection::Priority),
+            "round_robin" => Ok(KeySelection::RoundRobin),
+            _ => Err(format!("unknown key selection '{}'", value)),
+        }
+    }
+}
+
+/// What the provider last said about a key. Disabling a key is separate, see
+/// `ApiKey::enabled`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum KeyStatus {
+    Active,
+    /// Rejected by the provider, e.g. revoked or unpaid; skipped until tested again
+    Invalid,
+    /// Out of credits or rate limited until `ApiKey::available_at`
+    Exhausted,
+}
+
+impl KeyStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            KeyStatus::Active => "active",
+            KeyStatus::Invalid => "invalid",
+            KeyStatus::Exhausted => "exhausted",
+        }
+    }
+}
+
+impl fmt::Display for KeyStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for KeyStatus {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "active" => Ok(KeyStatus::Active),
+            "invalid" => Ok(KeyStatus::Invalid),
+            "exhausted" => Ok(KeyStatus::Exhausted),
+            _ => Err(format!("unknown key status '{}'", value)),
+        }
+    }
+}
+
+/// A provider API key of the pool. `credits_used` counts the credits spent with it in the
+/// UTC month `credits_month`, `YYYY-MM`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ApiKey {
+    pub id: u32,
+    pub label: String,
+    pub key: String,
+    /// The provider plan, as entered, e.g. `basic`
+    pub plan: String,
+    /// Credits that may be spent per month with this key, unlimited when `None`
+    pub monthly_credits: Option<u32>,
+    pub priority: u32,
+    pub enabled: bool,
+    pub status: KeyStatus,
+    /// The provider's message when the key was last rejected
+    pub status_message: Option<String>,
+    /// When an exhausted key may be used again
+    pub available_at: Option<i64>,
+    pub credits_month: String,
+    pub credits_used: u32,
+    pub last_used_at: Option<i64>,
+    pub created_at: i64,
+}
+
+impl ApiKey {
+    pub fn new(
+        label: String,
+        key: String,
+        plan: String,
+        monthly_credits: Option<u32>,
+        priority: u32,
+        now: i64,
+    ) -> Self {
+        ApiKey {
+            id: 0,
+            label,
+            key,
+            plan,
+            monthly_credits,
+            priority,
+            enabled: true,
+            status: KeyStatus::Active,
+            status_message: None,
+            available_at: None,
+            credits_month: usage_month(now),
+            credits_used: 0,
+            last_used_at: None,
+            created_at: now,
+        }
+    }
+
+    /// Credits spent in the month of `now`; the count restarts every month.
+    pub fn credits_used_in(&self, now: i64) -> u32 {
+        if self.credits_month == usage_month(now) {
+            self.credits_used
+        } else {
+            0
+        }
+    }
+
+    /// Whether the key may be tried at `now`.
+    pub fn is_usable(&self, now: i64) -> bool {
+        let available = match self.status {
+            KeyStatus::Active => true,
+            KeyStatus::Invalid => false,
+            KeyStatus::Exhausted => self.available_at.is_none_or(|at| at <= now),
+        };
+        let within_limit = self
+            .monthly_credits
+            .is_none_or(|limit| self.credits_used_in(now) < limit);
+
+        self.enabled && available && within_limit
+    }
+
+    /// Records a call made with the key at `now` and the credits it cost.
+    pub fn used(&mut self, credits: u32, now: i64) {
+        let month = usage_month(now);
+
+        if self.credits_month != month {
+            self.credits_month = month;
+            self.credits_used = 0;
+        }
+
+        self.credits_used = self.credits_used.saturating_add(credits);
+        self.last_used_at = Some(now);
+    }
+
+    /// Applies what the provider said about the key.
+    pub fn failed(&mut self, failure: &KeyFailure, message: String) {
+        match failure {
+            KeyFailure::Invalid | KeyFailure::Expired => {
+                self.status = KeyStatus::Invalid;
+                self.available_at = None;
+            }
+            KeyFailure::Exhausted(until) => {
+                self.status = KeyStatus::Exhausted;
+                self.available_at = Some(*until);
+            }
+            // The key is fine, only not for this endpoint
+            KeyFailure::NotAuthorized => return,
+        }
+
+        self.status_message = Some(message);
+    }
+
+    /// Marks the key as accepted by the provider.
+    pub fn succeeded(&mut self) {
+        self.status = KeyStatus::Active;
+        self.status_message = None;
+        self.available_at = None;
+    }
+}
+
+/// Why the provider refused a key, in which case the request is retried with another one.
+#[derive(Clone, Debug, PartialEq)]
+pub enum KeyFailure {
+    Invalid,
+    /// The plan of the key requires a payment or its payment expired
+    Expired,
+    /// Out of credits or rate limited until the time given
+    Exhausted(i64),
+    /// The plan does not cover the endpoint, another key's might
+    NotAuthorized,
+}
+
+/// Classifies a provider `error_code` received at `now`. Errors that are not about the key,
+/// including the per-IP rate limit that every key shares, yield `None`.
+pub fn key_failure(error_code: i64, now: i64) -> Option<KeyFailure> {
+    match error_code {
+        // Invalid, missing, required or disabled
+        1001 | 1002 | 1005 | 1007 => Some(KeyFailure::Invalid),
+        1003 | 1004 => Some(KeyFailure::Expired),
+        1006 => Some(KeyFailure::NotAuthorized),
+        1008 => Some(KeyFailure::Exhausted(now + MINUTE_LIMIT_COOLDOWN_SECS)),
+        1009 => Some(KeyFailure::Exhausted(next_day(now))),
+        1010 => Some(KeyFailure::Exhausted(next_month(now))),
+        _ => None,
+    }
+}
+
+/// The usable keys in the order they are tried.
+pub fn select_keys(keys: &[ApiKey], selection: KeySelection, now: i64) -> Vec<&ApiKey> {
+    let mut keys = keys
+        .iter()
+        .filter(|key| key.is_usable(now))
+        .collect::<Vec<&ApiKey>>();
+
+    match selection {
+        KeySelection::Priority => keys.sort_by_key(|key| (key.priority, key.id)),
+        KeySelection::RoundRobin => keys.sort_by_key(|key| (key.last_used_at, key.id)),
+    }
+
+    keys
+}
+
+/// Hides all but the last four characters of a key.
+pub fn mask_key(key: &str) -> String {
+    let visible = key
+        .chars()
+        .rev()
+        .take(4)
+        .collect::<Vec<char>>()
+        .into_iter()
+        .rev()
+        .collect::<String>();
+
+    format!("****{}", visible)
+}
+
+/// The UTC month credits of a key are counted against, `YYYY-MM`.
+pub fn usage_month(timestamp: i64) -> String {
+    DateTime::from_timestamp(timestamp, 0)
+        .map(|time| time.format("%Y-%m").to_string())
+        .unwrap_or_default()
+}
+
+fn next_day(now: i64) -> i64 {
+    let day = DateTime::from_timestamp(now, 0)
+        .unwrap_or_default()
+        .date_naive();
+
+    start_of(day.succ_opt().unwrap_or(day))
+}
+
+fn next_month(now: i64) -> i64 {
+    let day = DateTime::from_timestamp(now, 0)
+        .unwrap_or_default()
+        .date_naive();
+    let (year, month) = match day.month() {
+        12 => (day.year() + 1, 1),
+        month => (day.year(), month + 1),
+    };
+
+    start_of(NaiveDate::from_ymd_opt(year, month, 1).unwrap_or(day))
+}
+
+fn start_of(day: NaiveDate) -> i64 {
+    day.and_time(chrono::NaiveTime::MIN).and_utc().timestamp()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 2024-03-15T12:00:00Z
+    const NOW: i64 = 1710504000;
+
+    fn key(id: u32, priority: u32, last_used_at: Option<i64>) -> ApiKey {
+        ApiKey {
+            id,
+            last_used_at,
+            ..ApiKey::new(
+                format!("key {}", id),
+                format!("secret-{}", id),
+                "basic".to_string(),
+                None,
+                priority,
+                NOW,
+            )
+        }
+    }
+
+    fn ids(keys: Vec<&ApiKey>) -> Vec<u32> {
+        keys.iter().map(|key| key.id).collect()
+    }
+
+    #[test]
+    fn priority_selection_prefers_the_lowest_priority() {
+        let keys = [key(1, 2, None), key(2, 1, None), key(3, 1, Some(NOW))];
+
+        assert_eq!(
+            ids(select_keys(&keys, KeySelection::Priority, NOW)),
+            vec![2, 3, 1]
+        );
+    }
+
+    #[test]
+    fn round_robin_selection_prefers_the_least_recently_used() {
+        let keys = [
+            key(1, 0, Some(NOW - 10)),
+            key(2, 0, Some(NOW - 20)),
+            key(3, 0, None),
+        ];
+
+        assert_eq!(
+            ids(select_keys(&keys, KeySelection::RoundRobin, NOW)),
+            vec![3, 2, 1]
+        );
+    }
+
+    #[test]
+    fn disabled_invalid_exhausted_and_spent_keys_are_skipped() {
+        let mut disabled = key(1, 0, None);
+        disabled.enabled = false;
+        let mut invalid = key(2, 0, None);
+        invalid.failed(&KeyFailure::Invalid, "API key invalid".to_string());
+        let mut exhausted = key(3, 0, None);
+        exhausted.failed(&KeyFailure::Exhausted(NOW + 60), "Daily limit".to_string());
+        let mut recovered = key(4, 0, None);
+        recovered.failed(&KeyFailure::Exhausted(NOW), "Minute limit".to_string());
+        let mut spent = key(5, 0, None);
+        spent.monthly_credits = Some(100);
+        spent.used(100, NOW);
+
+        let keys = [disabled, invalid, exhausted, recovered, spent];
+
+        assert_eq!(
+            ids(select_keys(&keys, KeySelection::Priority, NOW)),
+            vec![4]
+        );
+    }
+
+    #[test]
+    fn monthly_credits_restart_every_month() {
+        let mut key = key(1, 0, None);
+        key.monthly_credits = Some(100);
+        key.used(100, NOW);
+
+        assert!(!key.is_usable(NOW));
+        assert!(key.is_usable(next_month(NOW)));
+
+        key.used(5, next_month(NOW));
+        assert_eq!(key.credits_used_in(next_month(NOW)), 5);
+    }
+
+    #[test]
+    fn provider_errors_are_classified() {
+        assert_eq!(key_failure(1001, NOW), Some(KeyFailure::Invalid));
+        assert_eq!(key_failure(1004, NOW), Some(KeyFailure::Expired));
+        assert_eq!(key_failure(1006, NOW), Some(KeyFailure::NotAuthorized));
+        assert_eq!(
+            key_failure(1008, NOW),
+            Some(KeyFailure::Exhausted(NOW + 60))
+        );
+        // 2024-03-16T00:00:00Z and 2024-04-01T00:00:00Z
+        assert_eq!(
+            key_failure(1009, NOW),
+            Some(KeyFailure::Exhausted(1710547200))
+        );
+        assert_eq!(
+            key_failure(1010, NOW),
+            Some(KeyFailure::Exhausted(1711929600))
+        );
+        assert_eq!(key_failure(1011, NOW), None);
+        assert_eq!(key_failure(400, NOW), None);
+    }
+
+    #[test]
+    fn keys_are_masked() {
+        assert_eq!(mask_key("0123-4567-89ab"), "****89ab");
+        assert_eq!(mask_key("ab"), "****ab");
+    }
+}
@@ -0,0 +1,193 @@
+use std::sync::Arc;
+
+use thiserror::Error;
+use tokio::sync::Mutex;
+
+use crate::{
+    config::Config,
+    domain::{
+        settings::{
+            find_setting::{find_setting, FindError},
+            settings::Setting,
+        },
+        Value,
+    },
+    driven::repository::{
+        ProviderRepository, RepoCreateError, RepoDeleteError, RepoFindOneError, RepoGetAllError,
+        RepoUpdateError, Repository,
+    },
+    fetch::{coinmarketcap::fetch_key_info, FetchError},
+};
+
+use super::{api_key::ApiKey, provider::PlanInfo};
+
+#[derive(Debug, Error)]
+pub enum ApiKeyError {
+    #[error("Invalid data: {0}")]
+    InvalidData(String),
+    #[error("API key not found")]
+    NotFound,
+    #[error("CoinMarketCap token not specified")]
+    TokenNotSpecified,
+    #[error("Fetch error: {0}")]
+    Fetch(#[from] FetchError),
+    #[error("Unknown error: {0}")]
+    Unknown(String),
+}
+
+impl From<RepoCreateError> for ApiKeyError {
+    fn from(e: RepoCreateError) -> Self {
+        match e {
+            RepoCreateError::InvalidData(e) => ApiKeyError::InvalidData(e),
+            RepoCreateError::Unknown(e) => ApiKeyError::Unknown(e),
+        }
+    }
+}
+
+impl From<RepoFindOneError> for ApiKeyError {
+    fn from(e: RepoFindOneError) -> Self {
+        match e {
+            RepoFindOneError::NotFound => ApiKeyError::NotFound,
+            RepoFindOneError::Unknown(e) => ApiKeyError::Unknown(e),
+        }
+    }
+}
+
+impl From<RepoGetAllError> for ApiKeyError {
+    fn from(e: RepoGetAllError) -> Self {
+        match e {
+            RepoGetAllError::Unknown(e) => ApiKeyError::Unknown(e),
+        }
+    }
+}
+
+impl From<RepoUpdateError> for ApiKeyError {
+    fn from(e: RepoUpdateError) -> Self {
+        match e {
+            RepoUpdateError::InvalidData(e) => ApiKeyError::InvalidData(e),
+            RepoUpdateError::NotFound => ApiKeyError::NotFound,
+            RepoUpdateError::Unknown(e) => ApiKeyError::Unknown(e),
+        }
+    }
+}
+
+impl From<RepoDeleteError> for ApiKeyError {
+    fn from(e: RepoDeleteError) -> Self {
+        match e {
+            RepoDeleteError::InvalidData(e) => ApiKeyError::InvalidData(e),
+            RepoDeleteError::NotFound => ApiKeyError::NotFound,
+            RepoDeleteError::Unknown(e) => ApiKeyError::Unknown(e),
+        }
+    }
+}
+
+/// The outcome of testing a key: the plan the provider reports for it, or why it refused
+/// the key. `key` carries the status the test left it in.
+#[derive(Clone, Debug, PartialEq)]
+pub struct KeyTest {
+    pub key: ApiKey,
+    pub plan: Option<PlanInfo>,
+    pub error: Option<String>,
+}
+
+/// Adds a key to the pool, enabled. Lower `priority` is tried first.
+pub async fn add_api_key<R: ProviderRepository>(
+    repository: Arc<Mutex<R>>,
+    label: String,
+    key: String,
+    plan: Option<String>,
+    monthly_credits: Option<u32>,
+    priority: Option<u32>,
+) -> Result<ApiKey, ApiKeyError> {
+    let label = label.trim().to_string();
+    let key = key.trim().to_string();
+
+    if label.is_empty() || key.is_empty() {
+        return Err(ApiKeyError::InvalidData(
+            "label and key must not be empty".to_string(),
+        ));
+    }
+
+    let key = ApiKey::new(
+        label,
+        key,
+        plan.unwrap_or_default().trim().to_string(),
+        monthly_credits.filter(|credits| *credits > 0),
+        priority.unwrap_or_default(),
+        chrono::Utc::now().timestamp(),
+    );
+
+    Ok(repository.lock().await.add_key(key).await?)
+}
+
+pub async fn get_api_keys<R: ProviderRepository>(
+    repository: Arc<Mutex<R>>,
+) -> Result<Vec<ApiKey>, ApiKeyError> {
+    Ok(repository.lock().await.get_keys().await?)
+}
+
+/// Disables a key so it is no longer used, or enables it again.
+pub async fn set_api_key_enabled<R: ProviderRepository>(
+    repository: Arc<Mutex<R>>,
+    id: u32,
+    enabled: bool,
+) -> Result<ApiKey, ApiKeyError> {
+    let mut repository = repository.lock().await;
+
+    let mut key = repository.find_key(id).await?;
+    key.enabled = enabled;
+
+    Ok(repository.update_key(key).await?)
+}
+
+pub async fn delete_api_key<R: ProviderRepository>(
+    repository: Arc<Mutex<R>>,
+    id: u32,
+) -> Result<(), ApiKeyError> {
+    Ok(repository.lock().await.delete_key(id).await?)
+}
+
+/// Asks the provider for the plan of one key, which costs no credits. The key's status
+/// follows the answer, so an invalid or exhausted key that passes is used again. Errors
+/// that are not about the key, e.g. an unreachable provider, fail the test.
+pub async fn test_api_key<R: ProviderRepository>(
+    repository: Arc<Mutex<R>>,
+    config: &Config,
+    id: u32,
+) -> Result<KeyTest, ApiKeyError> {
+    let key = repository.lock().await.find_key(id).await?;
+
+    let (plan, error) = match fetch_key_info(repository.clone(), config, key.key).await {
+        Ok(plan) => (Some(plan), None),
+        Err(FetchError::Unauthorized(e) | FetchError::RateLimited(e)) => (None, Some(e)),
+        Err(e) => return Err(ApiKeyError::Fetch(e)),
+    };
+
+    Ok(KeyTest {
+        key: repository.lock().await.find_key(id).await?,
+        plan,
+        error,
+    })
+}
+
+/// The API key to pass to the provider calls: the `api_key` setting, or without it any key
+/// of the pool, since the calls pick their key from the pool first anyway.
+pub async fn find_token<R: Repository<Setting, String> + ProviderRepository>(
+    repository: Arc<Mutex<R>>,
+) -> Result<String, ApiKeyError> {
+    match find_setting(repository.clone(), "api_key".to_string()).await {
+        Ok(setting) => return Ok(setting.value().value().to_string()),
+        Err(FindError::NotFound) => {}
+        Err(FindError::Unknown(e)) => return Err(ApiKeyError::Unknown(e)),
+    }
+
+    repository
+        .lock()
+        .await
+        .get_keys()
+        .await?
+        .into_iter()
+        .find(|key| key.enabled)
+        .map(|key| key.key)
+        .ok_or(ApiKeyError::TokenNotSpecified)
+}
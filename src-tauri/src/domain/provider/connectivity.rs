@@ -0,0 +1,104 @@
+use std::{
+    collections::BTreeSet,
+    sync::{Arc, Mutex},
+};
+
+/// Whether the provider was reachable at the last attempt, and the coins whose refresh
+/// failed because it was not. Cloning shares the same state.
+#[derive(Clone)]
+pub struct Connectivity {
+    state: Arc<Mutex<ConnectivityState>>,
+}
+
+/// A snapshot of `Connectivity`; `since` is when it last went online or offline.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ConnectivityStatus {
+    pub online: bool,
+    pub since: i64,
+    pub queued: Vec<u32>,
+}
+
+struct ConnectivityState {
+    online: bool,
+    since: i64,
+    queued: BTreeSet<u32>,
+}
+
+impl Connectivity {
+    /// Starts online; the first unreachable request says otherwise.
+    pub fn new() -> Self {
+        Self {
+            state: Arc::new(Mutex::new(ConnectivityState {
+                online: true,
+                since: chrono::Utc::now().timestamp(),
+                queued: BTreeSet::new(),
+            })),
+        }
+    }
+
+    pub fn status(&self) -> ConnectivityStatus {
+        let state = self.lock();
+
+        ConnectivityStatus {
+            online: state.online,
+            since: state.since,
+            queued: state.queued.iter().copied().collect(),
+        }
+    }
+
+    pub fn is_online(&self) -> bool {
+        self.lock().online
+    }
+
+    pub fn has_queued(&self) -> bool {
+        !self.lock().queued.is_empty()
+    }
+
+    /// Marks the provider unreachable and queues `ids` for a refresh once it is back.
+    /// Returns whether this changed the state from online.
+    pub fn went_offline(&self, ids: &[u32], now: i64) -> bool {
+        let mut state = self.lock();
+        state.queued.extend(ids);
+
+        if !state.online {
+            return false;
+        }
+
+        state.online = false;
+        state.since = now;
+        true
+    }
+
+    /// Marks the provider reachable. Returns whether this changed the state from offline.
+    pub fn went_online(&self, now: i64) -> bool {
+        let mut state = self.lock();
+
+        if state.online {
+            return false;
+        }
+
+        state.online = true;
+        state.since = now;
+        true
+    }
+
+    /// Removes and returns the queued coin ids.
+    pub fn take_queued(&self) -> Vec<u32> {
+        std::mem::take(&mut self.lock().queued)
+            .into_iter()
+            .collect()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, ConnectivityState> {
+        // The state stays consistent even if a holder panicked, every update is one step
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl Default for Connectivity {
+    fn default() -> Self {
+        Self::new()
+    }
+}
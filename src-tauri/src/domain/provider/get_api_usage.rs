@@ -0,0 +1,44 @@
+use std::sync::Arc;
+
+use thiserror::Error;
+use tokio::sync::Mutex;
+
+use crate::driven::repository::{ProviderRepository, RepoGetAllError};
+
+use super::provider::{EndpointUsage, MAX_USAGE_DAYS};
+
+const SECONDS_PER_DAY: i64 = 24 * 60 * 60;
+
+#[derive(Debug, Error)]
+pub enum GetApiUsageError {
+    #[error("Invalid data: {0}")]
+    InvalidData(String),
+    #[error("Unknown error: {0}")]
+    Unknown(String),
+}
+
+/// Returns the recorded provider calls of the last `days` UTC days, today included,
+/// aggregated per day and endpoint.
+pub async fn get_api_usage<R: ProviderRepository>(
+    repository: Arc<Mutex<R>>,
+    days: u32,
+) -> Result<Vec<EndpointUsage>, GetApiUsageError> {
+    if days == 0 || days > MAX_USAGE_DAYS {
+        return Err(GetApiUsageError::InvalidData(format!(
+            "days must be between 1 and {}",
+            MAX_USAGE_DAYS
+        )));
+    }
+
+    let now = chrono::Utc::now().timestamp();
+    let since = now - now.rem_euclid(SECONDS_PER_DAY) - (days as i64 - 1) * SECONDS_PER_DAY;
+
+    repository
+        .lock()
+        .await
+        .usage_by_endpoint(since)
+        .await
+        .map_err(|e| match e {
+            RepoGetAllError::Unknown(e) => GetApiUsageError::Unknown(e),
+        })
+}
@@ -0,0 +1,56 @@
+use std::sync::Arc;
+
+use thiserror::Error;
+use tokio::sync::Mutex;
+
+use crate::{
+    config::Config,
+    driven::repository::{ProviderRepository, RepoCreateError, RepoFindOneError},
+    fetch::{coinmarketcap::fetch_key_info, FetchError},
+};
+
+use super::provider::PlanInfo;
+
+#[derive(Debug, Error)]
+pub enum GetPlanInfoError {
+    #[error("Fetch error: {0}")]
+    Fetch(#[from] FetchError),
+    #[error("CoinMarketCap token not specified")]
+    TokenNotSpecified,
+    #[error("Unknown error: {0}")]
+    Unknown(String),
+}
+
+/// Returns the plan limits of the API key. They are fetched from the provider the first
+/// time or when `refresh` is set and served from the database otherwise, so `token` is only
+/// needed then.
+pub async fn get_plan_info<R: ProviderRepository>(
+    repository: Arc<Mutex<R>>,
+    config: &Config,
+    token: Option<String>,
+    refresh: bool,
+) -> Result<PlanInfo, GetPlanInfoError> {
+    if !refresh {
+        match repository.lock().await.find_plan().await {
+            Ok(plan) => return Ok(plan),
+            Err(RepoFindOneError::NotFound) => {}
+            Err(RepoFindOneError::Unknown(e)) => return Err(GetPlanInfoError::Unknown(e)),
+        }
+    }
+
+    let token = token.ok_or(GetPlanInfoError::TokenNotSpecified)?;
+    let plan = fetch_key_info(repository.clone(), config, token).await?;
+
+    repository
+        .lock()
+        .await
+        .store_plan(plan.clone())
+        .await
+        .map_err(|e| match e {
+            RepoCreateError::InvalidData(e) | RepoCreateError::Unknown(e) => {
+                GetPlanInfoError::Unknown(e)
+            }
+        })?;
+
+    Ok(plan)
+}
@@ -0,0 +1,8 @@
+pub(crate) mod aggregation;
+pub(crate) mod api_key;
+pub(crate) mod api_keys;
+pub(crate) mod connectivity;
+pub(crate) mod get_api_usage;
+pub(crate) mod get_plan_info;
+pub(crate) mod provider;
+pub(crate) mod set_api_key;
@@ -0,0 +1,92 @@
+use chrono::DateTime;
+
+/// Provider responses stay usable this long after they expire, to answer requests made
+/// once the credit budget is exhausted.
+pub const STALE_RETENTION_SECS: i64 = 7 * 24 * 60 * 60;
+
+/// Recorded provider calls are kept this long.
+pub const CALL_RETENTION_SECS: i64 = 90 * 24 * 60 * 60;
+
+pub const DEFAULT_USAGE_DAYS: u32 = 30;
+pub const MAX_USAGE_DAYS: u32 = 90;
+
+/// The `data` of a provider response, stored to answer identical requests without spending
+/// credits. `key` identifies the request by endpoint and query.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CachedResponse {
+    pub key: String,
+    pub body: String,
+    pub fetched_at: i64,
+    pub expires_at: i64,
+}
+
+impl CachedResponse {
+    pub fn is_fresh(&self, now: i64) -> bool {
+        now < self.expires_at
+    }
+}
+
+/// Builds the cache key of a request; query parameters are sorted so their order does not
+/// matter.
+pub fn cache_key(path: &str, query: &[(&str, String)]) -> String {
+    let mut query = query
+        .iter()
+        .map(|(name, value)| format!("{}={}", name, value))
+        .collect::<Vec<String>>();
+    query.sort();
+
+    format!("{}?{}", path, query.join("&"))
+}
+
+/// The UTC day credits are counted against, `YYYY-MM-DD`; the provider resets daily credits
+/// at midnight UTC.
+pub fn usage_day(timestamp: i64) -> String {
+    DateTime::from_timestamp(timestamp, 0)
+        .map(|time| time.format("%Y-%m-%d").to_string())
+        .unwrap_or_default()
+}
+
+/// Whether `used` credits exhaust a daily budget; no budget means no limit.
+pub fn is_budget_exhausted(used: u32, budget: Option<u32>) -> bool {
+    budget.is_some_and(|budget| used >= budget)
+}
+
+/// One outbound request to the provider. `status` is missing when no response arrived and
+/// `error_code` is the provider's own code, 0 on success.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ProviderCall {
+    pub called_at: i64,
+    pub endpoint: String,
+    pub status: Option<u16>,
+    pub latency_ms: u32,
+    pub credits: u32,
+    pub error_code: Option<i64>,
+}
+
+/// Calls to one endpoint on one UTC day.
+#[derive(Clone, Debug, PartialEq)]
+pub struct EndpointUsage {
+    pub day: String,
+    pub endpoint: String,
+    pub calls: u32,
+    /// Calls without a successful response
+    pub errors: u32,
+    pub credits: u32,
+    pub avg_latency_ms: f64,
+    pub max_latency_ms: u32,
+}
+
+/// Limits of the provider plan of the API key and the usage the provider counted against
+/// them, as of `fetched_at`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PlanInfo {
+    pub credit_limit_daily: Option<u32>,
+    pub credit_limit_monthly: Option<u32>,
+    pub rate_limit_minute: Option<u32>,
+    pub credits_used_day: u32,
+    pub credits_used_month: u32,
+    /// RFC 3339 time the monthly credits reset
+    pub monthly_reset: Option<String>,
+    /// RFC 3339 time the plan was fetched
+    pub fetched_at: String,
+}
@@ -0,0 +1,160 @@
+use std::sync::Arc;
+
+use thiserror::Error;
+use tokio::sync::Mutex;
+
+use crate::{
+    config::Config,
+    domain::{
+        audit::audit::Origin,
+        settings::{
+            create_setting::{create_setting, CreateError},
+            settings::Setting,
+        },
+    },
+    driven::repository::{AuditRepository, ProviderRepository, RepoCreateError, Repository},
+    driving::tauri::settings::CreateSettingRequest,
+    fetch::{coinmarketcap::validate_key, FetchError},
+};
+
+use super::{api_key::KeyFailure, provider::PlanInfo};
+
+#[derive(Debug, Error)]
+pub enum SetApiKeyError {
+    #[error("Invalid data: {0}")]
+    InvalidData(String),
+    #[error("Fetch error: {0}")]
+    Fetch(#[from] FetchError),
+    #[error("Unknown error: {0}")]
+    Unknown(String),
+}
+
+impl From<CreateError> for SetApiKeyError {
+    fn from(e: CreateError) -> Self {
+        match e {
+            CreateError::Unknown(e) => SetApiKeyError::Unknown(e),
+            e => SetApiKeyError::InvalidData(e.to_string()),
+        }
+    }
+}
+
+impl From<RepoCreateError> for SetApiKeyError {
+    fn from(e: RepoCreateError) -> Self {
+        match e {
+            RepoCreateError::InvalidData(e) | RepoCreateError::Unknown(e) => {
+                SetApiKeyError::Unknown(e)
+            }
+        }
+    }
+}
+
+/// What the provider said about a key before it was stored.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum KeyValidity {
+    Valid,
+    /// Unknown, revoked or disabled
+    Invalid,
+    /// The plan requires a payment or its payment expired
+    Expired,
+    /// Genuine, but out of credits or rate limited for now
+    LimitReached,
+}
+
+impl KeyValidity {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            KeyValidity::Valid => "valid",
+            KeyValidity::Invalid => "invalid",
+            KeyValidity::Expired => "expired",
+            KeyValidity::LimitReached => "limit_reached",
+        }
+    }
+
+    /// Whether the key is worth storing; one that reached a limit works again once it resets.
+    pub fn is_usable(&self) -> bool {
+        matches!(self, KeyValidity::Valid | KeyValidity::LimitReached)
+    }
+}
+
+/// The outcome of validating a key: the plan the provider reports for it when it accepted
+/// the key, otherwise why not, and when a limit it reached resets.
+#[derive(Clone, Debug, PartialEq)]
+pub struct KeyValidation {
+    pub validity: KeyValidity,
+    pub message: Option<String>,
+    pub available_at: Option<i64>,
+    pub plan: Option<PlanInfo>,
+    /// Whether the key was stored as the `api_key` setting
+    pub stored: bool,
+}
+
+/// Checks `key` against the provider's key info endpoint, which costs no credits, and
+/// stores it as the `api_key` setting together with its plan unless the provider refused
+/// it. Errors that are not about the key, e.g. an unreachable provider, store nothing.
+pub async fn set_api_key<R>(
+    repository: Arc<Mutex<R>>,
+    origin: Origin,
+    config: &Config,
+    key: String,
+) -> Result<KeyValidation, SetApiKeyError>
+where
+    R: Repository<Setting, String> + ProviderRepository + AuditRepository,
+{
+    let key = key.trim().to_string();
+
+    if key.is_empty() {
+        return Err(SetApiKeyError::InvalidData(
+            "key must not be empty".to_string(),
+        ));
+    }
+
+    let mut validation = match validate_key(repository.clone(), config, key.clone()).await {
+        Ok(plan) => KeyValidation {
+            validity: KeyValidity::Valid,
+            message: None,
+            available_at: None,
+            plan: Some(plan),
+            stored: false,
+        },
+        Err((e, Some(failure))) => {
+            let (validity, available_at) = match failure {
+                KeyFailure::Invalid => (KeyValidity::Invalid, None),
+                KeyFailure::Expired => (KeyValidity::Expired, None),
+                KeyFailure::Exhausted(until) => (KeyValidity::LimitReached, Some(until)),
+                // The key is accepted, only its plan is not reported
+                KeyFailure::NotAuthorized => (KeyValidity::Valid, None),
+            };
+
+            KeyValidation {
+                validity,
+                message: Some(e.to_string()),
+                available_at,
+                plan: None,
+                stored: false,
+            }
+        }
+        Err((e, None)) => return Err(SetApiKeyError::Fetch(e)),
+    };
+
+    if !validation.validity.is_usable() {
+        return Ok(validation);
+    }
+
+    create_setting(
+        repository.clone(),
+        origin,
+        CreateSettingRequest {
+            key: "api_key".to_string(),
+            value: key,
+        },
+    )
+    .await?;
+
+    if let Some(plan) = &validation.plan {
+        repository.lock().await.store_plan(plan.clone()).await?;
+    }
+
+    validation.stored = true;
+
+    Ok(validation)
+}
@@ -2,7 +2,14 @@ use std::sync::Arc;
 use thiserror::Error;
 use tokio::sync::Mutex;
 
-use crate::{driven::repository::{RepoCreateError, Repository}, driving::tauri::settings::CreateSettingRequest};
+use crate::{
+    domain::{
+        audit::audit::{self, AuditRecord, Origin},
+        Value,
+    },
+    driven::repository::{AuditRepository, RepoCreateError, RepoFindOneError, Repository},
+    driving::tauri::settings::CreateSettingRequest,
+};
 
 use super::settings::{Setting, SettingError};
 
@@ -16,19 +23,30 @@ pub enum CreateError {
     SettingError(#[from] SettingError),
 }
 
-pub async fn create_setting<R: Repository<Setting, String>>(
+pub async fn create_setting<R: Repository<Setting, String> + AuditRepository>(
     repository: Arc<Mutex<R>>,
+    origin: Origin,
     setting: CreateSettingRequest,
 ) -> Result<Setting, CreateError> {
     let setting = Setting::try_from(setting)?;
+    let mut repository = repository.lock().await;
 
-    repository
-        .lock()
-        .await
-        .create(setting)
-        .await
-        .map_err(|e| match e {
-            RepoCreateError::InvalidData(e) => CreateError::InvalidData(e),
-            RepoCreateError::Unknown(e) => CreateError::Unknown(e),
-        })
+    let before = match repository.find_one(setting.key().value().clone()).await {
+        Ok(setting) => Some(setting),
+        Err(RepoFindOneError::NotFound) => None,
+        Err(RepoFindOneError::Unknown(e)) => return Err(CreateError::Unknown(e)),
+    };
+
+    let setting = repository.create(setting).await.map_err(|e| match e {
+        RepoCreateError::InvalidData(e) => CreateError::InvalidData(e),
+        RepoCreateError::Unknown(e) => CreateError::Unknown(e),
+    })?;
+
+    audit::append(
+        &mut *repository,
+        AuditRecord::setting(origin, "create_setting", before.as_ref(), Some(&setting)),
+    )
+    .await;
+
+    Ok(setting)
 }
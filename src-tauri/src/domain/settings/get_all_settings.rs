@@ -0,0 +1,27 @@
+use std::sync::Arc;
+
+use thiserror::Error;
+use tokio::sync::Mutex;
+
+use crate::driven::repository::{RepoGetAllError, Repository};
+
+use super::settings::Setting;
+
+#[derive(Debug, Error)]
+pub enum GetAllSettingsError {
+    #[error("Unknown error: {0}")]
+    Unknown(String),
+}
+
+pub async fn get_all_settings<R: Repository<Setting, String>>(
+    repository: Arc<Mutex<R>>,
+) -> Result<Vec<Setting>, GetAllSettingsError> {
+    repository
+        .lock()
+        .await
+        .get_all()
+        .await
+        .map_err(|e| match e {
+            RepoGetAllError::Unknown(e) => GetAllSettingsError::Unknown(e),
+        })
+}
@@ -1,3 +1,4 @@
 pub(crate) mod create_setting;
 pub(crate) mod find_setting;
+pub(crate) mod get_all_settings;
 pub(crate) mod settings;
@@ -0,0 +1,2 @@
+pub(crate) mod stream_prices;
+pub(crate) mod ticker;
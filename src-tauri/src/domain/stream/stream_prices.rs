@@ -0,0 +1,187 @@
+use std::{collections::BTreeMap, sync::Arc};
+
+use thiserror::Error;
+use tokio::sync::{broadcast::error::RecvError, mpsc, Mutex};
+
+use crate::{
+    config::{Config, StreamExchange},
+    domain::{
+        audit::audit::{self, AuditRecord, Origin},
+        coin::{
+            coin::{Coin, CoinSources},
+            get_all_coins::{get_all_coins, GetAllCoinsError},
+        },
+        events::{DomainEvent, EventBus},
+        provider::aggregation::QuoteSource,
+        Value,
+    },
+    driven::repository::{
+        AuditRepository, RepoFindOneError, RepoUpdateError, Repository, WatchlistRepository,
+    },
+    driving::tauri::{coins::CoinResponse, stream::LivePriceResponse},
+    stream::{stream_tickers, StreamEvent},
+};
+
+use super::ticker::{LivePrices, Ticker, WriteThrottle};
+
+#[derive(Debug, Error)]
+pub enum StreamPricesError {
+    #[error("{0}")]
+    Coins(#[from] GetAllCoinsError),
+}
+
+/// Streams the tickers of the tracked coins' symbols into `live_prices`, publishing
+/// `price-ticked` for each and writing a coin's price at most once per
+/// `stream.write_interval`. The subscription follows coins being added and deleted. Runs
+/// until the event bus closes.
+pub async fn stream_prices<R: Repository<Coin, u32> + WatchlistRepository + AuditRepository>(
+    repository: Arc<Mutex<R>>,
+    events: &EventBus,
+    config: &Config,
+    live_prices: &LivePrices,
+) -> Result<(), StreamPricesError> {
+    let mut domain_events = events.subscribe();
+    let mut throttle =
+        WriteThrottle::new(i64::try_from(config.stream.write_interval.as_secs()).unwrap_or(0));
+
+    loop {
+        let coins = coins_by_symbol(repository.clone()).await?;
+        let symbols = coins.keys().cloned().collect::<Vec<String>>();
+        live_prices.retain(&symbols);
+        live_prices.set_connected(false);
+
+        let (sender, mut receiver) = mpsc::unbounded_channel();
+        // Without coins nothing is streamed; the dropped sender leaves the receiver empty
+        let streaming = (!symbols.is_empty()).then(|| {
+            tokio::spawn(stream_tickers(
+                config.stream.exchange,
+                config.stream.url.clone(),
+                symbols,
+                sender,
+            ))
+        });
+
+        let closed = loop {
+            tokio::select! {
+                Some(event) = receiver.recv() => match event {
+                    StreamEvent::Connected => live_prices.set_connected(true),
+                    StreamEvent::Disconnected(reason) => {
+                        log::warn!("Price stream disconnected: {}", reason);
+                        live_prices.set_connected(false);
+                    }
+                    StreamEvent::Ticker(ticker) => {
+                        if throttle.should_write(&ticker.symbol, ticker.time) {
+                            for id in coins.get(&ticker.symbol).into_iter().flatten() {
+                                write_price(
+                                    repository.clone(),
+                                    events,
+                                    config.stream.exchange,
+                                    *id,
+                                    &ticker,
+                                )
+                                .await;
+                            }
+                        }
+
+                        live_prices.update(ticker.clone());
+                        events.publish(DomainEvent::PriceTicked(LivePriceResponse::from(ticker)));
+                    }
+                },
+                event = domain_events.recv() => match event {
+                    Ok(
+                        DomainEvent::CoinCreated(_)
+                        | DomainEvent::CoinDeleted(_)
+                        | DomainEvent::ProfileSwitched(_),
+                    )
+                    | Err(RecvError::Lagged(_)) => break false,
+                    Ok(_) => {}
+                    Err(RecvError::Closed) => break true,
+                },
+            }
+        };
+
+        if let Some(streaming) = streaming {
+            streaming.abort();
+        }
+
+        if closed {
+            live_prices.set_connected(false);
+            return Ok(());
+        }
+    }
+}
+
+/// The ids of the tracked coins per symbol; several coins may share one.
+async fn coins_by_symbol<R: Repository<Coin, u32> + WatchlistRepository>(
+    repository: Arc<Mutex<R>>,
+) -> Result<BTreeMap<String, Vec<u32>>, StreamPricesError> {
+    let mut coins = BTreeMap::<String, Vec<u32>>::new();
+
+    for coin in get_all_coins(repository, None).await? {
+        coins
+            .entry(coin.symbol().value().to_uppercase())
+            .or_default()
+            .push(*coin.id().value());
+    }
+
+    Ok(coins)
+}
+
+/// Stores and audits the streamed price of one coin, sourced from the exchange alone.
+/// Failures are logged, the stream goes on.
+async fn write_price<R: Repository<Coin, u32> + AuditRepository>(
+    repository: Arc<Mutex<R>>,
+    events: &EventBus,
+    exchange: StreamExchange,
+    id: u32,
+    ticker: &Ticker,
+) {
+    let last_updated = chrono::DateTime::from_timestamp(ticker.time, 0)
+        .unwrap_or_default()
+        .to_rfc3339_opts(chrono::SecondsFormat::Secs, true);
+
+    let mut repository = repository.lock().await;
+
+    let coin = match repository.find_one(id).await {
+        Ok(coin) => coin,
+        // Deleted since the subscription; the next one leaves it out
+        Err(RepoFindOneError::NotFound) => return,
+        Err(RepoFindOneError::Unknown(e)) => {
+            log::error!("Unable to load coin {}: {}", id, e);
+            return;
+        }
+    };
+
+    let source = match exchange {
+        StreamExchange::Binance => QuoteSource::Binance,
+        StreamExchange::Kraken => QuoteSource::Kraken,
+    };
+
+    let before = coin.clone();
+    let coin = match coin.with_price(ticker.price, last_updated) {
+        Ok(coin) => coin.with_sources(CoinSources::from(vec![source])),
+        Err(e) => {
+            log::warn!("Ignoring streamed price of {}: {}", ticker.symbol, e);
+            return;
+        }
+    };
+
+    match repository.update(coin).await {
+        Ok(coin) => {
+            audit::append(
+                &mut *repository,
+                AuditRecord::coin(
+                    Origin::Background,
+                    "stream_price",
+                    Some(&before),
+                    Some(&coin),
+                ),
+            )
+            .await;
+
+            events.publish(DomainEvent::CoinUpdated(CoinResponse::from(coin)));
+        }
+        Err(RepoUpdateError::NotFound) => {}
+        Err(e) => log::error!("Unable to store the streamed price of {}: {}", id, e),
+    }
+}
@@ -0,0 +1,144 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::{Arc, Mutex, MutexGuard},
+};
+
+/// One price update from an exchange ticker. `symbol` is the coin's, e.g. `BTC` for the
+/// `BTCUSDT` pair, and `time` the exchange's timestamp in seconds.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Ticker {
+    pub symbol: String,
+    pub price: f64,
+    /// 24h volume in the quote currency of the pair, not the coin's overall volume
+    pub volume_24h: Option<f64>,
+    pub time: i64,
+}
+
+/// The latest streamed ticker per symbol and whether the stream is connected. Cloning shares
+/// the same state.
+#[derive(Clone)]
+pub struct LivePrices {
+    state: Arc<Mutex<LivePricesState>>,
+}
+
+#[derive(Default)]
+struct LivePricesState {
+    connected: bool,
+    tickers: BTreeMap<String, Ticker>,
+}
+
+impl LivePrices {
+    pub fn new() -> Self {
+        Self {
+            state: Arc::new(Mutex::new(LivePricesState::default())),
+        }
+    }
+
+    pub fn is_connected(&self) -> bool {
+        self.lock().connected
+    }
+
+    pub fn set_connected(&self, connected: bool) {
+        self.lock().connected = connected;
+    }
+
+    /// Keeps `ticker` unless a later one for the same symbol is already known.
+    pub fn update(&self, ticker: Ticker) {
+        let mut state = self.lock();
+
+        match state.tickers.get(&ticker.symbol) {
+            Some(known) if known.time > ticker.time => {}
+            _ => {
+                state.tickers.insert(ticker.symbol.clone(), ticker);
+            }
+        }
+    }
+
+    /// Forgets the symbols that are no longer streamed.
+    pub fn retain(&self, symbols: &[String]) {
+        self.lock()
+            .tickers
+            .retain(|symbol, _| symbols.contains(symbol));
+    }
+
+    /// The latest tickers, ordered by symbol.
+    pub fn tickers(&self) -> Vec<Ticker> {
+        self.lock().tickers.values().cloned().collect()
+    }
+
+    fn lock(&self) -> MutexGuard<'_, LivePricesState> {
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl Default for LivePrices {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Limits how often a streamed price is written per symbol; tickers arrive about once a
+/// second.
+pub struct WriteThrottle {
+    interval: i64,
+    written: HashMap<String, i64>,
+}
+
+impl WriteThrottle {
+    /// `interval` in seconds; zero writes every ticker.
+    pub fn new(interval: i64) -> Self {
+        Self {
+            interval,
+            written: HashMap::new(),
+        }
+    }
+
+    /// Whether a ticker for `symbol` at `time` should be written, and if so remembers it.
+    pub fn should_write(&mut self, symbol: &str, time: i64) -> bool {
+        match self.written.get(symbol) {
+            Some(last) if time - last < self.interval => false,
+            _ => {
+                self.written.insert(symbol.to_string(), time);
+                true
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ticker(symbol: &str, price: f64, time: i64) -> Ticker {
+        Ticker {
+            symbol: symbol.to_string(),
+            price,
+            volume_24h: None,
+            time,
+        }
+    }
+
+    #[test]
+    fn writes_are_throttled_per_symbol() {
+        let mut throttle = WriteThrottle::new(10);
+
+        assert!(throttle.should_write("BTC", 100));
+        assert!(!throttle.should_write("BTC", 105));
+        assert!(throttle.should_write("ETH", 105));
+        assert!(throttle.should_write("BTC", 110));
+    }
+
+    #[test]
+    fn late_tickers_do_not_replace_newer_ones() {
+        let prices = LivePrices::new();
+
+        prices.update(ticker("BTC", 101.0, 20));
+        prices.update(ticker("BTC", 100.0, 10));
+        prices.update(ticker("ETH", 5.0, 15));
+        prices.retain(&["BTC".to_string()]);
+
+        assert_eq!(prices.tickers(), vec![ticker("BTC", 101.0, 20)]);
+    }
+}
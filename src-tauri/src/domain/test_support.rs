@@ -0,0 +1,12 @@
+//! Helpers shared by the unit tests of the domain.
+
+/// Asserts that a computed value is defined and within `tolerance` of `expected`.
+pub(crate) fn assert_close(actual: Option<f64>, expected: f64, tolerance: f64) {
+    let actual = actual.expect("value should be defined");
+    assert!(
+        (actual - expected).abs() < tolerance,
+        "expected {}, got {}",
+        expected,
+        actual
+    );
+}
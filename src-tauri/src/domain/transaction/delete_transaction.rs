@@ -0,0 +1,32 @@
+use std::sync::Arc;
+
+use thiserror::Error;
+use tokio::sync::Mutex;
+
+use crate::driven::repository::{RepoDeleteError, TransactionRepository};
+
+#[derive(Debug, Error)]
+pub enum DeleteTransactionError {
+    #[error("Invalid data: {0}")]
+    InvalidData(String),
+    #[error("Not found")]
+    NotFound,
+    #[error("Unknown error: {0}")]
+    Unknown(String),
+}
+
+pub async fn delete_transaction<R: TransactionRepository>(
+    repository: Arc<Mutex<R>>,
+    id: u32,
+) -> Result<(), DeleteTransactionError> {
+    repository
+        .lock()
+        .await
+        .delete_transaction(id)
+        .await
+        .map_err(|e| match e {
+            RepoDeleteError::InvalidData(e) => DeleteTransactionError::InvalidData(e),
+            RepoDeleteError::NotFound => DeleteTransactionError::NotFound,
+            RepoDeleteError::Unknown(e) => DeleteTransactionError::Unknown(e),
+        })
+}
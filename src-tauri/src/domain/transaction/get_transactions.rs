@@ -0,0 +1,29 @@
+use std::sync::Arc;
+
+use thiserror::Error;
+use tokio::sync::Mutex;
+
+use crate::driven::repository::{RepoGetAllError, TransactionRepository};
+
+use super::transaction::Transaction;
+
+#[derive(Debug, Error)]
+pub enum GetTransactionsError {
+    #[error("Unknown error: {0}")]
+    Unknown(String),
+}
+
+/// Returns the ledger in execution order, or only the trades involving `coin_id`.
+pub async fn get_transactions<R: TransactionRepository>(
+    repository: Arc<Mutex<R>>,
+    coin_id: Option<u32>,
+) -> Result<Vec<Transaction>, GetTransactionsError> {
+    repository
+        .lock()
+        .await
+        .get_transactions(coin_id)
+        .await
+        .map_err(|e| match e {
+            RepoGetAllError::Unknown(e) => GetTransactionsError::Unknown(e),
+        })
+}
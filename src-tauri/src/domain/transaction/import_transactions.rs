@@ -0,0 +1,249 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    sync::Arc,
+};
+
+use thiserror::Error;
+use tokio::sync::Mutex;
+
+use crate::{
+    config::Config,
+    domain::{
+        coin::{coin::Coin, get_all_coins::get_all_coins},
+        transfer::transfer::RowError,
+        Value,
+    },
+    driven::repository::{
+        ProviderRepository, Repository, TransactionRepository, WatchlistRepository,
+    },
+    fetch::{coinmarketcap::fetch_symbols, FetchError},
+    importers::{self, FIAT},
+};
+
+use super::transaction::{Exchange, Trade, Transaction, TransactionImportReport};
+
+#[derive(Debug, Error)]
+pub enum ImportTransactionsError {
+    #[error("Invalid data: {0}")]
+    InvalidData(String),
+    #[error("Unknown error: {0}")]
+    Unknown(String),
+}
+
+/// Imports an exchange trade-history export into the ledger. Symbols are resolved against
+/// the tracked coins first and then, when a `token` is given, the provider's symbol lookup;
+/// ambiguous symbols go to the coin with the highest market cap. Trades already in the
+/// ledger are counted as duplicates and skipped. With `dry_run` nothing is stored, but the
+/// counts are the same as for a real import.
+pub async fn import_transactions<R>(
+    repository: Arc<Mutex<R>>,
+    config: &Config,
+    token: Option<String>,
+    exchange: Exchange,
+    content: &str,
+    dry_run: bool,
+) -> Result<TransactionImportReport, ImportTransactionsError>
+where
+    R: Repository<Coin, u32> + WatchlistRepository + TransactionRepository + ProviderRepository,
+{
+    let parsed =
+        importers::parse(exchange, content).map_err(ImportTransactionsError::InvalidData)?;
+
+    let mut report = TransactionImportReport {
+        exchange,
+        dry_run,
+        total: parsed.trades.len() + parsed.errors.len(),
+        imported: 0,
+        duplicates: 0,
+        errors: parsed.errors,
+        unmapped_assets: vec![],
+    };
+
+    let symbols = parsed
+        .trades
+        .iter()
+        .flat_map(|(_, trade)| [trade.base.clone(), trade.quote.clone()])
+        .filter(|symbol| !is_fiat(symbol))
+        .collect::<BTreeSet<String>>();
+    let coin_ids = resolve_symbols(repository.clone(), config, token, symbols).await?;
+    let mut unmapped = BTreeSet::new();
+
+    // A dry run stores nothing, so duplicates are found against the ledger's trade ids
+    let mut stored_ids = if dry_run {
+        repository
+            .lock()
+            .await
+            .get_trade_ids(exchange)
+            .await
+            .map_err(|e| ImportTransactionsError::Unknown(e.to_string()))?
+            .into_iter()
+            .collect::<BTreeSet<String>>()
+    } else {
+        BTreeSet::new()
+    };
+
+    for (row, trade) in parsed.trades {
+        let transaction = match to_transaction(trade, &coin_ids) {
+            Ok(transaction) => transaction,
+            Err(Unresolved::Assets(symbols)) => {
+                report.errors.push(row_error(
+                    row,
+                    format!("Unmapped assets: {}", symbols.join(", ")),
+                ));
+                unmapped.extend(symbols);
+                continue;
+            }
+            Err(Unresolved::Invalid(message)) => {
+                report.errors.push(row_error(row, message));
+                continue;
+            }
+        };
+
+        if dry_run {
+            if stored_ids.insert(transaction.trade().trade_id.clone()) {
+                report.imported += 1;
+            } else {
+                report.duplicates += 1;
+            }
+            continue;
+        }
+
+        match repository.lock().await.add_transaction(transaction).await {
+            Ok(Some(_)) => report.imported += 1,
+            Ok(None) => report.duplicates += 1,
+            Err(e) => report.errors.push(row_error(row, e.to_string())),
+        }
+    }
+
+    report.errors.sort_by_key(|error| error.row);
+    report.unmapped_assets = unmapped.into_iter().collect();
+
+    Ok(report)
+}
+
+enum Unresolved {
+    Assets(Vec<String>),
+    Invalid(String),
+}
+
+fn to_transaction(
+    trade: Trade,
+    coin_ids: &BTreeMap<String, u32>,
+) -> Result<Transaction, Unresolved> {
+    let base_coin_id = coin_ids.get(&trade.base).copied();
+    let quote_coin_id = coin_ids.get(&trade.quote).copied();
+
+    let mut missing = vec![];
+
+    if base_coin_id.is_none() {
+        missing.push(trade.base.clone());
+    }
+
+    if quote_coin_id.is_none() && !is_fiat(&trade.quote) {
+        missing.push(trade.quote.clone());
+    }
+
+    match base_coin_id {
+        Some(base_coin_id) if missing.is_empty() => {
+            Transaction::new(trade, base_coin_id, quote_coin_id)
+                .map_err(|e| Unresolved::Invalid(e.to_string()))
+        }
+        _ => Err(Unresolved::Assets(missing)),
+    }
+}
+
+/// Maps each symbol to a coin id, leaving out the ones that cannot be resolved.
+async fn resolve_symbols<R>(
+    repository: Arc<Mutex<R>>,
+    config: &Config,
+    token: Option<String>,
+    symbols: BTreeSet<String>,
+) -> Result<BTreeMap<String, u32>, ImportTransactionsError>
+where
+    R: Repository<Coin, u32> + WatchlistRepository + ProviderRepository,
+{
+    let coins = get_all_coins(repository.clone(), None)
+        .await
+        .map_err(|e| ImportTransactionsError::Unknown(e.to_string()))?;
+
+    let mut resolved = best_matches(coins.iter().map(|coin| {
+        (
+            coin.symbol().value().to_uppercase(),
+            *coin.id().value(),
+            coin.market_cap().value().unwrap_or_default(),
+        )
+    }));
+    resolved.retain(|symbol, _| symbols.contains(symbol));
+
+    let missing = symbols
+        .into_iter()
+        .filter(|symbol| !resolved.contains_key(symbol))
+        .collect::<Vec<String>>();
+
+    if let (Some(token), false) = (token, missing.is_empty()) {
+        resolved.extend(lookup_symbols(repository, config, missing, token).await);
+    }
+
+    Ok(resolved)
+}
+
+/// Looks the symbols up with the provider. An unknown symbol fails the whole request, so
+/// on invalid input each symbol is retried on its own.
+async fn lookup_symbols<R: ProviderRepository>(
+    repository: Arc<Mutex<R>>,
+    config: &Config,
+    symbols: Vec<String>,
+    token: String,
+) -> BTreeMap<String, u32> {
+    let mut found = vec![];
+
+    match fetch_symbols(repository.clone(), config, symbols.clone(), token.clone()).await {
+        Ok(response) => found = response.coins,
+        Err(FetchError::InvalidInput(_)) if symbols.len() > 1 => {
+            for symbol in symbols {
+                let fetched =
+                    fetch_symbols(repository.clone(), config, vec![symbol], token.clone()).await;
+
+                if let Ok(response) = fetched {
+                    found.extend(response.coins);
+                }
+            }
+        }
+        Err(e) => log::warn!("Could not look up symbols: {}", e),
+    }
+
+    best_matches(
+        found
+            .into_iter()
+            .map(|coin| (coin.symbol.to_uppercase(), coin.id, coin.market_cap)),
+    )
+}
+
+/// Keeps the coin with the highest market cap for every symbol.
+fn best_matches(coins: impl Iterator<Item = (String, u32, f64)>) -> BTreeMap<String, u32> {
+    let mut best: BTreeMap<String, (u32, f64)> = BTreeMap::new();
+
+    for (symbol, id, market_cap) in coins {
+        let current = best.entry(symbol).or_insert((id, market_cap));
+
+        if market_cap > current.1 {
+            *current = (id, market_cap);
+        }
+    }
+
+    best.into_iter()
+        .map(|(symbol, (id, _))| (symbol, id))
+        .collect()
+}
+
+fn is_fiat(symbol: &str) -> bool {
+    FIAT.contains(&symbol)
+}
+
+fn row_error(row: usize, message: String) -> RowError {
+    RowError {
+        row,
+        message,
+        details: BTreeMap::new(),
+    }
+}
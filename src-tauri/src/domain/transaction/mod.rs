@@ -0,0 +1,4 @@
+pub(crate) mod delete_transaction;
+pub(crate) mod get_transactions;
+pub(crate) mod import_transactions;
+pub(crate) mod transaction;
@@ -0,0 +1,165 @@
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+use utoipa::ToSchema;
+
+use crate::domain::{coin::coin::CoinId, transfer::transfer::RowError, Entity, Value};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize, ToSchema, TS)]
+#[ts(export, export_to = "../src/bindings/")]
+#[serde(rename_all = "snake_case")]
+pub enum Exchange {
+    Binance,
+    Kraken,
+    Coinbase,
+}
+
+impl Exchange {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Exchange::Binance => "binance",
+            Exchange::Kraken => "kraken",
+            Exchange::Coinbase => "coinbase",
+        }
+    }
+}
+
+impl FromStr for Exchange {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_lowercase().as_str() {
+            "binance" => Ok(Exchange::Binance),
+            "kraken" => Ok(Exchange::Kraken),
+            "coinbase" => Ok(Exchange::Coinbase),
+            _ => Err(format!("Unknown exchange {}", value)),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize, ToSchema, TS)]
+#[ts(export, export_to = "../src/bindings/")]
+#[serde(rename_all = "snake_case")]
+pub enum TradeSide {
+    Buy,
+    Sell,
+}
+
+impl TradeSide {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TradeSide::Buy => "buy",
+            TradeSide::Sell => "sell",
+        }
+    }
+}
+
+impl FromStr for TradeSide {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_lowercase().as_str() {
+            "buy" | "b" => Ok(TradeSide::Buy),
+            "sell" | "s" => Ok(TradeSide::Sell),
+            _ => Err(format!("Unknown trade side {}", value)),
+        }
+    }
+}
+
+/// A trade as read from an exchange export, with the pair already split into base and
+/// quote symbols but not yet resolved to coins.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Trade {
+    pub exchange: Exchange,
+    pub trade_id: String,
+    /// RFC 3339 timestamp in UTC
+    pub executed_at: String,
+    pub side: TradeSide,
+    pub base: String,
+    pub quote: String,
+    pub quantity: f64,
+    pub price: f64,
+    pub fee: f64,
+    pub fee_asset: String,
+}
+
+/// A trade in the ledger. The base asset is always a coin; the quote asset is a coin too
+/// unless it is fiat.
+#[derive(Clone, Debug)]
+pub struct Transaction {
+    id: Option<u32>,
+    trade: Trade,
+    base_coin_id: CoinId,
+    quote_coin_id: Option<CoinId>,
+}
+
+impl Entity for Transaction {}
+
+impl Transaction {
+    pub fn new(
+        trade: Trade,
+        base_coin_id: u32,
+        quote_coin_id: Option<u32>,
+    ) -> Result<Self, &'static str> {
+        if trade.trade_id.is_empty() {
+            return Err("Trade ID must not be empty");
+        }
+
+        if !trade.quantity.is_finite() || trade.quantity <= 0.0 {
+            return Err("Quantity must be greater than 0");
+        }
+
+        if !trade.price.is_finite()
+            || trade.price < 0.0
+            || !trade.fee.is_finite()
+            || trade.fee < 0.0
+        {
+            return Err("Price and fee must be greater than or equal to 0");
+        }
+
+        Ok(Self {
+            id: None,
+            trade,
+            base_coin_id: CoinId::try_from(base_coin_id)?,
+            quote_coin_id: quote_coin_id.map(CoinId::try_from).transpose()?,
+        })
+    }
+
+    pub fn with_id(self, id: u32) -> Self {
+        Self {
+            id: Some(id),
+            ..self
+        }
+    }
+
+    pub fn id(&self) -> Option<u32> {
+        self.id
+    }
+
+    pub fn trade(&self) -> &Trade {
+        &self.trade
+    }
+
+    pub fn base_coin_id(&self) -> u32 {
+        *self.base_coin_id.value()
+    }
+
+    pub fn quote_coin_id(&self) -> Option<u32> {
+        self.quote_coin_id.as_ref().map(|id| *id.value())
+    }
+}
+
+/// Outcome of a transaction import. Rows are 1-based and do not count the CSV header;
+/// `unmapped_assets` lists the symbols that matched no coin.
+#[derive(Clone, Debug, Serialize, ToSchema, TS)]
+#[ts(export, export_to = "../src/bindings/")]
+pub struct TransactionImportReport {
+    pub exchange: Exchange,
+    pub dry_run: bool,
+    pub total: usize,
+    pub imported: usize,
+    pub duplicates: usize,
+    pub errors: Vec<RowError>,
+    pub unmapped_assets: Vec<String>,
+}
@@ -0,0 +1,107 @@
+use std::sync::Arc;
+
+use thiserror::Error;
+use tokio::sync::Mutex;
+
+use crate::{
+    domain::{
+        candle::candle::CandleInterval,
+        coin::{coin::Coin, get_all_coins::get_all_coins},
+        settings::{get_all_settings::get_all_settings, settings::Setting},
+        Value,
+    },
+    driven::repository::{
+        CandleRepository, Repository, TransactionRepository, WatchlistRepository,
+    },
+    driving::tauri::{
+        candles::CandleRow, coins::CoinResponse, settings::SettingResponse,
+        transactions::TransactionRow,
+    },
+};
+
+use super::transfer::{serialize_rows, DataFormat, Dataset};
+
+/// Settings that hold credentials and are left out of exports unless asked for.
+pub(crate) const SECRET_SETTINGS: [&str; 1] = ["api_key"];
+
+#[derive(Debug, Error)]
+pub enum ExportError {
+    #[error("Unable to serialize: {0}")]
+    Serialize(String),
+    #[error("Unknown error: {0}")]
+    Unknown(String),
+}
+
+/// Serializes the dataset; candles are exported for the tracked coins only.
+pub async fn export_data<R>(
+    repository: Arc<Mutex<R>>,
+    dataset: Dataset,
+    format: DataFormat,
+    include_secrets: bool,
+) -> Result<String, ExportError>
+where
+    R: Repository<Coin, u32>
+        + Repository<Setting, String>
+        + WatchlistRepository
+        + TransactionRepository
+        + CandleRepository,
+{
+    match dataset {
+        Dataset::Coins => {
+            let coins = get_all_coins(repository, None)
+                .await
+                .map_err(|e| ExportError::Unknown(e.to_string()))?
+                .into_iter()
+                .map(CoinResponse::from)
+                .collect::<Vec<CoinResponse>>();
+
+            serialize_rows(&coins, format).map_err(ExportError::Serialize)
+        }
+        Dataset::Settings => {
+            let settings = get_all_settings(repository)
+                .await
+                .map_err(|e| ExportError::Unknown(e.to_string()))?
+                .into_iter()
+                .filter(|setting| {
+                    include_secrets || !SECRET_SETTINGS.contains(&setting.key().value().as_str())
+                })
+                .map(SettingResponse::from)
+                .collect::<Vec<SettingResponse>>();
+
+            serialize_rows(&settings, format).map_err(ExportError::Serialize)
+        }
+        Dataset::Transactions => {
+            let transactions = repository
+                .lock()
+                .await
+                .get_transactions(None)
+                .await
+                .map_err(|e| ExportError::Unknown(e.to_string()))?
+                .into_iter()
+                .map(TransactionRow::from)
+                .collect::<Vec<TransactionRow>>();
+
+            serialize_rows(&transactions, format).map_err(ExportError::Serialize)
+        }
+        Dataset::Candles => {
+            let coins = get_all_coins(repository.clone(), None)
+                .await
+                .map_err(|e| ExportError::Unknown(e.to_string()))?;
+            let mut repository = repository.lock().await;
+            let mut candles = vec![];
+
+            for coin in coins {
+                for interval in [CandleInterval::Hourly, CandleInterval::Daily] {
+                    let stored = repository
+                        .get_candles(*coin.id().value(), interval, i64::MIN, i64::MAX)
+                        .await
+                        .map_err(|e| ExportError::Unknown(e.to_string()))?;
+
+                    candles.extend(stored.into_iter().map(CandleRow::from));
+                }
+            }
+
+            serialize_rows(&candles, format).map_err(ExportError::Serialize)
+        }
+    }
+}
@@ -0,0 +1,208 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    sync::Arc,
+};
+
+use serde::de::DeserializeOwned;
+use thiserror::Error;
+use tokio::sync::Mutex;
+use validator::Validate;
+
+use crate::{
+    domain::{
+        audit::audit::Origin,
+        candle::candle::Candle,
+        coin::{coin::Coin, create_coin::create_coin},
+        events::EventBus,
+        settings::{create_setting::create_setting, settings::Setting},
+        transaction::transaction::Transaction,
+    },
+    driven::repository::{
+        AuditRepository, CandleRepository, RepoFindOneError, Repository, TransactionRepository,
+    },
+    driving::tauri::{
+        candles::CandleRow, coins::CreateCoinRequest, errors::validation_details,
+        settings::CreateSettingRequest, transactions::TransactionRow,
+    },
+};
+
+use super::transfer::{deserialize_rows, DataFormat, Dataset, ImportReport, RowError};
+
+#[derive(Debug, Error)]
+pub enum ImportError {
+    #[error("Invalid data: {0}")]
+    InvalidData(String),
+}
+
+/// Validates every row with the same rules as the create commands and stores the valid
+/// ones. Invalid rows are reported and skipped; with `dry_run` nothing is stored. Trades
+/// already in the ledger are counted as duplicates, candles replace the stored ones and
+/// must belong to a tracked coin.
+pub async fn import_data<R>(
+    repository: Arc<Mutex<R>>,
+    events: &EventBus,
+    origin: Origin,
+    dataset: Dataset,
+    format: DataFormat,
+    content: &str,
+    dry_run: bool,
+) -> Result<ImportReport, ImportError>
+where
+    R: Repository<Coin, u32>
+        + Repository<Setting, String>
+        + AuditRepository
+        + TransactionRepository
+        + CandleRepository,
+{
+    let mut report = ImportReport {
+        dataset,
+        dry_run,
+        total: 0,
+        valid: 0,
+        imported: 0,
+        duplicates: 0,
+        errors: vec![],
+    };
+
+    match dataset {
+        Dataset::Coins => {
+            for (row, coin) in validated_rows::<CreateCoinRequest>(content, format, &mut report)? {
+                if dry_run {
+                    continue;
+                }
+
+                match create_coin(repository.clone(), events, origin, coin).await {
+                    Ok(_) => report.imported += 1,
+                    Err(e) => report.errors.push(row_error(row, e.to_string())),
+                }
+            }
+        }
+        Dataset::Settings => {
+            for (row, setting) in
+                validated_rows::<CreateSettingRequest>(content, format, &mut report)?
+            {
+                if dry_run {
+                    continue;
+                }
+
+                match create_setting(repository.clone(), origin, setting).await {
+                    Ok(_) => report.imported += 1,
+                    Err(e) => report.errors.push(row_error(row, e.to_string())),
+                }
+            }
+        }
+        Dataset::Transactions => {
+            for (row, transaction) in
+                validated_rows::<TransactionRow>(content, format, &mut report)?
+            {
+                let transaction = match Transaction::try_from(transaction) {
+                    Ok(transaction) => transaction,
+                    Err(e) => {
+                        report.errors.push(row_error(row, e.to_string()));
+                        continue;
+                    }
+                };
+
+                if dry_run {
+                    continue;
+                }
+
+                match repository.lock().await.add_transaction(transaction).await {
+                    Ok(Some(_)) => report.imported += 1,
+                    Ok(None) => report.duplicates += 1,
+                    Err(e) => report.errors.push(row_error(row, e.to_string())),
+                }
+            }
+        }
+        Dataset::Candles => {
+            let rows = validated_rows::<CandleRow>(content, format, &mut report)?;
+            let mut repository = repository.lock().await;
+            let mut tracked = BTreeSet::new();
+            let mut untracked = BTreeSet::new();
+            let mut candles = vec![];
+
+            for (row, candle) in rows {
+                let coin_id = candle.coin_id;
+
+                if !tracked.contains(&coin_id) && !untracked.contains(&coin_id) {
+                    match repository.find_one(coin_id).await {
+                        Ok(_) => tracked.insert(coin_id),
+                        Err(RepoFindOneError::NotFound) => untracked.insert(coin_id),
+                        Err(RepoFindOneError::Unknown(e)) => {
+                            report.errors.push(row_error(row, e));
+                            continue;
+                        }
+                    };
+                }
+
+                if untracked.contains(&coin_id) {
+                    report
+                        .errors
+                        .push(row_error(row, format!("Coin {} is not tracked", coin_id)));
+                    continue;
+                }
+
+                match Candle::try_from(candle) {
+                    Ok(candle) => candles.push((row, candle)),
+                    Err(e) => report.errors.push(row_error(row, e.to_string())),
+                }
+            }
+
+            if !dry_run && !candles.is_empty() {
+                let (rows, candles): (Vec<usize>, Vec<Candle>) = candles.into_iter().unzip();
+
+                match repository.upsert_candles(candles).await {
+                    Ok(_) => report.imported += rows.len(),
+                    Err(e) => report
+                        .errors
+                        .extend(rows.into_iter().map(|row| row_error(row, e.to_string()))),
+                }
+            }
+        }
+    }
+
+    report.errors.sort_by_key(|error| error.row);
+
+    Ok(report)
+}
+
+/// Parses and validates the rows, recording rejected ones in `report`, and returns the
+/// valid ones with their row numbers.
+fn validated_rows<T: DeserializeOwned + Validate>(
+    content: &str,
+    format: DataFormat,
+    report: &mut ImportReport,
+) -> Result<Vec<(usize, T)>, ImportError> {
+    let rows = deserialize_rows::<T>(content, format).map_err(ImportError::InvalidData)?;
+    let mut valid = vec![];
+
+    report.total = rows.len();
+
+    for (index, row) in rows.into_iter().enumerate() {
+        let row_number = index + 1;
+
+        match row {
+            Ok(row) => match row.validate() {
+                Ok(_) => valid.push((row_number, row)),
+                Err(e) => report.errors.push(RowError {
+                    row: row_number,
+                    message: "Validation failed".to_string(),
+                    details: validation_details(&e),
+                }),
+            },
+            Err(e) => report.errors.push(row_error(row_number, e)),
+        }
+    }
+
+    report.valid = valid.len();
+
+    Ok(valid)
+}
+
+fn row_error(row: usize, message: String) -> RowError {
+    RowError {
+        row,
+        message,
+        details: BTreeMap::new(),
+    }
+}
@@ -0,0 +1,3 @@
+pub(crate) mod export_data;
+pub(crate) mod import_data;
+pub(crate) mod transfer;
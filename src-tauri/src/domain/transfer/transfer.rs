@@ -0,0 +1,106 @@
+use std::collections::BTreeMap;
+
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use ts_rs::TS;
+use utoipa::ToSchema;
+
+/// File format of an export or import.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize, ToSchema, TS)]
+#[ts(export, export_to = "../src/bindings/")]
+#[serde(rename_all = "snake_case")]
+pub enum DataFormat {
+    Csv,
+    Json,
+}
+
+impl DataFormat {
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            DataFormat::Csv => "text/csv",
+            DataFormat::Json => "application/json",
+        }
+    }
+}
+
+/// What to export or import. Coins use the fields of `CoinResponse`, settings are
+/// `key,value` pairs, transactions the fields of `TransactionRow` and candles those of
+/// `CandleRow`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize, ToSchema, TS)]
+#[ts(export, export_to = "../src/bindings/")]
+#[serde(rename_all = "snake_case")]
+pub enum Dataset {
+    Coins,
+    Settings,
+    Transactions,
+    Candles,
+}
+
+/// A rejected row. `row` is 1-based and does not count the CSV header.
+#[derive(Clone, Debug, Serialize, ToSchema, TS)]
+#[ts(export, export_to = "../src/bindings/")]
+pub struct RowError {
+    pub row: usize,
+    pub message: String,
+    pub details: BTreeMap<String, Vec<String>>,
+}
+
+#[derive(Clone, Debug, Serialize, ToSchema, TS)]
+#[ts(export, export_to = "../src/bindings/")]
+pub struct ImportReport {
+    pub dataset: Dataset,
+    pub dry_run: bool,
+    pub total: usize,
+    pub valid: usize,
+    pub imported: usize,
+    /// Rows skipped because they are already stored, which only happens for trades
+    pub duplicates: usize,
+    pub errors: Vec<RowError>,
+}
+
+pub(crate) fn serialize_rows<T: Serialize>(
+    rows: &[T],
+    format: DataFormat,
+) -> Result<String, String> {
+    match format {
+        DataFormat::Json => serde_json::to_string_pretty(rows).map_err(|e| e.to_string()),
+        DataFormat::Csv => {
+            let mut writer = csv::Writer::from_writer(vec![]);
+
+            for row in rows {
+                writer.serialize(row).map_err(|e| e.to_string())?;
+            }
+
+            let bytes = writer.into_inner().map_err(|e| e.to_string())?;
+            String::from_utf8(bytes).map_err(|e| e.to_string())
+        }
+    }
+}
+
+/// Parses every row on its own so one malformed row does not hide the others. Fails only
+/// when the content as a whole cannot be read, e.g. JSON that is not an array.
+pub(crate) fn deserialize_rows<T: DeserializeOwned>(
+    content: &str,
+    format: DataFormat,
+) -> Result<Vec<Result<T, String>>, String> {
+    match format {
+        DataFormat::Json => {
+            let values: Vec<serde_json::Value> =
+                serde_json::from_str(content).map_err(|e| e.to_string())?;
+
+            Ok(values
+                .into_iter()
+                .map(|value| serde_json::from_value(value).map_err(|e| e.to_string()))
+                .collect())
+        }
+        DataFormat::Csv => {
+            let mut reader = csv::ReaderBuilder::new()
+                .trim(csv::Trim::All)
+                .from_reader(content.as_bytes());
+
+            Ok(reader
+                .deserialize()
+                .map(|row| row.map_err(|e| e.to_string()))
+                .collect())
+        }
+    }
+}
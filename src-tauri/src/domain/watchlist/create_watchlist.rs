@@ -0,0 +1,45 @@
+use std::sync::Arc;
+
+use thiserror::Error;
+use tokio::sync::Mutex;
+
+use crate::{
+    domain::events::{DomainEvent, EventBus},
+    driven::repository::{RepoCreateError, Repository},
+    driving::tauri::watchlists::{CreateWatchlistRequest, WatchlistResponse},
+};
+
+use super::watchlist::Watchlist;
+
+#[derive(Debug, Error)]
+pub enum CreateError {
+    #[error("Invalid data: {0}")]
+    InvalidData(String),
+    #[error("Unknown error: {0}")]
+    Unknown(String),
+}
+
+pub async fn create_watchlist<R: Repository<Watchlist, u32>>(
+    repository: Arc<Mutex<R>>,
+    events: &EventBus,
+    watchlist: CreateWatchlistRequest,
+) -> Result<Watchlist, CreateError> {
+    let watchlist =
+        Watchlist::try_from(watchlist).map_err(|e| CreateError::InvalidData(e.to_string()))?;
+
+    let watchlist = repository
+        .lock()
+        .await
+        .create(watchlist)
+        .await
+        .map_err(|e| match e {
+            RepoCreateError::InvalidData(e) => CreateError::InvalidData(e),
+            RepoCreateError::Unknown(e) => CreateError::Unknown(e),
+        })?;
+
+    events.publish(DomainEvent::WatchlistUpdated(WatchlistResponse::from(
+        watchlist.clone(),
+    )));
+
+    Ok(watchlist)
+}
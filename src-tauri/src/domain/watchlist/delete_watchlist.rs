@@ -0,0 +1,43 @@
+use std::sync::Arc;
+
+use thiserror::Error;
+use tokio::sync::Mutex;
+
+use crate::{
+    domain::events::{DomainEvent, EventBus, WatchlistDeletedEvent},
+    driven::repository::{RepoDeleteError, Repository},
+};
+
+use super::watchlist::Watchlist;
+
+#[derive(Debug, Error)]
+pub enum DeleteError {
+    #[error("Invalid data: {0}")]
+    InvalidData(String),
+    #[error("Not found")]
+    NotFound,
+    #[error("Unknown error: {0}")]
+    Unknown(String),
+}
+
+/// Deletes the watchlist only; its coins stay tracked.
+pub async fn delete_watchlist<R: Repository<Watchlist, u32>>(
+    repository: Arc<Mutex<R>>,
+    events: &EventBus,
+    id: u32,
+) -> Result<(), DeleteError> {
+    repository
+        .lock()
+        .await
+        .delete(id)
+        .await
+        .map_err(|e| match e {
+            RepoDeleteError::InvalidData(e) => DeleteError::InvalidData(e),
+            RepoDeleteError::Unknown(e) => DeleteError::Unknown(e),
+            RepoDeleteError::NotFound => DeleteError::NotFound,
+        })?;
+
+    events.publish(DomainEvent::WatchlistDeleted(WatchlistDeletedEvent { id }));
+
+    Ok(())
+}
@@ -0,0 +1,31 @@
+use std::sync::Arc;
+
+use thiserror::Error;
+use tokio::sync::Mutex;
+
+use crate::driven::repository::{RepoFindOneError, Repository};
+
+use super::watchlist::Watchlist;
+
+#[derive(Debug, Error)]
+pub enum FindError {
+    #[error("Unknown error: {0}")]
+    Unknown(String),
+    #[error("Not found")]
+    NotFound,
+}
+
+pub async fn find_watchlist<R: Repository<Watchlist, u32>>(
+    repository: Arc<Mutex<R>>,
+    id: u32,
+) -> Result<Watchlist, FindError> {
+    repository
+        .lock()
+        .await
+        .find_one(id)
+        .await
+        .map_err(|e| match e {
+            RepoFindOneError::Unknown(e) => FindError::Unknown(e),
+            RepoFindOneError::NotFound => FindError::NotFound,
+        })
+}
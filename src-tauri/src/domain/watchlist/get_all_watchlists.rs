@@ -0,0 +1,27 @@
+use std::sync::Arc;
+
+use thiserror::Error;
+use tokio::sync::Mutex;
+
+use crate::driven::repository::{RepoGetAllError, Repository};
+
+use super::watchlist::Watchlist;
+
+#[derive(Debug, Error)]
+pub enum GetAllWatchlistsError {
+    #[error("Unknown error: {0}")]
+    Unknown(String),
+}
+
+pub async fn get_all_watchlists<R: Repository<Watchlist, u32>>(
+    repository: Arc<Mutex<R>>,
+) -> Result<Vec<Watchlist>, GetAllWatchlistsError> {
+    repository
+        .lock()
+        .await
+        .get_all()
+        .await
+        .map_err(|e| match e {
+            RepoGetAllError::Unknown(e) => GetAllWatchlistsError::Unknown(e),
+        })
+}
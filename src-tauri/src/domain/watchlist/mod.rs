@@ -0,0 +1,7 @@
+pub(crate) mod create_watchlist;
+pub(crate) mod delete_watchlist;
+pub(crate) mod find_watchlist;
+pub(crate) mod get_all_watchlists;
+pub(crate) mod rename_watchlist;
+pub(crate) mod watchlist;
+pub(crate) mod watchlist_coins;
@@ -0,0 +1,51 @@
+use std::sync::Arc;
+
+use thiserror::Error;
+use tokio::sync::Mutex;
+
+use crate::{
+    domain::events::{DomainEvent, EventBus},
+    driven::repository::{RepoFindOneError, RepoUpdateError, Repository},
+    driving::tauri::watchlists::WatchlistResponse,
+};
+
+use super::watchlist::Watchlist;
+
+#[derive(Debug, Error)]
+pub enum RenameError {
+    #[error("Invalid data: {0}")]
+    InvalidData(String),
+    #[error("Not found")]
+    NotFound,
+    #[error("Unknown error: {0}")]
+    Unknown(String),
+}
+
+pub async fn rename_watchlist<R: Repository<Watchlist, u32>>(
+    repository: Arc<Mutex<R>>,
+    events: &EventBus,
+    id: u32,
+    name: String,
+) -> Result<Watchlist, RenameError> {
+    let mut repository = repository.lock().await;
+
+    let watchlist = repository.find_one(id).await.map_err(|e| match e {
+        RepoFindOneError::NotFound => RenameError::NotFound,
+        RepoFindOneError::Unknown(e) => RenameError::Unknown(e),
+    })?;
+
+    let watchlist = Watchlist::new(id, name, watchlist.coin_ids().to_vec())
+        .map_err(|e| RenameError::InvalidData(e.to_string()))?;
+
+    let watchlist = repository.update(watchlist).await.map_err(|e| match e {
+        RepoUpdateError::InvalidData(e) => RenameError::InvalidData(e),
+        RepoUpdateError::NotFound => RenameError::NotFound,
+        RepoUpdateError::Unknown(e) => RenameError::Unknown(e),
+    })?;
+
+    events.publish(DomainEvent::WatchlistUpdated(WatchlistResponse::from(
+        watchlist.clone(),
+    )));
+
+    Ok(watchlist)
+}
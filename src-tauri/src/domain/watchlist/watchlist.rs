@@ -0,0 +1,111 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    domain::{Entity, Value},
+    driving::tauri::watchlists::CreateWatchlistRequest,
+};
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct WatchlistId(u32);
+
+impl Value for WatchlistId {
+    type ValueType = u32;
+
+    fn value(&self) -> &Self::ValueType {
+        &self.0
+    }
+}
+
+impl TryFrom<u32> for WatchlistId {
+    type Error = &'static str;
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        if value > 0 {
+            Ok(Self(value))
+        } else {
+            Err("Watchlist ID must be greater than 0")
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct WatchlistName(String);
+
+impl Value for WatchlistName {
+    type ValueType = String;
+
+    fn value(&self) -> &Self::ValueType {
+        &self.0
+    }
+}
+
+impl TryFrom<String> for WatchlistName {
+    type Error = &'static str;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let value = value.trim().to_string();
+
+        if value.is_empty() {
+            Err("Watchlist name must not be empty")
+        } else {
+            Ok(Self(value))
+        }
+    }
+}
+
+/// A named, ordered group of tracked coins. A coin may belong to any number of watchlists;
+/// `coin_ids` is in display order.
+#[derive(Debug, Clone)]
+pub struct Watchlist {
+    id: Option<WatchlistId>,
+    name: WatchlistName,
+    coin_ids: Vec<u32>,
+}
+
+impl Entity for Watchlist {}
+
+impl Watchlist {
+    pub fn new(id: u32, name: String, coin_ids: Vec<u32>) -> Result<Self, &'static str> {
+        Ok(Self {
+            id: Some(WatchlistId::try_from(id)?),
+            name: WatchlistName::try_from(name)?,
+            coin_ids,
+        })
+    }
+
+    /// A watchlist that has not been stored yet; the repository assigns its id.
+    pub fn unsaved(name: String) -> Result<Self, &'static str> {
+        Ok(Self {
+            id: None,
+            name: WatchlistName::try_from(name)?,
+            coin_ids: vec![],
+        })
+    }
+
+    pub fn id(&self) -> Option<&WatchlistId> {
+        self.id.as_ref()
+    }
+
+    pub fn name(&self) -> &WatchlistName {
+        &self.name
+    }
+
+    pub fn coin_ids(&self) -> &[u32] {
+        &self.coin_ids
+    }
+
+    pub fn with_id(self, id: u32) -> Result<Self, &'static str> {
+        Ok(Self {
+            id: Some(WatchlistId::try_from(id)?),
+            ..self
+        })
+    }
+}
+
+impl TryFrom<CreateWatchlistRequest> for Watchlist {
+    type Error = &'static str;
+
+    fn try_from(value: CreateWatchlistRequest) -> Result<Self, Self::Error> {
+        Self::unsaved(value.name)
+    }
+}
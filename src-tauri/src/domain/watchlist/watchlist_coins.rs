@@ -0,0 +1,122 @@
+use std::sync::Arc;
+
+use thiserror::Error;
+use tokio::sync::Mutex;
+
+use crate::{
+    domain::events::{DomainEvent, EventBus},
+    driven::repository::{
+        RepoDeleteError, RepoFindOneError, RepoUpdateError, Repository, WatchlistRepository,
+    },
+    driving::tauri::watchlists::WatchlistResponse,
+};
+
+use super::watchlist::Watchlist;
+
+#[derive(Debug, Error)]
+pub enum WatchlistCoinsError {
+    #[error("Invalid data: {0}")]
+    InvalidData(String),
+    #[error("Not found")]
+    NotFound,
+    #[error("Unknown error: {0}")]
+    Unknown(String),
+}
+
+impl From<RepoFindOneError> for WatchlistCoinsError {
+    fn from(e: RepoFindOneError) -> Self {
+        match e {
+            RepoFindOneError::NotFound => WatchlistCoinsError::NotFound,
+            RepoFindOneError::Unknown(e) => WatchlistCoinsError::Unknown(e),
+        }
+    }
+}
+
+impl From<RepoUpdateError> for WatchlistCoinsError {
+    fn from(e: RepoUpdateError) -> Self {
+        match e {
+            RepoUpdateError::InvalidData(e) => WatchlistCoinsError::InvalidData(e),
+            RepoUpdateError::NotFound => WatchlistCoinsError::NotFound,
+            RepoUpdateError::Unknown(e) => WatchlistCoinsError::Unknown(e),
+        }
+    }
+}
+
+impl From<RepoDeleteError> for WatchlistCoinsError {
+    fn from(e: RepoDeleteError) -> Self {
+        match e {
+            RepoDeleteError::InvalidData(e) => WatchlistCoinsError::InvalidData(e),
+            RepoDeleteError::NotFound => WatchlistCoinsError::NotFound,
+            RepoDeleteError::Unknown(e) => WatchlistCoinsError::Unknown(e),
+        }
+    }
+}
+
+/// Appends a tracked coin to the end of the watchlist. Adding a coin twice is a no-op.
+pub async fn add_coin<R: Repository<Watchlist, u32> + WatchlistRepository>(
+    repository: Arc<Mutex<R>>,
+    events: &EventBus,
+    watchlist_id: u32,
+    coin_id: u32,
+) -> Result<Watchlist, WatchlistCoinsError> {
+    let mut repository = repository.lock().await;
+
+    repository.find_one(watchlist_id).await?;
+    repository.add_coin(watchlist_id, coin_id).await?;
+
+    changed(&mut *repository, events, watchlist_id).await
+}
+
+pub async fn remove_coin<R: Repository<Watchlist, u32> + WatchlistRepository>(
+    repository: Arc<Mutex<R>>,
+    events: &EventBus,
+    watchlist_id: u32,
+    coin_id: u32,
+) -> Result<Watchlist, WatchlistCoinsError> {
+    let mut repository = repository.lock().await;
+
+    repository.remove_coin(watchlist_id, coin_id).await?;
+
+    changed(&mut *repository, events, watchlist_id).await
+}
+
+/// Reorders the watchlist; `coin_ids` must contain exactly the coins already in it.
+pub async fn reorder_coins<R: Repository<Watchlist, u32> + WatchlistRepository>(
+    repository: Arc<Mutex<R>>,
+    events: &EventBus,
+    watchlist_id: u32,
+    coin_ids: Vec<u32>,
+) -> Result<Watchlist, WatchlistCoinsError> {
+    let mut repository = repository.lock().await;
+
+    let watchlist = repository.find_one(watchlist_id).await?;
+
+    let mut current = watchlist.coin_ids().to_vec();
+    let mut requested = coin_ids.clone();
+    current.sort_unstable();
+    requested.sort_unstable();
+
+    if current != requested {
+        return Err(WatchlistCoinsError::InvalidData(
+            "coin_ids must contain every coin of the watchlist exactly once".to_string(),
+        ));
+    }
+
+    repository.reorder_coins(watchlist_id, coin_ids).await?;
+
+    changed(&mut *repository, events, watchlist_id).await
+}
+
+async fn changed<R: Repository<Watchlist, u32>>(
+    repository: &mut R,
+    events: &EventBus,
+    watchlist_id: u32,
+) -> Result<Watchlist, WatchlistCoinsError> {
+    let watchlist = repository.find_one(watchlist_id).await?;
+
+    events.publish(DomainEvent::WatchlistUpdated(WatchlistResponse::from(
+        watchlist.clone(),
+    )));
+
+    Ok(watchlist)
+}
@@ -3,7 +3,21 @@ pub(crate) mod sqlite;
 
 use thiserror::Error;
 
-use crate::domain::Entity;
+use crate::domain::{
+    audit::audit::{AuditFilter, AuditRecord},
+    candle::candle::{Candle, CandleInterval},
+    catalog::catalog::{CatalogEntry, CatalogMatch},
+    coin::{
+        coin::{Coin, DeletedCoin},
+        metadata::CoinMetadata,
+    },
+    provider::{
+        api_key::ApiKey,
+        provider::{CachedResponse, EndpointUsage, PlanInfo, ProviderCall},
+    },
+    transaction::transaction::{Exchange, Transaction},
+    Entity,
+};
 
 #[derive(Error, Debug)]
 pub(crate) enum RepoCreateError {
@@ -66,3 +80,132 @@ where
     async fn delete_all(&mut self) -> Result<(), RepoDeleteError>;
     async fn get_all(&mut self) -> Result<Vec<T>, RepoGetAllError>;
 }
+
+/// Membership and order of the coins in a watchlist. The watchlists themselves are stored
+/// through `Repository<Watchlist, u32>`.
+pub(crate) trait WatchlistRepository {
+    async fn add_coin(&mut self, watchlist_id: u32, coin_id: u32) -> Result<(), RepoUpdateError>;
+    async fn remove_coin(&mut self, watchlist_id: u32, coin_id: u32)
+        -> Result<(), RepoDeleteError>;
+    async fn reorder_coins(
+        &mut self,
+        watchlist_id: u32,
+        coin_ids: Vec<u32>,
+    ) -> Result<(), RepoUpdateError>;
+    async fn get_coins(&mut self, watchlist_id: u32) -> Result<Vec<Coin>, RepoFindOneError>;
+}
+
+/// The transaction ledger. Trades are unique per exchange and exchange trade id.
+pub(crate) trait TransactionRepository {
+    /// Stores the transaction unless the same trade is already in the ledger, in which case
+    /// `None` is returned.
+    async fn add_transaction(
+        &mut self,
+        transaction: Transaction,
+    ) -> Result<Option<Transaction>, RepoCreateError>;
+    async fn get_transactions(
+        &mut self,
+        coin_id: Option<u32>,
+    ) -> Result<Vec<Transaction>, RepoGetAllError>;
+    async fn delete_transaction(&mut self, id: u32) -> Result<(), RepoDeleteError>;
+    /// Returns the ids of the trades from the exchange that are already in the ledger.
+    async fn get_trade_ids(&mut self, exchange: Exchange) -> Result<Vec<String>, RepoGetAllError>;
+}
+
+/// Historical candles, unique per coin, interval and open time.
+pub(crate) trait CandleRepository {
+    /// Stores the candles, replacing any already stored for the same open times.
+    async fn upsert_candles(&mut self, candles: Vec<Candle>) -> Result<(), RepoCreateError>;
+    /// Returns the candles with open times between `start` and `end` inclusive, oldest
+    /// first. Coins in the trash have none.
+    async fn get_candles(
+        &mut self,
+        coin_id: u32,
+        interval: CandleInterval,
+        start: i64,
+        end: i64,
+    ) -> Result<Vec<Candle>, RepoGetAllError>;
+}
+
+/// The local copy of the provider's coin list, unique per coin id.
+pub(crate) trait CatalogRepository {
+    /// Replaces the whole catalog with `entries`.
+    async fn replace_catalog(&mut self, entries: Vec<CatalogEntry>) -> Result<(), RepoCreateError>;
+    /// Stores the entries, replacing any already stored for the same coins.
+    async fn upsert_catalog(&mut self, entries: Vec<CatalogEntry>) -> Result<(), RepoCreateError>;
+    /// Returns up to `limit` entries whose symbol, name or slug contains `query`, ignoring
+    /// case: exact symbol matches first, then by rank.
+    async fn search_catalog(
+        &mut self,
+        query: String,
+        limit: u32,
+    ) -> Result<Vec<CatalogMatch>, RepoGetAllError>;
+}
+
+/// Metadata of tracked coins, at most one per coin.
+pub(crate) trait CoinMetadataRepository {
+    /// Stores the metadata, replacing any already stored for the coin.
+    async fn upsert_metadata(&mut self, metadata: CoinMetadata) -> Result<(), RepoCreateError>;
+    async fn find_metadata(&mut self, coin_id: u32) -> Result<CoinMetadata, RepoFindOneError>;
+}
+
+/// Bookkeeping of the provider: cached responses, credits spent per day, every call made,
+/// the plan of the API key and the pool of keys.
+pub(crate) trait ProviderRepository {
+    async fn find_response(&mut self, key: String) -> Result<CachedResponse, RepoFindOneError>;
+    /// Stores the response, replacing any already stored under its key.
+    async fn store_response(&mut self, response: CachedResponse) -> Result<(), RepoCreateError>;
+    /// Deletes the responses that expired before `before`.
+    async fn purge_responses(&mut self, before: i64) -> Result<(), RepoDeleteError>;
+    async fn add_credits(&mut self, day: String, credits: u32) -> Result<(), RepoUpdateError>;
+    /// Credits spent on `day`, 0 when none are recorded.
+    async fn credits_used(&mut self, day: String) -> Result<u32, RepoFindOneError>;
+    /// Records the call and deletes the ones made before `purge_before`.
+    async fn record_call(
+        &mut self,
+        call: ProviderCall,
+        purge_before: i64,
+    ) -> Result<(), RepoCreateError>;
+    /// Calls made since `since`, grouped by UTC day and endpoint, oldest day first.
+    async fn usage_by_endpoint(
+        &mut self,
+        since: i64,
+    ) -> Result<Vec<EndpointUsage>, RepoGetAllError>;
+    async fn store_plan(&mut self, plan: PlanInfo) -> Result<(), RepoCreateError>;
+    async fn find_plan(&mut self) -> Result<PlanInfo, RepoFindOneError>;
+    /// Stores a new key; labels and keys are unique.
+    async fn add_key(&mut self, key: ApiKey) -> Result<ApiKey, RepoCreateError>;
+    async fn find_key(&mut self, id: u32) -> Result<ApiKey, RepoFindOneError>;
+    /// Every key of the pool, oldest first.
+    async fn get_keys(&mut self) -> Result<Vec<ApiKey>, RepoGetAllError>;
+    async fn update_key(&mut self, key: ApiKey) -> Result<ApiKey, RepoUpdateError>;
+    async fn delete_key(&mut self, id: u32) -> Result<(), RepoDeleteError>;
+}
+
+/// A repository whose database can be replaced at runtime, one per profile.
+pub(crate) trait ProfileRepository {
+    /// Opens the database at `db_path`, creating and migrating it on first use, and closes
+    /// the current one. The current one stays open when the other cannot be opened.
+    async fn open(&mut self, db_path: String) -> Result<(), RepoCreateError>;
+}
+
+/// The append-only log of changes to coins and settings.
+pub(crate) trait AuditRepository {
+    async fn append_audit(&mut self, record: AuditRecord) -> Result<(), RepoCreateError>;
+    /// Records matching the filter, newest first and at most `filter.limit`.
+    async fn find_audit(
+        &mut self,
+        filter: AuditFilter,
+    ) -> Result<Vec<AuditRecord>, RepoGetAllError>;
+}
+
+/// Deleted coins. `Repository<Coin, u32>` only sees tracked coins; deleting one moves it to
+/// the trash, keeping its candles, metadata and watchlist memberships until it is purged.
+pub(crate) trait CoinTrashRepository {
+    /// The coins in the trash, most recently deleted first. Each delete, whether of one coin
+    /// or of all of them, gives its coins a new `deletion_id`.
+    async fn get_deleted_coins(&mut self) -> Result<Vec<DeletedCoin>, RepoGetAllError>;
+    async fn restore_coin(&mut self, id: u32) -> Result<Coin, RepoUpdateError>;
+    /// Permanently deletes the coins deleted before `before`.
+    async fn purge_deleted_coins(&mut self, before: i64) -> Result<(), RepoDeleteError>;
+}
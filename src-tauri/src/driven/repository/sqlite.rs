@@ -1,15 +1,37 @@
 #![allow(unused)]
+use std::path::{Path, PathBuf};
+
 use serde::{Deserialize, Serialize};
-use sqlx::{migrate::MigrateDatabase, pool::PoolConnection, FromRow, Pool, Sqlite};
+use sqlx::{
+    migrate::MigrateDatabase, pool::PoolConnection, FromRow, Pool, Sqlite, SqliteConnection,
+};
 
 use crate::{
     config::SqtliteConfig,
-    domain::{coin::coin::Coin, settings::settings::Setting, Value},
+    domain::{
+        audit::audit::{AuditFilter, AuditRecord},
+        candle::candle::{Candle, CandleInterval, Ohlcv},
+        catalog::catalog::{CatalogEntry, CatalogMatch},
+        coin::{
+            coin::{Coin, CoinSources, DeletedCoin},
+            metadata::{CoinLink, CoinMetadata, Logo, TokenContract},
+        },
+        provider::{
+            api_key::ApiKey,
+            provider::{CachedResponse, EndpointUsage, PlanInfo, ProviderCall},
+        },
+        settings::settings::Setting,
+        transaction::transaction::{Exchange, Trade, Transaction},
+        watchlist::watchlist::Watchlist,
+        Value,
+    },
     driven::repository::{RepoCreateError, Repository},
 };
 
 use super::{
-    RepoDeleteError, RepoFindAllError, RepoFindOneError, RepoGetAllError, RepoUpdateError,
+    AuditRepository, CandleRepository, CatalogRepository, CoinMetadataRepository,
+    CoinTrashRepository, ProfileRepository, ProviderRepository, RepoDeleteError, RepoFindAllError,
+    RepoFindOneError, RepoGetAllError, RepoUpdateError, TransactionRepository, WatchlistRepository,
 };
 
 pub(crate) const SQLITE_LOCAL_PATH: &str = "databases";
@@ -27,6 +49,7 @@ pub(crate) struct CoinSql {
     percent_change_7d: f64,
     market_cap: f64,
     last_updated: String,
+    sources: String,
 }
 
 impl From<Coin> for CoinSql {
@@ -42,6 +65,7 @@ impl From<Coin> for CoinSql {
             percent_change_7d: coin.percent_change_7d().value().unwrap_or_default(),
             market_cap: coin.market_cap().value().unwrap_or_default(),
             last_updated: coin.last_updated().value().to_string(),
+            sources: coin.sources().to_string(),
         }
     }
 }
@@ -62,6 +86,27 @@ impl TryInto<Coin> for CoinSql {
             self.market_cap,
             self.last_updated,
         )
+        .map(|coin| coin.with_sources(CoinSources::parse(&self.sources)))
+    }
+}
+
+#[derive(Debug, Clone, FromRow)]
+pub(crate) struct DeletedCoinSql {
+    #[sqlx(flatten)]
+    coin: CoinSql,
+    deleted_at: i64,
+    deletion_id: i64,
+}
+
+impl TryInto<DeletedCoin> for DeletedCoinSql {
+    type Error = &'static str;
+
+    fn try_into(self) -> Result<DeletedCoin, Self::Error> {
+        Ok(DeletedCoin {
+            coin: self.coin.try_into()?,
+            deleted_at: self.deleted_at,
+            deletion_id: self.deletion_id,
+        })
     }
 }
 
@@ -78,22 +123,24 @@ pub(crate) struct SqliteRepository {
 
 impl SqliteRepository {
     pub fn new(config: &SqtliteConfig) -> Self {
-        #[cfg(mobile)]
-        let db_path = format!("sqlite:/{}", config.db_path);
-        #[cfg(not(mobile))]
-        let db_path = format!("{}/{}", SQLITE_LOCAL_PATH, SQLITE_FILE);
+        let db_path = config.db_path.clone();
 
         Self {
             pool: PoolWrapper::NotExists,
             db_url: format!("sqlite:{}", db_path),
-            db_path
+            db_path,
         }
     }
 
     async fn create_pool(&self) -> Result<Pool<Sqlite>, sqlx::Error> {
         if !sqlx::Sqlite::database_exists(&self.db_url).await? {
-            std::fs::create_dir_all(&self.db_path.rsplit_once("/").unwrap().0).unwrap();
-            Sqlite::create_database(&self.db_url).await?;
+            if let Some(parent) = Path::new(&self.db_path).parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+
+            if !self.migrate_legacy_database()? {
+                Sqlite::create_database(&self.db_url).await?;
+            }
         }
 
         let pool = Pool::<Sqlite>::connect_lazy(&self.db_url)?;
@@ -101,6 +148,38 @@ impl SqliteRepository {
         Ok(pool)
     }
 
+    /// Moves a database left behind by older desktop builds, which stored it relative to
+    /// the current working directory, to the configured location. Returns `true` when a
+    /// legacy database was found and moved.
+    fn migrate_legacy_database(&self) -> std::io::Result<bool> {
+        if cfg!(mobile) {
+            return Ok(false);
+        }
+
+        let legacy_path = Path::new(SQLITE_LOCAL_PATH).join(SQLITE_FILE);
+        let db_path = Path::new(&self.db_path);
+
+        if !legacy_path.is_file() || db_path.exists() {
+            return Ok(false);
+        }
+
+        for suffix in ["", "-wal", "-shm"] {
+            let from = PathBuf::from(format!("{}{}", legacy_path.display(), suffix));
+            let to = PathBuf::from(format!("{}{}", self.db_path, suffix));
+
+            if !from.is_file() {
+                continue;
+            }
+
+            if std::fs::rename(&from, &to).is_err() {
+                std::fs::copy(&from, &to)?;
+                std::fs::remove_file(&from)?;
+            }
+        }
+
+        Ok(true)
+    }
+
     pub async fn conn(&mut self) -> Result<PoolConnection<Sqlite>, sqlx::Error> {
         use PoolWrapper::*;
 
@@ -126,8 +205,8 @@ impl Repository<Coin, u32> for SqliteRepository {
 
         let result = sqlx::query(
             r#"
-            INSERT INTO coins (id, name, symbol, price, volume_24h, percent_change_1h, percent_change_24h, percent_change_7d, market_cap, last_updated) 
-            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            INSERT INTO coins (id, name, symbol, price, volume_24h, percent_change_1h, percent_change_24h, percent_change_7d, market_cap, last_updated, sources) 
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
             ON CONFLICT(id) DO UPDATE SET
                 name = excluded.name,
                 symbol = excluded.symbol,
@@ -137,7 +216,10 @@ impl Repository<Coin, u32> for SqliteRepository {
                 percent_change_24h = excluded.percent_change_24h,
                 percent_change_7d = excluded.percent_change_7d,
                 market_cap = excluded.market_cap,
-                last_updated = excluded.last_updated;
+                last_updated = excluded.last_updated,
+                sources = excluded.sources,
+                deleted_at = NULL,
+                deletion_id = NULL;
             "#
         )
         .bind(entity.id().value())
@@ -150,6 +232,7 @@ impl Repository<Coin, u32> for SqliteRepository {
         .bind(entity.percent_change_7d().value())
         .bind(entity.market_cap().value())
         .bind(entity.last_updated().value())
+        .bind(entity.sources().to_string())
         .execute(&mut *conn).await;
 
         match result {
@@ -166,7 +249,7 @@ impl Repository<Coin, u32> for SqliteRepository {
 
         let result = sqlx::query_as::<Sqlite, CoinSql>(
             r#"
-                    SELECT * FROM coins WHERE id = ?
+                    SELECT * FROM coins WHERE id = ? AND deleted_at IS NULL
                     "#,
         )
         .bind(entity)
@@ -175,6 +258,7 @@ impl Repository<Coin, u32> for SqliteRepository {
 
         match result {
             Ok(coin) => Ok(coin.try_into().unwrap()),
+            Err(sqlx::Error::RowNotFound) => Err(super::RepoFindOneError::NotFound),
             Err(e) => Err(super::RepoFindOneError::Unknown(e.to_string())),
         }
     }
@@ -187,7 +271,7 @@ impl Repository<Coin, u32> for SqliteRepository {
 
         let result = sqlx::query_as::<Sqlite, CoinSql>(
             r#"
-                    SELECT * FROM coins WHERE id = ?
+                    SELECT * FROM coins WHERE id = ? AND deleted_at IS NULL
                     "#,
         )
         .bind(entity)
@@ -212,8 +296,8 @@ impl Repository<Coin, u32> for SqliteRepository {
         let result = sqlx::query(
             r#"
             UPDATE coins
-            SET price = ?, volume_24h = ?, percent_change_1h = ?, percent_change_24h = ?, percent_change_7d = ?, market_cap = ?, last_updated = ?
-            WHERE id = ?
+            SET price = ?, volume_24h = ?, percent_change_1h = ?, percent_change_24h = ?, percent_change_7d = ?, market_cap = ?, last_updated = ?, sources = ?
+            WHERE id = ? AND deleted_at IS NULL
             "#,
         )
         .bind(entity.price().value())
@@ -223,11 +307,13 @@ impl Repository<Coin, u32> for SqliteRepository {
         .bind(entity.percent_change_7d().value())
         .bind(entity.market_cap().value())
         .bind(entity.last_updated().value())
+        .bind(entity.sources().to_string())
         .bind(entity.id().value())
         .execute(&mut *conn)
         .await;
 
         match result {
+            Ok(result) if result.rows_affected() == 0 => Err(RepoUpdateError::NotFound),
             Ok(_) => Ok(entity),
             Err(e) => Err(RepoUpdateError::Unknown(e.to_string())),
         }
@@ -241,7 +327,10 @@ impl Repository<Coin, u32> for SqliteRepository {
 
         let result = sqlx::query(
             r#"
-                    DELETE FROM coins WHERE id = ?
+                    UPDATE coins SET
+                        deleted_at = CAST(strftime('%s', 'now') AS INTEGER),
+                        deletion_id = (SELECT COALESCE(MAX(deletion_id), 0) + 1 FROM coins)
+                    WHERE id = ? AND deleted_at IS NULL
                     "#,
         )
         .bind(entity)
@@ -249,6 +338,7 @@ impl Repository<Coin, u32> for SqliteRepository {
         .await;
 
         match result {
+            Ok(result) if result.rows_affected() == 0 => Err(RepoDeleteError::NotFound),
             Ok(_) => Ok(()),
             Err(e) => Err(RepoDeleteError::Unknown(e.to_string())),
         }
@@ -262,7 +352,10 @@ impl Repository<Coin, u32> for SqliteRepository {
 
         let result = sqlx::query(
             r#"
-                    DELETE FROM coins
+                    UPDATE coins SET
+                        deleted_at = CAST(strftime('%s', 'now') AS INTEGER),
+                        deletion_id = (SELECT COALESCE(MAX(deletion_id), 0) + 1 FROM coins)
+                    WHERE deleted_at IS NULL
                     "#,
         )
         .execute(&mut *conn)
@@ -282,7 +375,7 @@ impl Repository<Coin, u32> for SqliteRepository {
 
         let result = sqlx::query_as::<Sqlite, CoinSql>(
             r#"
-                    SELECT * FROM coins
+                    SELECT * FROM coins WHERE deleted_at IS NULL
                     "#,
         )
         .fetch_all(&mut *conn)
@@ -298,6 +391,70 @@ impl Repository<Coin, u32> for SqliteRepository {
     }
 }
 
+impl CoinTrashRepository for SqliteRepository {
+    async fn get_deleted_coins(&mut self) -> Result<Vec<DeletedCoin>, RepoGetAllError> {
+        let mut conn = self
+            .conn()
+            .await
+            .map_err(|e| RepoGetAllError::Unknown(e.to_string()))?;
+
+        let result = sqlx::query_as::<Sqlite, DeletedCoinSql>(
+            r#"
+                    SELECT * FROM coins WHERE deleted_at IS NOT NULL
+                    ORDER BY deletion_id DESC, id
+                    "#,
+        )
+        .fetch_all(&mut *conn)
+        .await;
+
+        match result {
+            Ok(coins) => Ok(coins
+                .into_iter()
+                .map(|coin| coin.try_into().unwrap())
+                .collect()),
+            Err(e) => Err(RepoGetAllError::Unknown(e.to_string())),
+        }
+    }
+
+    async fn restore_coin(&mut self, id: u32) -> Result<Coin, RepoUpdateError> {
+        let mut conn = self
+            .conn()
+            .await
+            .map_err(|e| RepoUpdateError::Unknown(e.to_string()))?;
+
+        let result = sqlx::query_as::<Sqlite, CoinSql>(
+            r#"
+                    UPDATE coins SET deleted_at = NULL, deletion_id = NULL
+                    WHERE id = ? AND deleted_at IS NOT NULL
+                    RETURNING *
+                    "#,
+        )
+        .bind(id)
+        .fetch_one(&mut *conn)
+        .await;
+
+        match result {
+            Ok(coin) => Ok(coin.try_into().unwrap()),
+            Err(sqlx::Error::RowNotFound) => Err(RepoUpdateError::NotFound),
+            Err(e) => Err(RepoUpdateError::Unknown(e.to_string())),
+        }
+    }
+
+    async fn purge_deleted_coins(&mut self, before: i64) -> Result<(), RepoDeleteError> {
+        let mut conn = self
+            .conn()
+            .await
+            .map_err(|e| RepoDeleteError::Unknown(e.to_string()))?;
+
+        sqlx::query("DELETE FROM coins WHERE deleted_at IS NOT NULL AND deleted_at < ?")
+            .bind(before)
+            .execute(&mut *conn)
+            .await
+            .map(|_| ())
+            .map_err(|e| RepoDeleteError::Unknown(e.to_string()))
+    }
+}
+
 impl Repository<Setting, String> for SqliteRepository {
     async fn create(&mut self, entity: Setting) -> Result<Setting, RepoCreateError> {
         let mut conn = self
@@ -341,6 +498,7 @@ impl Repository<Setting, String> for SqliteRepository {
 
         match result {
             Ok((key, value)) => Ok(Setting::new(key, value).unwrap()),
+            Err(sqlx::Error::RowNotFound) => Err(super::RepoFindOneError::NotFound),
             Err(e) => Err(super::RepoFindOneError::Unknown(e.to_string())),
         }
     }
@@ -388,6 +546,7 @@ impl Repository<Setting, String> for SqliteRepository {
         .await;
 
         match result {
+            Ok(result) if result.rows_affected() == 0 => Err(RepoUpdateError::NotFound),
             Ok(_) => Ok(entity),
             Err(e) => Err(RepoUpdateError::Unknown(e.to_string())),
         }
@@ -409,6 +568,7 @@ impl Repository<Setting, String> for SqliteRepository {
         .await;
 
         match result {
+            Ok(result) if result.rows_affected() == 0 => Err(RepoDeleteError::NotFound),
             Ok(_) => Ok(()),
             Err(e) => Err(RepoDeleteError::Unknown(e.to_string())),
         }
@@ -457,3 +617,1512 @@ impl Repository<Setting, String> for SqliteRepository {
         }
     }
 }
+
+#[derive(Debug, Clone, FromRow)]
+pub(crate) struct WatchlistSql {
+    id: u32,
+    name: String,
+}
+
+fn is_unique_violation(e: &sqlx::Error) -> bool {
+    e.as_database_error()
+        .map(|e| e.is_unique_violation())
+        .unwrap_or(false)
+}
+
+fn is_foreign_key_violation(e: &sqlx::Error) -> bool {
+    e.as_database_error()
+        .map(|e| e.is_foreign_key_violation())
+        .unwrap_or(false)
+}
+
+/// Loads the coin ids of the given watchlists, in display order, and builds the entities.
+async fn with_coin_ids(
+    conn: &mut SqliteConnection,
+    watchlists: Vec<WatchlistSql>,
+) -> Result<Vec<Watchlist>, sqlx::Error> {
+    let mut result = vec![];
+
+    for watchlist in watchlists {
+        let coin_ids = sqlx::query_as::<Sqlite, (u32,)>(
+            r#"
+                    SELECT watchlist_coins.coin_id FROM watchlist_coins
+                    JOIN coins ON coins.id = watchlist_coins.coin_id
+                    WHERE watchlist_coins.watchlist_id = ? AND coins.deleted_at IS NULL
+                    ORDER BY watchlist_coins.position
+                    "#,
+        )
+        .bind(watchlist.id)
+        .fetch_all(&mut *conn)
+        .await?
+        .into_iter()
+        .map(|(coin_id,)| coin_id)
+        .collect();
+
+        result.push(Watchlist::new(watchlist.id, watchlist.name, coin_ids).unwrap());
+    }
+
+    Ok(result)
+}
+
+impl Repository<Watchlist, u32> for SqliteRepository {
+    async fn create(&mut self, entity: Watchlist) -> Result<Watchlist, RepoCreateError> {
+        let mut conn = self
+            .conn()
+            .await
+            .map_err(|e| RepoCreateError::Unknown(e.to_string()))?;
+
+        let result = sqlx::query(
+            r#"
+            INSERT INTO watchlists (name)
+            VALUES (?)
+            "#,
+        )
+        .bind(entity.name().value())
+        .execute(&mut *conn)
+        .await;
+
+        match result {
+            Ok(result) => entity
+                .with_id(result.last_insert_rowid() as u32)
+                .map_err(|e| RepoCreateError::Unknown(e.to_string())),
+            Err(e) if is_unique_violation(&e) => Err(RepoCreateError::InvalidData(format!(
+                "Watchlist {} already exists",
+                entity.name().value()
+            ))),
+            Err(e) => Err(RepoCreateError::Unknown(e.to_string())),
+        }
+    }
+
+    async fn find_one(&mut self, entity: u32) -> Result<Watchlist, RepoFindOneError> {
+        let mut conn = self
+            .conn()
+            .await
+            .map_err(|e| RepoFindOneError::Unknown(e.to_string()))?;
+
+        let result = sqlx::query_as::<Sqlite, WatchlistSql>(
+            r#"
+                    SELECT * FROM watchlists WHERE id = ?
+                    "#,
+        )
+        .bind(entity)
+        .fetch_one(&mut *conn)
+        .await;
+
+        let watchlist = match result {
+            Ok(watchlist) => watchlist,
+            Err(sqlx::Error::RowNotFound) => return Err(RepoFindOneError::NotFound),
+            Err(e) => return Err(RepoFindOneError::Unknown(e.to_string())),
+        };
+
+        with_coin_ids(&mut conn, vec![watchlist])
+            .await
+            .map(|mut watchlists| watchlists.remove(0))
+            .map_err(|e| RepoFindOneError::Unknown(e.to_string()))
+    }
+
+    async fn find_all(&mut self, entity: u32) -> Result<Vec<Watchlist>, RepoFindAllError> {
+        let mut conn = self
+            .conn()
+            .await
+            .map_err(|e| RepoFindAllError::Unknown(e.to_string()))?;
+
+        let result = sqlx::query_as::<Sqlite, WatchlistSql>(
+            r#"
+                    SELECT * FROM watchlists WHERE id = ?
+                    "#,
+        )
+        .bind(entity)
+        .fetch_all(&mut *conn)
+        .await
+        .map_err(|e| RepoFindAllError::Unknown(e.to_string()))?;
+
+        with_coin_ids(&mut conn, result)
+            .await
+            .map_err(|e| RepoFindAllError::Unknown(e.to_string()))
+    }
+
+    async fn update(&mut self, entity: Watchlist) -> Result<Watchlist, RepoUpdateError> {
+        let Some(id) = entity.id() else {
+            return Err(RepoUpdateError::NotFound);
+        };
+
+        let mut conn = self
+            .conn()
+            .await
+            .map_err(|e| RepoUpdateError::Unknown(e.to_string()))?;
+
+        let result = sqlx::query(
+            r#"
+            UPDATE watchlists
+            SET name = ?
+            WHERE id = ?
+            "#,
+        )
+        .bind(entity.name().value())
+        .bind(id.value())
+        .execute(&mut *conn)
+        .await;
+
+        match result {
+            Ok(result) if result.rows_affected() == 0 => Err(RepoUpdateError::NotFound),
+            Ok(_) => Ok(entity),
+            Err(e) if is_unique_violation(&e) => Err(RepoUpdateError::InvalidData(format!(
+                "Watchlist {} already exists",
+                entity.name().value()
+            ))),
+            Err(e) => Err(RepoUpdateError::Unknown(e.to_string())),
+        }
+    }
+
+    async fn delete(&mut self, entity: u32) -> Result<(), RepoDeleteError> {
+        let mut conn = self
+            .conn()
+            .await
+            .map_err(|e| RepoDeleteError::Unknown(e.to_string()))?;
+
+        let result = sqlx::query(
+            r#"
+                    DELETE FROM watchlists WHERE id = ?
+                    "#,
+        )
+        .bind(entity)
+        .execute(&mut *conn)
+        .await;
+
+        match result {
+            Ok(result) if result.rows_affected() == 0 => Err(RepoDeleteError::NotFound),
+            Ok(_) => Ok(()),
+            Err(e) => Err(RepoDeleteError::Unknown(e.to_string())),
+        }
+    }
+
+    async fn delete_all(&mut self) -> Result<(), RepoDeleteError> {
+        let mut conn = self
+            .conn()
+            .await
+            .map_err(|e| RepoDeleteError::Unknown(e.to_string()))?;
+
+        let result = sqlx::query(
+            r#"
+                    DELETE FROM watchlists
+                    "#,
+        )
+        .execute(&mut *conn)
+        .await;
+
+        match result {
+            Ok(_) => Ok(()),
+            Err(e) => Err(RepoDeleteError::Unknown(e.to_string())),
+        }
+    }
+
+    async fn get_all(&mut self) -> Result<Vec<Watchlist>, RepoGetAllError> {
+        let mut conn = self
+            .conn()
+            .await
+            .map_err(|e| RepoGetAllError::Unknown(e.to_string()))?;
+
+        let result = sqlx::query_as::<Sqlite, WatchlistSql>(
+            r#"
+                    SELECT * FROM watchlists ORDER BY id
+                    "#,
+        )
+        .fetch_all(&mut *conn)
+        .await
+        .map_err(|e| RepoGetAllError::Unknown(e.to_string()))?;
+
+        with_coin_ids(&mut conn, result)
+            .await
+            .map_err(|e| RepoGetAllError::Unknown(e.to_string()))
+    }
+}
+
+impl WatchlistRepository for SqliteRepository {
+    async fn add_coin(&mut self, watchlist_id: u32, coin_id: u32) -> Result<(), RepoUpdateError> {
+        let mut conn = self
+            .conn()
+            .await
+            .map_err(|e| RepoUpdateError::Unknown(e.to_string()))?;
+
+        let tracked = sqlx::query_as::<Sqlite, (u32,)>(
+            r#"
+                    SELECT id FROM coins WHERE id = ? AND deleted_at IS NULL
+                    "#,
+        )
+        .bind(coin_id)
+        .fetch_optional(&mut *conn)
+        .await
+        .map_err(|e| RepoUpdateError::Unknown(e.to_string()))?;
+
+        if tracked.is_none() {
+            return Err(RepoUpdateError::NotFound);
+        }
+
+        let result = sqlx::query(
+            r#"
+            INSERT INTO watchlist_coins (watchlist_id, coin_id, position)
+            SELECT ?, ?, COALESCE(MAX(position) + 1, 0) FROM watchlist_coins WHERE watchlist_id = ?
+            ON CONFLICT(watchlist_id, coin_id) DO NOTHING;
+            "#,
+        )
+        .bind(watchlist_id)
+        .bind(coin_id)
+        .bind(watchlist_id)
+        .execute(&mut *conn)
+        .await;
+
+        match result {
+            Ok(_) => Ok(()),
+            Err(e) if is_foreign_key_violation(&e) => Err(RepoUpdateError::NotFound),
+            Err(e) => Err(RepoUpdateError::Unknown(e.to_string())),
+        }
+    }
+
+    async fn remove_coin(
+        &mut self,
+        watchlist_id: u32,
+        coin_id: u32,
+    ) -> Result<(), RepoDeleteError> {
+        let mut conn = self
+            .conn()
+            .await
+            .map_err(|e| RepoDeleteError::Unknown(e.to_string()))?;
+
+        let result = sqlx::query(
+            r#"
+                    DELETE FROM watchlist_coins WHERE watchlist_id = ? AND coin_id = ?
+                    "#,
+        )
+        .bind(watchlist_id)
+        .bind(coin_id)
+        .execute(&mut *conn)
+        .await;
+
+        match result {
+            Ok(result) if result.rows_affected() == 0 => Err(RepoDeleteError::NotFound),
+            Ok(_) => Ok(()),
+            Err(e) => Err(RepoDeleteError::Unknown(e.to_string())),
+        }
+    }
+
+    async fn reorder_coins(
+        &mut self,
+        watchlist_id: u32,
+        coin_ids: Vec<u32>,
+    ) -> Result<(), RepoUpdateError> {
+        let mut conn = self
+            .conn()
+            .await
+            .map_err(|e| RepoUpdateError::Unknown(e.to_string()))?;
+
+        let mut tx = sqlx::Connection::begin(&mut *conn)
+            .await
+            .map_err(|e| RepoUpdateError::Unknown(e.to_string()))?;
+
+        for (position, coin_id) in coin_ids.into_iter().enumerate() {
+            let result = sqlx::query(
+                r#"
+                UPDATE watchlist_coins
+                SET position = ?
+                WHERE watchlist_id = ? AND coin_id = ?
+                "#,
+            )
+            .bind(position as u32)
+            .bind(watchlist_id)
+            .bind(coin_id)
+            .execute(&mut *tx)
+            .await;
+
+            match result {
+                Ok(result) if result.rows_affected() == 0 => return Err(RepoUpdateError::NotFound),
+                Ok(_) => {}
+                Err(e) => return Err(RepoUpdateError::Unknown(e.to_string())),
+            }
+        }
+
+        tx.commit()
+            .await
+            .map_err(|e| RepoUpdateError::Unknown(e.to_string()))
+    }
+
+    async fn get_coins(&mut self, watchlist_id: u32) -> Result<Vec<Coin>, RepoFindOneError> {
+        let mut conn = self
+            .conn()
+            .await
+            .map_err(|e| RepoFindOneError::Unknown(e.to_string()))?;
+
+        let exists = sqlx::query_as::<Sqlite, (u32,)>(
+            r#"
+                    SELECT id FROM watchlists WHERE id = ?
+                    "#,
+        )
+        .bind(watchlist_id)
+        .fetch_optional(&mut *conn)
+        .await
+        .map_err(|e| RepoFindOneError::Unknown(e.to_string()))?;
+
+        if exists.is_none() {
+            return Err(RepoFindOneError::NotFound);
+        }
+
+        let result = sqlx::query_as::<Sqlite, CoinSql>(
+            r#"
+                    SELECT coins.* FROM coins
+                    JOIN watchlist_coins ON watchlist_coins.coin_id = coins.id
+                    WHERE watchlist_coins.watchlist_id = ? AND coins.deleted_at IS NULL
+                    ORDER BY watchlist_coins.position
+                    "#,
+        )
+        .bind(watchlist_id)
+        .fetch_all(&mut *conn)
+        .await;
+
+        match result {
+            Ok(coins) => Ok(coins
+                .into_iter()
+                .map(|coin| coin.try_into().unwrap())
+                .collect()),
+            Err(e) => Err(RepoFindOneError::Unknown(e.to_string())),
+        }
+    }
+}
+
+#[derive(Debug, Clone, FromRow)]
+pub(crate) struct TransactionSql {
+    id: u32,
+    exchange: String,
+    trade_id: String,
+    executed_at: String,
+    side: String,
+    base_coin_id: u32,
+    base_symbol: String,
+    quote_coin_id: Option<u32>,
+    quote_symbol: String,
+    quantity: f64,
+    price: f64,
+    fee: f64,
+    fee_asset: String,
+}
+
+impl TryInto<Transaction> for TransactionSql {
+    type Error = String;
+
+    fn try_into(self) -> Result<Transaction, Self::Error> {
+        let trade = Trade {
+            exchange: self.exchange.parse()?,
+            trade_id: self.trade_id,
+            executed_at: self.executed_at,
+            side: self.side.parse()?,
+            base: self.base_symbol,
+            quote: self.quote_symbol,
+            quantity: self.quantity,
+            price: self.price,
+            fee: self.fee,
+            fee_asset: self.fee_asset,
+        };
+
+        Transaction::new(trade, self.base_coin_id, self.quote_coin_id)
+            .map(|transaction| transaction.with_id(self.id))
+            .map_err(|e| e.to_string())
+    }
+}
+
+impl TransactionRepository for SqliteRepository {
+    async fn add_transaction(
+        &mut self,
+        transaction: Transaction,
+    ) -> Result<Option<Transaction>, RepoCreateError> {
+        let mut conn = self
+            .conn()
+            .await
+            .map_err(|e| RepoCreateError::Unknown(e.to_string()))?;
+
+        let trade = transaction.trade();
+        let result = sqlx::query(
+            r#"
+            INSERT INTO transactions (exchange, trade_id, executed_at, side, base_coin_id, base_symbol, quote_coin_id, quote_symbol, quantity, price, fee, fee_asset)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            ON CONFLICT(exchange, trade_id) DO NOTHING;
+            "#,
+        )
+        .bind(trade.exchange.as_str())
+        .bind(&trade.trade_id)
+        .bind(&trade.executed_at)
+        .bind(trade.side.as_str())
+        .bind(transaction.base_coin_id())
+        .bind(&trade.base)
+        .bind(transaction.quote_coin_id())
+        .bind(&trade.quote)
+        .bind(trade.quantity)
+        .bind(trade.price)
+        .bind(trade.fee)
+        .bind(&trade.fee_asset)
+        .execute(&mut *conn)
+        .await;
+
+        match result {
+            Ok(result) if result.rows_affected() == 0 => Ok(None),
+            Ok(result) => Ok(Some(transaction.with_id(result.last_insert_rowid() as u32))),
+            Err(e) => Err(RepoCreateError::Unknown(e.to_string())),
+        }
+    }
+
+    async fn get_transactions(
+        &mut self,
+        coin_id: Option<u32>,
+    ) -> Result<Vec<Transaction>, RepoGetAllError> {
+        let mut conn = self
+            .conn()
+            .await
+            .map_err(|e| RepoGetAllError::Unknown(e.to_string()))?;
+
+        let result = sqlx::query_as::<Sqlite, TransactionSql>(
+            r#"
+                    SELECT * FROM transactions
+                    WHERE ? IS NULL OR base_coin_id = ? OR quote_coin_id = ?
+                    ORDER BY executed_at, id
+                    "#,
+        )
+        .bind(coin_id)
+        .bind(coin_id)
+        .bind(coin_id)
+        .fetch_all(&mut *conn)
+        .await
+        .map_err(|e| RepoGetAllError::Unknown(e.to_string()))?;
+
+        result
+            .into_iter()
+            .map(|transaction| transaction.try_into())
+            .collect::<Result<Vec<Transaction>, String>>()
+            .map_err(RepoGetAllError::Unknown)
+    }
+
+    async fn delete_transaction(&mut self, id: u32) -> Result<(), RepoDeleteError> {
+        let mut conn = self
+            .conn()
+            .await
+            .map_err(|e| RepoDeleteError::Unknown(e.to_string()))?;
+
+        let result = sqlx::query(
+            r#"
+                    DELETE FROM transactions WHERE id = ?
+                    "#,
+        )
+        .bind(id)
+        .execute(&mut *conn)
+        .await;
+
+        match result {
+            Ok(result) if result.rows_affected() == 0 => Err(RepoDeleteError::NotFound),
+            Ok(_) => Ok(()),
+            Err(e) => Err(RepoDeleteError::Unknown(e.to_string())),
+        }
+    }
+
+    async fn get_trade_ids(&mut self, exchange: Exchange) -> Result<Vec<String>, RepoGetAllError> {
+        let mut conn = self
+            .conn()
+            .await
+            .map_err(|e| RepoGetAllError::Unknown(e.to_string()))?;
+
+        sqlx::query_scalar::<Sqlite, String>("SELECT trade_id FROM transactions WHERE exchange = ?")
+            .bind(exchange.as_str())
+            .fetch_all(&mut *conn)
+            .await
+            .map_err(|e| RepoGetAllError::Unknown(e.to_string()))
+    }
+}
+
+#[derive(Debug, Clone, FromRow)]
+pub(crate) struct CandleSql {
+    coin_id: u32,
+    interval: String,
+    open_time: i64,
+    open: f64,
+    high: f64,
+    low: f64,
+    close: f64,
+    volume: f64,
+}
+
+impl TryInto<Candle> for CandleSql {
+    type Error = String;
+
+    fn try_into(self) -> Result<Candle, Self::Error> {
+        let ohlcv = Ohlcv {
+            open: self.open,
+            high: self.high,
+            low: self.low,
+            close: self.close,
+            volume: self.volume,
+        };
+
+        Candle::new(self.coin_id, self.interval.parse()?, self.open_time, ohlcv)
+            .map_err(|e| e.to_string())
+    }
+}
+
+impl CandleRepository for SqliteRepository {
+    async fn upsert_candles(&mut self, candles: Vec<Candle>) -> Result<(), RepoCreateError> {
+        let mut conn = self
+            .conn()
+            .await
+            .map_err(|e| RepoCreateError::Unknown(e.to_string()))?;
+
+        let mut tx = sqlx::Connection::begin(&mut *conn)
+            .await
+            .map_err(|e| RepoCreateError::Unknown(e.to_string()))?;
+
+        for candle in candles {
+            let ohlcv = candle.ohlcv();
+            let result = sqlx::query(
+                r#"
+                INSERT INTO candles (coin_id, interval, open_time, open, high, low, close, volume)
+                VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+                ON CONFLICT(coin_id, interval, open_time) DO UPDATE SET
+                    open = excluded.open,
+                    high = excluded.high,
+                    low = excluded.low,
+                    close = excluded.close,
+                    volume = excluded.volume
+                "#,
+            )
+            .bind(candle.coin_id())
+            .bind(candle.interval().as_str())
+            .bind(candle.open_time())
+            .bind(ohlcv.open)
+            .bind(ohlcv.high)
+            .bind(ohlcv.low)
+            .bind(ohlcv.close)
+            .bind(ohlcv.volume)
+            .execute(&mut *tx)
+            .await;
+
+            match result {
+                Ok(_) => {}
+                Err(e) if is_foreign_key_violation(&e) => {
+                    return Err(RepoCreateError::InvalidData(format!(
+                        "Coin {} is not tracked",
+                        candle.coin_id()
+                    )))
+                }
+                Err(e) => return Err(RepoCreateError::Unknown(e.to_string())),
+            }
+        }
+
+        tx.commit()
+            .await
+            .map_err(|e| RepoCreateError::Unknown(e.to_string()))
+    }
+
+    async fn get_candles(
+        &mut self,
+        coin_id: u32,
+        interval: CandleInterval,
+        start: i64,
+        end: i64,
+    ) -> Result<Vec<Candle>, RepoGetAllError> {
+        let mut conn = self
+            .conn()
+            .await
+            .map_err(|e| RepoGetAllError::Unknown(e.to_string()))?;
+
+        let result = sqlx::query_as::<Sqlite, CandleSql>(
+            r#"
+                    SELECT candles.* FROM candles
+                    JOIN coins ON coins.id = candles.coin_id
+                    WHERE candles.coin_id = ? AND candles.interval = ?
+                        AND candles.open_time BETWEEN ? AND ?
+                        AND coins.deleted_at IS NULL
+                    ORDER BY candles.open_time
+                    "#,
+        )
+        .bind(coin_id)
+        .bind(interval.as_str())
+        .bind(start)
+        .bind(end)
+        .fetch_all(&mut *conn)
+        .await
+        .map_err(|e| RepoGetAllError::Unknown(e.to_string()))?;
+
+        result
+            .into_iter()
+            .map(|candle| candle.try_into())
+            .collect::<Result<Vec<Candle>, String>>()
+            .map_err(RepoGetAllError::Unknown)
+    }
+}
+
+#[derive(Debug, Clone, FromRow)]
+pub(crate) struct CatalogSql {
+    id: u32,
+    slug: String,
+    symbol: String,
+    name: String,
+    rank: Option<u32>,
+    tracked: bool,
+}
+
+impl TryInto<CatalogMatch> for CatalogSql {
+    type Error = &'static str;
+
+    fn try_into(self) -> Result<CatalogMatch, Self::Error> {
+        Ok(CatalogMatch {
+            entry: CatalogEntry::new(self.id, self.slug, self.symbol, self.name, self.rank)?,
+            tracked: self.tracked,
+        })
+    }
+}
+
+async fn insert_catalog(
+    conn: &mut SqliteConnection,
+    entries: Vec<CatalogEntry>,
+) -> Result<(), sqlx::Error> {
+    for entry in entries {
+        sqlx::query(
+            r#"
+            INSERT INTO coin_catalog (id, slug, symbol, name, rank)
+            VALUES (?, ?, ?, ?, ?)
+            ON CONFLICT(id) DO UPDATE SET
+                slug = excluded.slug,
+                symbol = excluded.symbol,
+                name = excluded.name,
+                rank = excluded.rank
+            "#,
+        )
+        .bind(entry.id())
+        .bind(entry.slug())
+        .bind(entry.symbol())
+        .bind(entry.name())
+        .bind(entry.rank())
+        .execute(&mut *conn)
+        .await?;
+    }
+
+    Ok(())
+}
+
+impl CatalogRepository for SqliteRepository {
+    async fn replace_catalog(&mut self, entries: Vec<CatalogEntry>) -> Result<(), RepoCreateError> {
+        let mut conn = self
+            .conn()
+            .await
+            .map_err(|e| RepoCreateError::Unknown(e.to_string()))?;
+
+        let mut tx = sqlx::Connection::begin(&mut *conn)
+            .await
+            .map_err(|e| RepoCreateError::Unknown(e.to_string()))?;
+
+        sqlx::query("DELETE FROM coin_catalog")
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| RepoCreateError::Unknown(e.to_string()))?;
+
+        insert_catalog(&mut tx, entries)
+            .await
+            .map_err(|e| RepoCreateError::Unknown(e.to_string()))?;
+
+        tx.commit()
+            .await
+            .map_err(|e| RepoCreateError::Unknown(e.to_string()))
+    }
+
+    async fn upsert_catalog(&mut self, entries: Vec<CatalogEntry>) -> Result<(), RepoCreateError> {
+        let mut conn = self
+            .conn()
+            .await
+            .map_err(|e| RepoCreateError::Unknown(e.to_string()))?;
+
+        let mut tx = sqlx::Connection::begin(&mut *conn)
+            .await
+            .map_err(|e| RepoCreateError::Unknown(e.to_string()))?;
+
+        insert_catalog(&mut tx, entries)
+            .await
+            .map_err(|e| RepoCreateError::Unknown(e.to_string()))?;
+
+        tx.commit()
+            .await
+            .map_err(|e| RepoCreateError::Unknown(e.to_string()))
+    }
+
+    async fn search_catalog(
+        &mut self,
+        query: String,
+        limit: u32,
+    ) -> Result<Vec<CatalogMatch>, RepoGetAllError> {
+        let mut conn = self
+            .conn()
+            .await
+            .map_err(|e| RepoGetAllError::Unknown(e.to_string()))?;
+
+        let query = query.trim().to_lowercase();
+
+        let result = sqlx::query_as::<Sqlite, CatalogSql>(
+            r#"
+                    SELECT coin_catalog.*, coins.id IS NOT NULL AS tracked
+                    FROM coin_catalog
+                    LEFT JOIN coins ON coins.id = coin_catalog.id AND coins.deleted_at IS NULL
+                    WHERE instr(lower(coin_catalog.symbol), ?1) > 0
+                        OR instr(lower(coin_catalog.name), ?1) > 0
+                        OR instr(coin_catalog.slug, ?1) > 0
+                    ORDER BY lower(coin_catalog.symbol) = ?1 DESC,
+                        coin_catalog.rank IS NULL,
+                        coin_catalog.rank,
+                        coin_catalog.name
+                    LIMIT ?2
+                    "#,
+        )
+        .bind(query)
+        .bind(limit)
+        .fetch_all(&mut *conn)
+        .await
+        .map_err(|e| RepoGetAllError::Unknown(e.to_string()))?;
+
+        result
+            .into_iter()
+            .map(|entry| entry.try_into())
+            .collect::<Result<Vec<CatalogMatch>, &'static str>>()
+            .map_err(|e| RepoGetAllError::Unknown(e.to_string()))
+    }
+}
+
+#[derive(Debug, Clone, FromRow)]
+pub(crate) struct CoinMetadataSql {
+    coin_id: u32,
+    description: String,
+    logo_url: Option<String>,
+    logo: Option<Vec<u8>>,
+    logo_type: Option<String>,
+    fetched_at: String,
+}
+
+impl CoinMetadataRepository for SqliteRepository {
+    async fn upsert_metadata(&mut self, metadata: CoinMetadata) -> Result<(), RepoCreateError> {
+        let mut conn = self
+            .conn()
+            .await
+            .map_err(|e| RepoCreateError::Unknown(e.to_string()))?;
+
+        let mut tx = sqlx::Connection::begin(&mut *conn)
+            .await
+            .map_err(|e| RepoCreateError::Unknown(e.to_string()))?;
+
+        let result = sqlx::query(
+            r#"
+            INSERT INTO coin_metadata (coin_id, description, logo_url, logo, logo_type, fetched_at)
+            VALUES (?, ?, ?, ?, ?, ?)
+            ON CONFLICT(coin_id) DO UPDATE SET
+                description = excluded.description,
+                logo_url = excluded.logo_url,
+                logo = excluded.logo,
+                logo_type = excluded.logo_type,
+                fetched_at = excluded.fetched_at
+            "#,
+        )
+        .bind(metadata.coin_id)
+        .bind(&metadata.description)
+        .bind(&metadata.logo_url)
+        .bind(metadata.logo.as_ref().map(|logo| logo.bytes.clone()))
+        .bind(metadata.logo.as_ref().map(|logo| logo.content_type.clone()))
+        .bind(&metadata.fetched_at)
+        .execute(&mut *tx)
+        .await;
+
+        match result {
+            Ok(_) => {}
+            Err(e) if is_foreign_key_violation(&e) => {
+                return Err(RepoCreateError::InvalidData(format!(
+                    "Coin {} is not tracked",
+                    metadata.coin_id
+                )))
+            }
+            Err(e) => return Err(RepoCreateError::Unknown(e.to_string())),
+        }
+
+        for table in ["coin_links", "coin_tags", "coin_contracts"] {
+            sqlx::query(&format!("DELETE FROM {} WHERE coin_id = ?", table))
+                .bind(metadata.coin_id)
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| RepoCreateError::Unknown(e.to_string()))?;
+        }
+
+        for (position, link) in metadata.links.iter().enumerate() {
+            sqlx::query(
+                "INSERT OR IGNORE INTO coin_links (coin_id, kind, url, position) VALUES (?, ?, ?, ?)",
+            )
+            .bind(metadata.coin_id)
+            .bind(link.kind.as_str())
+            .bind(&link.url)
+            .bind(position as u32)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| RepoCreateError::Unknown(e.to_string()))?;
+        }
+
+        for (position, tag) in metadata.tags.iter().enumerate() {
+            sqlx::query(
+                "INSERT OR IGNORE INTO coin_tags (coin_id, tag, position) VALUES (?, ?, ?)",
+            )
+            .bind(metadata.coin_id)
+            .bind(tag)
+            .bind(position as u32)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| RepoCreateError::Unknown(e.to_string()))?;
+        }
+
+        for contract in &metadata.contracts {
+            sqlx::query(
+                "INSERT OR IGNORE INTO coin_contracts (coin_id, platform, address) VALUES (?, ?, ?)",
+            )
+            .bind(metadata.coin_id)
+            .bind(&contract.platform)
+            .bind(&contract.address)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| RepoCreateError::Unknown(e.to_string()))?;
+        }
+
+        tx.commit()
+            .await
+            .map_err(|e| RepoCreateError::Unknown(e.to_string()))
+    }
+
+    async fn find_metadata(&mut self, coin_id: u32) -> Result<CoinMetadata, RepoFindOneError> {
+        let mut conn = self
+            .conn()
+            .await
+            .map_err(|e| RepoFindOneError::Unknown(e.to_string()))?;
+
+        let metadata = sqlx::query_as::<Sqlite, CoinMetadataSql>(
+            "SELECT * FROM coin_metadata WHERE coin_id = ?",
+        )
+        .bind(coin_id)
+        .fetch_optional(&mut *conn)
+        .await
+        .map_err(|e| RepoFindOneError::Unknown(e.to_string()))?
+        .ok_or(RepoFindOneError::NotFound)?;
+
+        let links = sqlx::query_as::<Sqlite, (String, String)>(
+            "SELECT kind, url FROM coin_links WHERE coin_id = ? ORDER BY position",
+        )
+        .bind(coin_id)
+        .fetch_all(&mut *conn)
+        .await
+        .map_err(|e| RepoFindOneError::Unknown(e.to_string()))?
+        .into_iter()
+        .map(|(kind, url)| {
+            Ok(CoinLink {
+                kind: kind.parse()?,
+                url,
+            })
+        })
+        .collect::<Result<Vec<CoinLink>, String>>()
+        .map_err(RepoFindOneError::Unknown)?;
+
+        let tags = sqlx::query_scalar::<Sqlite, String>(
+            "SELECT tag FROM coin_tags WHERE coin_id = ? ORDER BY position",
+        )
+        .bind(coin_id)
+        .fetch_all(&mut *conn)
+        .await
+        .map_err(|e| RepoFindOneError::Unknown(e.to_string()))?;
+
+        let contracts = sqlx::query_as::<Sqlite, (String, String)>(
+            "SELECT platform, address FROM coin_contracts WHERE coin_id = ? ORDER BY platform",
+        )
+        .bind(coin_id)
+        .fetch_all(&mut *conn)
+        .await
+        .map_err(|e| RepoFindOneError::Unknown(e.to_string()))?
+        .into_iter()
+        .map(|(platform, address)| TokenContract { platform, address })
+        .collect();
+
+        let logo = match (metadata.logo, metadata.logo_type) {
+            (Some(bytes), Some(content_type)) => Some(Logo {
+                content_type,
+                bytes,
+            }),
+            _ => None,
+        };
+
+        Ok(CoinMetadata {
+            coin_id: metadata.coin_id,
+            description: metadata.description,
+            logo_url: metadata.logo_url,
+            logo,
+            links,
+            tags,
+            contracts,
+            fetched_at: metadata.fetched_at,
+        })
+    }
+}
+
+#[derive(Debug, Clone, FromRow)]
+pub(crate) struct CachedResponseSql {
+    key: String,
+    body: String,
+    fetched_at: i64,
+    expires_at: i64,
+}
+
+impl From<CachedResponseSql> for CachedResponse {
+    fn from(response: CachedResponseSql) -> Self {
+        CachedResponse {
+            key: response.key,
+            body: response.body,
+            fetched_at: response.fetched_at,
+            expires_at: response.expires_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, FromRow)]
+pub(crate) struct EndpointUsageSql {
+    day: String,
+    endpoint: String,
+    calls: u32,
+    errors: u32,
+    credits: u32,
+    avg_latency_ms: f64,
+    max_latency_ms: u32,
+}
+
+impl From<EndpointUsageSql> for EndpointUsage {
+    fn from(usage: EndpointUsageSql) -> Self {
+        EndpointUsage {
+            day: usage.day,
+            endpoint: usage.endpoint,
+            calls: usage.calls,
+            errors: usage.errors,
+            credits: usage.credits,
+            avg_latency_ms: usage.avg_latency_ms,
+            max_latency_ms: usage.max_latency_ms,
+        }
+    }
+}
+
+#[derive(Debug, Clone, FromRow)]
+pub(crate) struct PlanInfoSql {
+    credit_limit_daily: Option<u32>,
+    credit_limit_monthly: Option<u32>,
+    rate_limit_minute: Option<u32>,
+    credits_used_day: u32,
+    credits_used_month: u32,
+    monthly_reset: Option<String>,
+    fetched_at: String,
+}
+
+impl From<PlanInfoSql> for PlanInfo {
+    fn from(plan: PlanInfoSql) -> Self {
+        PlanInfo {
+            credit_limit_daily: plan.credit_limit_daily,
+            credit_limit_monthly: plan.credit_limit_monthly,
+            rate_limit_minute: plan.rate_limit_minute,
+            credits_used_day: plan.credits_used_day,
+            credits_used_month: plan.credits_used_month,
+            monthly_reset: plan.monthly_reset,
+            fetched_at: plan.fetched_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, FromRow)]
+pub(crate) struct ApiKeySql {
+    id: u32,
+    label: String,
+    key: String,
+    plan: String,
+    monthly_credits: Option<u32>,
+    priority: u32,
+    enabled: bool,
+    status: String,
+    status_message: Option<String>,
+    available_at: Option<i64>,
+    credits_month: String,
+    credits_used: u32,
+    last_used_at: Option<i64>,
+    created_at: i64,
+}
+
+impl TryInto<ApiKey> for ApiKeySql {
+    type Error = String;
+
+    fn try_into(self) -> Result<ApiKey, Self::Error> {
+        Ok(ApiKey {
+            id: self.id,
+            label: self.label,
+            key: self.key,
+            plan: self.plan,
+            monthly_credits: self.monthly_credits,
+            priority: self.priority,
+            enabled: self.enabled,
+            status: self.status.parse()?,
+            status_message: self.status_message,
+            available_at: self.available_at,
+            credits_month: self.credits_month,
+            credits_used: self.credits_used,
+            last_used_at: self.last_used_at,
+            created_at: self.created_at,
+        })
+    }
+}
+
+impl ProviderRepository for SqliteRepository {
+    async fn find_response(&mut self, key: String) -> Result<CachedResponse, RepoFindOneError> {
+        let mut conn = self
+            .conn()
+            .await
+            .map_err(|e| RepoFindOneError::Unknown(e.to_string()))?;
+
+        sqlx::query_as::<Sqlite, CachedResponseSql>("SELECT * FROM provider_cache WHERE key = ?")
+            .bind(key)
+            .fetch_optional(&mut *conn)
+            .await
+            .map_err(|e| RepoFindOneError::Unknown(e.to_string()))?
+            .map(CachedResponse::from)
+            .ok_or(RepoFindOneError::NotFound)
+    }
+
+    async fn store_response(&mut self, response: CachedResponse) -> Result<(), RepoCreateError> {
+        let mut conn = self
+            .conn()
+            .await
+            .map_err(|e| RepoCreateError::Unknown(e.to_string()))?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO provider_cache (key, body, fetched_at, expires_at)
+            VALUES (?, ?, ?, ?)
+            ON CONFLICT(key) DO UPDATE SET
+                body = excluded.body,
+                fetched_at = excluded.fetched_at,
+                expires_at = excluded.expires_at
+            "#,
+        )
+        .bind(response.key)
+        .bind(response.body)
+        .bind(response.fetched_at)
+        .bind(response.expires_at)
+        .execute(&mut *conn)
+        .await
+        .map(|_| ())
+        .map_err(|e| RepoCreateError::Unknown(e.to_string()))
+    }
+
+    async fn purge_responses(&mut self, before: i64) -> Result<(), RepoDeleteError> {
+        let mut conn = self
+            .conn()
+            .await
+            .map_err(|e| RepoDeleteError::Unknown(e.to_string()))?;
+
+        sqlx::query("DELETE FROM provider_cache WHERE expires_at < ?")
+            .bind(before)
+            .execute(&mut *conn)
+            .await
+            .map(|_| ())
+            .map_err(|e| RepoDeleteError::Unknown(e.to_string()))
+    }
+
+    async fn add_credits(&mut self, day: String, credits: u32) -> Result<(), RepoUpdateError> {
+        let mut conn = self
+            .conn()
+            .await
+            .map_err(|e| RepoUpdateError::Unknown(e.to_string()))?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO provider_credits (day, credits)
+            VALUES (?, ?)
+            ON CONFLICT(day) DO UPDATE SET credits = credits + excluded.credits
+            "#,
+        )
+        .bind(day)
+        .bind(credits)
+        .execute(&mut *conn)
+        .await
+        .map(|_| ())
+        .map_err(|e| RepoUpdateError::Unknown(e.to_string()))
+    }
+
+    async fn credits_used(&mut self, day: String) -> Result<u32, RepoFindOneError> {
+        let mut conn = self
+            .conn()
+            .await
+            .map_err(|e| RepoFindOneError::Unknown(e.to_string()))?;
+
+        sqlx::query_scalar::<Sqlite, u32>("SELECT credits FROM provider_credits WHERE day = ?")
+            .bind(day)
+            .fetch_optional(&mut *conn)
+            .await
+            .map(|credits| credits.unwrap_or_default())
+            .map_err(|e| RepoFindOneError::Unknown(e.to_string()))
+    }
+
+    async fn record_call(
+        &mut self,
+        call: ProviderCall,
+        purge_before: i64,
+    ) -> Result<(), RepoCreateError> {
+        let mut conn = self
+            .conn()
+            .await
+            .map_err(|e| RepoCreateError::Unknown(e.to_string()))?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO provider_calls (called_at, endpoint, status, latency_ms, credits, error_code)
+            VALUES (?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(call.called_at)
+        .bind(call.endpoint)
+        .bind(call.status)
+        .bind(call.latency_ms)
+        .bind(call.credits)
+        .bind(call.error_code)
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| RepoCreateError::Unknown(e.to_string()))?;
+
+        sqlx::query("DELETE FROM provider_calls WHERE called_at < ?")
+            .bind(purge_before)
+            .execute(&mut *conn)
+            .await
+            .map(|_| ())
+            .map_err(|e| RepoCreateError::Unknown(e.to_string()))
+    }
+
+    async fn usage_by_endpoint(
+        &mut self,
+        since: i64,
+    ) -> Result<Vec<EndpointUsage>, RepoGetAllError> {
+        let mut conn = self
+            .conn()
+            .await
+            .map_err(|e| RepoGetAllError::Unknown(e.to_string()))?;
+
+        let usage = sqlx::query_as::<Sqlite, EndpointUsageSql>(
+            r#"
+            SELECT date(called_at, 'unixepoch') AS day,
+                   endpoint,
+                   COUNT(*) AS calls,
+                   SUM(CASE
+                       WHEN status BETWEEN 200 AND 299 AND IFNULL(error_code, 0) = 0 THEN 0
+                       ELSE 1
+                   END) AS errors,
+                   SUM(credits) AS credits,
+                   AVG(latency_ms) AS avg_latency_ms,
+                   MAX(latency_ms) AS max_latency_ms
+            FROM provider_calls
+            WHERE called_at >= ?
+            GROUP BY day, endpoint
+            ORDER BY day, endpoint
+            "#,
+        )
+        .bind(since)
+        .fetch_all(&mut *conn)
+        .await
+        .map_err(|e| RepoGetAllError::Unknown(e.to_string()))?;
+
+        Ok(usage.into_iter().map(EndpointUsage::from).collect())
+    }
+
+    async fn store_plan(&mut self, plan: PlanInfo) -> Result<(), RepoCreateError> {
+        let mut conn = self
+            .conn()
+            .await
+            .map_err(|e| RepoCreateError::Unknown(e.to_string()))?;
+
+        sqlx::query(
+            r#"
+            INSERT OR REPLACE INTO provider_plan (
+                id, credit_limit_daily, credit_limit_monthly, rate_limit_minute,
+                credits_used_day, credits_used_month, monthly_reset, fetched_at
+            )
+            VALUES (1, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(plan.credit_limit_daily)
+        .bind(plan.credit_limit_monthly)
+        .bind(plan.rate_limit_minute)
+        .bind(plan.credits_used_day)
+        .bind(plan.credits_used_month)
+        .bind(plan.monthly_reset)
+        .bind(plan.fetched_at)
+        .execute(&mut *conn)
+        .await
+        .map(|_| ())
+        .map_err(|e| RepoCreateError::Unknown(e.to_string()))
+    }
+
+    async fn find_plan(&mut self) -> Result<PlanInfo, RepoFindOneError> {
+        let mut conn = self
+            .conn()
+            .await
+            .map_err(|e| RepoFindOneError::Unknown(e.to_string()))?;
+
+        sqlx::query_as::<Sqlite, PlanInfoSql>("SELECT * FROM provider_plan WHERE id = 1")
+            .fetch_optional(&mut *conn)
+            .await
+            .map_err(|e| RepoFindOneError::Unknown(e.to_string()))?
+            .map(PlanInfo::from)
+            .ok_or(RepoFindOneError::NotFound)
+    }
+
+    async fn add_key(&mut self, key: ApiKey) -> Result<ApiKey, RepoCreateError> {
+        let mut conn = self
+            .conn()
+            .await
+            .map_err(|e| RepoCreateError::Unknown(e.to_string()))?;
+
+        let result = sqlx::query(
+            r#"
+            INSERT INTO api_keys (
+                label, key, plan, monthly_credits, priority, enabled, status, status_message,
+                available_at, credits_month, credits_used, last_used_at, created_at
+            )
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(&key.label)
+        .bind(&key.key)
+        .bind(&key.plan)
+        .bind(key.monthly_credits)
+        .bind(key.priority)
+        .bind(key.enabled)
+        .bind(key.status.as_str())
+        .bind(&key.status_message)
+        .bind(key.available_at)
+        .bind(&key.credits_month)
+        .bind(key.credits_used)
+        .bind(key.last_used_at)
+        .bind(key.created_at)
+        .execute(&mut *conn)
+        .await;
+
+        match result {
+            Ok(result) => Ok(ApiKey {
+                id: result.last_insert_rowid() as u32,
+                ..key
+            }),
+            Err(e) if is_unique_violation(&e) => Err(RepoCreateError::InvalidData(format!(
+                "API key {} or its value is already in the pool",
+                key.label
+            ))),
+            Err(e) => Err(RepoCreateError::Unknown(e.to_string())),
+        }
+    }
+
+    async fn find_key(&mut self, id: u32) -> Result<ApiKey, RepoFindOneError> {
+        let mut conn = self
+            .conn()
+            .await
+            .map_err(|e| RepoFindOneError::Unknown(e.to_string()))?;
+
+        sqlx::query_as::<Sqlite, ApiKeySql>("SELECT * FROM api_keys WHERE id = ?")
+            .bind(id)
+            .fetch_optional(&mut *conn)
+            .await
+            .map_err(|e| RepoFindOneError::Unknown(e.to_string()))?
+            .ok_or(RepoFindOneError::NotFound)?
+            .try_into()
+            .map_err(RepoFindOneError::Unknown)
+    }
+
+    async fn get_keys(&mut self) -> Result<Vec<ApiKey>, RepoGetAllError> {
+        let mut conn = self
+            .conn()
+            .await
+            .map_err(|e| RepoGetAllError::Unknown(e.to_string()))?;
+
+        sqlx::query_as::<Sqlite, ApiKeySql>("SELECT * FROM api_keys ORDER BY id")
+            .fetch_all(&mut *conn)
+            .await
+            .map_err(|e| RepoGetAllError::Unknown(e.to_string()))?
+            .into_iter()
+            .map(|key| key.try_into())
+            .collect::<Result<Vec<ApiKey>, String>>()
+            .map_err(RepoGetAllError::Unknown)
+    }
+
+    async fn update_key(&mut self, key: ApiKey) -> Result<ApiKey, RepoUpdateError> {
+        let mut conn = self
+            .conn()
+            .await
+            .map_err(|e| RepoUpdateError::Unknown(e.to_string()))?;
+
+        let result = sqlx::query(
+            r#"
+            UPDATE api_keys
+            SET label = ?, plan = ?, monthly_credits = ?, priority = ?, enabled = ?, status = ?,
+                status_message = ?, available_at = ?, credits_month = ?, credits_used = ?,
+                last_used_at = ?
+            WHERE id = ?
+            "#,
+        )
+        .bind(&key.label)
+        .bind(&key.plan)
+        .bind(key.monthly_credits)
+        .bind(key.priority)
+        .bind(key.enabled)
+        .bind(key.status.as_str())
+        .bind(&key.status_message)
+        .bind(key.available_at)
+        .bind(&key.credits_month)
+        .bind(key.credits_used)
+        .bind(key.last_used_at)
+        .bind(key.id)
+        .execute(&mut *conn)
+        .await;
+
+        match result {
+            Ok(result) if result.rows_affected() == 0 => Err(RepoUpdateError::NotFound),
+            Ok(_) => Ok(key),
+            Err(e) if is_unique_violation(&e) => Err(RepoUpdateError::InvalidData(format!(
+                "API key {} already exists",
+                key.label
+            ))),
+            Err(e) => Err(RepoUpdateError::Unknown(e.to_string())),
+        }
+    }
+
+    async fn delete_key(&mut self, id: u32) -> Result<(), RepoDeleteError> {
+        let mut conn = self
+            .conn()
+            .await
+            .map_err(|e| RepoDeleteError::Unknown(e.to_string()))?;
+
+        let result = sqlx::query("DELETE FROM api_keys WHERE id = ?")
+            .bind(id)
+            .execute(&mut *conn)
+            .await;
+
+        match result {
+            Ok(result) if result.rows_affected() == 0 => Err(RepoDeleteError::NotFound),
+            Ok(_) => Ok(()),
+            Err(e) => Err(RepoDeleteError::Unknown(e.to_string())),
+        }
+    }
+}
+
+impl ProfileRepository for SqliteRepository {
+    async fn open(&mut self, db_path: String) -> Result<(), RepoCreateError> {
+        let mut repository = SqliteRepository::new(&SqtliteConfig { db_path });
+
+        repository
+            .conn()
+            .await
+            .map_err(|e| RepoCreateError::Unknown(e.to_string()))?;
+
+        let previous = std::mem::replace(self, repository);
+
+        if let PoolWrapper::Exists(pool) = previous.pool {
+            pool.close().await;
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, FromRow)]
+pub(crate) struct AuditRecordSql {
+    id: u32,
+    recorded_at: i64,
+    operation: String,
+    entity: String,
+    entity_id: String,
+    before: Option<String>,
+    after: Option<String>,
+    origin: String,
+}
+
+impl TryInto<AuditRecord> for AuditRecordSql {
+    type Error = String;
+
+    fn try_into(self) -> Result<AuditRecord, Self::Error> {
+        Ok(AuditRecord {
+            id: self.id,
+            recorded_at: self.recorded_at,
+            operation: self.operation,
+            entity: self.entity.parse()?,
+            entity_id: self.entity_id,
+            before: self.before,
+            after: self.after,
+            origin: self.origin.parse()?,
+        })
+    }
+}
+
+impl AuditRepository for SqliteRepository {
+    async fn append_audit(&mut self, record: AuditRecord) -> Result<(), RepoCreateError> {
+        let mut conn = self
+            .conn()
+            .await
+            .map_err(|e| RepoCreateError::Unknown(e.to_string()))?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO audit_log (recorded_at, operation, entity, entity_id, before, after, origin)
+            VALUES (?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(record.recorded_at)
+        .bind(&record.operation)
+        .bind(record.entity.as_str())
+        .bind(&record.entity_id)
+        .bind(&record.before)
+        .bind(&record.after)
+        .bind(record.origin.as_str())
+        .execute(&mut *conn)
+        .await
+        .map(|_| ())
+        .map_err(|e| RepoCreateError::Unknown(e.to_string()))
+    }
+
+    async fn find_audit(
+        &mut self,
+        filter: AuditFilter,
+    ) -> Result<Vec<AuditRecord>, RepoGetAllError> {
+        let mut conn = self
+            .conn()
+            .await
+            .map_err(|e| RepoGetAllError::Unknown(e.to_string()))?;
+
+        let entity = filter.entity.map(|entity| entity.as_str());
+        let records = sqlx::query_as::<Sqlite, AuditRecordSql>(
+            r#"
+            SELECT * FROM audit_log
+            WHERE (? IS NULL OR entity = ?)
+              AND (? IS NULL OR entity_id = ?)
+              AND (? IS NULL OR recorded_at >= ?)
+              AND (? IS NULL OR recorded_at <= ?)
+            ORDER BY recorded_at DESC, id DESC
+            LIMIT ?
+            "#,
+        )
+        .bind(entity)
+        .bind(entity)
+        .bind(&filter.entity_id)
+        .bind(&filter.entity_id)
+        .bind(filter.start)
+        .bind(filter.start)
+        .bind(filter.end)
+        .bind(filter.end)
+        .bind(filter.limit)
+        .fetch_all(&mut *conn)
+        .await
+        .map_err(|e| RepoGetAllError::Unknown(e.to_string()))?;
+
+        records
+            .into_iter()
+            .map(|record| record.try_into())
+            .collect::<Result<Vec<AuditRecord>, String>>()
+            .map_err(RepoGetAllError::Unknown)
+    }
+}
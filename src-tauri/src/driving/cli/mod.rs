@@ -0,0 +1,1182 @@
+pub(crate) mod output;
+
+use std::{io::Write, path::PathBuf, sync::Arc};
+
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use thiserror::Error;
+use tokio::sync::Mutex;
+use validator::Validate;
+
+use crate::{
+    config::{profile::ActiveProfile, Config, ConfigError},
+    domain::{
+        self,
+        audit::{
+            audit::{AuditEntity, AuditFilter, Origin, DEFAULT_AUDIT_LIMIT},
+            get_audit_log::GetAuditLogError,
+        },
+        candle::{
+            backfill_candles::BackfillError,
+            candle::{resolve_range, CandleInterval},
+        },
+        catalog::{
+            add_top_coins::AddTopCoinsError, catalog::DEFAULT_SEARCH_LIMIT,
+            refresh_catalog::RefreshCatalogError, search_catalog::SearchCatalogError,
+        },
+        coin::{
+            get_all_coins::GetAllCoinsError, get_coin_details::GetCoinDetailsError,
+            refresh_coins::RefreshError, restore_coin::RestoreError,
+        },
+        events::EventBus,
+        indicator::{get_indicators::GetIndicatorsError, indicator::IndicatorSpec},
+        profile::profiles::ProfileError,
+        provider::{
+            api_keys::ApiKeyError, connectivity::Connectivity, get_api_usage::GetApiUsageError,
+            get_plan_info::GetPlanInfoError, provider::DEFAULT_USAGE_DAYS,
+            set_api_key::SetApiKeyError,
+        },
+        transaction::{import_transactions::ImportTransactionsError, transaction::Exchange},
+        transfer::transfer::{DataFormat, Dataset},
+    },
+    driven::repository::sqlite::SqliteRepository,
+    driving::tauri::{
+        api_keys::{ApiKeyResponse, ApiKeyTestResponse, KeyValidationResponse},
+        audit::AuditRecordResponse,
+        candles::CandlesResponse,
+        catalog::CatalogEntryResponse,
+        coins::{CoinDetailsResponse, CoinResponse, CreateCoinRequest},
+        indicators::IndicatorsResponse,
+        profiles::ProfileResponse,
+        trash::DeletedCoinResponse,
+        usage::{ApiUsageResponse, PlanInfoResponse},
+    },
+    fetch::{
+        coinmarketcap::{fetch_ids, fetch_symbols},
+        FetchError,
+    },
+};
+
+use self::output::{
+    write_audit, write_candles, write_catalog, write_coins, write_details, write_indicators,
+    write_key_test, write_key_validation, write_keys, write_plan, write_profiles, write_report,
+    write_transaction_report, write_trash, write_usage, OutputFormat,
+};
+
+pub(crate) const EXIT_OK: i32 = 0;
+pub(crate) const EXIT_FAILURE: i32 = 1;
+pub(crate) const EXIT_USAGE: i32 = 2;
+pub(crate) const EXIT_CONFIG: i32 = 3;
+pub(crate) const EXIT_TOKEN_NOT_SPECIFIED: i32 = 4;
+pub(crate) const EXIT_PROVIDER: i32 = 5;
+
+#[derive(Debug, Error)]
+pub enum CliError {
+    #[error("Configuration error: {0}")]
+    Config(#[from] ConfigError),
+    #[error("CoinMarketCap token not specified, run `cryptoscan-cli set-key <KEY>` first")]
+    TokenNotSpecified,
+    #[error("Provider error: {0}")]
+    Provider(#[from] FetchError),
+    #[error("Invalid input: {0}")]
+    InvalidInput(String),
+    #[error("Output error: {0}")]
+    Output(String),
+    #[error("{0} rows rejected")]
+    Rejected(usize),
+    #[error("Unknown error: {0}")]
+    Unknown(String),
+}
+
+impl CliError {
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            CliError::Config(_) => EXIT_CONFIG,
+            CliError::TokenNotSpecified => EXIT_TOKEN_NOT_SPECIFIED,
+            CliError::Provider(_) => EXIT_PROVIDER,
+            CliError::InvalidInput(_) => EXIT_USAGE,
+            CliError::Output(_) | CliError::Rejected(_) | CliError::Unknown(_) => EXIT_FAILURE,
+        }
+    }
+}
+
+/// Headless access to the tracked coins, operating on the same database as the GUI.
+#[derive(Debug, Parser)]
+#[command(name = "cryptoscan-cli", version)]
+struct Cli {
+    /// Output format
+    #[arg(long, short, global = true, value_enum, default_value_t = OutputFormat::Table)]
+    format: OutputFormat,
+
+    #[command(flatten)]
+    config: ConfigArgs,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+/// Configuration flags; they are read by `Config::load` and only declared here so the
+/// parser accepts them and lists them in `--help`.
+#[derive(Debug, Args)]
+struct ConfigArgs {
+    /// Config file to read instead of the one in the app config directory
+    #[arg(long, global = true)]
+    config: Option<PathBuf>,
+    /// Database file of the default profile
+    #[arg(long, global = true)]
+    db_path: Option<PathBuf>,
+    /// Profile to operate on instead of the last one switched to
+    #[arg(long, global = true)]
+    profile: Option<String>,
+    /// Price data provider
+    #[arg(long, global = true)]
+    provider: Option<String>,
+    /// Base URL of the price data provider
+    #[arg(long, global = true)]
+    provider_base_url: Option<String>,
+    /// Seconds provider responses are reused, 0 to disable the cache
+    #[arg(long, global = true)]
+    provider_cache_ttl: Option<u64>,
+    /// Provider credits that may be spent per day, 0 for no limit
+    #[arg(long, global = true)]
+    provider_daily_credits: Option<u32>,
+    /// Order in which pooled API keys are tried: priority or round_robin
+    #[arg(long, global = true)]
+    provider_key_selection: Option<String>,
+    /// Secondary price sources to aggregate with the provider, e.g. coingecko,binance
+    #[arg(long, global = true)]
+    aggregation_sources: Option<String>,
+    /// How aggregated prices are combined: median or volume_weighted
+    #[arg(long, global = true)]
+    aggregation_method: Option<String>,
+    /// Percent from the median beyond which a source's price is dropped
+    #[arg(long, global = true)]
+    aggregation_max_deviation: Option<f64>,
+    /// Refresh interval in seconds
+    #[arg(long, global = true)]
+    refresh_interval: Option<u64>,
+    /// Seconds between reachability probes while the provider is offline (GUI only)
+    #[arg(long, global = true)]
+    offline_probe_interval: Option<u64>,
+    /// Stream live prices from an exchange's WebSocket tickers (GUI only)
+    #[arg(long, global = true)]
+    stream_enabled: Option<bool>,
+    /// Exchange to stream tickers from: binance or kraken
+    #[arg(long, global = true)]
+    stream_exchange: Option<String>,
+    /// WebSocket URL of the exchange, its public endpoint by default
+    #[arg(long, global = true)]
+    stream_url: Option<String>,
+    /// Least seconds between two database writes of a coin's streamed price
+    #[arg(long, global = true)]
+    stream_write_interval: Option<u64>,
+    /// Days a deleted coin is kept in the trash before it is purged
+    #[arg(long, global = true)]
+    trash_retention_days: Option<u64>,
+    /// HTTP connect timeout in seconds
+    #[arg(long, global = true)]
+    http_connect_timeout: Option<u64>,
+    /// HTTP request timeout in seconds
+    #[arg(long, global = true)]
+    http_request_timeout: Option<u64>,
+    /// Log level
+    #[arg(long, global = true)]
+    log_level: Option<String>,
+    /// Start the embedded HTTP server (GUI only)
+    #[arg(long, global = true)]
+    server_enabled: Option<bool>,
+    /// Port of the embedded HTTP server
+    #[arg(long, global = true)]
+    server_port: Option<u16>,
+    /// Bearer token required by the embedded HTTP server
+    #[arg(long, global = true)]
+    server_token: Option<String>,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Fetch coins from the provider and start tracking them
+    Add(CoinSelector),
+    /// Start tracking the top coins by market cap
+    AddTop {
+        /// Number of coins
+        #[arg(long, default_value_t = 10)]
+        limit: u32,
+    },
+    /// Search the local coin catalog by symbol, name or slug
+    Search {
+        query: String,
+        /// Most results
+        #[arg(long, default_value_t = DEFAULT_SEARCH_LIMIT)]
+        limit: u32,
+    },
+    /// Download the provider's list of active coins into the local catalog
+    RefreshCatalog,
+    /// List tracked coins
+    List(WatchlistSelector),
+    /// Show the description, logo, links, tags and contracts of a tracked coin
+    Details {
+        /// CoinMarketCap coin id
+        #[arg(long)]
+        id: u32,
+        /// Fetch the metadata again even if it is stored
+        #[arg(long)]
+        refresh: bool,
+    },
+    /// Fetch fresh quotes for every tracked coin
+    Refresh(WatchlistSelector),
+    /// Fetch quotes from the provider without storing them
+    Fetch(CoinSelector),
+    /// Show stored historical candles of a tracked coin
+    Candles {
+        /// CoinMarketCap coin id
+        #[arg(long)]
+        id: u32,
+        #[arg(long, value_enum, default_value_t = IntervalArg::Daily)]
+        interval: IntervalArg,
+        /// First candle, as Unix seconds, a date or an RFC 3339 timestamp
+        #[arg(long, value_parser = parse_time)]
+        start: Option<i64>,
+        /// Last candle, as Unix seconds, a date or an RFC 3339 timestamp
+        #[arg(long, value_parser = parse_time)]
+        end: Option<i64>,
+        /// Fetch missing candles from the provider first
+        #[arg(long)]
+        backfill: bool,
+    },
+    /// Compute technical indicators over stored candles of a tracked coin
+    Indicators {
+        /// CoinMarketCap coin id
+        #[arg(long)]
+        id: u32,
+        #[arg(long, value_enum, default_value_t = IntervalArg::Daily)]
+        interval: IntervalArg,
+        /// First candle, as Unix seconds, a date or an RFC 3339 timestamp
+        #[arg(long, value_parser = parse_time)]
+        start: Option<i64>,
+        /// Last candle, as Unix seconds, a date or an RFC 3339 timestamp
+        #[arg(long, value_parser = parse_time)]
+        end: Option<i64>,
+        /// Indicators such as sma:20, ema:50, rsi:14, macd:12:26:9, bb:20:2, vol:30 or drawdown
+        #[arg(long = "indicator", required = true, value_delimiter = ',')]
+        indicators: Vec<IndicatorSpec>,
+    },
+    /// Show provider calls and credits spent per day and endpoint
+    Usage {
+        /// UTC days back from today
+        #[arg(long, default_value_t = DEFAULT_USAGE_DAYS)]
+        days: u32,
+    },
+    /// Show the plan limits of the provider API key
+    Plan {
+        /// Fetch the plan again even if it is stored
+        #[arg(long)]
+        refresh: bool,
+    },
+    /// Check the provider API key and store it unless the provider refuses it
+    SetKey {
+        /// CoinMarketCap API key
+        key: String,
+    },
+    /// Manage the pool of provider API keys
+    Keys {
+        #[command(subcommand)]
+        action: KeysCommand,
+    },
+    /// Manage the profiles, each with its own database
+    Profiles {
+        #[command(subcommand)]
+        action: ProfilesCommand,
+    },
+    /// Write tracked coins, settings, transactions or candles to stdout or a file
+    Export {
+        /// File to write to instead of stdout
+        #[arg(long, short)]
+        output: Option<PathBuf>,
+        /// Data to export
+        #[arg(long, value_enum, default_value_t = DatasetArg::Coins)]
+        dataset: DatasetArg,
+        /// Include the API key when exporting settings
+        #[arg(long)]
+        include_secrets: bool,
+        #[command(flatten)]
+        watchlist: WatchlistSelector,
+    },
+    /// Import coins, settings, transactions or candles from a CSV or JSON file
+    Import {
+        /// File to read
+        file: PathBuf,
+        /// Data to import
+        #[arg(long, value_enum, default_value_t = DatasetArg::Coins)]
+        dataset: DatasetArg,
+        /// Format of the file, guessed from its extension when omitted
+        #[arg(long, value_enum)]
+        input_format: Option<InputFormat>,
+        /// Validate every row and report errors without storing anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Import trades from an exchange's trade-history CSV export into the ledger
+    ImportTrades {
+        /// File to read
+        file: PathBuf,
+        /// Exchange the export comes from
+        #[arg(long, value_enum)]
+        exchange: ExchangeArg,
+        /// Parse the file and resolve symbols without storing anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Show the changes made to coins and settings, newest first
+    Audit {
+        /// Only changes of coins or of settings
+        #[arg(long, value_enum)]
+        entity: Option<EntityArg>,
+        /// Coin id or setting key
+        #[arg(long)]
+        id: Option<String>,
+        /// Earliest change, as Unix seconds, a date or an RFC 3339 timestamp
+        #[arg(long, value_parser = parse_time)]
+        start: Option<i64>,
+        /// Latest change, as Unix seconds, a date or an RFC 3339 timestamp
+        #[arg(long, value_parser = parse_time)]
+        end: Option<i64>,
+        /// Most changes to show
+        #[arg(long, default_value_t = DEFAULT_AUDIT_LIMIT)]
+        limit: u32,
+    },
+    /// Manage deleted coins, kept for `trash.retention_days` before they are purged
+    Trash {
+        #[command(subcommand)]
+        action: TrashCommand,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+enum TrashCommand {
+    /// List the deleted coins, most recently deleted first
+    List,
+    /// Track a deleted coin again
+    Restore { id: u32 },
+    /// Restore the coins of the most recent deletion
+    Undo,
+    /// Purge the coins kept longer than the retention now, then list the rest
+    Purge,
+}
+
+#[derive(Debug, Subcommand)]
+enum ProfilesCommand {
+    /// List the profiles
+    List,
+    /// Create an empty profile
+    Create { name: String },
+    /// Use a profile from now on, also in the desktop app on its next launch
+    Switch { name: String },
+    /// Delete a profile and its database
+    Delete { name: String },
+}
+
+#[derive(Debug, Subcommand)]
+enum KeysCommand {
+    /// Add a key to the pool
+    Add {
+        /// Name shown instead of the key
+        label: String,
+        /// CoinMarketCap API key
+        key: String,
+        /// Provider plan of the key, e.g. basic
+        #[arg(long)]
+        plan: Option<String>,
+        /// Credits that may be spent per month with the key
+        #[arg(long)]
+        monthly_credits: Option<u32>,
+        /// Keys with a lower priority are tried first
+        #[arg(long)]
+        priority: Option<u32>,
+    },
+    /// List the keys of the pool
+    List,
+    /// Stop using a key
+    Disable { id: u32 },
+    /// Use a disabled key again
+    Enable { id: u32 },
+    /// Check a key against the provider and show its plan
+    Test { id: u32 },
+    /// Remove a key from the pool
+    Remove { id: u32 },
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+enum DatasetArg {
+    Coins,
+    Settings,
+    Transactions,
+    Candles,
+}
+
+impl From<DatasetArg> for Dataset {
+    fn from(dataset: DatasetArg) -> Self {
+        match dataset {
+            DatasetArg::Coins => Dataset::Coins,
+            DatasetArg::Settings => Dataset::Settings,
+            DatasetArg::Transactions => Dataset::Transactions,
+            DatasetArg::Candles => Dataset::Candles,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+enum EntityArg {
+    Coin,
+    Setting,
+}
+
+impl From<EntityArg> for AuditEntity {
+    fn from(entity: EntityArg) -> Self {
+        match entity {
+            EntityArg::Coin => AuditEntity::Coin,
+            EntityArg::Setting => AuditEntity::Setting,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+enum IntervalArg {
+    Hourly,
+    Daily,
+}
+
+impl From<IntervalArg> for CandleInterval {
+    fn from(interval: IntervalArg) -> Self {
+        match interval {
+            IntervalArg::Hourly => CandleInterval::Hourly,
+            IntervalArg::Daily => CandleInterval::Daily,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+enum ExchangeArg {
+    Binance,
+    Kraken,
+    Coinbase,
+}
+
+impl From<ExchangeArg> for Exchange {
+    fn from(exchange: ExchangeArg) -> Self {
+        match exchange {
+            ExchangeArg::Binance => Exchange::Binance,
+            ExchangeArg::Kraken => Exchange::Kraken,
+            ExchangeArg::Coinbase => Exchange::Coinbase,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+enum InputFormat {
+    Csv,
+    Json,
+}
+
+impl From<InputFormat> for DataFormat {
+    fn from(format: InputFormat) -> Self {
+        match format {
+            InputFormat::Csv => DataFormat::Csv,
+            InputFormat::Json => DataFormat::Json,
+        }
+    }
+}
+
+#[derive(Debug, Args)]
+struct WatchlistSelector {
+    /// Only the coins of this watchlist, in watchlist order
+    #[arg(long = "watchlist")]
+    id: Option<u32>,
+}
+
+#[derive(Debug, Args)]
+struct CoinSelector {
+    /// CoinMarketCap coin id, may be repeated
+    #[arg(long = "id", value_delimiter = ',')]
+    ids: Vec<u32>,
+    /// Coin symbol, may be repeated
+    #[arg(long = "symbol", value_delimiter = ',')]
+    symbols: Vec<String>,
+}
+
+pub fn run() -> i32 {
+    let cli = match Cli::try_parse() {
+        Ok(cli) => cli,
+        Err(e) => {
+            let _ = e.print();
+            return if e.use_stderr() { EXIT_USAGE } else { EXIT_OK };
+        }
+    };
+
+    match tauri::async_runtime::block_on(execute(cli)) {
+        Ok(_) => EXIT_OK,
+        Err(e) => {
+            eprintln!("{}", e);
+            e.exit_code()
+        }
+    }
+}
+
+async fn execute(cli: Cli) -> Result<(), CliError> {
+    let config = Config::load_standalone()?;
+    let repository = Arc::new(Mutex::new(SqliteRepository::new(&config.sqlite)));
+    let events = EventBus::new();
+
+    match cli.command {
+        Command::Add(selector) => {
+            let token = api_key(repository.clone()).await?;
+            let fetched = fetch(repository.clone(), &config, selector, token).await?;
+            let mut coins = vec![];
+
+            for coin in fetched {
+                let request = CreateCoinRequest::from(coin);
+                request
+                    .validate()
+                    .map_err(|e| CliError::InvalidInput(e.to_string()))?;
+
+                let coin = domain::coin::create_coin::create_coin(
+                    repository.clone(),
+                    &events,
+                    Origin::Cli,
+                    request,
+                )
+                .await
+                .map_err(|e| match e {
+                    domain::coin::create_coin::CreateError::InvalidData(e) => {
+                        CliError::InvalidInput(e)
+                    }
+                    e => CliError::Unknown(e.to_string()),
+                })?;
+
+                coins.push(CoinResponse::from(coin));
+            }
+
+            write_coins(&mut std::io::stdout(), cli.format, &coins)
+        }
+        Command::AddTop { limit } => {
+            let token = api_key(repository.clone()).await?;
+            let coins = domain::catalog::add_top_coins::add_top_coins(
+                repository,
+                &events,
+                Origin::Cli,
+                &config,
+                token,
+                limit,
+            )
+            .await
+            .map_err(|e| match e {
+                AddTopCoinsError::Fetch(e) => CliError::Provider(e),
+                AddTopCoinsError::InvalidData(e) => CliError::InvalidInput(e),
+                e => CliError::Unknown(e.to_string()),
+            })?;
+
+            let coins = coins
+                .into_iter()
+                .map(CoinResponse::from)
+                .collect::<Vec<CoinResponse>>();
+            write_coins(&mut std::io::stdout(), cli.format, &coins)
+        }
+        Command::Search { query, limit } => {
+            let results = domain::catalog::search_catalog::search_catalog(repository, query, limit)
+                .await
+                .map_err(|e| match e {
+                    SearchCatalogError::InvalidData(e) => CliError::InvalidInput(e),
+                    SearchCatalogError::Unknown(e) => CliError::Unknown(e),
+                })?;
+
+            let results = results
+                .into_iter()
+                .map(CatalogEntryResponse::from)
+                .collect::<Vec<CatalogEntryResponse>>();
+            write_catalog(&mut std::io::stdout(), cli.format, &results)
+        }
+        Command::RefreshCatalog => {
+            let token = api_key(repository.clone()).await?;
+            let count =
+                domain::catalog::refresh_catalog::refresh_catalog(repository, &config, token)
+                    .await
+                    .map_err(|e| match e {
+                        RefreshCatalogError::Fetch(e) => CliError::Provider(e),
+                        RefreshCatalogError::Unknown(e) => CliError::Unknown(e),
+                    })?;
+
+            writeln!(std::io::stdout(), "{} coins in the catalog", count)
+                .map_err(|e| CliError::Output(e.to_string()))
+        }
+        Command::List(watchlist) => {
+            let coins = tracked_coins(repository, watchlist.id).await?;
+            write_coins(&mut std::io::stdout(), cli.format, &coins)
+        }
+        Command::Details { id, refresh } => {
+            // Stored metadata is shown without an API key
+            let token = api_key(repository.clone()).await.ok();
+            let details = domain::coin::get_coin_details::get_coin_details(
+                repository, &config, token, id, refresh,
+            )
+            .await
+            .map_err(|e| match e {
+                GetCoinDetailsError::Fetch(e) => CliError::Provider(e),
+                GetCoinDetailsError::CoinNotFound => {
+                    CliError::InvalidInput(format!("coin {} is not tracked", id))
+                }
+                GetCoinDetailsError::TokenNotSpecified => CliError::TokenNotSpecified,
+                GetCoinDetailsError::Unknown(e) => CliError::Unknown(e),
+            })?;
+
+            write_details(
+                &mut std::io::stdout(),
+                cli.format,
+                &CoinDetailsResponse::from(details),
+            )
+        }
+        Command::Export {
+            output,
+            dataset: DatasetArg::Coins,
+            watchlist,
+            ..
+        } => {
+            let coins = tracked_coins(repository, watchlist.id).await?;
+            write_coins(&mut open_output(output)?, cli.format, &coins)
+        }
+        Command::Export {
+            output,
+            dataset,
+            include_secrets,
+            ..
+        } => {
+            let format = match cli.format {
+                OutputFormat::Json => DataFormat::Json,
+                OutputFormat::Csv => DataFormat::Csv,
+                OutputFormat::Table => {
+                    return Err(CliError::InvalidInput(
+                        "only coins can be exported as a table, use json or csv".to_string(),
+                    ))
+                }
+            };
+
+            let content = domain::transfer::export_data::export_data(
+                repository,
+                Dataset::from(dataset),
+                format,
+                include_secrets,
+            )
+            .await
+            .map_err(|e| CliError::Unknown(e.to_string()))?;
+
+            writeln!(open_output(output)?, "{}", content.trim_end())
+                .map_err(|e| CliError::Output(e.to_string()))
+        }
+        Command::Import {
+            file,
+            dataset,
+            input_format,
+            dry_run,
+        } => {
+            let format = match input_format {
+                Some(format) => DataFormat::from(format),
+                None => match file.extension().and_then(|extension| extension.to_str()) {
+                    Some("csv") => DataFormat::Csv,
+                    Some("json") => DataFormat::Json,
+                    _ => {
+                        return Err(CliError::InvalidInput(
+                            "unknown file extension, pass --input-format".to_string(),
+                        ))
+                    }
+                },
+            };
+
+            let content = std::fs::read_to_string(&file)
+                .map_err(|e| CliError::InvalidInput(format!("{}: {}", file.display(), e)))?;
+
+            let report = domain::transfer::import_data::import_data(
+                repository,
+                &events,
+                Origin::Cli,
+                Dataset::from(dataset),
+                format,
+                &content,
+                dry_run,
+            )
+            .await
+            .map_err(|e| CliError::InvalidInput(e.to_string()))?;
+
+            write_report(&mut std::io::stdout(), cli.format, &report)?;
+
+            if report.errors.is_empty() {
+                Ok(())
+            } else {
+                Err(CliError::Rejected(report.errors.len()))
+            }
+        }
+        Command::ImportTrades {
+            file,
+            exchange,
+            dry_run,
+        } => {
+            let content = std::fs::read_to_string(&file)
+                .map_err(|e| CliError::InvalidInput(format!("{}: {}", file.display(), e)))?;
+
+            // Without an API key symbols are only resolved against the tracked coins
+            let token = api_key(repository.clone()).await.ok();
+
+            let report = domain::transaction::import_transactions::import_transactions(
+                repository,
+                &config,
+                token,
+                Exchange::from(exchange),
+                &content,
+                dry_run,
+            )
+            .await
+            .map_err(|e| match e {
+                ImportTransactionsError::InvalidData(e) => CliError::InvalidInput(e),
+                ImportTransactionsError::Unknown(e) => CliError::Unknown(e),
+            })?;
+
+            write_transaction_report(&mut std::io::stdout(), cli.format, &report)?;
+
+            if report.errors.is_empty() {
+                Ok(())
+            } else {
+                Err(CliError::Rejected(report.errors.len()))
+            }
+        }
+        Command::Audit {
+            entity,
+            id,
+            start,
+            end,
+            limit,
+        } => {
+            let filter = AuditFilter {
+                entity: entity.map(AuditEntity::from),
+                entity_id: id,
+                start,
+                end,
+                limit,
+            };
+
+            let records = domain::audit::get_audit_log::get_audit_log(repository, filter)
+                .await
+                .map_err(|e| match e {
+                    GetAuditLogError::InvalidData(e) => CliError::InvalidInput(e),
+                    GetAuditLogError::Unknown(e) => CliError::Unknown(e),
+                })?
+                .into_iter()
+                .map(AuditRecordResponse::from)
+                .collect::<Vec<AuditRecordResponse>>();
+
+            write_audit(&mut std::io::stdout(), cli.format, &records)
+        }
+        Command::Refresh(watchlist) => {
+            let ids = tracked_coins(repository.clone(), watchlist.id)
+                .await?
+                .into_iter()
+                .map(|coin| coin.id)
+                .collect::<Vec<u32>>();
+
+            if ids.is_empty() {
+                return write_coins(&mut std::io::stdout(), cli.format, &[]);
+            }
+
+            let token = api_key(repository.clone()).await?;
+            // A refresh queued while offline dies with the process; the stored coins are
+            // printed instead and marked stale once old enough
+            let connectivity = Connectivity::new();
+            let coins = domain::coin::refresh_coins::refresh_coins(
+                repository,
+                &events,
+                Origin::Cli,
+                &config,
+                &connectivity,
+                ids,
+                token,
+            )
+            .await
+            .map_err(|e| match e {
+                RefreshError::Fetch(e) => CliError::Provider(e),
+                e => CliError::Unknown(e.to_string()),
+            })?
+            .into_iter()
+            .map(CoinResponse::from)
+            .collect::<Vec<CoinResponse>>();
+
+            write_coins(&mut std::io::stdout(), cli.format, &coins)
+        }
+        Command::Fetch(selector) => {
+            let token = api_key(repository.clone()).await?;
+            let coins = fetch(repository.clone(), &config, selector, token).await?;
+            write_coins(&mut std::io::stdout(), cli.format, &coins)
+        }
+        Command::Candles {
+            id,
+            interval,
+            start,
+            end,
+            backfill,
+        } => {
+            let interval = CandleInterval::from(interval);
+            let range = resolve_range(interval, start, end, chrono::Utc::now().timestamp())
+                .map_err(|e| CliError::InvalidInput(e.to_string()))?;
+
+            let series = if backfill {
+                let token = api_key(repository.clone()).await?;
+
+                domain::candle::backfill_candles::backfill_candles(
+                    repository, &config, token, id, interval, range,
+                )
+                .await
+                .map_err(|e| match e {
+                    BackfillError::Fetch(e) => CliError::Provider(e),
+                    BackfillError::Unknown(e) => CliError::Unknown(e),
+                    e => CliError::InvalidInput(e.to_string()),
+                })?
+            } else {
+                domain::candle::get_candles::get_candles(repository, id, interval, range)
+                    .await
+                    .map_err(|e| CliError::Unknown(e.to_string()))?
+            };
+
+            write_candles(
+                &mut std::io::stdout(),
+                cli.format,
+                &CandlesResponse::from(series),
+            )
+        }
+        Command::Indicators {
+            id,
+            interval,
+            start,
+            end,
+            indicators,
+        } => {
+            let interval = CandleInterval::from(interval);
+            let range = resolve_range(interval, start, end, chrono::Utc::now().timestamp())
+                .map_err(|e| CliError::InvalidInput(e.to_string()))?;
+
+            let set = domain::indicator::get_indicators::get_indicators(
+                repository, id, interval, range, indicators,
+            )
+            .await
+            .map_err(|e| match e {
+                GetIndicatorsError::InvalidData(e) => CliError::InvalidInput(e),
+                GetIndicatorsError::Unknown(e) => CliError::Unknown(e),
+            })?;
+
+            write_indicators(
+                &mut std::io::stdout(),
+                cli.format,
+                &IndicatorsResponse::from(set),
+            )
+        }
+        Command::Usage { days } => {
+            let usage = domain::provider::get_api_usage::get_api_usage(repository, days)
+                .await
+                .map_err(|e| match e {
+                    GetApiUsageError::InvalidData(e) => CliError::InvalidInput(e),
+                    GetApiUsageError::Unknown(e) => CliError::Unknown(e),
+                })?;
+
+            let response = ApiUsageResponse::new(usage, config.provider.daily_credits);
+            write_usage(&mut std::io::stdout(), cli.format, &response)
+        }
+        Command::Plan { refresh } => {
+            // A stored plan is shown without an API key
+            let token = api_key(repository.clone()).await.ok();
+            let plan =
+                domain::provider::get_plan_info::get_plan_info(repository, &config, token, refresh)
+                    .await
+                    .map_err(|e| match e {
+                        GetPlanInfoError::Fetch(e) => CliError::Provider(e),
+                        GetPlanInfoError::TokenNotSpecified => CliError::TokenNotSpecified,
+                        GetPlanInfoError::Unknown(e) => CliError::Unknown(e),
+                    })?;
+
+            write_plan(
+                &mut std::io::stdout(),
+                cli.format,
+                &PlanInfoResponse::from(plan),
+            )
+        }
+        Command::SetKey { key } => {
+            let validation =
+                domain::provider::set_api_key::set_api_key(repository, Origin::Cli, &config, key)
+                    .await
+                    .map_err(|e| match e {
+                        SetApiKeyError::InvalidData(e) => CliError::InvalidInput(e),
+                        SetApiKeyError::Fetch(e) => CliError::Provider(e),
+                        SetApiKeyError::Unknown(e) => CliError::Unknown(e),
+                    })?;
+            let response = KeyValidationResponse::from(validation);
+
+            write_key_validation(&mut std::io::stdout(), cli.format, &response)?;
+
+            if !response.stored {
+                return Err(CliError::InvalidInput(format!(
+                    "the API key is {} and was not saved",
+                    response.validity
+                )));
+            }
+
+            Ok(())
+        }
+        Command::Keys { action } => keys(repository, &config, cli.format, action).await,
+        Command::Trash { action } => trash(repository, &events, &config, cli.format, action).await,
+        Command::Profiles { action } => {
+            profiles(repository, &events, &config, cli.format, action).await
+        }
+    }
+}
+
+async fn profiles(
+    repository: Arc<Mutex<SqliteRepository>>,
+    events: &EventBus,
+    config: &Config,
+    format: OutputFormat,
+    action: ProfilesCommand,
+) -> Result<(), CliError> {
+    let to_cli_error = |e: ProfileError| match e {
+        ProfileError::Unknown(e) => CliError::Unknown(e),
+        e => CliError::InvalidInput(e.to_string()),
+    };
+    let active = ActiveProfile::new(config.profile.clone());
+
+    let profiles = match action {
+        ProfilesCommand::List => domain::profile::profiles::get_profiles(&config.profiles, &active)
+            .map_err(to_cli_error)?,
+        ProfilesCommand::Create { name } => {
+            vec![
+                domain::profile::profiles::create_profile(&config.profiles, &active, name)
+                    .map_err(to_cli_error)?,
+            ]
+        }
+        ProfilesCommand::Switch { name } => {
+            vec![domain::profile::profiles::switch_profile(
+                repository,
+                events,
+                &Connectivity::new(),
+                &config.profiles,
+                &active,
+                name,
+            )
+            .await
+            .map_err(to_cli_error)?]
+        }
+        ProfilesCommand::Delete { name } => {
+            return domain::profile::profiles::delete_profile(&config.profiles, &active, name)
+                .map_err(to_cli_error);
+        }
+    };
+
+    let profiles = profiles
+        .into_iter()
+        .map(ProfileResponse::from)
+        .collect::<Vec<ProfileResponse>>();
+    write_profiles(&mut std::io::stdout(), format, &profiles)
+}
+
+async fn trash(
+    repository: Arc<Mutex<SqliteRepository>>,
+    events: &EventBus,
+    config: &Config,
+    format: OutputFormat,
+    action: TrashCommand,
+) -> Result<(), CliError> {
+    let to_cli_error = |e: RestoreError| match e {
+        RestoreError::NotFound => CliError::InvalidInput(e.to_string()),
+        RestoreError::Unknown(e) => CliError::Unknown(e),
+    };
+
+    let restored = match action {
+        TrashCommand::List => None,
+        TrashCommand::Restore { id } => Some(vec![domain::coin::restore_coin::restore_coin(
+            repository.clone(),
+            events,
+            Origin::Cli,
+            id,
+        )
+        .await
+        .map_err(to_cli_error)?]),
+        TrashCommand::Undo => Some(
+            domain::coin::restore_coin::undo_delete(repository.clone(), events, Origin::Cli)
+                .await
+                .map_err(to_cli_error)?,
+        ),
+        TrashCommand::Purge => {
+            domain::coin::purge_deleted_coins::purge_deleted_coins(
+                repository.clone(),
+                Origin::Cli,
+                config.trash.retention,
+            )
+            .await
+            .map_err(|e| CliError::Unknown(e.to_string()))?;
+            None
+        }
+    };
+
+    if let Some(coins) = restored {
+        let coins = coins
+            .into_iter()
+            .map(CoinResponse::from)
+            .collect::<Vec<CoinResponse>>();
+        return write_coins(&mut std::io::stdout(), format, &coins);
+    }
+
+    let deleted = domain::coin::get_deleted_coins::get_deleted_coins(repository)
+        .await
+        .map_err(|e| CliError::Unknown(e.to_string()))?
+        .into_iter()
+        .map(DeletedCoinResponse::from)
+        .collect::<Vec<DeletedCoinResponse>>();
+    write_trash(&mut std::io::stdout(), format, &deleted)
+}
+
+async fn keys(
+    repository: Arc<Mutex<SqliteRepository>>,
+    config: &Config,
+    format: OutputFormat,
+    action: KeysCommand,
+) -> Result<(), CliError> {
+    let to_cli_error = |e: ApiKeyError| match e {
+        ApiKeyError::Fetch(e) => CliError::Provider(e),
+        ApiKeyError::TokenNotSpecified => CliError::TokenNotSpecified,
+        ApiKeyError::Unknown(e) => CliError::Unknown(e),
+        e => CliError::InvalidInput(e.to_string()),
+    };
+
+    let keys = match action {
+        KeysCommand::Add {
+            label,
+            key,
+            plan,
+            monthly_credits,
+            priority,
+        } => vec![domain::provider::api_keys::add_api_key(
+            repository,
+            label,
+            key,
+            plan,
+            monthly_credits,
+            priority,
+        )
+        .await
+        .map_err(to_cli_error)?],
+        KeysCommand::List => domain::provider::api_keys::get_api_keys(repository)
+            .await
+            .map_err(to_cli_error)?,
+        KeysCommand::Disable { id } => {
+            vec![
+                domain::provider::api_keys::set_api_key_enabled(repository, id, false)
+                    .await
+                    .map_err(to_cli_error)?,
+            ]
+        }
+        KeysCommand::Enable { id } => {
+            vec![
+                domain::provider::api_keys::set_api_key_enabled(repository, id, true)
+                    .await
+                    .map_err(to_cli_error)?,
+            ]
+        }
+        KeysCommand::Test { id } => {
+            let test = domain::provider::api_keys::test_api_key(repository, config, id)
+                .await
+                .map_err(to_cli_error)?;
+
+            return write_key_test(
+                &mut std::io::stdout(),
+                format,
+                &ApiKeyTestResponse::from(test),
+            );
+        }
+        KeysCommand::Remove { id } => {
+            return domain::provider::api_keys::delete_api_key(repository, id)
+                .await
+                .map_err(to_cli_error);
+        }
+    };
+
+    let keys = keys
+        .into_iter()
+        .map(ApiKeyResponse::from)
+        .collect::<Vec<ApiKeyResponse>>();
+    write_keys(&mut std::io::stdout(), format, &keys)
+}
+
+/// Parses Unix seconds, a `YYYY-MM-DD` date or an RFC 3339 timestamp.
+fn parse_time(value: &str) -> Result<i64, String> {
+    if let Ok(seconds) = value.parse::<i64>() {
+        return Ok(seconds);
+    }
+
+    if let Ok(date) = chrono::NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+        return Ok(date.and_time(chrono::NaiveTime::MIN).and_utc().timestamp());
+    }
+
+    chrono::DateTime::parse_from_rfc3339(value)
+        .map(|time| time.timestamp())
+        .map_err(|_| format!("invalid time '{}'", value))
+}
+
+fn open_output(output: Option<PathBuf>) -> Result<Box<dyn Write>, CliError> {
+    match output {
+        Some(path) => std::fs::File::create(&path)
+            .map(|file| Box::new(file) as Box<dyn Write>)
+            .map_err(|e| CliError::Output(format!("{}: {}", path.display(), e))),
+        None => Ok(Box::new(std::io::stdout())),
+    }
+}
+
+async fn api_key(repository: Arc<Mutex<SqliteRepository>>) -> Result<String, CliError> {
+    domain::provider::api_keys::find_token(repository)
+        .await
+        .map_err(|_| CliError::TokenNotSpecified)
+}
+
+async fn tracked_coins(
+    repository: Arc<Mutex<SqliteRepository>>,
+    watchlist_id: Option<u32>,
+) -> Result<Vec<CoinResponse>, CliError> {
+    domain::coin::get_all_coins::get_all_coins(repository, watchlist_id)
+        .await
+        .map(|coins| coins.into_iter().map(CoinResponse::from).collect())
+        .map_err(|e| match e {
+            GetAllCoinsError::WatchlistNotFound => CliError::InvalidInput(e.to_string()),
+            e => CliError::Unknown(e.to_string()),
+        })
+}
+
+async fn fetch(
+    repository: Arc<Mutex<SqliteRepository>>,
+    config: &Config,
+    selector: CoinSelector,
+    token: String,
+) -> Result<Vec<CoinResponse>, CliError> {
+    if selector.ids.is_empty() && selector.symbols.is_empty() {
+        return Err(CliError::InvalidInput(
+            "at least one --id or --symbol is required".to_string(),
+        ));
+    }
+
+    let mut coins = vec![];
+
+    if !selector.ids.is_empty() {
+        let mut fetched = fetch_ids(repository.clone(), config, selector.ids, token.clone())
+            .await
+            .map_err(CliError::Provider)?;
+        coins.append(&mut fetched.coins);
+    }
+
+    if !selector.symbols.is_empty() {
+        let mut fetched = fetch_symbols(repository, config, selector.symbols, token)
+            .await
+            .map_err(CliError::Provider)?;
+        coins.append(&mut fetched.coins);
+    }
+
+    Ok(coins)
+}
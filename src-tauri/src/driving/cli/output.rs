@@ -0,0 +1,751 @@
+use std::io::Write;
+
+use clap::ValueEnum;
+use serde::Serialize;
+
+use crate::{
+    domain::{
+        transaction::transaction::TransactionImportReport,
+        transfer::transfer::{ImportReport, RowError},
+    },
+    driving::tauri::{
+        api_keys::{ApiKeyResponse, ApiKeyTestResponse, KeyValidationResponse},
+        audit::AuditRecordResponse,
+        candles::CandlesResponse,
+        catalog::CatalogEntryResponse,
+        coins::{CoinDetailsResponse, CoinResponse},
+        indicators::IndicatorsResponse,
+        profiles::ProfileResponse,
+        trash::DeletedCoinResponse,
+        usage::{ApiUsageResponse, PlanInfoResponse},
+    },
+};
+
+use super::CliError;
+
+const KEY_HEADERS: [&str; 8] = [
+    "ID",
+    "LABEL",
+    "KEY",
+    "PLAN",
+    "PRIORITY",
+    "STATUS",
+    "CREDITS",
+    "LAST USED",
+];
+
+const PROFILE_HEADERS: [&str; 3] = ["NAME", "ACTIVE", "DATABASE"];
+
+const AUDIT_HEADERS: [&str; 7] = [
+    "TIME",
+    "ORIGIN",
+    "OPERATION",
+    "ENTITY",
+    "ID",
+    "BEFORE",
+    "AFTER",
+];
+
+const TRASH_HEADERS: [&str; 5] = ["DELETED", "ID", "SYMBOL", "NAME", "PRICE"];
+
+const CANDLE_HEADERS: [&str; 6] = ["OPEN TIME", "OPEN", "HIGH", "LOW", "CLOSE", "VOLUME"];
+
+const CATALOG_HEADERS: [&str; 5] = ["RANK", "ID", "SYMBOL", "NAME", "TRACKED"];
+
+const USAGE_HEADERS: [&str; 7] = [
+    "DAY", "ENDPOINT", "CALLS", "ERRORS", "CREDITS", "AVG MS", "MAX MS",
+];
+
+const TABLE_HEADERS: [&str; 8] = [
+    "ID",
+    "SYMBOL",
+    "NAME",
+    "PRICE",
+    "24H %",
+    "MARKET CAP",
+    "LAST UPDATED",
+    "AGE",
+];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    Table,
+    Json,
+    Csv,
+}
+
+pub fn write_coins<W: Write>(
+    writer: &mut W,
+    format: OutputFormat,
+    coins: &[CoinResponse],
+) -> Result<(), CliError> {
+    match format {
+        OutputFormat::Table => write_table(writer, &TABLE_HEADERS, &coin_rows(coins)),
+        OutputFormat::Json => write_json(writer, coins),
+        OutputFormat::Csv => write_csv(writer, coins),
+    }
+}
+
+pub fn write_catalog<W: Write>(
+    writer: &mut W,
+    format: OutputFormat,
+    entries: &[CatalogEntryResponse],
+) -> Result<(), CliError> {
+    match format {
+        OutputFormat::Table => {
+            let rows = entries
+                .iter()
+                .map(|entry| {
+                    vec![
+                        entry.rank.map(|rank| rank.to_string()).unwrap_or_default(),
+                        entry.id.to_string(),
+                        entry.symbol.clone(),
+                        entry.name.clone(),
+                        if entry.tracked { "yes" } else { "" }.to_string(),
+                    ]
+                })
+                .collect::<Vec<Vec<String>>>();
+
+            write_table(writer, &CATALOG_HEADERS, &rows)
+        }
+        OutputFormat::Json => write_json(writer, entries),
+        OutputFormat::Csv => write_csv(writer, entries),
+    }
+}
+
+/// Writes provider usage: JSON as the whole response, CSV one row per day and endpoint,
+/// tables followed by the totals.
+pub fn write_usage<W: Write>(
+    writer: &mut W,
+    format: OutputFormat,
+    response: &ApiUsageResponse,
+) -> Result<(), CliError> {
+    match format {
+        OutputFormat::Json => write_json(writer, response),
+        OutputFormat::Csv => write_csv(writer, &response.usage),
+        OutputFormat::Table => {
+            let rows = response
+                .usage
+                .iter()
+                .map(|usage| {
+                    vec![
+                        usage.day.clone(),
+                        usage.endpoint.clone(),
+                        usage.calls.to_string(),
+                        usage.errors.to_string(),
+                        usage.credits.to_string(),
+                        format!("{:.0}", usage.avg_latency_ms),
+                        usage.max_latency_ms.to_string(),
+                    ]
+                })
+                .collect::<Vec<Vec<String>>>();
+
+            write_table(writer, &USAGE_HEADERS, &rows)?;
+
+            let budget = response
+                .daily_credits
+                .map(|credits| format!(", budget {} credits a day", credits))
+                .unwrap_or_default();
+
+            writeln!(
+                writer,
+                "\n{} calls, {} credits{}",
+                response.calls, response.credits, budget
+            )
+            .map_err(output_error)
+        }
+    }
+}
+
+/// Writes the plan of the API key: JSON as is, otherwise one labelled line per field.
+pub fn write_plan<W: Write>(
+    writer: &mut W,
+    format: OutputFormat,
+    plan: &PlanInfoResponse,
+) -> Result<(), CliError> {
+    if format == OutputFormat::Json {
+        return write_json(writer, plan);
+    }
+
+    let limit = |limit: Option<u32>| {
+        limit
+            .map(|limit| limit.to_string())
+            .unwrap_or_else(|| "none".to_string())
+    };
+    let lines = [
+        (
+            "today",
+            format!(
+                "{} of {} credits",
+                plan.credits_used_day,
+                limit(plan.credit_limit_daily)
+            ),
+        ),
+        (
+            "this month",
+            format!(
+                "{} of {} credits",
+                plan.credits_used_month,
+                limit(plan.credit_limit_monthly)
+            ),
+        ),
+        (
+            "rate limit",
+            format!("{} calls a minute", limit(plan.rate_limit_minute)),
+        ),
+        ("resets", plan.monthly_reset.clone().unwrap_or_default()),
+        ("fetched", plan.fetched_at.clone()),
+    ];
+
+    for (label, value) in lines.iter().filter(|(_, value)| !value.is_empty()) {
+        writeln!(writer, "{:<14}{}", format!("{}:", label), value).map_err(output_error)?;
+    }
+
+    Ok(())
+}
+
+/// Writes the key pool, disabled keys with the status `disabled`.
+pub fn write_keys<W: Write>(
+    writer: &mut W,
+    format: OutputFormat,
+    keys: &[ApiKeyResponse],
+) -> Result<(), CliError> {
+    match format {
+        OutputFormat::Json => write_json(writer, keys),
+        OutputFormat::Csv => write_csv(writer, keys),
+        OutputFormat::Table => {
+            let rows = keys
+                .iter()
+                .map(|key| {
+                    let status = match (key.enabled, &key.available_at) {
+                        (false, _) => "disabled".to_string(),
+                        (true, Some(at)) => format!("{} until {}", key.status, at),
+                        (true, None) => key.status.clone(),
+                    };
+                    let credits = match key.monthly_credits {
+                        Some(limit) => format!("{}/{}", key.credits_used, limit),
+                        None => key.credits_used.to_string(),
+                    };
+
+                    vec![
+                        key.id.to_string(),
+                        key.label.clone(),
+                        key.key.clone(),
+                        key.plan.clone(),
+                        key.priority.to_string(),
+                        status,
+                        credits,
+                        key.last_used_at.clone().unwrap_or_default(),
+                    ]
+                })
+                .collect::<Vec<Vec<String>>>();
+
+            write_table(writer, &KEY_HEADERS, &rows)
+        }
+    }
+}
+
+/// Writes whether the provider accepted a key, followed by the plan it reported.
+pub fn write_key_test<W: Write>(
+    writer: &mut W,
+    format: OutputFormat,
+    test: &ApiKeyTestResponse,
+) -> Result<(), CliError> {
+    if format == OutputFormat::Json {
+        return write_json(writer, test);
+    }
+
+    let result = match &test.message {
+        Some(message) => format!("refused, {}", message),
+        None => "valid".to_string(),
+    };
+
+    writeln!(writer, "{:<14}{}", format!("{}:", test.key.label), result).map_err(output_error)?;
+
+    match &test.plan {
+        Some(plan) => write_plan(writer, format, plan),
+        None => Ok(()),
+    }
+}
+
+/// Writes what the provider said about a key being saved, followed by its plan.
+pub fn write_key_validation<W: Write>(
+    writer: &mut W,
+    format: OutputFormat,
+    validation: &KeyValidationResponse,
+) -> Result<(), CliError> {
+    if format == OutputFormat::Json {
+        return write_json(writer, validation);
+    }
+
+    let lines = [
+        ("key", validation.validity.clone()),
+        ("reason", validation.message.clone().unwrap_or_default()),
+        ("until", validation.available_at.clone().unwrap_or_default()),
+    ];
+
+    for (label, value) in lines.iter().filter(|(_, value)| !value.is_empty()) {
+        writeln!(writer, "{:<14}{}", format!("{}:", label), value).map_err(output_error)?;
+    }
+
+    match &validation.plan {
+        Some(plan) => write_plan(writer, format, plan),
+        None => Ok(()),
+    }
+}
+
+pub fn write_profiles<W: Write>(
+    writer: &mut W,
+    format: OutputFormat,
+    profiles: &[ProfileResponse],
+) -> Result<(), CliError> {
+    match format {
+        OutputFormat::Json => write_json(writer, profiles),
+        OutputFormat::Csv => write_csv(writer, profiles),
+        OutputFormat::Table => {
+            let rows = profiles
+                .iter()
+                .map(|profile| {
+                    vec![
+                        profile.name.clone(),
+                        if profile.active { "*" } else { "" }.to_string(),
+                        profile.db_path.clone(),
+                    ]
+                })
+                .collect::<Vec<Vec<String>>>();
+
+            write_table(writer, &PROFILE_HEADERS, &rows)
+        }
+    }
+}
+
+/// Writes the audit log; the table shows the snapshots as their JSON.
+pub fn write_audit<W: Write>(
+    writer: &mut W,
+    format: OutputFormat,
+    records: &[AuditRecordResponse],
+) -> Result<(), CliError> {
+    match format {
+        OutputFormat::Json => write_json(writer, records),
+        OutputFormat::Csv => write_csv(writer, records),
+        OutputFormat::Table => {
+            let rows = records
+                .iter()
+                .map(|record| {
+                    vec![
+                        format_time(record.recorded_at),
+                        record.origin.to_string(),
+                        record.operation.clone(),
+                        record.entity.to_string(),
+                        record.entity_id.clone(),
+                        record.before.clone().unwrap_or_else(|| "-".to_string()),
+                        record.after.clone().unwrap_or_else(|| "-".to_string()),
+                    ]
+                })
+                .collect::<Vec<Vec<String>>>();
+
+            write_table(writer, &AUDIT_HEADERS, &rows)
+        }
+    }
+}
+
+/// Writes the coins in the trash; CSV flattens each coin next to its deletion time.
+pub fn write_trash<W: Write>(
+    writer: &mut W,
+    format: OutputFormat,
+    coins: &[DeletedCoinResponse],
+) -> Result<(), CliError> {
+    match format {
+        OutputFormat::Json => write_json(writer, coins),
+        OutputFormat::Csv => {
+            let mut csv = csv::Writer::from_writer(writer);
+
+            csv.write_record(["deleted_at", "id", "symbol", "name", "price", "market_cap"])
+                .map_err(output_error)?;
+
+            for deleted in coins {
+                csv.write_record([
+                    deleted.deleted_at.to_string(),
+                    deleted.coin.id.to_string(),
+                    deleted.coin.symbol.clone(),
+                    deleted.coin.name.clone(),
+                    deleted.coin.price.to_string(),
+                    deleted.coin.market_cap.to_string(),
+                ])
+                .map_err(output_error)?;
+            }
+
+            csv.flush().map_err(output_error)
+        }
+        OutputFormat::Table => {
+            let rows = coins
+                .iter()
+                .map(|deleted| {
+                    vec![
+                        format_time(deleted.deleted_at),
+                        deleted.coin.id.to_string(),
+                        deleted.coin.symbol.clone(),
+                        deleted.coin.name.clone(),
+                        format!("{:.8}", deleted.coin.price),
+                    ]
+                })
+                .collect::<Vec<Vec<String>>>();
+
+            write_table(writer, &TRASH_HEADERS, &rows)
+        }
+    }
+}
+
+/// Writes the details of one coin: JSON as the whole response including the logo,
+/// otherwise one labelled line per field followed by the links.
+pub fn write_details<W: Write>(
+    writer: &mut W,
+    format: OutputFormat,
+    details: &CoinDetailsResponse,
+) -> Result<(), CliError> {
+    if format == OutputFormat::Json {
+        return write_json(writer, details);
+    }
+
+    let contracts = details
+        .contracts
+        .iter()
+        .map(|contract| format!("{} {}", contract.platform, contract.address))
+        .collect::<Vec<String>>();
+
+    let mut lines = vec![
+        (
+            "name",
+            format!("{} ({})", details.coin.name, details.coin.symbol),
+        ),
+        ("id", details.coin.id.to_string()),
+        ("logo", details.logo_url.clone().unwrap_or_default()),
+        ("tags", details.tags.join(", ")),
+        ("contracts", contracts.join(", ")),
+        ("fetched", details.fetched_at.clone()),
+    ];
+    lines.extend(
+        details
+            .links
+            .iter()
+            .map(|link| (link.kind.as_str(), link.url.clone())),
+    );
+
+    for (label, value) in lines.iter().filter(|(_, value)| !value.is_empty()) {
+        writeln!(writer, "{:<14}{}", format!("{}:", label), value).map_err(output_error)?;
+    }
+
+    if !details.description.is_empty() {
+        writeln!(writer, "\n{}", details.description.trim()).map_err(output_error)?;
+    }
+
+    Ok(())
+}
+
+/// Writes candles: JSON as the whole response including gaps, CSV and tables one row per
+/// candle. Tables end with the ranges that have no candles.
+pub fn write_candles<W: Write>(
+    writer: &mut W,
+    format: OutputFormat,
+    response: &CandlesResponse,
+) -> Result<(), CliError> {
+    match format {
+        OutputFormat::Json => write_json(writer, response),
+        OutputFormat::Csv => write_csv(writer, &response.candles),
+        OutputFormat::Table => {
+            let rows = response
+                .candles
+                .iter()
+                .map(|candle| {
+                    vec![
+                        format_time(candle.open_time),
+                        format!("{:.8}", candle.open),
+                        format!("{:.8}", candle.high),
+                        format!("{:.8}", candle.low),
+                        format!("{:.8}", candle.close),
+                        format!("{:.0}", candle.volume),
+                    ]
+                })
+                .collect::<Vec<Vec<String>>>();
+
+            write_table(writer, &CANDLE_HEADERS, &rows)?;
+
+            for gap in &response.gaps {
+                writeln!(
+                    writer,
+                    "missing: {} - {}",
+                    format_time(gap.start),
+                    format_time(gap.end)
+                )
+                .map_err(output_error)?;
+            }
+
+            Ok(())
+        }
+    }
+}
+
+/// Writes indicators: JSON as the whole response, CSV and tables one row per candle with a
+/// column per indicator line. Tables end with the ranges that have no candles.
+pub fn write_indicators<W: Write>(
+    writer: &mut W,
+    format: OutputFormat,
+    response: &IndicatorsResponse,
+) -> Result<(), CliError> {
+    let columns = response
+        .indicators
+        .iter()
+        .flat_map(|series| {
+            series.lines.iter().map(move |(name, values)| {
+                let header = if name == "value" {
+                    series.indicator.to_string()
+                } else {
+                    format!("{} {}", series.indicator, name)
+                };
+
+                (header, values)
+            })
+        })
+        .collect::<Vec<(String, &Vec<Option<f64>>)>>();
+
+    let rows = |time: fn(i64) -> String| {
+        response
+            .timestamps
+            .iter()
+            .zip(&response.close)
+            .enumerate()
+            .map(|(index, (open_time, close))| {
+                let mut row = vec![time(*open_time), format!("{:.8}", close)];
+                row.extend(columns.iter().map(|(_, values)| {
+                    values[index]
+                        .map(|value| format!("{:.8}", value))
+                        .unwrap_or_default()
+                }));
+                row
+            })
+            .collect::<Vec<Vec<String>>>()
+    };
+
+    match format {
+        OutputFormat::Json => write_json(writer, response),
+        OutputFormat::Csv => {
+            let mut csv = csv::Writer::from_writer(writer);
+            let headers = ["open_time".to_string(), "close".to_string()]
+                .into_iter()
+                .chain(columns.iter().map(|(header, _)| header.clone()));
+
+            csv.write_record(headers).map_err(output_error)?;
+
+            for row in rows(|time| time.to_string()) {
+                csv.write_record(&row).map_err(output_error)?;
+            }
+
+            csv.flush().map_err(output_error)
+        }
+        OutputFormat::Table => {
+            let headers = columns
+                .iter()
+                .map(|(header, _)| header.to_uppercase())
+                .collect::<Vec<String>>();
+            let headers = ["OPEN TIME", "CLOSE"]
+                .into_iter()
+                .chain(headers.iter().map(String::as_str))
+                .collect::<Vec<&str>>();
+
+            write_table(writer, &headers, &rows(format_time))?;
+
+            for gap in &response.gaps {
+                writeln!(
+                    writer,
+                    "missing: {} - {}",
+                    format_time(gap.start),
+                    format_time(gap.end)
+                )
+                .map_err(output_error)?;
+            }
+
+            Ok(())
+        }
+    }
+}
+
+/// Writes an import report: JSON as is, otherwise a summary line followed by one line per
+/// rejected row.
+pub fn write_report<W: Write>(
+    writer: &mut W,
+    format: OutputFormat,
+    report: &ImportReport,
+) -> Result<(), CliError> {
+    if format == OutputFormat::Json {
+        return write_json(writer, report);
+    }
+
+    writeln!(
+        writer,
+        "{} rows, {} valid, {} imported{}{}",
+        report.total,
+        report.valid,
+        report.imported,
+        match report.duplicates {
+            0 => String::new(),
+            duplicates => format!(", {} duplicates", duplicates),
+        },
+        if report.dry_run { " (dry run)" } else { "" }
+    )
+    .map_err(output_error)?;
+
+    write_row_errors(writer, &report.errors)
+}
+
+/// Writes a transaction import report like `write_report`, followed by the unmapped assets.
+pub fn write_transaction_report<W: Write>(
+    writer: &mut W,
+    format: OutputFormat,
+    report: &TransactionImportReport,
+) -> Result<(), CliError> {
+    if format == OutputFormat::Json {
+        return write_json(writer, report);
+    }
+
+    writeln!(
+        writer,
+        "{} rows, {} imported, {} duplicates{}",
+        report.total,
+        report.imported,
+        report.duplicates,
+        if report.dry_run { " (dry run)" } else { "" }
+    )
+    .map_err(output_error)?;
+
+    write_row_errors(writer, &report.errors)?;
+
+    if !report.unmapped_assets.is_empty() {
+        writeln!(
+            writer,
+            "unmapped assets: {}",
+            report.unmapped_assets.join(", ")
+        )
+        .map_err(output_error)?;
+    }
+
+    Ok(())
+}
+
+fn write_row_errors<W: Write>(writer: &mut W, errors: &[RowError]) -> Result<(), CliError> {
+    for error in errors {
+        let details = error
+            .details
+            .iter()
+            .map(|(field, messages)| format!("{}: {}", field, messages.join(", ")))
+            .collect::<Vec<String>>();
+
+        let line = if details.is_empty() {
+            format!("row {}: {}", error.row, error.message)
+        } else {
+            format!(
+                "row {}: {} ({})",
+                error.row,
+                error.message,
+                details.join("; ")
+            )
+        };
+
+        writeln!(writer, "{}", line).map_err(output_error)?;
+    }
+
+    Ok(())
+}
+
+/// Writes the value as indented JSON followed by a newline.
+fn write_json<W: Write, T: Serialize + ?Sized>(writer: &mut W, value: &T) -> Result<(), CliError> {
+    serde_json::to_writer_pretty(&mut *writer, value).map_err(output_error)?;
+    writeln!(writer).map_err(output_error)
+}
+
+/// Writes one row per item with a header row named after its fields.
+fn write_csv<W: Write, T: Serialize>(writer: &mut W, rows: &[T]) -> Result<(), CliError> {
+    let mut csv = csv::Writer::from_writer(writer);
+
+    for row in rows {
+        csv.serialize(row).map_err(output_error)?;
+    }
+
+    csv.flush().map_err(output_error)
+}
+
+fn output_error(e: impl ToString) -> CliError {
+    CliError::Output(e.to_string())
+}
+
+fn coin_rows(coins: &[CoinResponse]) -> Vec<Vec<String>> {
+    coins
+        .iter()
+        .map(|coin| {
+            vec![
+                coin.id.to_string(),
+                coin.symbol.clone(),
+                coin.name.clone(),
+                format!("{:.8}", coin.price),
+                format!("{:.2}", coin.percent_change_24h),
+                format!("{:.0}", coin.market_cap),
+                coin.last_updated.clone(),
+                match (coin.age_seconds, coin.is_stale) {
+                    (Some(age), false) => format_age(age),
+                    (Some(age), true) => format!("{} (stale)", format_age(age)),
+                    (None, _) => "-".to_string(),
+                },
+            ]
+        })
+        .collect()
+}
+
+/// Rounds an age down to its largest unit, e.g. `90` to `1m`.
+fn format_age(seconds: u32) -> String {
+    match seconds {
+        0..=59 => format!("{}s", seconds),
+        60..=3599 => format!("{}m", seconds / 60),
+        3600..=86399 => format!("{}h", seconds / 3600),
+        _ => format!("{}d", seconds / 86400),
+    }
+}
+
+fn format_time(seconds: i64) -> String {
+    chrono::DateTime::from_timestamp(seconds, 0)
+        .map(|time| time.to_rfc3339_opts(chrono::SecondsFormat::Secs, true))
+        .unwrap_or_else(|| seconds.to_string())
+}
+
+fn write_table<W: Write>(
+    writer: &mut W,
+    headers: &[&str],
+    rows: &[Vec<String>],
+) -> Result<(), CliError> {
+    let mut widths = headers
+        .iter()
+        .map(|header| header.len())
+        .collect::<Vec<usize>>();
+
+    for row in rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let headers = headers
+        .iter()
+        .map(|header| header.to_string())
+        .collect::<Vec<String>>();
+
+    for row in std::iter::once(&headers).chain(rows) {
+        let line = row
+            .iter()
+            .zip(widths.iter().copied())
+            .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+            .collect::<Vec<String>>()
+            .join("  ");
+
+        writeln!(writer, "{}", line.trim_end()).map_err(output_error)?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,170 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::{Path, State},
+    Json,
+};
+use validator::Validate;
+
+use crate::{
+    domain::{self, audit::audit::Origin},
+    driving::tauri::{
+        api_keys::{
+            AddApiKeyRequest, ApiKeyResponse, ApiKeyTestResponse, KeyValidationResponse,
+            SetApiKeyRequest,
+        },
+        errors::TauriErrors,
+    },
+    AppState,
+};
+
+use super::errors::HttpErrors;
+
+#[utoipa::path(
+    post,
+    path = "/api/keys",
+    tag = "keys",
+    request_body = AddApiKeyRequest,
+    responses(
+        (status = 200, body = ApiKeyResponse),
+        (status = 422, body = ErrorPayload),
+    ),
+    security(("bearer" = []))
+)]
+pub(crate) async fn add_api_key(
+    State(state): State<Arc<AppState>>,
+    Json(request): Json<AddApiKeyRequest>,
+) -> Result<Json<ApiKeyResponse>, HttpErrors> {
+    request.validate()?;
+
+    match domain::provider::api_keys::add_api_key(
+        state.sqlite_repo.clone(),
+        request.label,
+        request.key,
+        request.plan,
+        request.monthly_credits,
+        request.priority,
+    )
+    .await
+    {
+        Ok(key) => Ok(Json(ApiKeyResponse::from(key))),
+        Err(e) => Err(TauriErrors::from(e).into()),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/keys",
+    tag = "keys",
+    responses((status = 200, body = [ApiKeyResponse])),
+    security(("bearer" = []))
+)]
+pub(crate) async fn get_api_keys(
+    State(state): State<Arc<AppState>>,
+) -> Result<Json<Vec<ApiKeyResponse>>, HttpErrors> {
+    match domain::provider::api_keys::get_api_keys(state.sqlite_repo.clone()).await {
+        Ok(keys) => Ok(Json(keys.into_iter().map(ApiKeyResponse::from).collect())),
+        Err(e) => Err(TauriErrors::from(e).into()),
+    }
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/keys/{id}/enabled",
+    tag = "keys",
+    params(("id" = u32, Path, description = "API key id")),
+    request_body(content = bool, description = "Whether the key may be used"),
+    responses(
+        (status = 200, body = ApiKeyResponse),
+        (status = 404, body = ErrorPayload),
+    ),
+    security(("bearer" = []))
+)]
+pub(crate) async fn set_api_key_enabled(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<u32>,
+    Json(enabled): Json<bool>,
+) -> Result<Json<ApiKeyResponse>, HttpErrors> {
+    match domain::provider::api_keys::set_api_key_enabled(state.sqlite_repo.clone(), id, enabled)
+        .await
+    {
+        Ok(key) => Ok(Json(ApiKeyResponse::from(key))),
+        Err(e) => Err(TauriErrors::from(e).into()),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/keys/{id}/test",
+    tag = "keys",
+    params(("id" = u32, Path, description = "API key id")),
+    responses(
+        (status = 200, body = ApiKeyTestResponse),
+        (status = 404, body = ErrorPayload),
+        (status = 502, body = ErrorPayload),
+    ),
+    security(("bearer" = []))
+)]
+pub(crate) async fn test_api_key(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<u32>,
+) -> Result<Json<ApiKeyTestResponse>, HttpErrors> {
+    match domain::provider::api_keys::test_api_key(state.sqlite_repo.clone(), &state.config, id)
+        .await
+    {
+        Ok(test) => Ok(Json(ApiKeyTestResponse::from(test))),
+        Err(e) => Err(TauriErrors::from(e).into()),
+    }
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/keys/{id}",
+    tag = "keys",
+    params(("id" = u32, Path, description = "API key id")),
+    responses(
+        (status = 200),
+        (status = 404, body = ErrorPayload),
+    ),
+    security(("bearer" = []))
+)]
+pub(crate) async fn delete_api_key(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<u32>,
+) -> Result<(), HttpErrors> {
+    match domain::provider::api_keys::delete_api_key(state.sqlite_repo.clone(), id).await {
+        Ok(_) => Ok(()),
+        Err(e) => Err(TauriErrors::from(e).into()),
+    }
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/key",
+    tag = "keys",
+    request_body = SetApiKeyRequest,
+    responses(
+        (status = 200, body = KeyValidationResponse),
+        (status = 422, body = ErrorPayload),
+        (status = 503, body = ErrorPayload),
+    ),
+    security(("bearer" = []))
+)]
+pub(crate) async fn set_api_key(
+    State(state): State<Arc<AppState>>,
+    Json(request): Json<SetApiKeyRequest>,
+) -> Result<Json<KeyValidationResponse>, HttpErrors> {
+    request.validate()?;
+
+    match domain::provider::set_api_key::set_api_key(
+        state.sqlite_repo.clone(),
+        Origin::Http,
+        &state.config,
+        request.key,
+    )
+    .await
+    {
+        Ok(validation) => Ok(Json(KeyValidationResponse::from(validation))),
+        Err(e) => Err(TauriErrors::from(e).into()),
+    }
+}
@@ -0,0 +1,54 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::{Query, State},
+    Json,
+};
+use validator::Validate;
+
+use crate::{
+    domain::{self, audit::audit::AuditFilter},
+    driving::tauri::{
+        audit::{AuditRecordResponse, GetAuditLogRequest},
+        errors::TauriErrors,
+    },
+    AppState,
+};
+
+use super::errors::HttpErrors;
+
+#[utoipa::path(
+    get,
+    path = "/api/audit",
+    tag = "audit",
+    params(
+        ("entity" = Option<AuditEntity>, Query, description = "Only changes of coins or of settings"),
+        ("entity_id" = Option<String>, Query, description = "Coin id or setting key"),
+        ("start" = Option<i64>, Query, description = "Unix seconds, inclusive"),
+        ("end" = Option<i64>, Query, description = "Unix seconds, inclusive"),
+        ("limit" = Option<u32>, Query, description = "Defaults to 100, at most 1000"),
+    ),
+    responses(
+        (status = 200, body = [AuditRecordResponse]),
+        (status = 422, body = ErrorPayload),
+    ),
+    security(("bearer" = []))
+)]
+pub(crate) async fn get_audit_log(
+    State(state): State<Arc<AppState>>,
+    Query(request): Query<GetAuditLogRequest>,
+) -> Result<Json<Vec<AuditRecordResponse>>, HttpErrors> {
+    request.validate()?;
+
+    match domain::audit::get_audit_log::get_audit_log(
+        state.sqlite_repo.clone(),
+        AuditFilter::from(request),
+    )
+    .await
+    {
+        Ok(records) => Ok(Json(
+            records.into_iter().map(AuditRecordResponse::from).collect(),
+        )),
+        Err(e) => Err(TauriErrors::from(e).into()),
+    }
+}
@@ -0,0 +1,118 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::{Path, Query, State},
+    Json,
+};
+use serde::Deserialize;
+use validator::Validate;
+
+use crate::{
+    domain::{self, candle::candle::CandleInterval},
+    driving::tauri::{
+        candles::{CandlesResponse, GetCandlesRequest},
+        errors::TauriErrors,
+    },
+    AppState,
+};
+
+use super::{api_key, errors::HttpErrors};
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct CandlesQuery {
+    interval: CandleInterval,
+    start: Option<i64>,
+    end: Option<i64>,
+}
+
+impl CandlesQuery {
+    fn request(self, coin_id: u32) -> GetCandlesRequest {
+        GetCandlesRequest {
+            coin_id,
+            interval: self.interval,
+            start: self.start,
+            end: self.end,
+        }
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/coins/{id}/candles",
+    tag = "candles",
+    params(
+        ("id" = u32, Path, description = "Tracked coin id"),
+        ("interval" = CandleInterval, Query, description = "Candle interval"),
+        ("start" = Option<i64>, Query, description = "Unix seconds; defaults to 90 candles before end"),
+        ("end" = Option<i64>, Query, description = "Unix seconds; defaults to the last closed candle"),
+    ),
+    responses(
+        (status = 200, body = CandlesResponse),
+        (status = 422, body = ErrorPayload),
+    ),
+    security(("bearer" = []))
+)]
+pub(crate) async fn get_candles(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<u32>,
+    Query(query): Query<CandlesQuery>,
+) -> Result<Json<CandlesResponse>, HttpErrors> {
+    let request = query.request(id);
+    request.validate()?;
+
+    match domain::candle::get_candles::get_candles(
+        state.sqlite_repo.clone(),
+        request.coin_id,
+        request.interval,
+        request.range()?,
+    )
+    .await
+    {
+        Ok(series) => Ok(Json(CandlesResponse::from(series))),
+        Err(e) => Err(TauriErrors::from(e).into()),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/coins/{id}/candles/backfill",
+    tag = "candles",
+    params(
+        ("id" = u32, Path, description = "Tracked coin id"),
+        ("interval" = CandleInterval, Query, description = "Candle interval"),
+        ("start" = Option<i64>, Query, description = "Unix seconds; defaults to 90 candles before end"),
+        ("end" = Option<i64>, Query, description = "Unix seconds; defaults to the last closed candle"),
+    ),
+    responses(
+        (status = 200, body = CandlesResponse),
+        (status = 401, body = ErrorPayload),
+        (status = 404, body = ErrorPayload),
+        (status = 422, body = ErrorPayload),
+        (status = 502, body = ErrorPayload),
+    ),
+    security(("bearer" = []))
+)]
+pub(crate) async fn backfill_candles(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<u32>,
+    Query(query): Query<CandlesQuery>,
+) -> Result<Json<CandlesResponse>, HttpErrors> {
+    let request = query.request(id);
+    request.validate()?;
+
+    let token = api_key(&state).await?;
+
+    match domain::candle::backfill_candles::backfill_candles(
+        state.sqlite_repo.clone(),
+        &state.config,
+        token,
+        request.coin_id,
+        request.interval,
+        request.range()?,
+    )
+    .await
+    {
+        Ok(series) => Ok(Json(CandlesResponse::from(series))),
+        Err(e) => Err(TauriErrors::from(e).into()),
+    }
+}
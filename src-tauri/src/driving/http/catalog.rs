@@ -0,0 +1,122 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::{Query, State},
+    Json,
+};
+use validator::Validate;
+
+use crate::{
+    domain::{self, audit::audit::Origin, catalog::catalog::DEFAULT_SEARCH_LIMIT},
+    driving::tauri::{
+        catalog::{
+            AddTopCoinsRequest, CatalogEntryResponse, CatalogRefreshResponse, SearchCatalogRequest,
+        },
+        coins::CoinResponse,
+        errors::TauriErrors,
+    },
+    AppState,
+};
+
+use super::{api_key, errors::HttpErrors};
+
+#[utoipa::path(
+    get,
+    path = "/api/catalog",
+    tag = "catalog",
+    params(
+        ("query" = String, Query, description = "Text to find in symbols, names and slugs"),
+        ("limit" = Option<u32>, Query, description = "Most results, 20 by default"),
+    ),
+    responses(
+        (status = 200, body = [CatalogEntryResponse]),
+        (status = 422, body = ErrorPayload),
+    ),
+    security(("bearer" = []))
+)]
+pub(crate) async fn search_catalog(
+    State(state): State<Arc<AppState>>,
+    Query(request): Query<SearchCatalogRequest>,
+) -> Result<Json<Vec<CatalogEntryResponse>>, HttpErrors> {
+    request.validate()?;
+
+    match domain::catalog::search_catalog::search_catalog(
+        state.sqlite_repo.clone(),
+        request.query,
+        request.limit.unwrap_or(DEFAULT_SEARCH_LIMIT),
+    )
+    .await
+    {
+        Ok(results) => Ok(Json(
+            results
+                .into_iter()
+                .map(CatalogEntryResponse::from)
+                .collect(),
+        )),
+        Err(e) => Err(TauriErrors::from(e).into()),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/catalog/refresh",
+    tag = "catalog",
+    responses(
+        (status = 200, body = CatalogRefreshResponse),
+        (status = 401, body = ErrorPayload),
+        (status = 502, body = ErrorPayload),
+    ),
+    security(("bearer" = []))
+)]
+pub(crate) async fn refresh_catalog(
+    State(state): State<Arc<AppState>>,
+) -> Result<Json<CatalogRefreshResponse>, HttpErrors> {
+    let token = api_key(&state).await?;
+
+    match domain::catalog::refresh_catalog::refresh_catalog(
+        state.sqlite_repo.clone(),
+        &state.config,
+        token,
+    )
+    .await
+    {
+        Ok(count) => Ok(Json(CatalogRefreshResponse { count })),
+        Err(e) => Err(TauriErrors::from(e).into()),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/coins/top",
+    tag = "coins",
+    request_body = AddTopCoinsRequest,
+    responses(
+        (status = 200, body = [CoinResponse]),
+        (status = 401, body = ErrorPayload),
+        (status = 422, body = ErrorPayload),
+        (status = 502, body = ErrorPayload),
+    ),
+    security(("bearer" = []))
+)]
+pub(crate) async fn add_top_coins(
+    State(state): State<Arc<AppState>>,
+    Json(request): Json<AddTopCoinsRequest>,
+) -> Result<Json<Vec<CoinResponse>>, HttpErrors> {
+    request.validate()?;
+
+    let token = api_key(&state).await?;
+
+    match domain::catalog::add_top_coins::add_top_coins(
+        state.sqlite_repo.clone(),
+        &state.events,
+        Origin::Http,
+        &state.config,
+        token,
+        request.limit,
+    )
+    .await
+    {
+        Ok(coins) => Ok(Json(coins.into_iter().map(CoinResponse::from).collect())),
+        Err(e) => Err(TauriErrors::from(e).into()),
+    }
+}
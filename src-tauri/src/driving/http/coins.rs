@@ -0,0 +1,329 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::{Path, Query, State},
+    Json,
+};
+use serde::Deserialize;
+use validator::Validate;
+
+use crate::{
+    domain::{self, audit::audit::Origin},
+    driving::tauri::{
+        coins::{
+            CoinDetailsResponse, CoinResponse, CreateCoinRequest, CreateCoinsRequest,
+            FetchCoinsByIdRequest, FetchCoinsBySymbolRequest, GetAllCoinsRequest,
+            GetCoinDetailsRequest, UpdateCoinsRequest,
+        },
+        errors::TauriErrors,
+    },
+    fetch::coinmarketcap::{fetch_ids, fetch_symbols},
+    AppState,
+};
+
+use super::{api_key, errors::HttpErrors};
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct DetailsQuery {
+    refresh: Option<bool>,
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/coins",
+    tag = "coins",
+    request_body = CreateCoinRequest,
+    responses(
+        (status = 200, body = CoinResponse),
+        (status = 422, body = ErrorPayload),
+    ),
+    security(("bearer" = []))
+)]
+pub(crate) async fn create_coin(
+    State(state): State<Arc<AppState>>,
+    Json(request): Json<CreateCoinRequest>,
+) -> Result<Json<CoinResponse>, HttpErrors> {
+    request.validate()?;
+
+    match domain::coin::create_coin::create_coin(
+        state.sqlite_repo.clone(),
+        &state.events,
+        Origin::Http,
+        request,
+    )
+    .await
+    {
+        Ok(coin) => Ok(Json(CoinResponse::from(coin))),
+        Err(e) => Err(TauriErrors::from(e).into()),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/coins/batch",
+    tag = "coins",
+    request_body = CreateCoinsRequest,
+    responses(
+        (status = 200, body = [CoinResponse]),
+        (status = 422, body = ErrorPayload),
+    ),
+    security(("bearer" = []))
+)]
+pub(crate) async fn create_coins(
+    State(state): State<Arc<AppState>>,
+    Json(request): Json<CreateCoinsRequest>,
+) -> Result<Json<Vec<CoinResponse>>, HttpErrors> {
+    request.validate()?;
+
+    let mut result = vec![];
+
+    for coin in request.coins {
+        match domain::coin::create_coin::create_coin(
+            state.sqlite_repo.clone(),
+            &state.events,
+            Origin::Http,
+            coin,
+        )
+        .await
+        {
+            Ok(coin) => result.push(CoinResponse::from(coin)),
+            Err(e) => return Err(TauriErrors::from(e).into()),
+        }
+    }
+
+    Ok(Json(result))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/coins/refresh",
+    tag = "coins",
+    request_body = UpdateCoinsRequest,
+    responses(
+        (status = 200, body = [CoinResponse]),
+        (status = 401, body = ErrorPayload),
+        (status = 502, body = ErrorPayload),
+    ),
+    security(("bearer" = []))
+)]
+pub(crate) async fn update_coins(
+    State(state): State<Arc<AppState>>,
+    Json(request): Json<UpdateCoinsRequest>,
+) -> Result<Json<Vec<CoinResponse>>, HttpErrors> {
+    request.validate()?;
+
+    let token = api_key(&state).await?;
+    match domain::coin::refresh_coins::refresh_coins(
+        state.sqlite_repo.clone(),
+        &state.events,
+        Origin::Http,
+        &state.config,
+        &state.connectivity,
+        request.ids,
+        token,
+    )
+    .await
+    {
+        Ok(coins) => Ok(Json(coins.into_iter().map(CoinResponse::from).collect())),
+        Err(e) => Err(TauriErrors::from(e).into()),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/coins/{id}",
+    tag = "coins",
+    params(("id" = u32, Path, description = "CoinMarketCap coin id")),
+    responses(
+        (status = 200, body = CoinResponse),
+        (status = 404, body = ErrorPayload),
+    ),
+    security(("bearer" = []))
+)]
+pub(crate) async fn find_coin(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<u32>,
+) -> Result<Json<CoinResponse>, HttpErrors> {
+    match domain::coin::find_coin::find_coin(state.sqlite_repo.clone(), id).await {
+        Ok(coin) => Ok(Json(CoinResponse::from(coin))),
+        Err(e) => Err(TauriErrors::from(e).into()),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/coins/{id}/details",
+    tag = "coins",
+    params(
+        ("id" = u32, Path, description = "Tracked coin id"),
+        ("refresh" = Option<bool>, Query, description = "Fetch the metadata again even if it is stored"),
+    ),
+    responses(
+        (status = 200, body = CoinDetailsResponse),
+        (status = 401, body = ErrorPayload),
+        (status = 404, body = ErrorPayload),
+        (status = 502, body = ErrorPayload),
+    ),
+    security(("bearer" = []))
+)]
+pub(crate) async fn get_coin_details(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<u32>,
+    Query(query): Query<DetailsQuery>,
+) -> Result<Json<CoinDetailsResponse>, HttpErrors> {
+    let request = GetCoinDetailsRequest {
+        id,
+        refresh: query.refresh,
+    };
+    request.validate()?;
+
+    // Stored metadata is served without a token
+    let token = api_key(&state).await.ok();
+
+    match domain::coin::get_coin_details::get_coin_details(
+        state.sqlite_repo.clone(),
+        &state.config,
+        token,
+        request.id,
+        request.refresh.unwrap_or(false),
+    )
+    .await
+    {
+        Ok(details) => Ok(Json(CoinDetailsResponse::from(details))),
+        Err(e) => Err(TauriErrors::from(e).into()),
+    }
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/coins/{id}",
+    tag = "coins",
+    params(("id" = u32, Path, description = "CoinMarketCap coin id")),
+    responses((status = 200)),
+    security(("bearer" = []))
+)]
+pub(crate) async fn delete_coin(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<u32>,
+) -> Result<(), HttpErrors> {
+    match domain::coin::delete_coin::delete_coin(
+        state.sqlite_repo.clone(),
+        &state.events,
+        Origin::Http,
+        id,
+    )
+    .await
+    {
+        Ok(_) => Ok(()),
+        Err(e) => Err(TauriErrors::from(e).into()),
+    }
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/coins",
+    tag = "coins",
+    responses((status = 200)),
+    security(("bearer" = []))
+)]
+pub(crate) async fn delete_all_coins(State(state): State<Arc<AppState>>) -> Result<(), HttpErrors> {
+    match domain::coin::delete_coin::delete_all_coins(
+        state.sqlite_repo.clone(),
+        &state.events,
+        Origin::Http,
+    )
+    .await
+    {
+        Ok(_) => Ok(()),
+        Err(e) => Err(TauriErrors::from(e).into()),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/coins",
+    tag = "coins",
+    params(
+        ("watchlist_id" = Option<u32>, Query, description = "Only coins of this watchlist")
+    ),
+    responses(
+        (status = 200, body = [CoinResponse]),
+        (status = 404, body = ErrorPayload),
+    ),
+    security(("bearer" = []))
+)]
+pub(crate) async fn get_all_coins(
+    State(state): State<Arc<AppState>>,
+    Query(request): Query<GetAllCoinsRequest>,
+) -> Result<Json<Vec<CoinResponse>>, HttpErrors> {
+    request.validate()?;
+
+    match domain::coin::get_all_coins::get_all_coins(
+        state.sqlite_repo.clone(),
+        request.watchlist_id,
+    )
+    .await
+    {
+        Ok(coins) => Ok(Json(coins.into_iter().map(CoinResponse::from).collect())),
+        Err(e) => Err(TauriErrors::from(e).into()),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/quotes/ids",
+    tag = "quotes",
+    request_body = FetchCoinsByIdRequest,
+    responses(
+        (status = 200, body = [CoinResponse]),
+        (status = 401, body = ErrorPayload),
+        (status = 502, body = ErrorPayload),
+    ),
+    security(("bearer" = []))
+)]
+pub(crate) async fn fetch_coins_by_id(
+    State(state): State<Arc<AppState>>,
+    Json(request): Json<FetchCoinsByIdRequest>,
+) -> Result<Json<Vec<CoinResponse>>, HttpErrors> {
+    request.validate()?;
+
+    let token = api_key(&state).await?;
+
+    match fetch_ids(state.sqlite_repo.clone(), &state.config, request.ids, token).await {
+        Ok(coins) => Ok(Json(coins.coins)),
+        Err(e) => Err(TauriErrors::from(e).into()),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/quotes/symbols",
+    tag = "quotes",
+    request_body = FetchCoinsBySymbolRequest,
+    responses(
+        (status = 200, body = [CoinResponse]),
+        (status = 401, body = ErrorPayload),
+        (status = 502, body = ErrorPayload),
+    ),
+    security(("bearer" = []))
+)]
+pub(crate) async fn fetch_coins_by_symbol(
+    State(state): State<Arc<AppState>>,
+    Json(request): Json<FetchCoinsBySymbolRequest>,
+) -> Result<Json<Vec<CoinResponse>>, HttpErrors> {
+    request.validate()?;
+
+    let token = api_key(&state).await?;
+
+    match fetch_symbols(
+        state.sqlite_repo.clone(),
+        &state.config,
+        request.symbols,
+        token,
+    )
+    .await
+    {
+        Ok(coins) => Ok(Json(coins.coins)),
+        Err(e) => Err(TauriErrors::from(e).into()),
+    }
+}
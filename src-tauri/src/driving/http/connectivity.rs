@@ -0,0 +1,20 @@
+use std::sync::Arc;
+
+use axum::{extract::State, Json};
+
+use crate::{driving::tauri::connectivity::ConnectivityResponse, AppState};
+
+#[utoipa::path(
+    get,
+    path = "/api/connectivity",
+    tag = "connectivity",
+    responses(
+        (status = 200, body = ConnectivityResponse),
+    ),
+    security(("bearer" = []))
+)]
+pub(crate) async fn get_connectivity(
+    State(state): State<Arc<AppState>>,
+) -> Json<ConnectivityResponse> {
+    Json(ConnectivityResponse::from(state.connectivity.status()))
+}
@@ -0,0 +1,61 @@
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use thiserror::Error;
+
+use crate::driving::tauri::errors::{ErrorCategory, ErrorPayload, TauriErrors};
+
+#[derive(Debug, Error)]
+pub enum HttpErrors {
+    #[error(transparent)]
+    Command(#[from] TauriErrors),
+    #[error("Missing or invalid bearer token")]
+    Unauthorized,
+}
+
+impl From<validator::ValidationErrors> for HttpErrors {
+    fn from(e: validator::ValidationErrors) -> Self {
+        HttpErrors::Command(TauriErrors::ValidateError(e))
+    }
+}
+
+impl HttpErrors {
+    fn payload(&self) -> ErrorPayload {
+        match self {
+            HttpErrors::Command(e) => e.payload(),
+            HttpErrors::Unauthorized => ErrorPayload {
+                code: "unauthorized".to_string(),
+                category: ErrorCategory::Unauthorized,
+                message: self.to_string(),
+                details: None,
+            },
+        }
+    }
+}
+
+impl IntoResponse for HttpErrors {
+    fn into_response(self) -> Response {
+        let payload = self.payload();
+        let status = match (&self, payload.category) {
+            (HttpErrors::Command(TauriErrors::ValidateError(_)), _) => {
+                StatusCode::UNPROCESSABLE_ENTITY
+            }
+            (HttpErrors::Command(TauriErrors::RateLimited(_)), _)
+            | (HttpErrors::Command(TauriErrors::BudgetExhausted(_)), _) => {
+                StatusCode::TOO_MANY_REQUESTS
+            }
+            (HttpErrors::Command(TauriErrors::Unreachable(_)), _) => {
+                StatusCode::SERVICE_UNAVAILABLE
+            }
+            (_, ErrorCategory::NotFound) => StatusCode::NOT_FOUND,
+            (_, ErrorCategory::InvalidInput) => StatusCode::BAD_REQUEST,
+            (_, ErrorCategory::Unauthorized) => StatusCode::UNAUTHORIZED,
+            (_, ErrorCategory::Upstream) => StatusCode::BAD_GATEWAY,
+            (_, ErrorCategory::Internal) => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+
+        (status, Json(payload)).into_response()
+    }
+}
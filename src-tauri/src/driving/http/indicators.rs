@@ -0,0 +1,71 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::{Path, State},
+    Json,
+};
+use serde::Deserialize;
+use utoipa::ToSchema;
+use validator::Validate;
+
+use crate::{
+    domain::{self, candle::candle::CandleInterval, indicator::indicator::IndicatorSpec},
+    driving::tauri::{
+        errors::TauriErrors,
+        indicators::{GetIndicatorsRequest, IndicatorsResponse},
+    },
+    AppState,
+};
+
+use super::errors::HttpErrors;
+
+/// `GetIndicatorsRequest` without the coin id, which comes from the path.
+#[derive(Debug, Deserialize, ToSchema)]
+pub(crate) struct IndicatorsBody {
+    interval: CandleInterval,
+    /// Unix seconds; defaults to 90 candles before end
+    start: Option<i64>,
+    /// Unix seconds; defaults to the last closed candle
+    end: Option<i64>,
+    indicators: Vec<IndicatorSpec>,
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/coins/{id}/indicators",
+    tag = "candles",
+    params(("id" = u32, Path, description = "Tracked coin id")),
+    request_body = IndicatorsBody,
+    responses(
+        (status = 200, body = IndicatorsResponse),
+        (status = 422, body = ErrorPayload),
+    ),
+    security(("bearer" = []))
+)]
+pub(crate) async fn get_indicators(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<u32>,
+    Json(body): Json<IndicatorsBody>,
+) -> Result<Json<IndicatorsResponse>, HttpErrors> {
+    let request = GetIndicatorsRequest {
+        coin_id: id,
+        interval: body.interval,
+        start: body.start,
+        end: body.end,
+        indicators: body.indicators,
+    };
+    request.validate()?;
+
+    match domain::indicator::get_indicators::get_indicators(
+        state.sqlite_repo.clone(),
+        request.coin_id,
+        request.interval,
+        request.range()?,
+        request.indicators,
+    )
+    .await
+    {
+        Ok(set) => Ok(Json(IndicatorsResponse::from(set))),
+        Err(e) => Err(TauriErrors::from(e).into()),
+    }
+}
@@ -0,0 +1,356 @@
+pub(crate) mod api_keys;
+pub(crate) mod audit;
+pub(crate) mod candles;
+pub(crate) mod catalog;
+pub(crate) mod coins;
+pub(crate) mod connectivity;
+pub(crate) mod errors;
+pub(crate) mod indicators;
+pub(crate) mod profiles;
+pub(crate) mod settings;
+pub(crate) mod stream;
+pub(crate) mod transactions;
+pub(crate) mod transfer;
+pub(crate) mod trash;
+pub(crate) mod usage;
+pub(crate) mod watchlists;
+
+use std::{
+    net::{Ipv4Addr, SocketAddr},
+    sync::Arc,
+};
+
+use axum::{
+    extract::{Request, State},
+    http::header::AUTHORIZATION,
+    middleware::{self, Next},
+    response::Response,
+    routing::{delete, get, post, put},
+    Json, Router,
+};
+use utoipa::{
+    openapi::security::{HttpAuthScheme, HttpBuilder, SecurityScheme},
+    Modify, OpenApi,
+};
+
+use crate::{
+    domain::{
+        self,
+        audit::audit::{AuditEntity, Origin},
+        candle::candle::{CandleInterval, CandleRange},
+        coin::metadata::{CoinLink, LinkKind, TokenContract},
+        indicator::indicator::IndicatorSpec,
+        transaction::transaction::{Exchange, TradeSide, TransactionImportReport},
+        transfer::transfer::{DataFormat, Dataset, ImportReport, RowError},
+    },
+    driving::tauri::{
+        api_keys::{
+            AddApiKeyRequest, ApiKeyResponse, ApiKeyTestResponse, KeyValidationResponse,
+            SetApiKeyRequest,
+        },
+        audit::AuditRecordResponse,
+        candles::{CandleResponse, CandlesResponse},
+        catalog::{
+            AddTopCoinsRequest, CatalogEntryResponse, CatalogRefreshResponse, SearchCatalogRequest,
+        },
+        coins::{
+            CoinDetailsResponse, CoinResponse, CreateCoinRequest, CreateCoinsRequest,
+            FetchCoinsByIdRequest, FetchCoinsBySymbolRequest, UpdateCoinsRequest,
+        },
+        connectivity::ConnectivityResponse,
+        errors::{ErrorCategory, ErrorPayload, TauriErrors},
+        indicators::{IndicatorSeriesResponse, IndicatorsResponse},
+        profiles::{ProfileRequest, ProfileResponse},
+        settings::{CreateSettingRequest, SettingResponse},
+        stream::{LivePriceResponse, LivePricesResponse},
+        transactions::TransactionResponse,
+        trash::DeletedCoinResponse,
+        usage::{
+            ApiUsageResponse, EndpointUsageResponse, GetApiUsageRequest, GetPlanInfoRequest,
+            PlanInfoResponse,
+        },
+        watchlists::{CreateWatchlistRequest, WatchlistResponse},
+    },
+    AppState,
+};
+
+use self::errors::HttpErrors;
+
+#[derive(OpenApi)]
+#[openapi(
+    info(title = "cryptoscan", description = "Tracked coins and settings of a running cryptoscan app"),
+    paths(
+        coins::create_coin,
+        coins::create_coins,
+        coins::update_coins,
+        coins::find_coin,
+        coins::get_coin_details,
+        coins::delete_coin,
+        coins::delete_all_coins,
+        coins::get_all_coins,
+        trash::get_deleted_coins,
+        trash::restore_coin,
+        trash::undo_delete,
+        coins::fetch_coins_by_id,
+        coins::fetch_coins_by_symbol,
+        catalog::search_catalog,
+        catalog::refresh_catalog,
+        catalog::add_top_coins,
+        candles::get_candles,
+        candles::backfill_candles,
+        indicators::get_indicators,
+        settings::create_setting,
+        settings::find_setting,
+        transfer::export_data,
+        transfer::import_data,
+        audit::get_audit_log,
+        transactions::import_transactions,
+        transactions::get_transactions,
+        transactions::delete_transaction,
+        usage::get_api_usage,
+        usage::get_plan_info,
+        api_keys::add_api_key,
+        api_keys::get_api_keys,
+        api_keys::set_api_key_enabled,
+        api_keys::test_api_key,
+        api_keys::delete_api_key,
+        api_keys::set_api_key,
+        profiles::get_profiles,
+        profiles::create_profile,
+        profiles::switch_profile,
+        profiles::delete_profile,
+        connectivity::get_connectivity,
+        stream::get_live_prices,
+        watchlists::create_watchlist,
+        watchlists::get_all_watchlists,
+        watchlists::find_watchlist,
+        watchlists::rename_watchlist,
+        watchlists::delete_watchlist,
+        watchlists::reorder_watchlist,
+        watchlists::add_watchlist_coin,
+        watchlists::remove_watchlist_coin,
+        watchlists::refresh_watchlist,
+    ),
+    components(schemas(
+        CreateCoinRequest,
+        CreateCoinsRequest,
+        UpdateCoinsRequest,
+        FetchCoinsByIdRequest,
+        FetchCoinsBySymbolRequest,
+        CoinResponse,
+        CoinDetailsResponse,
+        DeletedCoinResponse,
+        LinkKind,
+        CoinLink,
+        TokenContract,
+        SearchCatalogRequest,
+        AddTopCoinsRequest,
+        CatalogEntryResponse,
+        CatalogRefreshResponse,
+        CandleInterval,
+        CandleRange,
+        CandleResponse,
+        CandlesResponse,
+        IndicatorSpec,
+        indicators::IndicatorsBody,
+        IndicatorSeriesResponse,
+        IndicatorsResponse,
+        CreateSettingRequest,
+        SettingResponse,
+        CreateWatchlistRequest,
+        WatchlistResponse,
+        Dataset,
+        DataFormat,
+        ImportReport,
+        RowError,
+        AuditEntity,
+        Origin,
+        AuditRecordResponse,
+        Exchange,
+        TradeSide,
+        TransactionResponse,
+        TransactionImportReport,
+        GetApiUsageRequest,
+        GetPlanInfoRequest,
+        EndpointUsageResponse,
+        ApiUsageResponse,
+        PlanInfoResponse,
+        AddApiKeyRequest,
+        ApiKeyResponse,
+        ApiKeyTestResponse,
+        SetApiKeyRequest,
+        KeyValidationResponse,
+        ProfileRequest,
+        ProfileResponse,
+        ConnectivityResponse,
+        LivePriceResponse,
+        LivePricesResponse,
+        ErrorCategory,
+        ErrorPayload,
+    )),
+    modifiers(&BearerAuth)
+)]
+pub(crate) struct ApiDoc;
+
+struct BearerAuth;
+
+impl Modify for BearerAuth {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        if let Some(components) = openapi.components.as_mut() {
+            components.add_security_scheme(
+                "bearer",
+                SecurityScheme::Http(HttpBuilder::new().scheme(HttpAuthScheme::Bearer).build()),
+            );
+        }
+    }
+}
+
+pub(crate) fn router(state: Arc<AppState>) -> Router {
+    let api = Router::new()
+        .route(
+            "/coins",
+            get(coins::get_all_coins)
+                .post(coins::create_coin)
+                .delete(coins::delete_all_coins),
+        )
+        .route("/coins/batch", post(coins::create_coins))
+        .route("/coins/refresh", post(coins::update_coins))
+        .route("/coins/top", post(catalog::add_top_coins))
+        .route(
+            "/coins/:id",
+            get(coins::find_coin).delete(coins::delete_coin),
+        )
+        .route("/coins/:id/details", get(coins::get_coin_details))
+        .route("/coins/:id/candles", get(candles::get_candles))
+        .route(
+            "/coins/:id/candles/backfill",
+            post(candles::backfill_candles),
+        )
+        .route("/coins/:id/indicators", post(indicators::get_indicators))
+        .route("/trash", get(trash::get_deleted_coins))
+        .route("/trash/undo", post(trash::undo_delete))
+        .route("/trash/:id/restore", post(trash::restore_coin))
+        .route("/catalog", get(catalog::search_catalog))
+        .route("/catalog/refresh", post(catalog::refresh_catalog))
+        .route("/quotes/ids", post(coins::fetch_coins_by_id))
+        .route("/quotes/symbols", post(coins::fetch_coins_by_symbol))
+        .route("/usage", get(usage::get_api_usage))
+        .route("/usage/plan", get(usage::get_plan_info))
+        .route(
+            "/keys",
+            get(api_keys::get_api_keys).post(api_keys::add_api_key),
+        )
+        .route("/keys/:id", delete(api_keys::delete_api_key))
+        .route("/keys/:id/enabled", put(api_keys::set_api_key_enabled))
+        .route("/keys/:id/test", post(api_keys::test_api_key))
+        .route("/key", put(api_keys::set_api_key))
+        .route(
+            "/profiles",
+            get(profiles::get_profiles).post(profiles::create_profile),
+        )
+        .route("/profiles/active", put(profiles::switch_profile))
+        .route("/profiles/:name", delete(profiles::delete_profile))
+        .route("/connectivity", get(connectivity::get_connectivity))
+        .route("/prices/live", get(stream::get_live_prices))
+        .route("/settings", post(settings::create_setting))
+        .route("/settings/:key", get(settings::find_setting))
+        .route("/export/:dataset", get(transfer::export_data))
+        .route("/import/:dataset", post(transfer::import_data))
+        .route("/audit", get(audit::get_audit_log))
+        .route("/transactions", get(transactions::get_transactions))
+        .route(
+            "/transactions/import/:exchange",
+            post(transactions::import_transactions),
+        )
+        .route(
+            "/transactions/:id",
+            delete(transactions::delete_transaction),
+        )
+        .route(
+            "/watchlists",
+            get(watchlists::get_all_watchlists).post(watchlists::create_watchlist),
+        )
+        .route(
+            "/watchlists/:id",
+            get(watchlists::find_watchlist)
+                .put(watchlists::rename_watchlist)
+                .delete(watchlists::delete_watchlist),
+        )
+        .route("/watchlists/:id/coins", put(watchlists::reorder_watchlist))
+        .route(
+            "/watchlists/:id/coins/:coin_id",
+            put(watchlists::add_watchlist_coin).delete(watchlists::remove_watchlist_coin),
+        )
+        .route(
+            "/watchlists/:id/refresh",
+            post(watchlists::refresh_watchlist),
+        )
+        .route_layer(middleware::from_fn_with_state(state.clone(), authorize))
+        .route("/openapi.json", get(openapi));
+
+    Router::new().nest("/api", api).with_state(state)
+}
+
+/// Serves the API on localhost until the app exits. Does nothing unless the server is
+/// enabled in the configuration, and refuses to start without a bearer token.
+pub(crate) async fn serve(state: Arc<AppState>) -> Result<(), String> {
+    let server = &state.config.server;
+
+    if !server.enabled {
+        return Ok(());
+    }
+
+    if server.token.is_empty() {
+        return Err("HTTP server is enabled but server.token is not set".to_string());
+    }
+
+    let address = SocketAddr::from((Ipv4Addr::LOCALHOST, server.port));
+    let listener = tokio::net::TcpListener::bind(address)
+        .await
+        .map_err(|e| format!("Unable to bind HTTP server to {}: {}", address, e))?;
+
+    log::info!("HTTP server listening on http://{}", address);
+
+    axum::serve(listener, router(state.clone()))
+        .await
+        .map_err(|e| format!("HTTP server error: {}", e))
+}
+
+async fn openapi() -> Json<utoipa::openapi::OpenApi> {
+    Json(ApiDoc::openapi())
+}
+
+async fn authorize(
+    State(state): State<Arc<AppState>>,
+    request: Request,
+    next: Next,
+) -> Result<Response, HttpErrors> {
+    let token = request
+        .headers()
+        .get(AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "));
+
+    match token {
+        Some(token) if tokens_match(token, &state.config.server.token) => {
+            Ok(next.run(request).await)
+        }
+        _ => Err(HttpErrors::Unauthorized),
+    }
+}
+
+/// Compares every byte so the time taken does not reveal how much of the token was right.
+fn tokens_match(given: &str, expected: &str) -> bool {
+    given.len() == expected.len()
+        && given
+            .bytes()
+            .zip(expected.bytes())
+            .fold(0, |difference, (a, b)| difference | (a ^ b))
+            == 0
+}
+
+async fn api_key(state: &AppState) -> Result<String, HttpErrors> {
+    domain::provider::api_keys::find_token(state.sqlite_repo.clone())
+        .await
+        .map_err(|_| TauriErrors::TokenNotSpecified.into())
+}
@@ -0,0 +1,118 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::{Path, State},
+    Json,
+};
+use validator::Validate;
+
+use crate::{
+    domain,
+    driving::tauri::{
+        errors::TauriErrors,
+        profiles::{ProfileRequest, ProfileResponse},
+    },
+    AppState,
+};
+
+use super::errors::HttpErrors;
+
+#[utoipa::path(
+    get,
+    path = "/api/profiles",
+    tag = "profiles",
+    responses((status = 200, body = [ProfileResponse])),
+    security(("bearer" = []))
+)]
+pub(crate) async fn get_profiles(
+    State(state): State<Arc<AppState>>,
+) -> Result<Json<Vec<ProfileResponse>>, HttpErrors> {
+    match domain::profile::profiles::get_profiles(&state.config.profiles, &state.profile) {
+        Ok(profiles) => Ok(Json(
+            profiles.into_iter().map(ProfileResponse::from).collect(),
+        )),
+        Err(e) => Err(TauriErrors::from(e).into()),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/profiles",
+    tag = "profiles",
+    request_body = ProfileRequest,
+    responses(
+        (status = 200, body = ProfileResponse),
+        (status = 422, body = ErrorPayload),
+    ),
+    security(("bearer" = []))
+)]
+pub(crate) async fn create_profile(
+    State(state): State<Arc<AppState>>,
+    Json(request): Json<ProfileRequest>,
+) -> Result<Json<ProfileResponse>, HttpErrors> {
+    request.validate()?;
+
+    match domain::profile::profiles::create_profile(
+        &state.config.profiles,
+        &state.profile,
+        request.name,
+    ) {
+        Ok(profile) => Ok(Json(ProfileResponse::from(profile))),
+        Err(e) => Err(TauriErrors::from(e).into()),
+    }
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/profiles/active",
+    tag = "profiles",
+    request_body = ProfileRequest,
+    responses(
+        (status = 200, body = ProfileResponse),
+        (status = 404, body = ErrorPayload),
+        (status = 422, body = ErrorPayload),
+    ),
+    security(("bearer" = []))
+)]
+pub(crate) async fn switch_profile(
+    State(state): State<Arc<AppState>>,
+    Json(request): Json<ProfileRequest>,
+) -> Result<Json<ProfileResponse>, HttpErrors> {
+    request.validate()?;
+
+    match domain::profile::profiles::switch_profile(
+        state.sqlite_repo.clone(),
+        &state.events,
+        &state.connectivity,
+        &state.config.profiles,
+        &state.profile,
+        request.name,
+    )
+    .await
+    {
+        Ok(profile) => Ok(Json(ProfileResponse::from(profile))),
+        Err(e) => Err(TauriErrors::from(e).into()),
+    }
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/profiles/{name}",
+    tag = "profiles",
+    params(("name" = String, Path, description = "Profile name")),
+    responses(
+        (status = 200),
+        (status = 404, body = ErrorPayload),
+        (status = 422, body = ErrorPayload),
+    ),
+    security(("bearer" = []))
+)]
+pub(crate) async fn delete_profile(
+    State(state): State<Arc<AppState>>,
+    Path(name): Path<String>,
+) -> Result<(), HttpErrors> {
+    match domain::profile::profiles::delete_profile(&state.config.profiles, &state.profile, name) {
+        Ok(_) => Ok(()),
+        Err(e) => Err(TauriErrors::from(e).into()),
+    }
+}
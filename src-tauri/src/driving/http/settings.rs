@@ -0,0 +1,81 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::{Path, State},
+    Json,
+};
+use validator::Validate;
+
+use crate::{
+    domain::{
+        self, audit::audit::Origin, provider::api_key::mask_key, settings::settings::Setting,
+        transfer::export_data::SECRET_SETTINGS,
+    },
+    driving::tauri::{
+        errors::TauriErrors,
+        settings::{CreateSettingRequest, SettingResponse},
+    },
+    AppState,
+};
+
+use super::errors::HttpErrors;
+
+/// Secret settings like the provider key never leave the machine in full over HTTP.
+fn masked(setting: Setting) -> SettingResponse {
+    let mut response = SettingResponse::from(setting);
+    if SECRET_SETTINGS.contains(&response.key.as_str()) {
+        response.value = mask_key(&response.value);
+    }
+
+    response
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/settings",
+    tag = "settings",
+    request_body = CreateSettingRequest,
+    responses(
+        (status = 200, body = SettingResponse),
+        (status = 422, body = ErrorPayload),
+    ),
+    security(("bearer" = []))
+)]
+pub(crate) async fn create_setting(
+    State(state): State<Arc<AppState>>,
+    Json(request): Json<CreateSettingRequest>,
+) -> Result<Json<SettingResponse>, HttpErrors> {
+    request.validate()?;
+
+    match domain::settings::create_setting::create_setting(
+        state.sqlite_repo.clone(),
+        Origin::Http,
+        request,
+    )
+    .await
+    {
+        Ok(setting) => Ok(Json(masked(setting))),
+        Err(e) => Err(TauriErrors::from(e).into()),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/settings/{key}",
+    tag = "settings",
+    params(("key" = String, Path, description = "Setting key")),
+    responses(
+        (status = 200, body = SettingResponse),
+        (status = 404, body = ErrorPayload),
+    ),
+    security(("bearer" = []))
+)]
+pub(crate) async fn find_setting(
+    State(state): State<Arc<AppState>>,
+    Path(key): Path<String>,
+) -> Result<Json<SettingResponse>, HttpErrors> {
+    match domain::settings::find_setting::find_setting(state.sqlite_repo.clone(), key).await {
+        Ok(setting) => Ok(Json(masked(setting))),
+        Err(e) => Err(TauriErrors::from(e).into()),
+    }
+}
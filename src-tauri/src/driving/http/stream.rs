@@ -0,0 +1,20 @@
+use std::sync::Arc;
+
+use axum::{extract::State, Json};
+
+use crate::{driving::tauri::stream::LivePricesResponse, AppState};
+
+#[utoipa::path(
+    get,
+    path = "/api/prices/live",
+    tag = "prices",
+    responses(
+        (status = 200, body = LivePricesResponse),
+    ),
+    security(("bearer" = []))
+)]
+pub(crate) async fn get_live_prices(
+    State(state): State<Arc<AppState>>,
+) -> Json<LivePricesResponse> {
+    Json(LivePricesResponse::new(&state.config, &state.live_prices))
+}
@@ -0,0 +1,123 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::{Path, Query, State},
+    Json,
+};
+use serde::Deserialize;
+use validator::Validate;
+
+use crate::{
+    domain::{
+        self,
+        transaction::transaction::{Exchange, TransactionImportReport},
+    },
+    driving::tauri::{
+        errors::TauriErrors,
+        transactions::{GetTransactionsRequest, TransactionResponse},
+    },
+    AppState,
+};
+
+use super::errors::HttpErrors;
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct ImportQuery {
+    #[serde(default)]
+    dry_run: bool,
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/transactions/import/{exchange}",
+    tag = "transactions",
+    params(
+        ("exchange" = Exchange, Path, description = "Exchange the export comes from"),
+        ("dry_run" = Option<bool>, Query, description = "Parse and resolve symbols without storing"),
+    ),
+    request_body(content = String, description = "Trade-history CSV export", content_type = "text/plain"),
+    responses(
+        (status = 200, body = TransactionImportReport),
+        (status = 400, body = ErrorPayload),
+    ),
+    security(("bearer" = []))
+)]
+pub(crate) async fn import_transactions(
+    State(state): State<Arc<AppState>>,
+    Path(exchange): Path<Exchange>,
+    Query(query): Query<ImportQuery>,
+    content: String,
+) -> Result<Json<TransactionImportReport>, HttpErrors> {
+    let token = domain::provider::api_keys::find_token(state.sqlite_repo.clone())
+        .await
+        .ok();
+
+    match domain::transaction::import_transactions::import_transactions(
+        state.sqlite_repo.clone(),
+        &state.config,
+        token,
+        exchange,
+        &content,
+        query.dry_run,
+    )
+    .await
+    {
+        Ok(report) => Ok(Json(report)),
+        Err(e) => Err(TauriErrors::from(e).into()),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/transactions",
+    tag = "transactions",
+    params(
+        ("coin_id" = Option<u32>, Query, description = "Only trades with this coin as base or quote")
+    ),
+    responses((status = 200, body = [TransactionResponse])),
+    security(("bearer" = []))
+)]
+pub(crate) async fn get_transactions(
+    State(state): State<Arc<AppState>>,
+    Query(request): Query<GetTransactionsRequest>,
+) -> Result<Json<Vec<TransactionResponse>>, HttpErrors> {
+    request.validate()?;
+
+    match domain::transaction::get_transactions::get_transactions(
+        state.sqlite_repo.clone(),
+        request.coin_id,
+    )
+    .await
+    {
+        Ok(transactions) => Ok(Json(
+            transactions
+                .into_iter()
+                .map(TransactionResponse::from)
+                .collect(),
+        )),
+        Err(e) => Err(TauriErrors::from(e).into()),
+    }
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/transactions/{id}",
+    tag = "transactions",
+    params(("id" = u32, Path, description = "Transaction id")),
+    responses(
+        (status = 200),
+        (status = 404, body = ErrorPayload),
+    ),
+    security(("bearer" = []))
+)]
+pub(crate) async fn delete_transaction(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<u32>,
+) -> Result<(), HttpErrors> {
+    match domain::transaction::delete_transaction::delete_transaction(state.sqlite_repo.clone(), id)
+        .await
+    {
+        Ok(_) => Ok(()),
+        Err(e) => Err(TauriErrors::from(e).into()),
+    }
+}
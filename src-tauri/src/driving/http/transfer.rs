@@ -0,0 +1,104 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::{Path, Query, State},
+    http::header::CONTENT_TYPE,
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::Deserialize;
+
+use crate::{
+    domain::{
+        self,
+        audit::audit::Origin,
+        transfer::transfer::{DataFormat, Dataset, ImportReport},
+    },
+    driving::tauri::errors::TauriErrors,
+    AppState,
+};
+
+use super::errors::HttpErrors;
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct ExportQuery {
+    format: DataFormat,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct ImportQuery {
+    format: DataFormat,
+    #[serde(default)]
+    dry_run: bool,
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/export/{dataset}",
+    tag = "transfer",
+    params(
+        ("dataset" = Dataset, Path, description = "Data to export"),
+        ("format" = DataFormat, Query, description = "File format"),
+    ),
+    responses(
+        (status = 200, description = "CSV or JSON document", content_type = ["text/csv", "application/json"]),
+        (status = 400, body = ErrorPayload),
+    ),
+    security(("bearer" = []))
+)]
+pub(crate) async fn export_data(
+    State(state): State<Arc<AppState>>,
+    Path(dataset): Path<Dataset>,
+    Query(query): Query<ExportQuery>,
+) -> Result<Response, HttpErrors> {
+    match domain::transfer::export_data::export_data(
+        state.sqlite_repo.clone(),
+        dataset,
+        query.format,
+        // Secret settings never leave the machine in full over HTTP
+        false,
+    )
+    .await
+    {
+        Ok(content) => Ok(([(CONTENT_TYPE, query.format.content_type())], content).into_response()),
+        Err(e) => Err(TauriErrors::from(e).into()),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/import/{dataset}",
+    tag = "transfer",
+    params(
+        ("dataset" = Dataset, Path, description = "Data to import"),
+        ("format" = DataFormat, Query, description = "File format"),
+        ("dry_run" = Option<bool>, Query, description = "Validate without storing"),
+    ),
+    request_body(content = String, description = "CSV or JSON document", content_type = "text/plain"),
+    responses(
+        (status = 200, body = ImportReport),
+        (status = 400, body = ErrorPayload),
+    ),
+    security(("bearer" = []))
+)]
+pub(crate) async fn import_data(
+    State(state): State<Arc<AppState>>,
+    Path(dataset): Path<Dataset>,
+    Query(query): Query<ImportQuery>,
+    content: String,
+) -> Result<Json<ImportReport>, HttpErrors> {
+    match domain::transfer::import_data::import_data(
+        state.sqlite_repo.clone(),
+        &state.events,
+        Origin::Http,
+        dataset,
+        query.format,
+        &content,
+        query.dry_run,
+    )
+    .await
+    {
+        Ok(report) => Ok(Json(report)),
+        Err(e) => Err(TauriErrors::from(e).into()),
+    }
+}
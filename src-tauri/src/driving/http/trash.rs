@@ -0,0 +1,83 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::{Path, State},
+    Json,
+};
+
+use crate::{
+    domain::{self, audit::audit::Origin},
+    driving::tauri::{coins::CoinResponse, errors::TauriErrors, trash::DeletedCoinResponse},
+    AppState,
+};
+
+use super::errors::HttpErrors;
+
+#[utoipa::path(
+    get,
+    path = "/api/trash",
+    tag = "trash",
+    responses((status = 200, body = [DeletedCoinResponse])),
+    security(("bearer" = []))
+)]
+pub(crate) async fn get_deleted_coins(
+    State(state): State<Arc<AppState>>,
+) -> Result<Json<Vec<DeletedCoinResponse>>, HttpErrors> {
+    match domain::coin::get_deleted_coins::get_deleted_coins(state.sqlite_repo.clone()).await {
+        Ok(coins) => Ok(Json(
+            coins.into_iter().map(DeletedCoinResponse::from).collect(),
+        )),
+        Err(e) => Err(TauriErrors::from(e).into()),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/trash/{id}/restore",
+    tag = "trash",
+    params(("id" = u32, Path, description = "CoinMarketCap coin id")),
+    responses(
+        (status = 200, body = CoinResponse),
+        (status = 404, body = ErrorPayload),
+    ),
+    security(("bearer" = []))
+)]
+pub(crate) async fn restore_coin(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<u32>,
+) -> Result<Json<CoinResponse>, HttpErrors> {
+    match domain::coin::restore_coin::restore_coin(
+        state.sqlite_repo.clone(),
+        &state.events,
+        Origin::Http,
+        id,
+    )
+    .await
+    {
+        Ok(coin) => Ok(Json(CoinResponse::from(coin))),
+        Err(e) => Err(TauriErrors::from(e).into()),
+    }
+}
+
+/// Restores the coins of the most recent deletion; nothing when the trash is empty.
+#[utoipa::path(
+    post,
+    path = "/api/trash/undo",
+    tag = "trash",
+    responses((status = 200, body = [CoinResponse])),
+    security(("bearer" = []))
+)]
+pub(crate) async fn undo_delete(
+    State(state): State<Arc<AppState>>,
+) -> Result<Json<Vec<CoinResponse>>, HttpErrors> {
+    match domain::coin::restore_coin::undo_delete(
+        state.sqlite_repo.clone(),
+        &state.events,
+        Origin::Http,
+    )
+    .await
+    {
+        Ok(coins) => Ok(Json(coins.into_iter().map(CoinResponse::from).collect())),
+        Err(e) => Err(TauriErrors::from(e).into()),
+    }
+}
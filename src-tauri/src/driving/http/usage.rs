@@ -0,0 +1,81 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::{Query, State},
+    Json,
+};
+use validator::Validate;
+
+use crate::{
+    domain::{self, provider::provider::DEFAULT_USAGE_DAYS},
+    driving::tauri::{
+        errors::TauriErrors,
+        usage::{ApiUsageResponse, GetApiUsageRequest, GetPlanInfoRequest, PlanInfoResponse},
+    },
+    AppState,
+};
+
+use super::{api_key, errors::HttpErrors};
+
+#[utoipa::path(
+    get,
+    path = "/api/usage",
+    tag = "usage",
+    params(("days" = Option<u32>, Query, description = "UTC days back from today, 30 by default")),
+    responses(
+        (status = 200, body = ApiUsageResponse),
+        (status = 400, body = ErrorPayload),
+    ),
+    security(("bearer" = []))
+)]
+pub(crate) async fn get_api_usage(
+    State(state): State<Arc<AppState>>,
+    Query(request): Query<GetApiUsageRequest>,
+) -> Result<Json<ApiUsageResponse>, HttpErrors> {
+    request.validate()?;
+
+    match domain::provider::get_api_usage::get_api_usage(
+        state.sqlite_repo.clone(),
+        request.days.unwrap_or(DEFAULT_USAGE_DAYS),
+    )
+    .await
+    {
+        Ok(usage) => Ok(Json(ApiUsageResponse::new(
+            usage,
+            state.config.provider.daily_credits,
+        ))),
+        Err(e) => Err(TauriErrors::from(e).into()),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/usage/plan",
+    tag = "usage",
+    params(("refresh" = Option<bool>, Query, description = "Fetch the plan again even if it is stored")),
+    responses(
+        (status = 200, body = PlanInfoResponse),
+        (status = 401, body = ErrorPayload),
+        (status = 502, body = ErrorPayload),
+    ),
+    security(("bearer" = []))
+)]
+pub(crate) async fn get_plan_info(
+    State(state): State<Arc<AppState>>,
+    Query(request): Query<GetPlanInfoRequest>,
+) -> Result<Json<PlanInfoResponse>, HttpErrors> {
+    // A stored plan is served without a token
+    let token = api_key(&state).await.ok();
+
+    match domain::provider::get_plan_info::get_plan_info(
+        state.sqlite_repo.clone(),
+        &state.config,
+        token,
+        request.refresh.unwrap_or(false),
+    )
+    .await
+    {
+        Ok(plan) => Ok(Json(PlanInfoResponse::from(plan))),
+        Err(e) => Err(TauriErrors::from(e).into()),
+    }
+}
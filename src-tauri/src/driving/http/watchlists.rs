@@ -0,0 +1,279 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::{Path, State},
+    Json,
+};
+use validator::Validate;
+
+use crate::{
+    domain::{self, audit::audit::Origin},
+    driving::tauri::{
+        coins::CoinResponse,
+        errors::TauriErrors,
+        watchlists::{CreateWatchlistRequest, WatchlistResponse},
+    },
+    AppState,
+};
+
+use super::{api_key, errors::HttpErrors};
+
+#[utoipa::path(
+    post,
+    path = "/api/watchlists",
+    tag = "watchlists",
+    request_body = CreateWatchlistRequest,
+    responses(
+        (status = 200, body = WatchlistResponse),
+        (status = 422, body = ErrorPayload),
+    ),
+    security(("bearer" = []))
+)]
+pub(crate) async fn create_watchlist(
+    State(state): State<Arc<AppState>>,
+    Json(request): Json<CreateWatchlistRequest>,
+) -> Result<Json<WatchlistResponse>, HttpErrors> {
+    request.validate()?;
+
+    match domain::watchlist::create_watchlist::create_watchlist(
+        state.sqlite_repo.clone(),
+        &state.events,
+        request,
+    )
+    .await
+    {
+        Ok(watchlist) => Ok(Json(WatchlistResponse::from(watchlist))),
+        Err(e) => Err(TauriErrors::from(e).into()),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/watchlists",
+    tag = "watchlists",
+    responses((status = 200, body = [WatchlistResponse])),
+    security(("bearer" = []))
+)]
+pub(crate) async fn get_all_watchlists(
+    State(state): State<Arc<AppState>>,
+) -> Result<Json<Vec<WatchlistResponse>>, HttpErrors> {
+    match domain::watchlist::get_all_watchlists::get_all_watchlists(state.sqlite_repo.clone()).await
+    {
+        Ok(watchlists) => Ok(Json(
+            watchlists
+                .into_iter()
+                .map(WatchlistResponse::from)
+                .collect(),
+        )),
+        Err(e) => Err(TauriErrors::from(e).into()),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/watchlists/{id}",
+    tag = "watchlists",
+    params(("id" = u32, Path, description = "Watchlist id")),
+    responses(
+        (status = 200, body = WatchlistResponse),
+        (status = 404, body = ErrorPayload),
+    ),
+    security(("bearer" = []))
+)]
+pub(crate) async fn find_watchlist(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<u32>,
+) -> Result<Json<WatchlistResponse>, HttpErrors> {
+    match domain::watchlist::find_watchlist::find_watchlist(state.sqlite_repo.clone(), id).await {
+        Ok(watchlist) => Ok(Json(WatchlistResponse::from(watchlist))),
+        Err(e) => Err(TauriErrors::from(e).into()),
+    }
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/watchlists/{id}",
+    tag = "watchlists",
+    params(("id" = u32, Path, description = "Watchlist id")),
+    request_body = CreateWatchlistRequest,
+    responses(
+        (status = 200, body = WatchlistResponse),
+        (status = 404, body = ErrorPayload),
+        (status = 422, body = ErrorPayload),
+    ),
+    security(("bearer" = []))
+)]
+pub(crate) async fn rename_watchlist(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<u32>,
+    Json(request): Json<CreateWatchlistRequest>,
+) -> Result<Json<WatchlistResponse>, HttpErrors> {
+    request.validate()?;
+
+    match domain::watchlist::rename_watchlist::rename_watchlist(
+        state.sqlite_repo.clone(),
+        &state.events,
+        id,
+        request.name,
+    )
+    .await
+    {
+        Ok(watchlist) => Ok(Json(WatchlistResponse::from(watchlist))),
+        Err(e) => Err(TauriErrors::from(e).into()),
+    }
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/watchlists/{id}",
+    tag = "watchlists",
+    params(("id" = u32, Path, description = "Watchlist id")),
+    responses(
+        (status = 200),
+        (status = 404, body = ErrorPayload),
+    ),
+    security(("bearer" = []))
+)]
+pub(crate) async fn delete_watchlist(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<u32>,
+) -> Result<(), HttpErrors> {
+    match domain::watchlist::delete_watchlist::delete_watchlist(
+        state.sqlite_repo.clone(),
+        &state.events,
+        id,
+    )
+    .await
+    {
+        Ok(_) => Ok(()),
+        Err(e) => Err(TauriErrors::from(e).into()),
+    }
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/watchlists/{id}/coins",
+    tag = "watchlists",
+    params(("id" = u32, Path, description = "Watchlist id")),
+    request_body(content = [u32], description = "Every coin id of the watchlist, in the new order"),
+    responses(
+        (status = 200, body = WatchlistResponse),
+        (status = 404, body = ErrorPayload),
+        (status = 422, body = ErrorPayload),
+    ),
+    security(("bearer" = []))
+)]
+pub(crate) async fn reorder_watchlist(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<u32>,
+    Json(coin_ids): Json<Vec<u32>>,
+) -> Result<Json<WatchlistResponse>, HttpErrors> {
+    match domain::watchlist::watchlist_coins::reorder_coins(
+        state.sqlite_repo.clone(),
+        &state.events,
+        id,
+        coin_ids,
+    )
+    .await
+    {
+        Ok(watchlist) => Ok(Json(WatchlistResponse::from(watchlist))),
+        Err(e) => Err(TauriErrors::from(e).into()),
+    }
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/watchlists/{id}/coins/{coin_id}",
+    tag = "watchlists",
+    params(
+        ("id" = u32, Path, description = "Watchlist id"),
+        ("coin_id" = u32, Path, description = "Tracked coin id"),
+    ),
+    responses(
+        (status = 200, body = WatchlistResponse),
+        (status = 404, body = ErrorPayload),
+    ),
+    security(("bearer" = []))
+)]
+pub(crate) async fn add_watchlist_coin(
+    State(state): State<Arc<AppState>>,
+    Path((id, coin_id)): Path<(u32, u32)>,
+) -> Result<Json<WatchlistResponse>, HttpErrors> {
+    match domain::watchlist::watchlist_coins::add_coin(
+        state.sqlite_repo.clone(),
+        &state.events,
+        id,
+        coin_id,
+    )
+    .await
+    {
+        Ok(watchlist) => Ok(Json(WatchlistResponse::from(watchlist))),
+        Err(e) => Err(TauriErrors::from(e).into()),
+    }
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/watchlists/{id}/coins/{coin_id}",
+    tag = "watchlists",
+    params(
+        ("id" = u32, Path, description = "Watchlist id"),
+        ("coin_id" = u32, Path, description = "Tracked coin id"),
+    ),
+    responses(
+        (status = 200, body = WatchlistResponse),
+        (status = 404, body = ErrorPayload),
+    ),
+    security(("bearer" = []))
+)]
+pub(crate) async fn remove_watchlist_coin(
+    State(state): State<Arc<AppState>>,
+    Path((id, coin_id)): Path<(u32, u32)>,
+) -> Result<Json<WatchlistResponse>, HttpErrors> {
+    match domain::watchlist::watchlist_coins::remove_coin(
+        state.sqlite_repo.clone(),
+        &state.events,
+        id,
+        coin_id,
+    )
+    .await
+    {
+        Ok(watchlist) => Ok(Json(WatchlistResponse::from(watchlist))),
+        Err(e) => Err(TauriErrors::from(e).into()),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/watchlists/{id}/refresh",
+    tag = "watchlists",
+    params(("id" = u32, Path, description = "Watchlist id")),
+    responses(
+        (status = 200, body = [CoinResponse]),
+        (status = 401, body = ErrorPayload),
+        (status = 404, body = ErrorPayload),
+        (status = 502, body = ErrorPayload),
+    ),
+    security(("bearer" = []))
+)]
+pub(crate) async fn refresh_watchlist(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<u32>,
+) -> Result<Json<Vec<CoinResponse>>, HttpErrors> {
+    let token = api_key(&state).await?;
+
+    match domain::coin::refresh_coins::refresh_watchlist(
+        state.sqlite_repo.clone(),
+        &state.events,
+        Origin::Http,
+        &state.config,
+        &state.connectivity,
+        id,
+        token,
+    )
+    .await
+    {
+        Ok(coins) => Ok(Json(coins.into_iter().map(CoinResponse::from).collect())),
+        Err(e) => Err(TauriErrors::from(e).into()),
+    }
+}
@@ -1 +1,3 @@
+pub(crate) mod cli;
+pub(crate) mod http;
 pub(crate) mod tauri;
\ No newline at end of file
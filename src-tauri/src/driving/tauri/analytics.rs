@@ -0,0 +1,200 @@
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use tauri::State;
+use ts_rs::TS;
+use utoipa::ToSchema;
+use validator::Validate;
+
+use crate::{
+    domain::{
+        self,
+        analytics::analytics::{
+            CoinRisk, CorrelationMatrix, Holding, PortfolioRisk, DEFAULT_CONFIDENCE,
+        },
+        candle::candle::{resolve_range, CandleInterval, CandleRange},
+    },
+    AppState,
+};
+
+use super::errors::TauriErrors;
+
+/// Resolves an optional daily range like `GetCandlesRequest::range`.
+fn daily_range(start: Option<i64>, end: Option<i64>) -> Result<CandleRange, TauriErrors> {
+    resolve_range(
+        CandleInterval::Daily,
+        start,
+        end,
+        chrono::Utc::now().timestamp(),
+    )
+    .map_err(|e| TauriErrors::InvalidData(e.to_string()))
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema, Validate, TS)]
+#[ts(export, export_to = "../src/bindings/")]
+pub struct GetCorrelationsRequest {
+    #[validate(length(min = 2, max = 50, message = "coin_ids must have between 2 and 50 ids"))]
+    pub coin_ids: Vec<u32>,
+    /// Unix seconds; defaults to 90 days before `end`
+    #[ts(type = "number | null")]
+    pub start: Option<i64>,
+    /// Unix seconds; defaults to the last closed day
+    #[ts(type = "number | null")]
+    pub end: Option<i64>,
+}
+
+/// Correlations of daily returns; `matrix[i][j]` pairs `coin_ids[i]` and `coin_ids[j]`.
+#[derive(Clone, Debug, Serialize, PartialEq, ToSchema, TS)]
+#[ts(export, export_to = "../src/bindings/")]
+pub struct CorrelationsResponse {
+    pub coin_ids: Vec<u32>,
+    pub range: CandleRange,
+    pub observations: usize,
+    pub matrix: Vec<Vec<Option<f64>>>,
+}
+
+impl From<CorrelationMatrix> for CorrelationsResponse {
+    fn from(matrix: CorrelationMatrix) -> Self {
+        CorrelationsResponse {
+            coin_ids: matrix.coin_ids,
+            range: matrix.range,
+            observations: matrix.observations,
+            matrix: matrix.matrix,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema, Validate, TS)]
+#[ts(export, export_to = "../src/bindings/")]
+pub struct HoldingRequest {
+    #[validate(range(min = 1, message = "coin_id must be greater than 0"))]
+    pub coin_id: u32,
+    /// Relative weight, e.g. the value held; weights are scaled to sum to 1
+    pub weight: f64,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema, Validate, TS)]
+#[ts(export, export_to = "../src/bindings/")]
+pub struct GetPortfolioRiskRequest {
+    #[validate(length(
+        min = 1,
+        max = 50,
+        message = "holdings must have between 1 and 50 coins"
+    ))]
+    #[validate]
+    pub holdings: Vec<HoldingRequest>,
+    /// Unix seconds; defaults to 90 days before `end`
+    #[ts(type = "number | null")]
+    pub start: Option<i64>,
+    /// Unix seconds; defaults to the last closed day
+    #[ts(type = "number | null")]
+    pub end: Option<i64>,
+    /// Value-at-Risk confidence level; defaults to 0.95
+    pub confidence: Option<f64>,
+    /// Annual risk-free rate for the Sharpe ratio; defaults to 0
+    pub risk_free_rate: Option<f64>,
+}
+
+#[derive(Clone, Debug, Serialize, PartialEq, ToSchema, TS)]
+#[ts(export, export_to = "../src/bindings/")]
+pub struct CoinRiskResponse {
+    pub coin_id: u32,
+    pub weight: f64,
+    pub volatility: Option<f64>,
+    pub beta: Option<f64>,
+}
+
+impl From<CoinRisk> for CoinRiskResponse {
+    fn from(risk: CoinRisk) -> Self {
+        CoinRiskResponse {
+            coin_id: risk.coin_id,
+            weight: risk.weight,
+            volatility: risk.volatility,
+            beta: risk.beta,
+        }
+    }
+}
+
+/// Annualized volatility and Sharpe ratio, beta to BTC and one-day Value-at-Risk as a
+/// fraction of the portfolio value. Figures are `null` when the prices did not move or no
+/// BTC candles are stored.
+#[derive(Clone, Debug, Serialize, PartialEq, ToSchema, TS)]
+#[ts(export, export_to = "../src/bindings/")]
+pub struct PortfolioRiskResponse {
+    pub range: CandleRange,
+    pub observations: usize,
+    pub confidence: f64,
+    pub risk_free_rate: f64,
+    pub volatility: Option<f64>,
+    pub beta: Option<f64>,
+    pub historical_var: Option<f64>,
+    pub parametric_var: Option<f64>,
+    pub sharpe_ratio: Option<f64>,
+    pub coins: Vec<CoinRiskResponse>,
+}
+
+impl From<PortfolioRisk> for PortfolioRiskResponse {
+    fn from(risk: PortfolioRisk) -> Self {
+        PortfolioRiskResponse {
+            range: risk.range,
+            observations: risk.observations,
+            confidence: risk.confidence,
+            risk_free_rate: risk.risk_free_rate,
+            volatility: risk.volatility,
+            beta: risk.beta,
+            historical_var: risk.historical_var,
+            parametric_var: risk.parametric_var,
+            sharpe_ratio: risk.sharpe_ratio,
+            coins: risk.coins.into_iter().map(CoinRiskResponse::from).collect(),
+        }
+    }
+}
+
+#[tauri::command]
+pub(crate) async fn get_correlations(
+    request: GetCorrelationsRequest,
+    state: State<'_, Arc<AppState>>,
+) -> Result<CorrelationsResponse, TauriErrors> {
+    request.validate()?;
+
+    match domain::analytics::get_correlations::get_correlations(
+        state.sqlite_repo.clone(),
+        request.coin_ids,
+        daily_range(request.start, request.end)?,
+    )
+    .await
+    {
+        Ok(matrix) => Ok(CorrelationsResponse::from(matrix)),
+        Err(e) => Err(TauriErrors::from(e)),
+    }
+}
+
+#[tauri::command]
+pub(crate) async fn get_portfolio_risk(
+    request: GetPortfolioRiskRequest,
+    state: State<'_, Arc<AppState>>,
+) -> Result<PortfolioRiskResponse, TauriErrors> {
+    request.validate()?;
+
+    let holdings = request
+        .holdings
+        .iter()
+        .map(|holding| Holding {
+            coin_id: holding.coin_id,
+            weight: holding.weight,
+        })
+        .collect();
+
+    match domain::analytics::get_portfolio_risk::get_portfolio_risk(
+        state.sqlite_repo.clone(),
+        holdings,
+        daily_range(request.start, request.end)?,
+        request.confidence.unwrap_or(DEFAULT_CONFIDENCE),
+        request.risk_free_rate.unwrap_or(0.0),
+    )
+    .await
+    {
+        Ok(risk) => Ok(PortfolioRiskResponse::from(risk)),
+        Err(e) => Err(TauriErrors::from(e)),
+    }
+}
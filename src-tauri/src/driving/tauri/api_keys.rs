@@ -0,0 +1,259 @@
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use tauri::State;
+use ts_rs::TS;
+use utoipa::ToSchema;
+use validator::Validate;
+
+use crate::{
+    domain::{
+        self,
+        audit::audit::Origin,
+        provider::{
+            api_key::{mask_key, ApiKey},
+            api_keys::KeyTest,
+            set_api_key::KeyValidation,
+        },
+    },
+    AppState,
+};
+
+use super::{errors::TauriErrors, usage::PlanInfoResponse};
+
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema, Validate, TS)]
+#[ts(export, export_to = "../src/bindings/")]
+pub struct AddApiKeyRequest {
+    #[validate(length(
+        min = 1,
+        max = 255,
+        message = "label must be between 1 and 255 characters"
+    ))]
+    pub label: String,
+    #[validate(length(min = 1, message = "key must not be empty"))]
+    pub key: String,
+    /// The provider plan of the key, e.g. `basic`
+    pub plan: Option<String>,
+    /// Credits that may be spent per month with the key, unlimited when missing or 0
+    pub monthly_credits: Option<u32>,
+    /// Keys with a lower priority are tried first, 0 by default
+    pub priority: Option<u32>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema, Validate, TS)]
+#[ts(export, export_to = "../src/bindings/")]
+pub struct ApiKeyRequest {
+    #[validate(range(min = 1, message = "id must be greater than 0"))]
+    pub id: u32,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema, Validate, TS)]
+#[ts(export, export_to = "../src/bindings/")]
+pub struct SetApiKeyEnabledRequest {
+    #[validate(range(min = 1, message = "id must be greater than 0"))]
+    pub id: u32,
+    pub enabled: bool,
+}
+
+#[derive(Clone, Debug, Serialize, PartialEq, ToSchema, TS)]
+#[ts(export, export_to = "../src/bindings/")]
+pub struct ApiKeyResponse {
+    pub id: u32,
+    pub label: String,
+    /// Only the last four characters of the key
+    pub key: String,
+    pub plan: String,
+    pub monthly_credits: Option<u32>,
+    pub priority: u32,
+    pub enabled: bool,
+    /// `active`, `invalid` or `exhausted`
+    pub status: String,
+    pub status_message: Option<String>,
+    /// When an exhausted key is used again
+    pub available_at: Option<String>,
+    /// Credits spent with the key this month
+    pub credits_used: u32,
+    pub last_used_at: Option<String>,
+}
+
+impl From<ApiKey> for ApiKeyResponse {
+    fn from(key: ApiKey) -> Self {
+        ApiKeyResponse {
+            credits_used: key.credits_used_in(chrono::Utc::now().timestamp()),
+            id: key.id,
+            label: key.label,
+            key: mask_key(&key.key),
+            plan: key.plan,
+            monthly_credits: key.monthly_credits,
+            priority: key.priority,
+            enabled: key.enabled,
+            status: key.status.to_string(),
+            status_message: key.status_message,
+            available_at: key.available_at.and_then(format_time),
+            last_used_at: key.last_used_at.and_then(format_time),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, PartialEq, ToSchema, TS)]
+#[ts(export, export_to = "../src/bindings/")]
+pub struct ApiKeyTestResponse {
+    pub key: ApiKeyResponse,
+    /// Whether the provider accepted the key
+    pub valid: bool,
+    /// Why the provider refused the key
+    pub message: Option<String>,
+    pub plan: Option<PlanInfoResponse>,
+}
+
+impl From<KeyTest> for ApiKeyTestResponse {
+    fn from(test: KeyTest) -> Self {
+        ApiKeyTestResponse {
+            key: ApiKeyResponse::from(test.key),
+            valid: test.error.is_none(),
+            message: test.error,
+            plan: test.plan.map(PlanInfoResponse::from),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema, Validate, TS)]
+#[ts(export, export_to = "../src/bindings/")]
+pub struct SetApiKeyRequest {
+    #[validate(length(min = 1, message = "key must not be empty"))]
+    pub key: String,
+}
+
+#[derive(Clone, Debug, Serialize, PartialEq, ToSchema, TS)]
+#[ts(export, export_to = "../src/bindings/")]
+pub struct KeyValidationResponse {
+    /// `valid`, `invalid`, `expired` or `limit_reached`
+    pub validity: String,
+    /// Why the provider refused the key or limited it
+    pub message: Option<String>,
+    /// When the limit the key reached resets
+    pub available_at: Option<String>,
+    pub plan: Option<PlanInfoResponse>,
+    /// Whether the key was saved; refused keys are not
+    pub stored: bool,
+}
+
+impl From<KeyValidation> for KeyValidationResponse {
+    fn from(validation: KeyValidation) -> Self {
+        KeyValidationResponse {
+            validity: validation.validity.as_str().to_string(),
+            message: validation.message,
+            available_at: validation.available_at.and_then(format_time),
+            plan: validation.plan.map(PlanInfoResponse::from),
+            stored: validation.stored,
+        }
+    }
+}
+
+fn format_time(seconds: i64) -> Option<String> {
+    chrono::DateTime::from_timestamp(seconds, 0)
+        .map(|time| time.to_rfc3339_opts(chrono::SecondsFormat::Secs, true))
+}
+
+#[tauri::command]
+pub(crate) async fn add_api_key(
+    request: AddApiKeyRequest,
+    state: State<'_, Arc<AppState>>,
+) -> Result<ApiKeyResponse, TauriErrors> {
+    request.validate()?;
+
+    match domain::provider::api_keys::add_api_key(
+        state.sqlite_repo.clone(),
+        request.label,
+        request.key,
+        request.plan,
+        request.monthly_credits,
+        request.priority,
+    )
+    .await
+    {
+        Ok(key) => Ok(ApiKeyResponse::from(key)),
+        Err(e) => Err(TauriErrors::from(e)),
+    }
+}
+
+#[tauri::command]
+pub(crate) async fn get_api_keys(
+    state: State<'_, Arc<AppState>>,
+) -> Result<Vec<ApiKeyResponse>, TauriErrors> {
+    match domain::provider::api_keys::get_api_keys(state.sqlite_repo.clone()).await {
+        Ok(keys) => Ok(keys.into_iter().map(ApiKeyResponse::from).collect()),
+        Err(e) => Err(TauriErrors::from(e)),
+    }
+}
+
+#[tauri::command]
+pub(crate) async fn set_api_key_enabled(
+    request: SetApiKeyEnabledRequest,
+    state: State<'_, Arc<AppState>>,
+) -> Result<ApiKeyResponse, TauriErrors> {
+    request.validate()?;
+
+    match domain::provider::api_keys::set_api_key_enabled(
+        state.sqlite_repo.clone(),
+        request.id,
+        request.enabled,
+    )
+    .await
+    {
+        Ok(key) => Ok(ApiKeyResponse::from(key)),
+        Err(e) => Err(TauriErrors::from(e)),
+    }
+}
+
+#[tauri::command]
+pub(crate) async fn test_api_key(
+    request: ApiKeyRequest,
+    state: State<'_, Arc<AppState>>,
+) -> Result<ApiKeyTestResponse, TauriErrors> {
+    request.validate()?;
+
+    match domain::provider::api_keys::test_api_key(
+        state.sqlite_repo.clone(),
+        &state.config,
+        request.id,
+    )
+    .await
+    {
+        Ok(test) => Ok(ApiKeyTestResponse::from(test)),
+        Err(e) => Err(TauriErrors::from(e)),
+    }
+}
+
+#[tauri::command]
+pub(crate) async fn delete_api_key(
+    request: ApiKeyRequest,
+    state: State<'_, Arc<AppState>>,
+) -> Result<(), TauriErrors> {
+    request.validate()?;
+
+    match domain::provider::api_keys::delete_api_key(state.sqlite_repo.clone(), request.id).await {
+        Ok(_) => Ok(()),
+        Err(e) => Err(TauriErrors::from(e)),
+    }
+}
+
+#[tauri::command]
+pub(crate) async fn set_api_key(
+    request: SetApiKeyRequest,
+    state: State<'_, Arc<AppState>>,
+) -> Result<KeyValidationResponse, TauriErrors> {
+    request.validate()?;
+
+    match domain::provider::set_api_key::set_api_key(
+        state.sqlite_repo.clone(),
+        Origin::Desktop,
+        &state.config,
+        request.key,
+    )
+    .await
+    {
+        Ok(validation) => Ok(KeyValidationResponse::from(validation)),
+        Err(e) => Err(TauriErrors::from(e)),
+    }
+}
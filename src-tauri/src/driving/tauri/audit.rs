@@ -0,0 +1,99 @@
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use tauri::State;
+use ts_rs::TS;
+use utoipa::ToSchema;
+use validator::Validate;
+
+use crate::{
+    domain::{
+        self,
+        audit::audit::{AuditEntity, AuditFilter, AuditRecord, Origin, DEFAULT_AUDIT_LIMIT},
+    },
+    AppState,
+};
+
+use super::errors::TauriErrors;
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize, ToSchema, Validate, TS)]
+#[ts(export, export_to = "../src/bindings/")]
+pub struct GetAuditLogRequest {
+    pub entity: Option<AuditEntity>,
+    /// Coin id or setting key; best combined with `entity`
+    #[validate(length(min = 1, message = "entity_id must not be empty"))]
+    pub entity_id: Option<String>,
+    /// Unix seconds, inclusive
+    #[ts(type = "number | null")]
+    pub start: Option<i64>,
+    /// Unix seconds, inclusive
+    #[ts(type = "number | null")]
+    pub end: Option<i64>,
+    /// Defaults to 100
+    #[validate(range(min = 1, max = 1000, message = "limit must be between 1 and 1000"))]
+    pub limit: Option<u32>,
+}
+
+impl From<GetAuditLogRequest> for AuditFilter {
+    fn from(request: GetAuditLogRequest) -> Self {
+        AuditFilter {
+            entity: request.entity,
+            entity_id: request.entity_id,
+            start: request.start,
+            end: request.end,
+            limit: request.limit.unwrap_or(DEFAULT_AUDIT_LIMIT),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, PartialEq, ToSchema, TS)]
+#[ts(export, export_to = "../src/bindings/")]
+pub struct AuditRecordResponse {
+    pub id: u32,
+    /// Unix seconds
+    #[ts(type = "number")]
+    pub recorded_at: i64,
+    /// The use case that made the change, e.g. `delete_coin`
+    pub operation: String,
+    pub entity: AuditEntity,
+    pub entity_id: String,
+    /// JSON snapshot before the change, missing for created entities
+    pub before: Option<String>,
+    /// JSON snapshot after the change, missing for deleted entities
+    pub after: Option<String>,
+    pub origin: Origin,
+}
+
+impl From<AuditRecord> for AuditRecordResponse {
+    fn from(record: AuditRecord) -> Self {
+        AuditRecordResponse {
+            id: record.id,
+            recorded_at: record.recorded_at,
+            operation: record.operation,
+            entity: record.entity,
+            entity_id: record.entity_id,
+            before: record.before,
+            after: record.after,
+            origin: record.origin,
+        }
+    }
+}
+
+#[tauri::command]
+pub(crate) async fn get_audit_log(
+    request: Option<GetAuditLogRequest>,
+    state: State<'_, Arc<AppState>>,
+) -> Result<Vec<AuditRecordResponse>, TauriErrors> {
+    let request = request.unwrap_or_default();
+    request.validate()?;
+
+    match domain::audit::get_audit_log::get_audit_log(
+        state.sqlite_repo.clone(),
+        AuditFilter::from(request),
+    )
+    .await
+    {
+        Ok(records) => Ok(records.into_iter().map(AuditRecordResponse::from).collect()),
+        Err(e) => Err(TauriErrors::from(e)),
+    }
+}
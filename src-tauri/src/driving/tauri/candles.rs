@@ -0,0 +1,197 @@
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use tauri::State;
+use ts_rs::TS;
+use utoipa::ToSchema;
+use validator::Validate;
+
+use crate::{
+    domain::{
+        self,
+        candle::candle::{resolve_range, Candle, CandleInterval, CandleRange, CandleSeries, Ohlcv},
+    },
+    AppState,
+};
+
+use super::errors::TauriErrors;
+
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema, Validate, TS)]
+#[ts(export, export_to = "../src/bindings/")]
+pub struct GetCandlesRequest {
+    #[validate(range(min = 1, message = "coin_id must be greater than 0"))]
+    pub coin_id: u32,
+    pub interval: CandleInterval,
+    /// Unix seconds; defaults to 90 candles before `end`
+    #[ts(type = "number | null")]
+    pub start: Option<i64>,
+    /// Unix seconds; defaults to the last closed candle
+    #[ts(type = "number | null")]
+    pub end: Option<i64>,
+}
+
+impl GetCandlesRequest {
+    pub(crate) fn range(&self) -> Result<CandleRange, TauriErrors> {
+        resolve_range(
+            self.interval,
+            self.start,
+            self.end,
+            chrono::Utc::now().timestamp(),
+        )
+        .map_err(|e| TauriErrors::InvalidData(e.to_string()))
+    }
+}
+
+#[derive(Clone, Debug, Serialize, PartialEq, ToSchema, TS)]
+#[ts(export, export_to = "../src/bindings/")]
+pub struct CandleResponse {
+    #[ts(type = "number")]
+    pub open_time: i64,
+    pub open: f64,
+    pub high: f64,
+    pub low: f64,
+    pub close: f64,
+    pub volume: f64,
+}
+
+impl From<Candle> for CandleResponse {
+    fn from(candle: Candle) -> Self {
+        let ohlcv = candle.ohlcv();
+
+        CandleResponse {
+            open_time: candle.open_time(),
+            open: ohlcv.open,
+            high: ohlcv.high,
+            low: ohlcv.low,
+            close: ohlcv.close,
+            volume: ohlcv.volume,
+        }
+    }
+}
+
+/// A stored candle as exported and imported with the `candles` dataset.
+#[derive(Clone, Debug, Deserialize, Serialize, Validate)]
+pub struct CandleRow {
+    #[validate(range(min = 1, message = "coin_id must be greater than 0"))]
+    pub coin_id: u32,
+    pub interval: CandleInterval,
+    /// Unix seconds, aligned to the interval
+    pub open_time: i64,
+    pub open: f64,
+    pub high: f64,
+    pub low: f64,
+    pub close: f64,
+    pub volume: f64,
+}
+
+impl From<Candle> for CandleRow {
+    fn from(candle: Candle) -> Self {
+        let ohlcv = candle.ohlcv();
+
+        CandleRow {
+            coin_id: candle.coin_id(),
+            interval: candle.interval(),
+            open_time: candle.open_time(),
+            open: ohlcv.open,
+            high: ohlcv.high,
+            low: ohlcv.low,
+            close: ohlcv.close,
+            volume: ohlcv.volume,
+        }
+    }
+}
+
+impl TryFrom<CandleRow> for Candle {
+    type Error = &'static str;
+
+    fn try_from(row: CandleRow) -> Result<Self, Self::Error> {
+        Candle::new(
+            row.coin_id,
+            row.interval,
+            row.open_time,
+            Ohlcv {
+                open: row.open,
+                high: row.high,
+                low: row.low,
+                close: row.close,
+                volume: row.volume,
+            },
+        )
+    }
+}
+
+/// Candles of one coin, oldest first, with the ranges of the request that have no stored
+/// candles.
+#[derive(Clone, Debug, Serialize, PartialEq, ToSchema, TS)]
+#[ts(export, export_to = "../src/bindings/")]
+pub struct CandlesResponse {
+    pub coin_id: u32,
+    pub interval: CandleInterval,
+    pub range: CandleRange,
+    pub candles: Vec<CandleResponse>,
+    pub gaps: Vec<CandleRange>,
+}
+
+impl From<CandleSeries> for CandlesResponse {
+    fn from(series: CandleSeries) -> Self {
+        CandlesResponse {
+            coin_id: series.coin_id,
+            interval: series.interval,
+            range: series.range,
+            candles: series
+                .candles
+                .into_iter()
+                .map(CandleResponse::from)
+                .collect(),
+            gaps: series.gaps,
+        }
+    }
+}
+
+#[tauri::command]
+pub(crate) async fn get_candles(
+    request: GetCandlesRequest,
+    state: State<'_, Arc<AppState>>,
+) -> Result<CandlesResponse, TauriErrors> {
+    request.validate()?;
+
+    match domain::candle::get_candles::get_candles(
+        state.sqlite_repo.clone(),
+        request.coin_id,
+        request.interval,
+        request.range()?,
+    )
+    .await
+    {
+        Ok(series) => Ok(CandlesResponse::from(series)),
+        Err(e) => Err(TauriErrors::from(e)),
+    }
+}
+
+#[tauri::command]
+pub(crate) async fn backfill_candles(
+    request: GetCandlesRequest,
+    state: State<'_, Arc<AppState>>,
+) -> Result<CandlesResponse, TauriErrors> {
+    request.validate()?;
+
+    let token_result = domain::provider::api_keys::find_token(state.sqlite_repo.clone()).await;
+
+    let Ok(token) = token_result else {
+        return Err(TauriErrors::TokenNotSpecified);
+    };
+
+    match domain::candle::backfill_candles::backfill_candles(
+        state.sqlite_repo.clone(),
+        &state.config,
+        token,
+        request.coin_id,
+        request.interval,
+        request.range()?,
+    )
+    .await
+    {
+        Ok(series) => Ok(CandlesResponse::from(series)),
+        Err(e) => Err(TauriErrors::from(e)),
+    }
+}
@@ -0,0 +1,142 @@
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use tauri::State;
+use ts_rs::TS;
+use utoipa::ToSchema;
+use validator::Validate;
+
+use crate::{
+    domain::{
+        self,
+        audit::audit::Origin,
+        catalog::catalog::{CatalogMatch, DEFAULT_SEARCH_LIMIT},
+    },
+    AppState,
+};
+
+use super::{coins::CoinResponse, errors::TauriErrors};
+
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema, Validate, TS)]
+#[ts(export, export_to = "../src/bindings/")]
+pub struct SearchCatalogRequest {
+    #[validate(length(
+        min = 1,
+        max = 100,
+        message = "query must be between 1 and 100 characters"
+    ))]
+    pub query: String,
+    /// Defaults to 20
+    #[validate(range(min = 1, max = 100, message = "limit must be between 1 and 100"))]
+    pub limit: Option<u32>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema, Validate, TS)]
+#[ts(export, export_to = "../src/bindings/")]
+pub struct AddTopCoinsRequest {
+    #[validate(range(min = 1, max = 5000, message = "limit must be between 1 and 5000"))]
+    pub limit: u32,
+}
+
+#[derive(Clone, Debug, Serialize, PartialEq, ToSchema, TS)]
+#[ts(export, export_to = "../src/bindings/")]
+pub struct CatalogEntryResponse {
+    pub id: u32,
+    pub slug: String,
+    pub symbol: String,
+    pub name: String,
+    pub rank: Option<u32>,
+    pub tracked: bool,
+}
+
+impl From<CatalogMatch> for CatalogEntryResponse {
+    fn from(result: CatalogMatch) -> Self {
+        CatalogEntryResponse {
+            id: result.entry.id(),
+            slug: result.entry.slug().to_string(),
+            symbol: result.entry.symbol().to_string(),
+            name: result.entry.name().to_string(),
+            rank: result.entry.rank(),
+            tracked: result.tracked,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, PartialEq, ToSchema, TS)]
+#[ts(export, export_to = "../src/bindings/")]
+pub struct CatalogRefreshResponse {
+    /// Coins now in the catalog
+    pub count: usize,
+}
+
+#[tauri::command]
+pub(crate) async fn refresh_catalog(
+    state: State<'_, Arc<AppState>>,
+) -> Result<CatalogRefreshResponse, TauriErrors> {
+    let token_result = domain::provider::api_keys::find_token(state.sqlite_repo.clone()).await;
+
+    let Ok(token) = token_result else {
+        return Err(TauriErrors::TokenNotSpecified);
+    };
+
+    match domain::catalog::refresh_catalog::refresh_catalog(
+        state.sqlite_repo.clone(),
+        &state.config,
+        token,
+    )
+    .await
+    {
+        Ok(count) => Ok(CatalogRefreshResponse { count }),
+        Err(e) => Err(TauriErrors::from(e)),
+    }
+}
+
+#[tauri::command]
+pub(crate) async fn search_catalog(
+    request: SearchCatalogRequest,
+    state: State<'_, Arc<AppState>>,
+) -> Result<Vec<CatalogEntryResponse>, TauriErrors> {
+    request.validate()?;
+
+    match domain::catalog::search_catalog::search_catalog(
+        state.sqlite_repo.clone(),
+        request.query,
+        request.limit.unwrap_or(DEFAULT_SEARCH_LIMIT),
+    )
+    .await
+    {
+        Ok(results) => Ok(results
+            .into_iter()
+            .map(CatalogEntryResponse::from)
+            .collect()),
+        Err(e) => Err(TauriErrors::from(e)),
+    }
+}
+
+#[tauri::command]
+pub(crate) async fn add_top_coins(
+    request: AddTopCoinsRequest,
+    state: State<'_, Arc<AppState>>,
+) -> Result<Vec<CoinResponse>, TauriErrors> {
+    request.validate()?;
+
+    let token_result = domain::provider::api_keys::find_token(state.sqlite_repo.clone()).await;
+
+    let Ok(token) = token_result else {
+        return Err(TauriErrors::TokenNotSpecified);
+    };
+
+    match domain::catalog::add_top_coins::add_top_coins(
+        state.sqlite_repo.clone(),
+        &state.events,
+        Origin::Desktop,
+        &state.config,
+        token,
+        request.limit,
+    )
+    .await
+    {
+        Ok(coins) => Ok(coins.into_iter().map(CoinResponse::from).collect()),
+        Err(e) => Err(TauriErrors::from(e)),
+    }
+}
@@ -1,18 +1,32 @@
 use std::sync::Arc;
 
+use base64::{engine::general_purpose::STANDARD, Engine};
 use serde::{Deserialize, Serialize};
 use tauri::State;
+use ts_rs::TS;
+use utoipa::ToSchema;
 use validator::Validate;
 
 use crate::{
-    domain::{self, coin::coin::Coin, Value},
+    domain::{
+        self,
+        audit::audit::Origin,
+        coin::{
+            coin::{is_stale, quote_age, Coin},
+            get_coin_details::CoinDetails,
+            metadata::{CoinLink, TokenContract},
+        },
+        provider::aggregation::QuoteSource,
+        Value,
+    },
     fetch::coinmarketcap::{fetch_ids, fetch_symbols},
     AppState,
 };
 
 use super::errors::TauriErrors;
 
-#[derive(Clone, Debug, Deserialize, Serialize, Validate)]
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema, Validate, TS)]
+#[ts(export, export_to = "../src/bindings/")]
 pub struct CreateCoinRequest {
     #[validate(range(min = 1, message = "id must be greater than 0"))]
     pub id: u32,
@@ -37,43 +51,84 @@ pub struct CreateCoinRequest {
     pub last_updated: String,
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize, Validate)]
+impl From<CoinResponse> for CreateCoinRequest {
+    fn from(coin: CoinResponse) -> Self {
+        CreateCoinRequest {
+            id: coin.id,
+            name: coin.name,
+            symbol: coin.symbol,
+            price: Some(coin.price),
+            volume_24h: Some(coin.volume_24h),
+            percent_change_1h: Some(coin.percent_change_1h),
+            percent_change_24h: Some(coin.percent_change_24h),
+            percent_change_7d: Some(coin.percent_change_7d),
+            market_cap: Some(coin.market_cap),
+            last_updated: coin.last_updated,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema, Validate, TS)]
+#[ts(export, export_to = "../src/bindings/")]
 pub struct CreateCoinsRequest {
     #[validate(length(min = 1, message = "coins must have at least 1 coin"))]
+    #[validate]
     pub coins: Vec<CreateCoinRequest>,
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize, Validate)]
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema, Validate, TS)]
+#[ts(export, export_to = "../src/bindings/")]
 pub struct UpdateCoinsRequest {
     #[validate(length(min = 1, message = "ids must have at least 1 id"))]
     pub ids: Vec<u32>,
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize, Validate)]
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema, Validate, TS)]
+#[ts(export, export_to = "../src/bindings/")]
+pub struct GetAllCoinsRequest {
+    #[validate(range(min = 1, message = "watchlist_id must be greater than 0"))]
+    pub watchlist_id: Option<u32>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema, Validate, TS)]
+#[ts(export, export_to = "../src/bindings/")]
 pub struct FindCoinRequest {
     #[validate(range(min = 1, message = "id must be greater than 0"))]
     pub id: u32,
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize, Validate)]
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema, Validate, TS)]
+#[ts(export, export_to = "../src/bindings/")]
 pub struct DeleteCoinRequest {
     #[validate(range(min = 1, message = "id must be greater than 0"))]
     pub id: u32,
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize, Validate)]
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema, Validate, TS)]
+#[ts(export, export_to = "../src/bindings/")]
 pub struct FetchCoinsByIdRequest {
     #[validate(length(min = 1, message = "ids must have at least 1 id"))]
     pub ids: Vec<u32>,
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize, Validate)]
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema, Validate, TS)]
+#[ts(export, export_to = "../src/bindings/")]
 pub struct FetchCoinsBySymbolRequest {
     #[validate(length(min = 1, max = 255, message = "symbols must have at least 1 symbol"))]
     pub symbols: Vec<String>,
 }
 
-#[derive(Debug, Serialize, PartialEq)]
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema, Validate, TS)]
+#[ts(export, export_to = "../src/bindings/")]
+pub struct GetCoinDetailsRequest {
+    #[validate(range(min = 1, message = "id must be greater than 0"))]
+    pub id: u32,
+    /// Fetches the metadata again even if it is stored
+    pub refresh: Option<bool>,
+}
+
+#[derive(Clone, Debug, Serialize, PartialEq, ToSchema, TS)]
+#[ts(export, export_to = "../src/bindings/")]
 pub struct CoinResponse {
     pub id: u32,
     pub name: String,
@@ -85,6 +140,12 @@ pub struct CoinResponse {
     pub percent_change_7d: f64,
     pub market_cap: f64,
     pub last_updated: String,
+    /// Seconds since `last_updated`, missing when it cannot be parsed
+    pub age_seconds: Option<u32>,
+    /// Older than the staleness threshold, e.g. served from storage while offline
+    pub is_stale: bool,
+    /// Comma-separated sources whose prices went into `price`, e.g. `coinmarketcap,binance`
+    pub sources: String,
 }
 
 impl<'de> Deserialize<'de> for CoinResponse {
@@ -118,6 +179,8 @@ impl<'de> Deserialize<'de> for CoinResponse {
         }
 
         let temp = CoinMarketCapData::deserialize(deserializer)?;
+        let age_seconds = quote_age(&temp.quote.usd.last_updated, chrono::Utc::now().timestamp());
+
         Ok(CoinResponse {
             id: temp.id,
             name: temp.name,
@@ -129,12 +192,17 @@ impl<'de> Deserialize<'de> for CoinResponse {
             percent_change_7d: temp.quote.usd.percent_change_7d.unwrap_or_default(),
             market_cap: temp.quote.usd.market_cap.unwrap_or_default(),
             last_updated: temp.quote.usd.last_updated,
+            age_seconds,
+            is_stale: is_stale(age_seconds),
+            sources: QuoteSource::CoinMarketCap.to_string(),
         })
     }
 }
 
 impl From<Coin> for CoinResponse {
     fn from(coin: Coin) -> Self {
+        let age_seconds = quote_age(coin.last_updated().value(), chrono::Utc::now().timestamp());
+
         CoinResponse {
             id: coin.id().value().to_owned(),
             name: coin.name().value().to_string().clone(),
@@ -146,11 +214,15 @@ impl From<Coin> for CoinResponse {
             percent_change_7d: coin.percent_change_7d().value().unwrap_or(0.0),
             market_cap: coin.market_cap().value().unwrap_or(0.0),
             last_updated: coin.last_updated().value().to_string().clone(),
+            age_seconds,
+            is_stale: is_stale(age_seconds),
+            sources: coin.sources().to_string(),
         }
     }
 }
 
-#[derive(Debug, Deserialize, Serialize, PartialEq)]
+#[derive(Debug, Deserialize, Serialize, PartialEq, TS)]
+#[ts(export, export_to = "../src/bindings/")]
 pub struct CoinsResponse {
     pub coins: Vec<CoinResponse>,
 }
@@ -163,18 +235,61 @@ impl From<Vec<Coin>> for CoinsResponse {
     }
 }
 
+#[derive(Clone, Debug, Serialize, PartialEq, ToSchema, TS)]
+#[ts(export, export_to = "../src/bindings/")]
+pub struct CoinDetailsResponse {
+    pub coin: CoinResponse,
+    pub description: String,
+    pub logo_url: Option<String>,
+    /// Cached logo as a `data:` URI, usable offline
+    pub logo: Option<String>,
+    pub links: Vec<CoinLink>,
+    pub tags: Vec<String>,
+    pub contracts: Vec<TokenContract>,
+    pub fetched_at: String,
+}
+
+impl From<CoinDetails> for CoinDetailsResponse {
+    fn from(details: CoinDetails) -> Self {
+        let metadata = details.metadata;
+
+        CoinDetailsResponse {
+            coin: CoinResponse::from(details.coin),
+            description: metadata.description,
+            logo_url: metadata.logo_url,
+            logo: metadata.logo.map(|logo| {
+                format!(
+                    "data:{};base64,{}",
+                    logo.content_type,
+                    STANDARD.encode(logo.bytes)
+                )
+            }),
+            links: metadata.links,
+            tags: metadata.tags,
+            contracts: metadata.contracts,
+            fetched_at: metadata.fetched_at,
+        }
+    }
+}
+
 #[tauri::command]
 pub(crate) async fn create_coin(
     request: CreateCoinRequest,
     state: State<'_, Arc<AppState>>,
-) -> Result<String, TauriErrors> {
+) -> Result<CoinResponse, TauriErrors> {
     request.validate()?;
 
-    let result = domain::coin::create_coin::create_coin(state.sqlite_repo.clone(), request).await;
+    let result = domain::coin::create_coin::create_coin(
+        state.sqlite_repo.clone(),
+        &state.events,
+        Origin::Desktop,
+        request,
+    )
+    .await;
 
     match result {
-        Ok(coin) => Ok(serde_json::to_string(&CoinResponse::from(coin)).unwrap()),
-        Err(e) => Err(TauriErrors::UnknownError(e.to_string())),
+        Ok(coin) => Ok(CoinResponse::from(coin)),
+        Err(e) => Err(TauriErrors::from(e)),
     }
 }
 
@@ -182,56 +297,55 @@ pub(crate) async fn create_coin(
 pub(crate) async fn create_coins(
     request: CreateCoinsRequest,
     state: State<'_, Arc<AppState>>,
-) -> Result<String, TauriErrors> {
+) -> Result<Vec<CoinResponse>, TauriErrors> {
     request.validate()?;
 
     let mut result = vec![];
 
     for coin in request.coins {
-        match domain::coin::create_coin::create_coin(state.sqlite_repo.clone(), coin).await {
+        match domain::coin::create_coin::create_coin(
+            state.sqlite_repo.clone(),
+            &state.events,
+            Origin::Desktop,
+            coin,
+        )
+        .await
+        {
             Ok(coin) => result.push(CoinResponse::from(coin)),
-            Err(e) => return Err(TauriErrors::UnknownError(e.to_string())),
+            Err(e) => return Err(TauriErrors::from(e)),
         }
     }
 
-    Ok(serde_json::to_string(&result).unwrap())
+    Ok(result)
 }
 
 #[tauri::command]
 pub(crate) async fn update_coins(
     request: UpdateCoinsRequest,
     state: State<'_, Arc<AppState>>,
-) -> Result<String, TauriErrors> {
+) -> Result<Vec<CoinResponse>, TauriErrors> {
     request.validate()?;
 
-    let token_result: Result<domain::settings::settings::Setting, domain::settings::find_setting::FindError> = domain::settings::find_setting::find_setting(
-        state.sqlite_repo.clone(),
-        "api_key".to_string(),
-    ).await;
+    let token_result = domain::provider::api_keys::find_token(state.sqlite_repo.clone()).await;
 
-    let Ok(token_value) = token_result else {
+    let Ok(token) = token_result else {
         return Err(TauriErrors::TokenNotSpecified);
     };
 
-    let token = token_value.value().value();
-
-    let result = fetch_ids(request.ids, token.to_string()).await;
+        let result = domain::coin::refresh_coins::refresh_coins(
+        state.sqlite_repo.clone(),
+        &state.events,
+        Origin::Desktop,
+        &state.config,
+        &state.connectivity,
+        request.ids,
+        token,
+    )
+    .await;
 
     match result {
-        Ok(coins) => {
-            let mut result = vec![];
-
-            for coin in coins.coins {
-                match domain::coin::update_coin::update_coin(state.sqlite_repo.clone(), coin).await
-                {
-                    Ok(coin) => result.push(CoinResponse::from(coin)),
-                    Err(e) => return Err(TauriErrors::UnknownError(e.to_string())),
-                }
-            }
-
-            Ok(serde_json::to_string(&result).unwrap())
-        }
-        Err(e) => Err(TauriErrors::UnknownError(e)),
+        Ok(coins) => Ok(coins.into_iter().map(CoinResponse::from).collect()),
+        Err(e) => Err(TauriErrors::from(e)),
     }
 }
 
@@ -239,12 +353,12 @@ pub(crate) async fn update_coins(
 pub(crate) async fn find_coin(
     request: FindCoinRequest,
     state: State<'_, Arc<AppState>>,
-) -> Result<String, TauriErrors> {
+) -> Result<CoinResponse, TauriErrors> {
     request.validate()?;
 
     match domain::coin::find_coin::find_coin(state.sqlite_repo.clone(), request.id).await {
-        Ok(coin) => Ok(serde_json::to_string(&CoinResponse::from(coin)).unwrap()),
-        Err(e) => Err(TauriErrors::UnknownError(e.to_string())),
+        Ok(coin) => Ok(CoinResponse::from(coin)),
+        Err(e) => Err(TauriErrors::from(e)),
     }
 }
 
@@ -252,12 +366,12 @@ pub(crate) async fn find_coin(
 pub(crate) async fn find_coins(
     request: FindCoinRequest,
     state: State<'_, Arc<AppState>>,
-) -> Result<String, TauriErrors> {
+) -> Result<Vec<CoinResponse>, TauriErrors> {
     request.validate()?;
 
     match domain::coin::find_coin::find_coins(state.sqlite_repo.clone(), request.id).await {
-        Ok(coins) => Ok(serde_json::to_string(&CoinsResponse::from(coins).coins).unwrap()),
-        Err(e) => Err(TauriErrors::UnknownError(e.to_string())),
+        Ok(coins) => Ok(CoinsResponse::from(coins).coins),
+        Err(e) => Err(TauriErrors::from(e)),
     }
 }
 
@@ -268,25 +382,50 @@ pub(crate) async fn delete_coin(
 ) -> Result<(), TauriErrors> {
     request.validate()?;
 
-    match domain::coin::delete_coin::delete_coin(state.sqlite_repo.clone(), request.id).await {
+    match domain::coin::delete_coin::delete_coin(
+        state.sqlite_repo.clone(),
+        &state.events,
+        Origin::Desktop,
+        request.id,
+    )
+    .await
+    {
         Ok(_) => Ok(()),
-        Err(e) => Err(TauriErrors::UnknownError(e.to_string())),
+        Err(e) => Err(TauriErrors::from(e)),
     }
 }
 
 #[tauri::command]
 pub(crate) async fn delete_all_coins(state: State<'_, Arc<AppState>>) -> Result<(), TauriErrors> {
-    match domain::coin::delete_coin::delete_all_coins(state.sqlite_repo.clone()).await {
+    match domain::coin::delete_coin::delete_all_coins(
+        state.sqlite_repo.clone(),
+        &state.events,
+        Origin::Desktop,
+    )
+    .await
+    {
         Ok(_) => Ok(()),
-        Err(e) => Err(TauriErrors::UnknownError(e.to_string())),
+        Err(e) => Err(TauriErrors::from(e)),
     }
 }
 
 #[tauri::command]
-pub(crate) async fn get_all_coins(state: State<'_, Arc<AppState>>) -> Result<String, TauriErrors> {
-    match domain::coin::get_all_coins::get_all_coins(state.sqlite_repo.clone()).await {
-        Ok(coins) => Ok(serde_json::to_string(&CoinsResponse::from(coins).coins).unwrap()),
-        Err(e) => Err(TauriErrors::UnknownError(e.to_string())),
+pub(crate) async fn get_all_coins(
+    request: Option<GetAllCoinsRequest>,
+    state: State<'_, Arc<AppState>>,
+) -> Result<Vec<CoinResponse>, TauriErrors> {
+    let watchlist_id = match request {
+        Some(request) => {
+            request.validate()?;
+            request.watchlist_id
+        }
+        None => None,
+    };
+
+    match domain::coin::get_all_coins::get_all_coins(state.sqlite_repo.clone(), watchlist_id).await
+    {
+        Ok(coins) => Ok(CoinsResponse::from(coins).coins),
+        Err(e) => Err(TauriErrors::from(e)),
     }
 }
 
@@ -294,25 +433,26 @@ pub(crate) async fn get_all_coins(state: State<'_, Arc<AppState>>) -> Result<Str
 pub(crate) async fn fetch_coins_by_id(
     request: FetchCoinsByIdRequest,
     state: State<'_, Arc<AppState>>,
-) -> Result<String, TauriErrors> {
+) -> Result<Vec<CoinResponse>, TauriErrors> {
     request.validate()?;
 
-    let token_result: Result<domain::settings::settings::Setting, domain::settings::find_setting::FindError> = domain::settings::find_setting::find_setting(
-        state.sqlite_repo.clone(),
-        "api_key".to_string(),
-    ).await;
+    let token_result = domain::provider::api_keys::find_token(state.sqlite_repo.clone()).await;
 
-    let Ok(token_value) = token_result else {
+    let Ok(token) = token_result else {
         return Err(TauriErrors::TokenNotSpecified);
     };
 
-    let token = token_value.value().value();
-
-    let result = fetch_ids(request.ids, token.to_string()).await;
+        let result = fetch_ids(
+        state.sqlite_repo.clone(),
+        &state.config,
+        request.ids,
+        token,
+    )
+    .await;
 
     match result {
-        Ok(coins) => Ok(serde_json::to_string(&coins.coins).unwrap()),
-        Err(e) => Err(TauriErrors::UnknownError(e)),
+        Ok(coins) => Ok(coins.coins),
+        Err(e) => Err(TauriErrors::from(e)),
     }
 }
 
@@ -320,24 +460,107 @@ pub(crate) async fn fetch_coins_by_id(
 pub(crate) async fn fetch_coins_by_symbol(
     request: FetchCoinsBySymbolRequest,
     state: State<'_, Arc<AppState>>,
-) -> Result<String, TauriErrors> {
+) -> Result<Vec<CoinResponse>, TauriErrors> {
     request.validate()?;
 
-    let token_result = domain::settings::find_setting::find_setting(
-        state.sqlite_repo.clone(),
-        "api_key".to_string(),
-    ).await;
+    let token_result = domain::provider::api_keys::find_token(state.sqlite_repo.clone()).await;
 
-    let Ok(token_value) = token_result else {
+    let Ok(token) = token_result else {
         return Err(TauriErrors::TokenNotSpecified);
     };
 
-    let token = token_value.value().value();
-
-    let result = fetch_symbols(request.symbols, token.to_string()).await;
+        let result = fetch_symbols(
+        state.sqlite_repo.clone(),
+        &state.config,
+        request.symbols,
+        token,
+    )
+    .await;
 
     match result {
-        Ok(coins) => Ok(serde_json::to_string(&coins.coins).unwrap()),
-        Err(e) => Err(TauriErrors::UnknownError(e)),
+        Ok(coins) => Ok(coins.coins),
+        Err(e) => Err(TauriErrors::from(e)),
     }
-}
\ No newline at end of file
+}
+
+#[tauri::command]
+pub(crate) async fn get_coin_details(
+    request: GetCoinDetailsRequest,
+    state: State<'_, Arc<AppState>>,
+) -> Result<CoinDetailsResponse, TauriErrors> {
+    request.validate()?;
+
+    // Stored metadata is served without a token, so a missing one is only an error later
+    let token = domain::provider::api_keys::find_token(state.sqlite_repo.clone())
+        .await
+        .ok();
+
+    match domain::coin::get_coin_details::get_coin_details(
+        state.sqlite_repo.clone(),
+        &state.config,
+        token,
+        request.id,
+        request.refresh.unwrap_or(false),
+    )
+    .await
+    {
+        Ok(details) => Ok(CoinDetailsResponse::from(details)),
+        Err(e) => Err(TauriErrors::from(e)),
+    }
+}
+
+/// Refreshes every tracked coin each `refresh_interval`, the first time one interval after
+/// startup. A zero interval disables it.
+pub(crate) fn refresh_periodically(state: Arc<AppState>) {
+    let period = state.config.refresh_interval;
+
+    if period.is_zero() {
+        return;
+    }
+
+    tauri::async_runtime::spawn(async move {
+        let mut interval = tokio::time::interval_at(tokio::time::Instant::now() + period, period);
+
+        loop {
+            interval.tick().await;
+
+            // Nothing can be refreshed until an API key is set
+            let Ok(token) = domain::provider::api_keys::find_token(state.sqlite_repo.clone()).await
+            else {
+                continue;
+            };
+
+            let ids =
+                match domain::coin::get_all_coins::get_all_coins(state.sqlite_repo.clone(), None)
+                    .await
+                {
+                    Ok(coins) => coins
+                        .iter()
+                        .map(|coin| *coin.id().value())
+                        .collect::<Vec<u32>>(),
+                    Err(e) => {
+                        log::warn!("Could not list the coins to refresh: {}", e);
+                        continue;
+                    }
+                };
+
+            if ids.is_empty() {
+                continue;
+            }
+
+            if let Err(e) = domain::coin::refresh_coins::refresh_coins(
+                state.sqlite_repo.clone(),
+                &state.events,
+                Origin::Background,
+                &state.config,
+                &state.connectivity,
+                ids,
+                token,
+            )
+            .await
+            {
+                log::warn!("Could not refresh the coins: {}", e);
+            }
+        }
+    });
+}
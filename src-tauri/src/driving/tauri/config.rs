@@ -0,0 +1,26 @@
+use std::sync::Arc;
+
+use serde::Serialize;
+use tauri::State;
+use ts_rs::TS;
+
+use crate::{config::ConfigEntry, AppState};
+
+use super::errors::TauriErrors;
+
+#[derive(Debug, Serialize, TS)]
+#[ts(export, export_to = "../src/bindings/")]
+pub struct ConfigResponse {
+    pub config_file: String,
+    pub entries: Vec<ConfigEntry>,
+}
+
+#[tauri::command]
+pub(crate) async fn get_config(
+    state: State<'_, Arc<AppState>>,
+) -> Result<ConfigResponse, TauriErrors> {
+    Ok(ConfigResponse {
+        config_file: state.config.config_file.clone(),
+        entries: state.config.entries(),
+    })
+}
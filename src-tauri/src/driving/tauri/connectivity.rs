@@ -0,0 +1,83 @@
+use std::sync::Arc;
+
+use serde::Serialize;
+use tauri::State;
+use ts_rs::TS;
+use utoipa::ToSchema;
+
+use crate::{
+    domain::{self, audit::audit::Origin, provider::connectivity::ConnectivityStatus},
+    fetch::coinmarketcap::is_reachable,
+    AppState,
+};
+
+use super::errors::TauriErrors;
+
+#[derive(Clone, Debug, Serialize, PartialEq, ToSchema, TS)]
+#[ts(export, export_to = "../src/bindings/")]
+pub struct ConnectivityResponse {
+    pub online: bool,
+    /// When the provider last became reachable or unreachable
+    pub since: String,
+    /// Coins whose refresh resumes once the provider is reachable
+    pub queued: Vec<u32>,
+}
+
+impl From<ConnectivityStatus> for ConnectivityResponse {
+    fn from(status: ConnectivityStatus) -> Self {
+        ConnectivityResponse {
+            online: status.online,
+            since: chrono::DateTime::from_timestamp(status.since, 0)
+                .map(|time| time.to_rfc3339_opts(chrono::SecondsFormat::Secs, true))
+                .unwrap_or_default(),
+            queued: status.queued,
+        }
+    }
+}
+
+#[tauri::command]
+pub(crate) async fn get_connectivity(
+    state: State<'_, Arc<AppState>>,
+) -> Result<ConnectivityResponse, TauriErrors> {
+    Ok(ConnectivityResponse::from(state.connectivity.status()))
+}
+
+/// While the provider is unreachable, probes it every `offline.probe_interval` and resumes
+/// the queued refreshes once it answers. A zero interval disables probing.
+pub(crate) fn watch(state: Arc<AppState>) {
+    if state.config.offline.probe_interval.is_zero() {
+        return;
+    }
+
+    tauri::async_runtime::spawn(async move {
+        let mut interval = tokio::time::interval(state.config.offline.probe_interval);
+
+        loop {
+            interval.tick().await;
+
+            if state.connectivity.is_online() && !state.connectivity.has_queued() {
+                continue;
+            }
+
+            if !is_reachable(&state.config).await {
+                continue;
+            }
+
+            // Refreshes are only queued after a token was found, so it is rarely missing
+            let Ok(token) = domain::provider::api_keys::find_token(state.sqlite_repo.clone()).await
+            else {
+                continue;
+            };
+
+            let _ = domain::coin::refresh_coins::resume_refreshes(
+                state.sqlite_repo.clone(),
+                &state.events,
+                Origin::Background,
+                &state.config,
+                &state.connectivity,
+                token,
+            )
+            .await;
+        }
+    });
+}
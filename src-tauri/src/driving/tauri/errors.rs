@@ -1,20 +1,568 @@
 #![allow(dead_code)]
-use serde::Serialize;
+use std::collections::BTreeMap;
+
+use serde::{Serialize, Serializer};
 use thiserror::Error;
+use ts_rs::TS;
+use utoipa::ToSchema;
+use validator::{ValidationErrors, ValidationErrorsKind};
 
-use crate::domain::settings::settings::SettingError;
+use crate::{
+    domain::{
+        analytics::{
+            get_correlations::GetCorrelationsError, get_portfolio_risk::GetPortfolioRiskError,
+        },
+        audit::get_audit_log::GetAuditLogError,
+        candle::{backfill_candles::BackfillError, get_candles::GetCandlesError},
+        catalog::{
+            add_top_coins::AddTopCoinsError, refresh_catalog::RefreshCatalogError,
+            search_catalog::SearchCatalogError,
+        },
+        coin::{
+            create_coin::CreateError, delete_coin::DeleteError, find_coin::FindError,
+            get_all_coins::GetAllCoinsError, get_coin_details::GetCoinDetailsError,
+            get_deleted_coins::GetDeletedCoinsError, purge_deleted_coins::PurgeError,
+            refresh_coins::RefreshError, restore_coin::RestoreError, update_coin::UpdateError,
+        },
+        indicator::get_indicators::GetIndicatorsError,
+        profile::profiles::ProfileError,
+        provider::{
+            api_keys::ApiKeyError, get_api_usage::GetApiUsageError,
+            get_plan_info::GetPlanInfoError, set_api_key::SetApiKeyError,
+        },
+        settings::{
+            create_setting::CreateError as CreateSettingError,
+            find_setting::FindError as FindSettingError, settings::SettingError,
+        },
+        transaction::{
+            delete_transaction::DeleteTransactionError, get_transactions::GetTransactionsError,
+            import_transactions::ImportTransactionsError,
+        },
+        transfer::{export_data::ExportError, import_data::ImportError},
+        watchlist::{
+            create_watchlist::CreateError as CreateWatchlistError,
+            delete_watchlist::DeleteError as DeleteWatchlistError,
+            find_watchlist::FindError as FindWatchlistError,
+            get_all_watchlists::GetAllWatchlistsError, rename_watchlist::RenameError,
+            watchlist_coins::WatchlistCoinsError,
+        },
+    },
+    fetch::FetchError,
+};
 
-#[derive(Debug, Error, Serialize)]
+#[derive(Debug, Error)]
 pub enum TauriErrors {
     #[error("Unknown error: {0}")]
     UnknownError(String),
     #[error("Error validating: {0}")]
-    ValidateError(#[from] validator::ValidationErrors),
+    ValidateError(#[from] ValidationErrors),
+    #[error("Invalid data: {0}")]
+    InvalidData(String),
+    #[error("{0} not found")]
+    NotFound(&'static str),
     #[error("CoinMarketCap token not specified")]
     TokenNotSpecified,
     #[error("CoinMarketCap token not valid")]
-    
     TokenNotValid,
+    #[error("CoinMarketCap rate limit exceeded: {0}")]
+    RateLimited(String),
+    #[error("CoinMarketCap credit budget exhausted: {0}")]
+    BudgetExhausted(String),
+    #[error("Upstream error: {0}")]
+    UpstreamError(String),
+    #[error("CoinMarketCap unreachable: {0}")]
+    Unreachable(String),
     #[error("Setting error: {0}")]
     SettingError(#[from] SettingError),
 }
+
+/// Broad, HTTP-like class of an error, for callers that only need to know how to react.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, ToSchema, TS)]
+#[ts(export, export_to = "../src/bindings/")]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorCategory {
+    NotFound,
+    InvalidInput,
+    Unauthorized,
+    Upstream,
+    Internal,
+}
+
+/// The serialized form of every error returned to a client. `code` is stable and safe to
+/// match on; `details` holds the validation messages per field, if any.
+#[derive(Debug, Serialize, ToSchema, TS)]
+#[ts(export, export_to = "../src/bindings/")]
+pub struct ErrorPayload {
+    pub code: String,
+    pub category: ErrorCategory,
+    pub message: String,
+    pub details: Option<BTreeMap<String, Vec<String>>>,
+}
+
+impl TauriErrors {
+    pub fn code(&self) -> String {
+        match self {
+            TauriErrors::UnknownError(_) => "internal_error".to_string(),
+            TauriErrors::ValidateError(_) => "validation_failed".to_string(),
+            TauriErrors::InvalidData(_) => "invalid_data".to_string(),
+            TauriErrors::NotFound(resource) => format!("{}_not_found", resource),
+            TauriErrors::TokenNotSpecified => "token_not_specified".to_string(),
+            TauriErrors::TokenNotValid => "token_not_valid".to_string(),
+            TauriErrors::RateLimited(_) => "rate_limited".to_string(),
+            TauriErrors::BudgetExhausted(_) => "credit_budget_exhausted".to_string(),
+            TauriErrors::UpstreamError(_) => "upstream_error".to_string(),
+            TauriErrors::Unreachable(_) => "provider_unreachable".to_string(),
+            TauriErrors::SettingError(SettingError::InvalidData(_)) => "invalid_data".to_string(),
+            TauriErrors::SettingError(SettingError::Unknown(_)) => "internal_error".to_string(),
+        }
+    }
+
+    pub fn category(&self) -> ErrorCategory {
+        match self {
+            TauriErrors::NotFound(_) => ErrorCategory::NotFound,
+            TauriErrors::ValidateError(_)
+            | TauriErrors::InvalidData(_)
+            | TauriErrors::SettingError(SettingError::InvalidData(_)) => {
+                ErrorCategory::InvalidInput
+            }
+            TauriErrors::TokenNotSpecified | TauriErrors::TokenNotValid => {
+                ErrorCategory::Unauthorized
+            }
+            TauriErrors::RateLimited(_)
+            | TauriErrors::BudgetExhausted(_)
+            | TauriErrors::UpstreamError(_)
+            | TauriErrors::Unreachable(_) => ErrorCategory::Upstream,
+            TauriErrors::UnknownError(_) | TauriErrors::SettingError(SettingError::Unknown(_)) => {
+                ErrorCategory::Internal
+            }
+        }
+    }
+
+    pub fn payload(&self) -> ErrorPayload {
+        let details = match self {
+            TauriErrors::ValidateError(errors) => Some(validation_details(errors)),
+            _ => None,
+        };
+
+        ErrorPayload {
+            code: self.code(),
+            category: self.category(),
+            message: self.to_string(),
+            details,
+        }
+    }
+}
+
+impl Serialize for TauriErrors {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.payload().serialize(serializer)
+    }
+}
+
+/// Validation messages per field, as reported in `ErrorPayload::details`.
+pub(crate) fn validation_details(errors: &ValidationErrors) -> BTreeMap<String, Vec<String>> {
+    let mut details = BTreeMap::new();
+    collect_validation_errors("", errors, &mut details);
+    details
+}
+
+/// Flattens nested validation errors into `field.path -> messages`, using `[index]` for
+/// list items.
+fn collect_validation_errors(
+    prefix: &str,
+    errors: &ValidationErrors,
+    details: &mut BTreeMap<String, Vec<String>>,
+) {
+    for (field, kind) in errors.errors() {
+        let path = if prefix.is_empty() {
+            field.to_string()
+        } else {
+            format!("{}.{}", prefix, field)
+        };
+
+        match kind {
+            ValidationErrorsKind::Field(errors) => {
+                details
+                    .entry(path)
+                    .or_default()
+                    .extend(errors.iter().map(|error| {
+                        error
+                            .message
+                            .as_ref()
+                            .map(|message| message.to_string())
+                            .unwrap_or_else(|| error.code.to_string())
+                    }));
+            }
+            ValidationErrorsKind::Struct(errors) => {
+                collect_validation_errors(&path, errors, details);
+            }
+            ValidationErrorsKind::List(errors) => {
+                for (index, errors) in errors {
+                    collect_validation_errors(&format!("{}[{}]", path, index), errors, details);
+                }
+            }
+        }
+    }
+}
+
+impl From<FetchError> for TauriErrors {
+    fn from(e: FetchError) -> Self {
+        match e {
+            FetchError::InvalidInput(e) => TauriErrors::InvalidData(e),
+            FetchError::Unauthorized(_) => TauriErrors::TokenNotValid,
+            FetchError::NotFound => TauriErrors::NotFound("quote"),
+            FetchError::RateLimited(e) => TauriErrors::RateLimited(e),
+            FetchError::Upstream(e) => TauriErrors::UpstreamError(e),
+            FetchError::Unreachable(e) => TauriErrors::Unreachable(e),
+            FetchError::BudgetExhausted(e) => TauriErrors::BudgetExhausted(e),
+        }
+    }
+}
+
+impl From<CreateError> for TauriErrors {
+    fn from(e: CreateError) -> Self {
+        match e {
+            CreateError::InvalidData(e) => TauriErrors::InvalidData(e),
+            CreateError::Unknown(e) => TauriErrors::UnknownError(e),
+        }
+    }
+}
+
+impl From<FindError> for TauriErrors {
+    fn from(e: FindError) -> Self {
+        match e {
+            FindError::NotFound => TauriErrors::NotFound("coin"),
+            FindError::Unknown(e) => TauriErrors::UnknownError(e),
+        }
+    }
+}
+
+impl From<UpdateError> for TauriErrors {
+    fn from(e: UpdateError) -> Self {
+        match e {
+            UpdateError::InvalidData(e) => TauriErrors::InvalidData(e),
+            UpdateError::NotFound => TauriErrors::NotFound("coin"),
+            UpdateError::Unknown(e) => TauriErrors::UnknownError(e),
+        }
+    }
+}
+
+impl From<RefreshError> for TauriErrors {
+    fn from(e: RefreshError) -> Self {
+        match e {
+            RefreshError::Fetch(e) => TauriErrors::from(e),
+            RefreshError::Update(e) => TauriErrors::from(e),
+            RefreshError::Coins(e) => TauriErrors::from(e),
+            RefreshError::Unknown(e) => TauriErrors::UnknownError(e),
+        }
+    }
+}
+
+impl From<DeleteError> for TauriErrors {
+    fn from(e: DeleteError) -> Self {
+        match e {
+            DeleteError::InvalidData(e) => TauriErrors::InvalidData(e),
+            DeleteError::NotFound => TauriErrors::NotFound("coin"),
+            DeleteError::Unknown(e) => TauriErrors::UnknownError(e),
+        }
+    }
+}
+
+impl From<GetDeletedCoinsError> for TauriErrors {
+    fn from(e: GetDeletedCoinsError) -> Self {
+        match e {
+            GetDeletedCoinsError::Unknown(e) => TauriErrors::UnknownError(e),
+        }
+    }
+}
+
+impl From<RestoreError> for TauriErrors {
+    fn from(e: RestoreError) -> Self {
+        match e {
+            RestoreError::NotFound => TauriErrors::NotFound("coin"),
+            RestoreError::Unknown(e) => TauriErrors::UnknownError(e),
+        }
+    }
+}
+
+impl From<PurgeError> for TauriErrors {
+    fn from(e: PurgeError) -> Self {
+        match e {
+            PurgeError::Unknown(e) => TauriErrors::UnknownError(e),
+        }
+    }
+}
+
+impl From<GetAllCoinsError> for TauriErrors {
+    fn from(e: GetAllCoinsError) -> Self {
+        match e {
+            GetAllCoinsError::Unknown(e) => TauriErrors::UnknownError(e),
+            GetAllCoinsError::WatchlistNotFound => TauriErrors::NotFound("watchlist"),
+        }
+    }
+}
+
+impl From<CreateSettingError> for TauriErrors {
+    fn from(e: CreateSettingError) -> Self {
+        match e {
+            CreateSettingError::InvalidData(e) => TauriErrors::InvalidData(e),
+            CreateSettingError::Unknown(e) => TauriErrors::UnknownError(e),
+            CreateSettingError::SettingError(e) => TauriErrors::SettingError(e),
+        }
+    }
+}
+
+impl From<FindSettingError> for TauriErrors {
+    fn from(e: FindSettingError) -> Self {
+        match e {
+            FindSettingError::NotFound => TauriErrors::NotFound("setting"),
+            FindSettingError::Unknown(e) => TauriErrors::UnknownError(e),
+        }
+    }
+}
+
+impl From<ExportError> for TauriErrors {
+    fn from(e: ExportError) -> Self {
+        match e {
+            ExportError::Serialize(e) => TauriErrors::UnknownError(e),
+            ExportError::Unknown(e) => TauriErrors::UnknownError(e),
+        }
+    }
+}
+
+impl From<ImportError> for TauriErrors {
+    fn from(e: ImportError) -> Self {
+        match e {
+            ImportError::InvalidData(e) => TauriErrors::InvalidData(e),
+        }
+    }
+}
+
+impl From<CreateWatchlistError> for TauriErrors {
+    fn from(e: CreateWatchlistError) -> Self {
+        match e {
+            CreateWatchlistError::InvalidData(e) => TauriErrors::InvalidData(e),
+            CreateWatchlistError::Unknown(e) => TauriErrors::UnknownError(e),
+        }
+    }
+}
+
+impl From<RenameError> for TauriErrors {
+    fn from(e: RenameError) -> Self {
+        match e {
+            RenameError::InvalidData(e) => TauriErrors::InvalidData(e),
+            RenameError::NotFound => TauriErrors::NotFound("watchlist"),
+            RenameError::Unknown(e) => TauriErrors::UnknownError(e),
+        }
+    }
+}
+
+impl From<DeleteWatchlistError> for TauriErrors {
+    fn from(e: DeleteWatchlistError) -> Self {
+        match e {
+            DeleteWatchlistError::InvalidData(e) => TauriErrors::InvalidData(e),
+            DeleteWatchlistError::NotFound => TauriErrors::NotFound("watchlist"),
+            DeleteWatchlistError::Unknown(e) => TauriErrors::UnknownError(e),
+        }
+    }
+}
+
+impl From<FindWatchlistError> for TauriErrors {
+    fn from(e: FindWatchlistError) -> Self {
+        match e {
+            FindWatchlistError::NotFound => TauriErrors::NotFound("watchlist"),
+            FindWatchlistError::Unknown(e) => TauriErrors::UnknownError(e),
+        }
+    }
+}
+
+impl From<GetAllWatchlistsError> for TauriErrors {
+    fn from(e: GetAllWatchlistsError) -> Self {
+        match e {
+            GetAllWatchlistsError::Unknown(e) => TauriErrors::UnknownError(e),
+        }
+    }
+}
+
+impl From<WatchlistCoinsError> for TauriErrors {
+    fn from(e: WatchlistCoinsError) -> Self {
+        match e {
+            WatchlistCoinsError::InvalidData(e) => TauriErrors::InvalidData(e),
+            WatchlistCoinsError::NotFound => TauriErrors::NotFound("watchlist_coin"),
+            WatchlistCoinsError::Unknown(e) => TauriErrors::UnknownError(e),
+        }
+    }
+}
+
+impl From<ImportTransactionsError> for TauriErrors {
+    fn from(e: ImportTransactionsError) -> Self {
+        match e {
+            ImportTransactionsError::InvalidData(e) => TauriErrors::InvalidData(e),
+            ImportTransactionsError::Unknown(e) => TauriErrors::UnknownError(e),
+        }
+    }
+}
+
+impl From<GetTransactionsError> for TauriErrors {
+    fn from(e: GetTransactionsError) -> Self {
+        match e {
+            GetTransactionsError::Unknown(e) => TauriErrors::UnknownError(e),
+        }
+    }
+}
+
+impl From<DeleteTransactionError> for TauriErrors {
+    fn from(e: DeleteTransactionError) -> Self {
+        match e {
+            DeleteTransactionError::InvalidData(e) => TauriErrors::InvalidData(e),
+            DeleteTransactionError::NotFound => TauriErrors::NotFound("transaction"),
+            DeleteTransactionError::Unknown(e) => TauriErrors::UnknownError(e),
+        }
+    }
+}
+
+impl From<GetCandlesError> for TauriErrors {
+    fn from(e: GetCandlesError) -> Self {
+        match e {
+            GetCandlesError::Unknown(e) => TauriErrors::UnknownError(e),
+        }
+    }
+}
+
+impl From<BackfillError> for TauriErrors {
+    fn from(e: BackfillError) -> Self {
+        match e {
+            BackfillError::Fetch(e) => TauriErrors::from(e),
+            BackfillError::InvalidData(e) => TauriErrors::InvalidData(e),
+            BackfillError::CoinNotFound => TauriErrors::NotFound("coin"),
+            BackfillError::Unknown(e) => TauriErrors::UnknownError(e),
+        }
+    }
+}
+
+impl From<GetIndicatorsError> for TauriErrors {
+    fn from(e: GetIndicatorsError) -> Self {
+        match e {
+            GetIndicatorsError::InvalidData(e) => TauriErrors::InvalidData(e),
+            GetIndicatorsError::Unknown(e) => TauriErrors::UnknownError(e),
+        }
+    }
+}
+
+impl From<GetCorrelationsError> for TauriErrors {
+    fn from(e: GetCorrelationsError) -> Self {
+        match e {
+            GetCorrelationsError::InvalidData(e) => TauriErrors::InvalidData(e),
+            GetCorrelationsError::Unknown(e) => TauriErrors::UnknownError(e),
+        }
+    }
+}
+
+impl From<GetPortfolioRiskError> for TauriErrors {
+    fn from(e: GetPortfolioRiskError) -> Self {
+        match e {
+            GetPortfolioRiskError::InvalidData(e) => TauriErrors::InvalidData(e),
+            GetPortfolioRiskError::Unknown(e) => TauriErrors::UnknownError(e),
+        }
+    }
+}
+
+impl From<RefreshCatalogError> for TauriErrors {
+    fn from(e: RefreshCatalogError) -> Self {
+        match e {
+            RefreshCatalogError::Fetch(e) => TauriErrors::from(e),
+            RefreshCatalogError::Unknown(e) => TauriErrors::UnknownError(e),
+        }
+    }
+}
+
+impl From<SearchCatalogError> for TauriErrors {
+    fn from(e: SearchCatalogError) -> Self {
+        match e {
+            SearchCatalogError::InvalidData(e) => TauriErrors::InvalidData(e),
+            SearchCatalogError::Unknown(e) => TauriErrors::UnknownError(e),
+        }
+    }
+}
+
+impl From<AddTopCoinsError> for TauriErrors {
+    fn from(e: AddTopCoinsError) -> Self {
+        match e {
+            AddTopCoinsError::Fetch(e) => TauriErrors::from(e),
+            AddTopCoinsError::InvalidData(e) => TauriErrors::InvalidData(e),
+            AddTopCoinsError::Create(e) => TauriErrors::from(e),
+            AddTopCoinsError::Unknown(e) => TauriErrors::UnknownError(e),
+        }
+    }
+}
+
+impl From<GetCoinDetailsError> for TauriErrors {
+    fn from(e: GetCoinDetailsError) -> Self {
+        match e {
+            GetCoinDetailsError::Fetch(e) => TauriErrors::from(e),
+            GetCoinDetailsError::CoinNotFound => TauriErrors::NotFound("coin"),
+            GetCoinDetailsError::TokenNotSpecified => TauriErrors::TokenNotSpecified,
+            GetCoinDetailsError::Unknown(e) => TauriErrors::UnknownError(e),
+        }
+    }
+}
+
+impl From<GetApiUsageError> for TauriErrors {
+    fn from(e: GetApiUsageError) -> Self {
+        match e {
+            GetApiUsageError::InvalidData(e) => TauriErrors::InvalidData(e),
+            GetApiUsageError::Unknown(e) => TauriErrors::UnknownError(e),
+        }
+    }
+}
+
+impl From<GetPlanInfoError> for TauriErrors {
+    fn from(e: GetPlanInfoError) -> Self {
+        match e {
+            GetPlanInfoError::Fetch(e) => TauriErrors::from(e),
+            GetPlanInfoError::TokenNotSpecified => TauriErrors::TokenNotSpecified,
+            GetPlanInfoError::Unknown(e) => TauriErrors::UnknownError(e),
+        }
+    }
+}
+
+impl From<ApiKeyError> for TauriErrors {
+    fn from(e: ApiKeyError) -> Self {
+        match e {
+            ApiKeyError::InvalidData(e) => TauriErrors::InvalidData(e),
+            ApiKeyError::NotFound => TauriErrors::NotFound("api_key"),
+            ApiKeyError::TokenNotSpecified => TauriErrors::TokenNotSpecified,
+            ApiKeyError::Fetch(e) => TauriErrors::from(e),
+            ApiKeyError::Unknown(e) => TauriErrors::UnknownError(e),
+        }
+    }
+}
+
+impl From<SetApiKeyError> for TauriErrors {
+    fn from(e: SetApiKeyError) -> Self {
+        match e {
+            SetApiKeyError::InvalidData(e) => TauriErrors::InvalidData(e),
+            SetApiKeyError::Fetch(e) => TauriErrors::from(e),
+            SetApiKeyError::Unknown(e) => TauriErrors::UnknownError(e),
+        }
+    }
+}
+
+impl From<ProfileError> for TauriErrors {
+    fn from(e: ProfileError) -> Self {
+        match e {
+            ProfileError::InvalidData(e) => TauriErrors::InvalidData(e),
+            ProfileError::NotFound => TauriErrors::NotFound("profile"),
+            ProfileError::Unknown(e) => TauriErrors::UnknownError(e),
+        }
+    }
+}
+
+impl From<GetAuditLogError> for TauriErrors {
+    fn from(e: GetAuditLogError) -> Self {
+        match e {
+            GetAuditLogError::InvalidData(e) => TauriErrors::InvalidData(e),
+            GetAuditLogError::Unknown(e) => TauriErrors::UnknownError(e),
+        }
+    }
+}
@@ -0,0 +1,40 @@
+use tauri::{AppHandle, Manager};
+use tokio::sync::broadcast::error::RecvError;
+
+use crate::domain::events::{DomainEvent, EventBus};
+
+/// Re-emits every domain event to the frontend under its event name, e.g. `coin-created`.
+pub(crate) fn forward(app: AppHandle, events: &EventBus) {
+    let mut receiver = events.subscribe();
+
+    tauri::async_runtime::spawn(async move {
+        loop {
+            let event = match receiver.recv().await {
+                Ok(event) => event,
+                Err(RecvError::Lagged(skipped)) => {
+                    log::warn!("Dropped {} domain events", skipped);
+                    continue;
+                }
+                Err(RecvError::Closed) => break,
+            };
+
+            let result = match &event {
+                DomainEvent::CoinCreated(payload) | DomainEvent::CoinUpdated(payload) => {
+                    app.emit(event.name(), payload)
+                }
+                DomainEvent::CoinDeleted(payload) => app.emit(event.name(), payload),
+                DomainEvent::CoinsRefreshed(payload) => app.emit(event.name(), payload),
+                DomainEvent::RefreshFailed(payload) => app.emit(event.name(), payload),
+                DomainEvent::WatchlistUpdated(payload) => app.emit(event.name(), payload),
+                DomainEvent::WatchlistDeleted(payload) => app.emit(event.name(), payload),
+                DomainEvent::ConnectivityChanged(payload) => app.emit(event.name(), payload),
+                DomainEvent::PriceTicked(payload) => app.emit(event.name(), payload),
+                DomainEvent::ProfileSwitched(payload) => app.emit(event.name(), payload),
+            };
+
+            if let Err(e) = result {
+                log::error!("Unable to emit {}: {}", event.name(), e);
+            }
+        }
+    });
+}
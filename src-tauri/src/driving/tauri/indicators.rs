@@ -0,0 +1,123 @@
+use std::{collections::BTreeMap, sync::Arc};
+
+use serde::{Deserialize, Serialize};
+use tauri::State;
+use ts_rs::TS;
+use utoipa::ToSchema;
+use validator::Validate;
+
+use crate::{
+    domain::{
+        self,
+        candle::candle::{resolve_range, CandleInterval, CandleRange},
+        indicator::{get_indicators::IndicatorSet, indicator::IndicatorSpec},
+    },
+    AppState,
+};
+
+use super::errors::TauriErrors;
+
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema, Validate, TS)]
+#[ts(export, export_to = "../src/bindings/")]
+pub struct GetIndicatorsRequest {
+    #[validate(range(min = 1, message = "coin_id must be greater than 0"))]
+    pub coin_id: u32,
+    pub interval: CandleInterval,
+    /// Unix seconds; defaults to 90 candles before `end`
+    #[ts(type = "number | null")]
+    pub start: Option<i64>,
+    /// Unix seconds; defaults to the last closed candle
+    #[ts(type = "number | null")]
+    pub end: Option<i64>,
+    #[validate(length(
+        min = 1,
+        max = 20,
+        message = "indicators must contain between 1 and 20 indicators"
+    ))]
+    pub indicators: Vec<IndicatorSpec>,
+}
+
+impl GetIndicatorsRequest {
+    pub(crate) fn range(&self) -> Result<CandleRange, TauriErrors> {
+        resolve_range(
+            self.interval,
+            self.start,
+            self.end,
+            chrono::Utc::now().timestamp(),
+        )
+        .map_err(|e| TauriErrors::InvalidData(e.to_string()))
+    }
+}
+
+/// Lines of one indicator, aligned with `IndicatorsResponse::timestamps`; `null` where the
+/// indicator has no value yet.
+#[derive(Clone, Debug, Serialize, PartialEq, ToSchema, TS)]
+#[ts(export, export_to = "../src/bindings/")]
+pub struct IndicatorSeriesResponse {
+    pub indicator: IndicatorSpec,
+    pub lines: BTreeMap<String, Vec<Option<f64>>>,
+}
+
+/// Indicators of one coin over the stored candles of a range, oldest first.
+#[derive(Clone, Debug, Serialize, PartialEq, ToSchema, TS)]
+#[ts(export, export_to = "../src/bindings/")]
+pub struct IndicatorsResponse {
+    pub coin_id: u32,
+    pub interval: CandleInterval,
+    pub range: CandleRange,
+    /// Candle open times in Unix seconds
+    #[ts(type = "Array<number>")]
+    pub timestamps: Vec<i64>,
+    pub close: Vec<f64>,
+    pub indicators: Vec<IndicatorSeriesResponse>,
+    pub gaps: Vec<CandleRange>,
+}
+
+impl From<IndicatorSet> for IndicatorsResponse {
+    fn from(set: IndicatorSet) -> Self {
+        IndicatorsResponse {
+            coin_id: set.series.coin_id,
+            interval: set.series.interval,
+            range: set.series.range,
+            timestamps: set
+                .series
+                .candles
+                .iter()
+                .map(|candle| candle.open_time())
+                .collect(),
+            close: set
+                .series
+                .candles
+                .iter()
+                .map(|candle| candle.ohlcv().close)
+                .collect(),
+            indicators: set
+                .indicators
+                .into_iter()
+                .map(|(indicator, lines)| IndicatorSeriesResponse { indicator, lines })
+                .collect(),
+            gaps: set.series.gaps,
+        }
+    }
+}
+
+#[tauri::command]
+pub(crate) async fn get_indicators(
+    request: GetIndicatorsRequest,
+    state: State<'_, Arc<AppState>>,
+) -> Result<IndicatorsResponse, TauriErrors> {
+    request.validate()?;
+
+    match domain::indicator::get_indicators::get_indicators(
+        state.sqlite_repo.clone(),
+        request.coin_id,
+        request.interval,
+        request.range()?,
+        request.indicators,
+    )
+    .await
+    {
+        Ok(set) => Ok(IndicatorsResponse::from(set)),
+        Err(e) => Err(TauriErrors::from(e)),
+    }
+}
@@ -1,3 +1,19 @@
+pub(crate) mod analytics;
+pub(crate) mod api_keys;
+pub(crate) mod audit;
+pub(crate) mod candles;
+pub(crate) mod catalog;
 pub(crate) mod coins;
+pub(crate) mod config;
+pub(crate) mod connectivity;
 pub(crate) mod errors;
-pub(crate) mod settings;
\ No newline at end of file
+pub(crate) mod events;
+pub(crate) mod indicators;
+pub(crate) mod profiles;
+pub(crate) mod settings;
+pub(crate) mod stream;
+pub(crate) mod transactions;
+pub(crate) mod transfer;
+pub(crate) mod trash;
+pub(crate) mod usage;
+pub(crate) mod watchlists;
\ No newline at end of file
@@ -0,0 +1,111 @@
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use tauri::State;
+use ts_rs::TS;
+use utoipa::ToSchema;
+use validator::Validate;
+
+use crate::{
+    domain::{self, profile::profiles::Profile},
+    AppState,
+};
+
+use super::errors::TauriErrors;
+
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema, Validate, TS)]
+#[ts(export, export_to = "../src/bindings/")]
+pub struct ProfileRequest {
+    /// Lowercase letters, digits, `-` and `_`
+    #[validate(length(
+        min = 1,
+        max = 32,
+        message = "name must be between 1 and 32 characters"
+    ))]
+    pub name: String,
+}
+
+#[derive(Clone, Debug, Serialize, PartialEq, ToSchema, TS)]
+#[ts(export, export_to = "../src/bindings/")]
+pub struct ProfileResponse {
+    pub name: String,
+    pub active: bool,
+    /// The database holding the coins, settings and API keys of the profile
+    pub db_path: String,
+}
+
+impl From<Profile> for ProfileResponse {
+    fn from(profile: Profile) -> Self {
+        ProfileResponse {
+            name: profile.name,
+            active: profile.active,
+            db_path: profile.db_path,
+        }
+    }
+}
+
+#[tauri::command]
+pub(crate) async fn get_profiles(
+    state: State<'_, Arc<AppState>>,
+) -> Result<Vec<ProfileResponse>, TauriErrors> {
+    match domain::profile::profiles::get_profiles(&state.config.profiles, &state.profile) {
+        Ok(profiles) => Ok(profiles.into_iter().map(ProfileResponse::from).collect()),
+        Err(e) => Err(TauriErrors::from(e)),
+    }
+}
+
+#[tauri::command]
+pub(crate) async fn create_profile(
+    request: ProfileRequest,
+    state: State<'_, Arc<AppState>>,
+) -> Result<ProfileResponse, TauriErrors> {
+    request.validate()?;
+
+    match domain::profile::profiles::create_profile(
+        &state.config.profiles,
+        &state.profile,
+        request.name,
+    ) {
+        Ok(profile) => Ok(ProfileResponse::from(profile)),
+        Err(e) => Err(TauriErrors::from(e)),
+    }
+}
+
+#[tauri::command]
+pub(crate) async fn switch_profile(
+    request: ProfileRequest,
+    state: State<'_, Arc<AppState>>,
+) -> Result<ProfileResponse, TauriErrors> {
+    request.validate()?;
+
+    match domain::profile::profiles::switch_profile(
+        state.sqlite_repo.clone(),
+        &state.events,
+        &state.connectivity,
+        &state.config.profiles,
+        &state.profile,
+        request.name,
+    )
+    .await
+    {
+        Ok(profile) => Ok(ProfileResponse::from(profile)),
+        Err(e) => Err(TauriErrors::from(e)),
+    }
+}
+
+#[tauri::command]
+pub(crate) async fn delete_profile(
+    request: ProfileRequest,
+    state: State<'_, Arc<AppState>>,
+) -> Result<(), TauriErrors> {
+    request.validate()?;
+
+    match domain::profile::profiles::delete_profile(
+        &state.config.profiles,
+        &state.profile,
+        request.name,
+    ) {
+        Ok(_) => Ok(()),
+        Err(e) => Err(TauriErrors::from(e)),
+    }
+}
@@ -2,13 +2,16 @@ use std::sync::Arc;
 
 use serde::{Deserialize, Serialize};
 use tauri::State;
+use ts_rs::TS;
+use utoipa::ToSchema;
 use validator::Validate;
 
-use crate::{domain::{self, settings::settings::Setting, Value}, AppState};
+use crate::{domain::{self, audit::audit::Origin, settings::settings::Setting, Value}, AppState};
 
 use super::errors::TauriErrors;
 
-#[derive(Clone, Debug, Deserialize, Serialize, Validate)]
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema, Validate, TS)]
+#[ts(export, export_to = "../src/bindings/")]
 pub struct CreateSettingRequest {
     #[validate(length(min = 1))]
     pub key: String,
@@ -16,7 +19,8 @@ pub struct CreateSettingRequest {
     pub value: String,
 }
 
-#[derive(Debug, Serialize, PartialEq)]
+#[derive(Debug, Serialize, PartialEq, ToSchema, TS)]
+#[ts(export, export_to = "../src/bindings/")]
 pub struct SettingResponse {
     pub key: String,
     pub value: String,
@@ -32,26 +36,27 @@ impl From<Setting> for SettingResponse {
 }
 
 #[tauri::command]
-pub async fn create_setting(request: CreateSettingRequest, state: State<'_, Arc<AppState>>,) -> Result<String, TauriErrors> {
+pub async fn create_setting(request: CreateSettingRequest, state: State<'_, Arc<AppState>>,) -> Result<SettingResponse, TauriErrors> {
     request.validate()?;
 
     let result = domain::settings::create_setting::create_setting(
         state.sqlite_repo.clone(),
+        Origin::Desktop,
         request,
     ).await;
 
     match result {
-        Ok(setting) => Ok(serde_json::to_string(&SettingResponse::from(setting)).unwrap()),
-        Err(e) => Err(TauriErrors::UnknownError(e.to_string())),
+        Ok(setting) => Ok(SettingResponse::from(setting)),
+        Err(e) => Err(TauriErrors::from(e)),
     }
 }
 
 #[tauri::command]
-pub async fn find_setting(request: String, state: State<'_, Arc<AppState>>) -> Result<String, TauriErrors> {
+pub async fn find_setting(request: String, state: State<'_, Arc<AppState>>) -> Result<SettingResponse, TauriErrors> {
     let result = domain::settings::find_setting::find_setting(state.sqlite_repo.clone(), request).await;
 
     match result {
-        Ok(setting) => Ok(serde_json::to_string(&SettingResponse::from(setting)).unwrap()),
-        Err(e) => Err(TauriErrors::UnknownError(e.to_string())),
+        Ok(setting) => Ok(SettingResponse::from(setting)),
+        Err(e) => Err(TauriErrors::from(e)),
     }
 }
\ No newline at end of file
@@ -0,0 +1,94 @@
+use std::sync::Arc;
+
+use serde::Serialize;
+use tauri::State;
+use ts_rs::TS;
+use utoipa::ToSchema;
+
+use crate::{
+    config::Config,
+    domain::{
+        self,
+        stream::ticker::{LivePrices, Ticker},
+    },
+    AppState,
+};
+
+use super::errors::TauriErrors;
+
+#[derive(Clone, Debug, Serialize, PartialEq, ToSchema, TS)]
+#[ts(export, export_to = "../src/bindings/")]
+pub struct LivePriceResponse {
+    pub symbol: String,
+    pub price: f64,
+    /// 24h volume of the exchange pair in its quote currency
+    pub volume_24h: Option<f64>,
+    pub updated_at: String,
+}
+
+impl From<Ticker> for LivePriceResponse {
+    fn from(ticker: Ticker) -> Self {
+        LivePriceResponse {
+            symbol: ticker.symbol,
+            price: ticker.price,
+            volume_24h: ticker.volume_24h,
+            updated_at: chrono::DateTime::from_timestamp(ticker.time, 0)
+                .map(|time| time.to_rfc3339_opts(chrono::SecondsFormat::Secs, true))
+                .unwrap_or_default(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, PartialEq, ToSchema, TS)]
+#[ts(export, export_to = "../src/bindings/")]
+pub struct LivePricesResponse {
+    /// Empty while streaming is disabled
+    pub exchange: String,
+    pub connected: bool,
+    pub prices: Vec<LivePriceResponse>,
+}
+
+impl LivePricesResponse {
+    pub fn new(config: &Config, live_prices: &LivePrices) -> Self {
+        LivePricesResponse {
+            exchange: if config.stream.enabled {
+                config.stream.exchange.as_str().to_string()
+            } else {
+                String::new()
+            },
+            connected: live_prices.is_connected(),
+            prices: live_prices
+                .tickers()
+                .into_iter()
+                .map(LivePriceResponse::from)
+                .collect(),
+        }
+    }
+}
+
+#[tauri::command]
+pub(crate) async fn get_live_prices(
+    state: State<'_, Arc<AppState>>,
+) -> Result<LivePricesResponse, TauriErrors> {
+    Ok(LivePricesResponse::new(&state.config, &state.live_prices))
+}
+
+/// Streams live prices while `stream.enabled` is set.
+pub(crate) fn start(state: Arc<AppState>) {
+    if !state.config.stream.enabled {
+        return;
+    }
+
+    tauri::async_runtime::spawn(async move {
+        if let Err(e) = domain::stream::stream_prices::stream_prices(
+            state.sqlite_repo.clone(),
+            &state.events,
+            &state.config,
+            &state.live_prices,
+        )
+        .await
+        {
+            log::error!("Price stream stopped: {}", e);
+        }
+    });
+}
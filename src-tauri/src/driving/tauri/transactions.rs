@@ -0,0 +1,221 @@
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use tauri::State;
+use ts_rs::TS;
+use utoipa::ToSchema;
+use validator::Validate;
+
+use crate::{
+    domain::{
+        self,
+        transaction::transaction::{
+            Exchange, Trade, TradeSide, Transaction, TransactionImportReport,
+        },
+    },
+    AppState,
+};
+
+use super::errors::TauriErrors;
+
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema, TS)]
+#[ts(export, export_to = "../src/bindings/")]
+pub struct ImportTransactionsRequest {
+    pub exchange: Exchange,
+    /// Content of the exchange's trade-history CSV export
+    pub content: String,
+    /// Parse and resolve symbols without storing anything
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema, Validate, TS)]
+#[ts(export, export_to = "../src/bindings/")]
+pub struct GetTransactionsRequest {
+    /// Only return trades with this coin as base or quote
+    #[validate(range(min = 1, message = "coin_id must be greater than 0"))]
+    pub coin_id: Option<u32>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema, Validate, TS)]
+#[ts(export, export_to = "../src/bindings/")]
+pub struct TransactionRequest {
+    #[validate(range(min = 1, message = "id must be greater than 0"))]
+    pub id: u32,
+}
+
+#[derive(Clone, Debug, Serialize, PartialEq, ToSchema, TS)]
+#[ts(export, export_to = "../src/bindings/")]
+pub struct TransactionResponse {
+    pub id: u32,
+    pub exchange: Exchange,
+    pub trade_id: String,
+    pub executed_at: String,
+    pub side: TradeSide,
+    pub base_coin_id: u32,
+    pub base_symbol: String,
+    pub quote_coin_id: Option<u32>,
+    pub quote_symbol: String,
+    pub quantity: f64,
+    pub price: f64,
+    pub fee: f64,
+    pub fee_asset: String,
+}
+
+impl From<Transaction> for TransactionResponse {
+    fn from(transaction: Transaction) -> Self {
+        let trade = transaction.trade().clone();
+
+        TransactionResponse {
+            id: transaction.id().unwrap_or_default(),
+            exchange: trade.exchange,
+            trade_id: trade.trade_id,
+            executed_at: trade.executed_at,
+            side: trade.side,
+            base_coin_id: transaction.base_coin_id(),
+            base_symbol: trade.base,
+            quote_coin_id: transaction.quote_coin_id(),
+            quote_symbol: trade.quote,
+            quantity: trade.quantity,
+            price: trade.price,
+            fee: trade.fee,
+            fee_asset: trade.fee_asset,
+        }
+    }
+}
+
+/// A ledger row as exported and imported with the `transactions` dataset. Trades are
+/// identified by exchange and trade id, so the local id is left out.
+#[derive(Clone, Debug, Deserialize, Serialize, Validate)]
+pub struct TransactionRow {
+    pub exchange: Exchange,
+    #[validate(length(min = 1, message = "trade_id must not be empty"))]
+    pub trade_id: String,
+    /// RFC 3339 timestamp in UTC
+    pub executed_at: String,
+    pub side: TradeSide,
+    #[validate(range(min = 1, message = "base_coin_id must be greater than 0"))]
+    pub base_coin_id: u32,
+    pub base_symbol: String,
+    #[validate(range(min = 1, message = "quote_coin_id must be greater than 0"))]
+    pub quote_coin_id: Option<u32>,
+    pub quote_symbol: String,
+    pub quantity: f64,
+    pub price: f64,
+    pub fee: f64,
+    pub fee_asset: String,
+}
+
+impl From<Transaction> for TransactionRow {
+    fn from(transaction: Transaction) -> Self {
+        let trade = transaction.trade().clone();
+
+        TransactionRow {
+            exchange: trade.exchange,
+            trade_id: trade.trade_id,
+            executed_at: trade.executed_at,
+            side: trade.side,
+            base_coin_id: transaction.base_coin_id(),
+            base_symbol: trade.base,
+            quote_coin_id: transaction.quote_coin_id(),
+            quote_symbol: trade.quote,
+            quantity: trade.quantity,
+            price: trade.price,
+            fee: trade.fee,
+            fee_asset: trade.fee_asset,
+        }
+    }
+}
+
+impl TryFrom<TransactionRow> for Transaction {
+    type Error = &'static str;
+
+    fn try_from(row: TransactionRow) -> Result<Self, Self::Error> {
+        Transaction::new(
+            Trade {
+                exchange: row.exchange,
+                trade_id: row.trade_id,
+                executed_at: row.executed_at,
+                side: row.side,
+                base: row.base_symbol,
+                quote: row.quote_symbol,
+                quantity: row.quantity,
+                price: row.price,
+                fee: row.fee,
+                fee_asset: row.fee_asset,
+            },
+            row.base_coin_id,
+            row.quote_coin_id,
+        )
+    }
+}
+
+#[tauri::command]
+pub(crate) async fn import_transactions(
+    request: ImportTransactionsRequest,
+    state: State<'_, Arc<AppState>>,
+) -> Result<TransactionImportReport, TauriErrors> {
+    // Without an API key symbols are only resolved against the tracked coins
+    let token = domain::provider::api_keys::find_token(state.sqlite_repo.clone())
+        .await
+        .ok();
+
+    match domain::transaction::import_transactions::import_transactions(
+        state.sqlite_repo.clone(),
+        &state.config,
+        token,
+        request.exchange,
+        &request.content,
+        request.dry_run,
+    )
+    .await
+    {
+        Ok(report) => Ok(report),
+        Err(e) => Err(TauriErrors::from(e)),
+    }
+}
+
+#[tauri::command]
+pub(crate) async fn get_transactions(
+    request: Option<GetTransactionsRequest>,
+    state: State<'_, Arc<AppState>>,
+) -> Result<Vec<TransactionResponse>, TauriErrors> {
+    let coin_id = match request {
+        Some(request) => {
+            request.validate()?;
+            request.coin_id
+        }
+        None => None,
+    };
+
+    match domain::transaction::get_transactions::get_transactions(
+        state.sqlite_repo.clone(),
+        coin_id,
+    )
+    .await
+    {
+        Ok(transactions) => Ok(transactions
+            .into_iter()
+            .map(TransactionResponse::from)
+            .collect()),
+        Err(e) => Err(TauriErrors::from(e)),
+    }
+}
+
+#[tauri::command]
+pub(crate) async fn delete_transaction(
+    request: TransactionRequest,
+    state: State<'_, Arc<AppState>>,
+) -> Result<(), TauriErrors> {
+    request.validate()?;
+
+    match domain::transaction::delete_transaction::delete_transaction(
+        state.sqlite_repo.clone(),
+        request.id,
+    )
+    .await
+    {
+        Ok(_) => Ok(()),
+        Err(e) => Err(TauriErrors::from(e)),
+    }
+}
@@ -0,0 +1,77 @@
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use tauri::State;
+use ts_rs::TS;
+use utoipa::ToSchema;
+
+use crate::{
+    domain::{
+        self,
+        audit::audit::Origin,
+        transfer::transfer::{DataFormat, Dataset, ImportReport},
+    },
+    AppState,
+};
+
+use super::errors::TauriErrors;
+
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema, TS)]
+#[ts(export, export_to = "../src/bindings/")]
+pub struct ExportDataRequest {
+    pub dataset: Dataset,
+    pub format: DataFormat,
+    /// Include settings holding credentials such as the API key
+    #[serde(default)]
+    pub include_secrets: bool,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema, TS)]
+#[ts(export, export_to = "../src/bindings/")]
+pub struct ImportDataRequest {
+    pub dataset: Dataset,
+    pub format: DataFormat,
+    pub content: String,
+    /// Validate and report without storing anything
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+#[tauri::command]
+pub(crate) async fn export_data(
+    request: ExportDataRequest,
+    state: State<'_, Arc<AppState>>,
+) -> Result<String, TauriErrors> {
+    match domain::transfer::export_data::export_data(
+        state.sqlite_repo.clone(),
+        request.dataset,
+        request.format,
+        request.include_secrets,
+    )
+    .await
+    {
+        Ok(content) => Ok(content),
+        Err(e) => Err(TauriErrors::from(e)),
+    }
+}
+
+#[tauri::command]
+pub(crate) async fn import_data(
+    request: ImportDataRequest,
+    state: State<'_, Arc<AppState>>,
+) -> Result<ImportReport, TauriErrors> {
+    match domain::transfer::import_data::import_data(
+        state.sqlite_repo.clone(),
+        &state.events,
+        Origin::Desktop,
+        request.dataset,
+        request.format,
+        &request.content,
+        request.dry_run,
+    )
+    .await
+    {
+        Ok(report) => Ok(report),
+        Err(e) => Err(TauriErrors::from(e)),
+    }
+}
@@ -0,0 +1,146 @@
+use std::{path::Path, sync::Arc, time::Duration};
+
+use serde::{Deserialize, Serialize};
+use tauri::State;
+use tokio::sync::Mutex;
+use ts_rs::TS;
+use utoipa::ToSchema;
+use validator::Validate;
+
+use crate::{
+    config::SqtliteConfig,
+    domain::{self, audit::audit::Origin, coin::coin::DeletedCoin},
+    driven::repository::sqlite::SqliteRepository,
+    AppState,
+};
+
+use super::{coins::CoinResponse, errors::TauriErrors};
+
+/// How often coins past `trash.retention_days` are purged.
+const PURGE_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+#[derive(Clone, Debug, Serialize, PartialEq, ToSchema, TS)]
+#[ts(export, export_to = "../src/bindings/")]
+pub struct DeletedCoinResponse {
+    pub coin: CoinResponse,
+    /// Unix seconds
+    #[ts(type = "number")]
+    pub deleted_at: i64,
+    /// Increases with every delete; coins deleted together share it
+    #[ts(type = "number")]
+    pub deletion_id: i64,
+}
+
+impl From<DeletedCoin> for DeletedCoinResponse {
+    fn from(deleted: DeletedCoin) -> Self {
+        DeletedCoinResponse {
+            coin: CoinResponse::from(deleted.coin),
+            deleted_at: deleted.deleted_at,
+            deletion_id: deleted.deletion_id,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema, Validate, TS)]
+#[ts(export, export_to = "../src/bindings/")]
+pub struct RestoreCoinRequest {
+    #[validate(range(min = 1, message = "id must be greater than 0"))]
+    pub id: u32,
+}
+
+#[tauri::command]
+pub(crate) async fn get_deleted_coins(
+    state: State<'_, Arc<AppState>>,
+) -> Result<Vec<DeletedCoinResponse>, TauriErrors> {
+    match domain::coin::get_deleted_coins::get_deleted_coins(state.sqlite_repo.clone()).await {
+        Ok(coins) => Ok(coins.into_iter().map(DeletedCoinResponse::from).collect()),
+        Err(e) => Err(TauriErrors::from(e)),
+    }
+}
+
+#[tauri::command]
+pub(crate) async fn restore_coin(
+    request: RestoreCoinRequest,
+    state: State<'_, Arc<AppState>>,
+) -> Result<CoinResponse, TauriErrors> {
+    request.validate()?;
+
+    match domain::coin::restore_coin::restore_coin(
+        state.sqlite_repo.clone(),
+        &state.events,
+        Origin::Desktop,
+        request.id,
+    )
+    .await
+    {
+        Ok(coin) => Ok(CoinResponse::from(coin)),
+        Err(e) => Err(TauriErrors::from(e)),
+    }
+}
+
+/// Restores the coins of the most recent deletion.
+#[tauri::command]
+pub(crate) async fn undo_delete(
+    state: State<'_, Arc<AppState>>,
+) -> Result<Vec<CoinResponse>, TauriErrors> {
+    match domain::coin::restore_coin::undo_delete(
+        state.sqlite_repo.clone(),
+        &state.events,
+        Origin::Desktop,
+    )
+    .await
+    {
+        Ok(coins) => Ok(coins.into_iter().map(CoinResponse::from).collect()),
+        Err(e) => Err(TauriErrors::from(e)),
+    }
+}
+
+/// Purges the coins kept in the trash for longer than `trash.retention_days` from the
+/// database of every profile, once at startup and then every hour.
+pub(crate) fn purge_periodically(state: Arc<AppState>) {
+    tauri::async_runtime::spawn(async move {
+        let mut interval = tokio::time::interval(PURGE_INTERVAL);
+
+        loop {
+            interval.tick().await;
+
+            let active = state.profile.name();
+            let names = state.config.profiles.names().unwrap_or_else(|e| {
+                log::warn!("Could not list the profiles: {}", e);
+                vec![active.clone()]
+            });
+
+            for name in names {
+                // Only the active profile's database is open; the others are opened for the
+                // purge, unless they were never used and have none yet
+                let repository = if name == active {
+                    state.sqlite_repo.clone()
+                } else {
+                    let db_path = state.config.profiles.db_path(&name);
+
+                    if !Path::new(&db_path).is_file() {
+                        continue;
+                    }
+
+                    Arc::new(Mutex::new(SqliteRepository::new(&SqtliteConfig {
+                        db_path,
+                    })))
+                };
+
+                match domain::coin::purge_deleted_coins::purge_deleted_coins(
+                    repository,
+                    Origin::Background,
+                    state.config.trash.retention,
+                )
+                .await
+                {
+                    Ok(0) => {}
+                    Ok(purged) => {
+                        log::info!("Purged {} coins from the trash of {}", purged, name)
+                    }
+                    Err(e) => log::warn!("Could not purge the trash of {}: {}", name, e),
+                }
+            }
+        }
+    });
+}
@@ -0,0 +1,156 @@
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use tauri::State;
+use ts_rs::TS;
+use utoipa::ToSchema;
+use validator::Validate;
+
+use crate::{
+    domain::{
+        self,
+        provider::provider::{EndpointUsage, PlanInfo, DEFAULT_USAGE_DAYS},
+    },
+    AppState,
+};
+
+use super::errors::TauriErrors;
+
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema, Validate, TS)]
+#[ts(export, export_to = "../src/bindings/")]
+pub struct GetApiUsageRequest {
+    /// UTC days back from today, defaults to 30
+    #[validate(range(min = 1, max = 90, message = "days must be between 1 and 90"))]
+    pub days: Option<u32>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema, Validate, TS)]
+#[ts(export, export_to = "../src/bindings/")]
+pub struct GetPlanInfoRequest {
+    /// Fetches the plan again even if it is stored
+    pub refresh: Option<bool>,
+}
+
+#[derive(Clone, Debug, Serialize, PartialEq, ToSchema, TS)]
+#[ts(export, export_to = "../src/bindings/")]
+pub struct EndpointUsageResponse {
+    /// UTC day, `YYYY-MM-DD`
+    pub day: String,
+    pub endpoint: String,
+    pub calls: u32,
+    pub errors: u32,
+    pub credits: u32,
+    pub avg_latency_ms: f64,
+    pub max_latency_ms: u32,
+}
+
+impl From<EndpointUsage> for EndpointUsageResponse {
+    fn from(usage: EndpointUsage) -> Self {
+        EndpointUsageResponse {
+            day: usage.day,
+            endpoint: usage.endpoint,
+            calls: usage.calls,
+            errors: usage.errors,
+            credits: usage.credits,
+            avg_latency_ms: usage.avg_latency_ms,
+            max_latency_ms: usage.max_latency_ms,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, PartialEq, ToSchema, TS)]
+#[ts(export, export_to = "../src/bindings/")]
+pub struct ApiUsageResponse {
+    pub usage: Vec<EndpointUsageResponse>,
+    pub calls: u32,
+    pub credits: u32,
+    /// The configured daily credit budget, if any
+    pub daily_credits: Option<u32>,
+}
+
+impl ApiUsageResponse {
+    pub(crate) fn new(usage: Vec<EndpointUsage>, daily_credits: Option<u32>) -> Self {
+        ApiUsageResponse {
+            calls: usage.iter().map(|usage| usage.calls).sum(),
+            credits: usage.iter().map(|usage| usage.credits).sum(),
+            usage: usage.into_iter().map(EndpointUsageResponse::from).collect(),
+            daily_credits,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, PartialEq, ToSchema, TS)]
+#[ts(export, export_to = "../src/bindings/")]
+pub struct PlanInfoResponse {
+    pub credit_limit_daily: Option<u32>,
+    pub credit_limit_monthly: Option<u32>,
+    pub rate_limit_minute: Option<u32>,
+    pub credits_used_day: u32,
+    pub credits_used_month: u32,
+    pub monthly_reset: Option<String>,
+    pub fetched_at: String,
+}
+
+impl From<PlanInfo> for PlanInfoResponse {
+    fn from(plan: PlanInfo) -> Self {
+        PlanInfoResponse {
+            credit_limit_daily: plan.credit_limit_daily,
+            credit_limit_monthly: plan.credit_limit_monthly,
+            rate_limit_minute: plan.rate_limit_minute,
+            credits_used_day: plan.credits_used_day,
+            credits_used_month: plan.credits_used_month,
+            monthly_reset: plan.monthly_reset,
+            fetched_at: plan.fetched_at,
+        }
+    }
+}
+
+#[tauri::command]
+pub(crate) async fn get_api_usage(
+    request: Option<GetApiUsageRequest>,
+    state: State<'_, Arc<AppState>>,
+) -> Result<ApiUsageResponse, TauriErrors> {
+    let days = match request {
+        Some(request) => {
+            request.validate()?;
+            request.days
+        }
+        None => None,
+    };
+
+    match domain::provider::get_api_usage::get_api_usage(
+        state.sqlite_repo.clone(),
+        days.unwrap_or(DEFAULT_USAGE_DAYS),
+    )
+    .await
+    {
+        Ok(usage) => Ok(ApiUsageResponse::new(
+            usage,
+            state.config.provider.daily_credits,
+        )),
+        Err(e) => Err(TauriErrors::from(e)),
+    }
+}
+
+#[tauri::command]
+pub(crate) async fn get_plan_info(
+    request: Option<GetPlanInfoRequest>,
+    state: State<'_, Arc<AppState>>,
+) -> Result<PlanInfoResponse, TauriErrors> {
+    // A stored plan is served without a token, so a missing one is only an error later
+    let token = domain::provider::api_keys::find_token(state.sqlite_repo.clone())
+        .await
+        .ok();
+
+    match domain::provider::get_plan_info::get_plan_info(
+        state.sqlite_repo.clone(),
+        &state.config,
+        token,
+        request.and_then(|request| request.refresh).unwrap_or(false),
+    )
+    .await
+    {
+        Ok(plan) => Ok(PlanInfoResponse::from(plan)),
+        Err(e) => Err(TauriErrors::from(e)),
+    }
+}
@@ -0,0 +1,256 @@
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use tauri::State;
+use ts_rs::TS;
+use utoipa::ToSchema;
+use validator::Validate;
+
+use crate::{
+    domain::{self, audit::audit::Origin, watchlist::watchlist::Watchlist, Value},
+    AppState,
+};
+
+use super::{coins::CoinResponse, errors::TauriErrors};
+
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema, Validate, TS)]
+#[ts(export, export_to = "../src/bindings/")]
+pub struct CreateWatchlistRequest {
+    #[validate(length(
+        min = 1,
+        max = 255,
+        message = "name must be between 1 and 255 characters"
+    ))]
+    pub name: String,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema, Validate, TS)]
+#[ts(export, export_to = "../src/bindings/")]
+pub struct RenameWatchlistRequest {
+    #[validate(range(min = 1, message = "id must be greater than 0"))]
+    pub id: u32,
+    #[validate(length(
+        min = 1,
+        max = 255,
+        message = "name must be between 1 and 255 characters"
+    ))]
+    pub name: String,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema, Validate, TS)]
+#[ts(export, export_to = "../src/bindings/")]
+pub struct WatchlistRequest {
+    #[validate(range(min = 1, message = "id must be greater than 0"))]
+    pub id: u32,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema, Validate, TS)]
+#[ts(export, export_to = "../src/bindings/")]
+pub struct WatchlistCoinRequest {
+    #[validate(range(min = 1, message = "watchlist_id must be greater than 0"))]
+    pub watchlist_id: u32,
+    #[validate(range(min = 1, message = "coin_id must be greater than 0"))]
+    pub coin_id: u32,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema, Validate, TS)]
+#[ts(export, export_to = "../src/bindings/")]
+pub struct ReorderWatchlistRequest {
+    #[validate(range(min = 1, message = "id must be greater than 0"))]
+    pub id: u32,
+    pub coin_ids: Vec<u32>,
+}
+
+#[derive(Clone, Debug, Serialize, PartialEq, ToSchema, TS)]
+#[ts(export, export_to = "../src/bindings/")]
+pub struct WatchlistResponse {
+    pub id: u32,
+    pub name: String,
+    pub coin_ids: Vec<u32>,
+}
+
+impl From<Watchlist> for WatchlistResponse {
+    fn from(watchlist: Watchlist) -> Self {
+        WatchlistResponse {
+            id: watchlist.id().map(|id| *id.value()).unwrap_or_default(),
+            name: watchlist.name().value().to_string(),
+            coin_ids: watchlist.coin_ids().to_vec(),
+        }
+    }
+}
+
+#[tauri::command]
+pub(crate) async fn create_watchlist(
+    request: CreateWatchlistRequest,
+    state: State<'_, Arc<AppState>>,
+) -> Result<WatchlistResponse, TauriErrors> {
+    request.validate()?;
+
+    match domain::watchlist::create_watchlist::create_watchlist(
+        state.sqlite_repo.clone(),
+        &state.events,
+        request,
+    )
+    .await
+    {
+        Ok(watchlist) => Ok(WatchlistResponse::from(watchlist)),
+        Err(e) => Err(TauriErrors::from(e)),
+    }
+}
+
+#[tauri::command]
+pub(crate) async fn rename_watchlist(
+    request: RenameWatchlistRequest,
+    state: State<'_, Arc<AppState>>,
+) -> Result<WatchlistResponse, TauriErrors> {
+    request.validate()?;
+
+    match domain::watchlist::rename_watchlist::rename_watchlist(
+        state.sqlite_repo.clone(),
+        &state.events,
+        request.id,
+        request.name,
+    )
+    .await
+    {
+        Ok(watchlist) => Ok(WatchlistResponse::from(watchlist)),
+        Err(e) => Err(TauriErrors::from(e)),
+    }
+}
+
+#[tauri::command]
+pub(crate) async fn delete_watchlist(
+    request: WatchlistRequest,
+    state: State<'_, Arc<AppState>>,
+) -> Result<(), TauriErrors> {
+    request.validate()?;
+
+    match domain::watchlist::delete_watchlist::delete_watchlist(
+        state.sqlite_repo.clone(),
+        &state.events,
+        request.id,
+    )
+    .await
+    {
+        Ok(_) => Ok(()),
+        Err(e) => Err(TauriErrors::from(e)),
+    }
+}
+
+#[tauri::command]
+pub(crate) async fn find_watchlist(
+    request: WatchlistRequest,
+    state: State<'_, Arc<AppState>>,
+) -> Result<WatchlistResponse, TauriErrors> {
+    request.validate()?;
+
+    match domain::watchlist::find_watchlist::find_watchlist(state.sqlite_repo.clone(), request.id)
+        .await
+    {
+        Ok(watchlist) => Ok(WatchlistResponse::from(watchlist)),
+        Err(e) => Err(TauriErrors::from(e)),
+    }
+}
+
+#[tauri::command]
+pub(crate) async fn get_all_watchlists(
+    state: State<'_, Arc<AppState>>,
+) -> Result<Vec<WatchlistResponse>, TauriErrors> {
+    match domain::watchlist::get_all_watchlists::get_all_watchlists(state.sqlite_repo.clone()).await
+    {
+        Ok(watchlists) => Ok(watchlists
+            .into_iter()
+            .map(WatchlistResponse::from)
+            .collect()),
+        Err(e) => Err(TauriErrors::from(e)),
+    }
+}
+
+#[tauri::command]
+pub(crate) async fn add_watchlist_coin(
+    request: WatchlistCoinRequest,
+    state: State<'_, Arc<AppState>>,
+) -> Result<WatchlistResponse, TauriErrors> {
+    request.validate()?;
+
+    match domain::watchlist::watchlist_coins::add_coin(
+        state.sqlite_repo.clone(),
+        &state.events,
+        request.watchlist_id,
+        request.coin_id,
+    )
+    .await
+    {
+        Ok(watchlist) => Ok(WatchlistResponse::from(watchlist)),
+        Err(e) => Err(TauriErrors::from(e)),
+    }
+}
+
+#[tauri::command]
+pub(crate) async fn remove_watchlist_coin(
+    request: WatchlistCoinRequest,
+    state: State<'_, Arc<AppState>>,
+) -> Result<WatchlistResponse, TauriErrors> {
+    request.validate()?;
+
+    match domain::watchlist::watchlist_coins::remove_coin(
+        state.sqlite_repo.clone(),
+        &state.events,
+        request.watchlist_id,
+        request.coin_id,
+    )
+    .await
+    {
+        Ok(watchlist) => Ok(WatchlistResponse::from(watchlist)),
+        Err(e) => Err(TauriErrors::from(e)),
+    }
+}
+
+#[tauri::command]
+pub(crate) async fn reorder_watchlist(
+    request: ReorderWatchlistRequest,
+    state: State<'_, Arc<AppState>>,
+) -> Result<WatchlistResponse, TauriErrors> {
+    request.validate()?;
+
+    match domain::watchlist::watchlist_coins::reorder_coins(
+        state.sqlite_repo.clone(),
+        &state.events,
+        request.id,
+        request.coin_ids,
+    )
+    .await
+    {
+        Ok(watchlist) => Ok(WatchlistResponse::from(watchlist)),
+        Err(e) => Err(TauriErrors::from(e)),
+    }
+}
+
+#[tauri::command]
+pub(crate) async fn refresh_watchlist(
+    request: WatchlistRequest,
+    state: State<'_, Arc<AppState>>,
+) -> Result<Vec<CoinResponse>, TauriErrors> {
+    request.validate()?;
+
+    let token_result = domain::provider::api_keys::find_token(state.sqlite_repo.clone()).await;
+
+    let Ok(token) = token_result else {
+        return Err(TauriErrors::TokenNotSpecified);
+    };
+
+    match domain::coin::refresh_coins::refresh_watchlist(
+        state.sqlite_repo.clone(),
+        &state.events,
+        Origin::Desktop,
+        &state.config,
+        &state.connectivity,
+        request.id,
+        token,
+    )
+    .await
+    {
+        Ok(coins) => Ok(coins.into_iter().map(CoinResponse::from).collect()),
+        Err(e) => Err(TauriErrors::from(e)),
+    }
+}
@@ -1,86 +1,987 @@
+use thiserror::Error;
+
+use crate::config::Config;
+
+#[derive(Debug, Error)]
+pub enum FetchError {
+    #[error("Invalid request: {0}")]
+    InvalidInput(String),
+    #[error("Unauthorized: {0}")]
+    Unauthorized(String),
+    #[error("No data found")]
+    NotFound,
+    #[error("Rate limited: {0}")]
+    RateLimited(String),
+    #[error("Upstream error: {0}")]
+    Upstream(String),
+    #[error("Provider unreachable: {0}")]
+    Unreachable(String),
+    #[error("Daily credit budget exhausted: {0}")]
+    BudgetExhausted(String),
+}
+
+fn client(config: &Config) -> Result<reqwest::Client, FetchError> {
+    reqwest::Client::builder()
+        .connect_timeout(config.http.connect_timeout)
+        .timeout(config.http.request_timeout)
+        .build()
+        .map_err(|e| FetchError::Upstream(e.to_string()))
+}
+
+/// Tells a provider that cannot be reached, e.g. while offline, apart from one that
+/// answered with an error.
+fn send_error(e: reqwest::Error) -> FetchError {
+    if e.is_connect() || e.is_timeout() {
+        FetchError::Unreachable(e.to_string())
+    } else {
+        FetchError::Upstream(e.to_string())
+    }
+}
+
 pub(crate) mod coinmarketcap {
-    use crate::driving::tauri::coins::{CoinResponse, CoinsResponse};
+    use std::{sync::Arc, time::Instant};
+
+    use chrono::DateTime;
+    use reqwest::StatusCode;
+    use tokio::sync::Mutex;
+
+    use crate::{
+        config::Config,
+        domain::{
+            candle::candle::{Candle, CandleInterval, CandleRange, Ohlcv},
+            catalog::catalog::CatalogEntry,
+            coin::metadata::{CoinLink, CoinMetadata, LinkKind, Logo, TokenContract},
+            provider::{
+                api_key::{key_failure, select_keys, KeyFailure},
+                provider::{
+                    cache_key, is_budget_exhausted, usage_day, CachedResponse, PlanInfo,
+                    ProviderCall, CALL_RETENTION_SECS, STALE_RETENTION_SECS,
+                },
+            },
+        },
+        driven::repository::{ProviderRepository, RepoFindOneError},
+        driving::tauri::coins::{CoinResponse, CoinsResponse},
+    };
+
+    use super::{client, send_error, FetchError};
 
     const API_KEY_HEADER: &str = "X-CMC_PRO_API_KEY";
-    const API_QUOTES_LATEST: &str =
-        "https://pro-api.coinmarketcap.com/v2/cryptocurrency/quotes/latest";
-
-    pub async fn fetch_ids(ids: Vec<u32>, token: String) -> Result<CoinsResponse, String> {
-        let client = reqwest::Client::new();
-        let res = client
-            .get(format!(
-                "{}?id={}",
-                API_QUOTES_LATEST,
-                ids.iter()
-                    .map(|id| id.to_string())
-                    .collect::<Vec<String>>()
-                    .join(",")
-            ))
-            .header(API_KEY_HEADER, token)
-            .send()
-            .await;
+    const API_QUOTES_LATEST: &str = "/v2/cryptocurrency/quotes/latest";
+    const API_OHLCV_HISTORICAL: &str = "/v2/cryptocurrency/ohlcv/historical";
+    const API_LISTINGS_LATEST: &str = "/v1/cryptocurrency/listings/latest";
+    const API_MAP: &str = "/v1/cryptocurrency/map";
+    const API_INFO: &str = "/v2/cryptocurrency/info";
+    const API_KEY_INFO: &str = "/v1/key/info";
+    /// Largest logo image that is cached, the provider's are a few kilobytes
+    const MAX_LOGO_BYTES: usize = 512 * 1024;
+    /// Most coins the listings and map endpoints return per request
+    const PAGE_SIZE: u32 = 5000;
 
-        match res {
-            Ok(body) => {
-                let body = body.json::<serde_json::Value>().await.unwrap();
+    /// Requests the latest quotes and returns the `data` object of the response, keyed by
+    /// coin id or symbol depending on the query.
+    async fn quotes_latest<R: ProviderRepository>(
+        repository: Arc<Mutex<R>>,
+        config: &Config,
+        query: (&str, String),
+        token: String,
+    ) -> Result<serde_json::Map<String, serde_json::Value>, FetchError> {
+        let query = [query, ("convert", "USD".to_string())];
+        get(repository, config, API_QUOTES_LATEST, &query, token).await
+    }
+
+    /// Sends a GET request to `path` and returns the `data` object of the response.
+    async fn get<R: ProviderRepository>(
+        repository: Arc<Mutex<R>>,
+        config: &Config,
+        path: &str,
+        query: &[(&str, String)],
+        token: String,
+    ) -> Result<serde_json::Map<String, serde_json::Value>, FetchError> {
+        let data = get_data(repository, config, path, query, token).await?;
+        let data = data
+            .as_object()
+            .ok_or_else(|| FetchError::Upstream("Response has no data".to_string()))?;
 
-                if body["status"]["error_code"] != 0 {
-                    return Err(format!("Error: {}", body["status"]["error_message"]));
+        if data.is_empty() {
+            return Err(FetchError::NotFound);
+        }
+
+        Ok(data.clone())
+    }
+
+    /// Sends a GET request to `path` and returns the `data` array of the response, which
+    /// may be empty.
+    async fn get_list<R: ProviderRepository>(
+        repository: Arc<Mutex<R>>,
+        config: &Config,
+        path: &str,
+        query: &[(&str, String)],
+        token: String,
+    ) -> Result<Vec<serde_json::Value>, FetchError> {
+        match get_data(repository, config, path, query, token).await? {
+            serde_json::Value::Array(data) => Ok(data),
+            _ => Err(FetchError::Upstream("Response has no data".to_string())),
+        }
+    }
+
+    /// Returns the `data` field of the response to a GET request to `path`, from the cache
+    /// while it is fresh. Requests that would exceed the daily credit budget are answered
+    /// from an expired cache entry instead, or refused when there is none.
+    async fn get_data<R: ProviderRepository>(
+        repository: Arc<Mutex<R>>,
+        config: &Config,
+        path: &str,
+        query: &[(&str, String)],
+        token: String,
+    ) -> Result<serde_json::Value, FetchError> {
+        let key = cache_key(path, query);
+        let now = chrono::Utc::now().timestamp();
+
+        // The cache only saves credits, requests still go out when it cannot be read
+        let cached = match repository.lock().await.find_response(key.clone()).await {
+            Ok(response) => Some(response),
+            Err(RepoFindOneError::NotFound) => None,
+            Err(RepoFindOneError::Unknown(e)) => {
+                log::warn!("Could not read the provider cache: {}", e);
+                None
+            }
+        };
+
+        if let Some(response) = cached.as_ref().filter(|response| response.is_fresh(now)) {
+            return parse_cached(response);
+        }
+
+        let used = repository
+            .lock()
+            .await
+            .credits_used(usage_day(now))
+            .await
+            .unwrap_or_default();
+
+        if is_budget_exhausted(used, config.provider.daily_credits) {
+            return match cached {
+                Some(response) => {
+                    log::warn!("Credit budget exhausted, serving a stale {}", key);
+                    parse_cached(&response)
                 }
+                None => Err(FetchError::BudgetExhausted(format!(
+                    "{} of {} credits spent today",
+                    used,
+                    config.provider.daily_credits.unwrap_or_default()
+                ))),
+            };
+        }
+
+        let data = request(repository.clone(), config, path, query, token).await?;
+
+        if !config.provider.cache_ttl.is_zero() {
+            let response = CachedResponse {
+                key,
+                body: data.to_string(),
+                fetched_at: now,
+                expires_at: now + config.provider.cache_ttl.as_secs() as i64,
+            };
+            let mut repository = repository.lock().await;
+
+            if let Err(e) = repository.store_response(response).await {
+                log::warn!("Could not store a provider response: {}", e);
+            }
+
+            if let Err(e) = repository.purge_responses(now - STALE_RETENTION_SECS).await {
+                log::warn!("Could not purge the provider cache: {}", e);
+            }
+        }
+
+        Ok(data)
+    }
 
-                let data = body["data"].as_object().unwrap();
+    fn parse_cached(response: &CachedResponse) -> Result<serde_json::Value, FetchError> {
+        serde_json::from_str(&response.body).map_err(|e| FetchError::Upstream(e.to_string()))
+    }
 
-                if data.is_empty() {
-                    return Err("Error: No data found".to_string());
+    /// Sends a GET request to `path` and returns the `data` field of the response. The usable
+    /// keys of the pool are tried in `provider.key_selection` order, moving on to the next
+    /// whenever the provider refuses one, and `token`, the `api_key` setting, last unless it
+    /// belongs to the pool.
+    async fn request<R: ProviderRepository>(
+        repository: Arc<Mutex<R>>,
+        config: &Config,
+        path: &str,
+        query: &[(&str, String)],
+        token: String,
+    ) -> Result<serde_json::Value, FetchError> {
+        let now = chrono::Utc::now().timestamp();
+
+        let pool = match repository.lock().await.get_keys().await {
+            Ok(keys) => keys,
+            Err(e) => {
+                log::warn!("Could not read the API key pool: {}", e);
+                vec![]
+            }
+        };
+
+        let mut tokens = select_keys(&pool, config.provider.key_selection, now)
+            .into_iter()
+            .map(|key| key.key.clone())
+            .collect::<Vec<String>>();
+
+        if !token.is_empty() && !pool.iter().any(|key| key.key == token) {
+            tokens.push(token);
+        }
+
+        let mut refused = None;
+
+        for token in tokens {
+            match request_with_key(repository.clone(), config, path, query, token).await {
+                Err((e, Some(_))) => {
+                    log::warn!("API key refused, trying the next one: {}", e);
+                    refused = Some(e);
                 }
+                result => return result.map_err(|(e, _)| e),
+            }
+        }
+
+        Err(refused.unwrap_or_else(|| {
+            FetchError::Unauthorized(
+                "Every API key of the pool is disabled, invalid or exhausted".to_string(),
+            )
+        }))
+    }
+
+    /// Sends a GET request to `path` with `token`, records the call with the credits it cost
+    /// and returns the `data` field of the response. Errors come with the reason when the
+    /// provider refused the key itself.
+    async fn request_with_key<R: ProviderRepository>(
+        repository: Arc<Mutex<R>>,
+        config: &Config,
+        path: &str,
+        query: &[(&str, String)],
+        token: String,
+    ) -> Result<serde_json::Value, (FetchError, Option<KeyFailure>)> {
+        let called_at = chrono::Utc::now().timestamp();
+        let started = Instant::now();
+        let response = send(config, path, query, token.clone()).await;
+
+        let (status, body) = match &response {
+            Ok((status, body)) => (Some(status.as_u16()), Some(body)),
+            Err(_) => (None, None),
+        };
+
+        let call = ProviderCall {
+            called_at,
+            endpoint: path.to_string(),
+            status,
+            latency_ms: u32::try_from(started.elapsed().as_millis()).unwrap_or(u32::MAX),
+            credits: body
+                .and_then(|body| body["status"]["credit_count"].as_u64())
+                .and_then(|credits| u32::try_from(credits).ok())
+                .unwrap_or_default(),
+            error_code: body.and_then(|body| body["status"]["error_code"].as_i64()),
+        };
+        let message = body.and_then(|body| body["status"]["error_message"].as_str());
 
-                let mut results: Vec<CoinResponse> = vec![];
+        // Failed requests may cost credits too
+        record_key_use(&repository, &token, &call, message).await;
+        record_call(&repository, call).await;
 
-                for (_, value) in data.iter() {
-                    results.push(serde_json::from_value(value.clone()).unwrap());
+        let (status, body) = response.map_err(|e| (e, None))?;
+        let error_code = body["status"]["error_code"].as_i64().unwrap_or_default();
+
+        if !status.is_success() || error_code != 0 {
+            let message = body["status"]["error_message"]
+                .as_str()
+                .map(|message| message.to_string())
+                .unwrap_or_else(|| status.to_string());
+
+            let error = match status {
+                StatusCode::BAD_REQUEST => FetchError::InvalidInput(message),
+                StatusCode::UNAUTHORIZED | StatusCode::PAYMENT_REQUIRED | StatusCode::FORBIDDEN => {
+                    FetchError::Unauthorized(message)
                 }
+                StatusCode::TOO_MANY_REQUESTS => FetchError::RateLimited(message),
+                _ => FetchError::Upstream(message),
+            };
 
-                Ok(CoinsResponse { coins: results })
-            }
-            Err(e) => Err(format!("Error: {}", e)),
+            return Err((error, key_failure(error_code, called_at)));
+        }
+
+        match body.get("data") {
+            Some(data) if !data.is_null() => Ok(data.clone()),
+            _ => Err((
+                FetchError::Upstream("Response has no data".to_string()),
+                None,
+            )),
         }
     }
 
-    pub async fn fetch_symbols(
-        symbol: Vec<String>,
+    async fn send(
+        config: &Config,
+        path: &str,
+        query: &[(&str, String)],
         token: String,
-    ) -> Result<CoinsResponse, String> {
-        let client = reqwest::Client::new();
-        let res = client
-            .get(format!("{}?symbol={}", API_QUOTES_LATEST, symbol.join(",")))
+    ) -> Result<(StatusCode, serde_json::Value), FetchError> {
+        let res = client(config)?
+            .get(format!("{}{}", config.provider.base_url, path))
+            .query(query)
             .header(API_KEY_HEADER, token)
             .send()
-            .await;
+            .await
+            .map_err(send_error)?;
 
-        match res {
-            Ok(body) => {
-                let body = body.json::<serde_json::Value>().await.unwrap();
+        let status = res.status();
+        let body = res
+            .json::<serde_json::Value>()
+            .await
+            .map_err(|e| FetchError::Upstream(e.to_string()))?;
 
-                if body["status"]["error_code"] != 0 {
-                    return Err(format!("Error: {}", body["status"]["error_message"]));
-                }
+        Ok((status, body))
+    }
 
-                let data = body["data"].as_object().unwrap();
+    /// Whether the provider answers at all. Any HTTP response counts; the request carries no
+    /// API key, so it costs no credits and is not recorded as a call.
+    pub async fn is_reachable(config: &Config) -> bool {
+        let Ok(client) = client(config) else {
+            return false;
+        };
 
-                if data.is_empty() {
-                    return Err("Error: No data found".to_string());
-                }
+        client.head(&config.provider.base_url).send().await.is_ok()
+    }
 
-                let mut results: Vec<CoinResponse> = vec![];
+    /// Counts the credits of a call against the pool key it was made with and keeps what the
+    /// provider said about the key. Calls with a key outside the pool are not tracked here.
+    async fn record_key_use<R: ProviderRepository>(
+        repository: &Arc<Mutex<R>>,
+        token: &str,
+        call: &ProviderCall,
+        message: Option<&str>,
+    ) {
+        let mut repository = repository.lock().await;
+
+        let key = match repository.get_keys().await {
+            Ok(keys) => keys.into_iter().find(|key| key.key == token),
+            Err(e) => {
+                log::warn!("Could not read the API key pool: {}", e);
+                None
+            }
+        };
 
-                for (_, value) in data.iter() {
-                    let mut result: Vec<CoinResponse> = serde_json::from_value(value.clone()).unwrap();
-                    results.append(&mut result);
+        let Some(mut key) = key else {
+            return;
+        };
+
+        key.used(call.credits, call.called_at);
+
+        match call.error_code {
+            Some(0) => key.succeeded(),
+            Some(code) => {
+                if let Some(failure) = key_failure(code, call.called_at) {
+                    key.failed(&failure, message.unwrap_or_default().to_string());
                 }
+            }
+            None => {}
+        }
+
+        if let Err(e) = repository.update_key(key).await {
+            log::warn!("Could not record the use of an API key: {}", e);
+        }
+    }
+
+    /// Records a call and adds its credits to the day's usage. Bookkeeping failures are
+    /// logged, they must not fail the call itself.
+    async fn record_call<R: ProviderRepository>(repository: &Arc<Mutex<R>>, call: ProviderCall) {
+        let mut repository = repository.lock().await;
+
+        if call.credits > 0 {
+            let day = usage_day(call.called_at);
+
+            if let Err(e) = repository.add_credits(day, call.credits).await {
+                log::warn!("Could not record spent credits: {}", e);
+            }
+        }
+
+        let purge_before = call.called_at - CALL_RETENTION_SECS;
+
+        if let Err(e) = repository.record_call(call, purge_before).await {
+            log::warn!("Could not record a provider call: {}", e);
+        }
+    }
+
+    pub async fn fetch_ids<R: ProviderRepository>(
+        repository: Arc<Mutex<R>>,
+        config: &Config,
+        mut ids: Vec<u32>,
+        token: String,
+    ) -> Result<CoinsResponse, FetchError> {
+        // Sorted so the same ids in any order hit the same cache entry
+        ids.sort_unstable();
+        ids.dedup();
+
+        let ids = ids
+            .iter()
+            .map(|id| id.to_string())
+            .collect::<Vec<String>>()
+            .join(",");
+        let data = quotes_latest(repository, config, ("id", ids), token).await?;
+
+        let mut results: Vec<CoinResponse> = vec![];
+
+        for (_, value) in data {
+            results.push(
+                serde_json::from_value(value).map_err(|e| FetchError::Upstream(e.to_string()))?,
+            );
+        }
+
+        Ok(CoinsResponse { coins: results })
+    }
+
+    pub async fn fetch_symbols<R: ProviderRepository>(
+        repository: Arc<Mutex<R>>,
+        config: &Config,
+        mut symbol: Vec<String>,
+        token: String,
+    ) -> Result<CoinsResponse, FetchError> {
+        symbol.sort();
+        symbol.dedup();
+
+        let data = quotes_latest(repository, config, ("symbol", symbol.join(",")), token).await?;
+
+        let mut results: Vec<CoinResponse> = vec![];
+
+        for (_, value) in data {
+            let mut result: Vec<CoinResponse> =
+                serde_json::from_value(value).map_err(|e| FetchError::Upstream(e.to_string()))?;
+            results.append(&mut result);
+        }
+
+        if results.is_empty() {
+            return Err(FetchError::NotFound);
+        }
+
+        Ok(CoinsResponse { coins: results })
+    }
+
+    /// Fetches the closed candles of one coin with open times within `range`, in USD.
+    pub async fn fetch_ohlcv<R: ProviderRepository>(
+        repository: Arc<Mutex<R>>,
+        config: &Config,
+        id: u32,
+        interval: CandleInterval,
+        range: CandleRange,
+        token: String,
+    ) -> Result<Vec<Candle>, FetchError> {
+        // `time_start` is exclusive, so ask from one candle earlier
+        let query = [
+            ("id", id.to_string()),
+            ("time_period", interval.as_str().to_string()),
+            ("interval", interval.as_str().to_string()),
+            ("time_start", (range.start - interval.seconds()).to_string()),
+            ("time_end", (range.end + interval.seconds()).to_string()),
+            ("convert", "USD".to_string()),
+        ];
+        let data = get(repository, config, API_OHLCV_HISTORICAL, &query, token).await?;
+
+        // The data is keyed by id on some plans and the coin itself on others
+        let coin = match data.get("quotes") {
+            Some(_) => serde_json::Value::Object(data),
+            None => data
+                .into_iter()
+                .next()
+                .map(|(_, coin)| coin)
+                .ok_or(FetchError::NotFound)?,
+        };
+
+        let mut candles = vec![];
+
+        for quote in coin["quotes"].as_array().into_iter().flatten() {
+            let open_time = quote["time_open"]
+                .as_str()
+                .and_then(|time| DateTime::parse_from_rfc3339(time).ok())
+                .map(|time| interval.align(time.timestamp()))
+                .ok_or_else(|| FetchError::Upstream("Candle has no open time".to_string()))?;
+
+            if open_time < range.start || open_time > range.end {
+                continue;
+            }
+
+            let usd = &quote["quote"]["USD"];
+            let value = |field: &str| usd[field].as_f64().unwrap_or_default();
+            let ohlcv = Ohlcv {
+                open: value("open"),
+                high: value("high"),
+                low: value("low"),
+                close: value("close"),
+                volume: value("volume"),
+            };
+
+            candles.push(
+                Candle::new(id, interval, open_time, ohlcv)
+                    .map_err(|e| FetchError::Upstream(e.to_string()))?,
+            );
+        }
+
+        Ok(candles)
+    }
+
+    /// Reads the catalog fields shared by the listings and map endpoints; the rank is
+    /// `cmc_rank` in listings and `rank` in the map.
+    fn catalog_entry(value: &serde_json::Value) -> Result<CatalogEntry, FetchError> {
+        let text = |field: &str| value[field].as_str().unwrap_or_default().to_string();
+        let rank = value["cmc_rank"]
+            .as_u64()
+            .or_else(|| value["rank"].as_u64())
+            .and_then(|rank| u32::try_from(rank).ok());
+
+        let id = value["id"]
+            .as_u64()
+            .and_then(|id| u32::try_from(id).ok())
+            .ok_or_else(|| FetchError::Upstream("Coin has no id".to_string()))?;
+
+        CatalogEntry::new(id, text("slug"), text("symbol"), text("name"), rank)
+            .map_err(|e| FetchError::Upstream(e.to_string()))
+    }
+
+    /// Fetches the provider's list of active coins, one page at a time.
+    pub async fn fetch_map<R: ProviderRepository>(
+        repository: Arc<Mutex<R>>,
+        config: &Config,
+        token: String,
+    ) -> Result<Vec<CatalogEntry>, FetchError> {
+        let mut entries = vec![];
+        let mut start = 1;
+
+        loop {
+            let query = [
+                ("listing_status", "active".to_string()),
+                ("start", start.to_string()),
+                ("limit", PAGE_SIZE.to_string()),
+                ("sort", "cmc_rank".to_string()),
+            ];
+            let page = get_list(repository.clone(), config, API_MAP, &query, token.clone()).await?;
+            let count = page.len();
+
+            // A few listed coins have no name or symbol; they cannot be searched anyway
+            entries.extend(page.iter().filter_map(|coin| catalog_entry(coin).ok()));
+
+            if count < PAGE_SIZE as usize {
+                return Ok(entries);
+            }
+
+            start += count;
+        }
+    }
+
+    /// Fetches the top `limit` coins by market cap with their latest quotes.
+    pub async fn fetch_listings<R: ProviderRepository>(
+        repository: Arc<Mutex<R>>,
+        config: &Config,
+        limit: u32,
+        token: String,
+    ) -> Result<Vec<(CatalogEntry, CoinResponse)>, FetchError> {
+        let query = [
+            ("start", "1".to_string()),
+            ("limit", limit.min(PAGE_SIZE).to_string()),
+            ("sort", "market_cap".to_string()),
+            ("convert", "USD".to_string()),
+        ];
+        let data = get_list(repository, config, API_LISTINGS_LATEST, &query, token).await?;
+
+        data.into_iter()
+            .map(|coin| {
+                let entry = catalog_entry(&coin)?;
+                let quote = serde_json::from_value::<CoinResponse>(coin)
+                    .map_err(|e| FetchError::Upstream(e.to_string()))?;
+
+                Ok((entry, quote))
+            })
+            .collect()
+    }
+
+    /// Fetches the descriptive data of one coin, without downloading its logo.
+    pub async fn fetch_info<R: ProviderRepository>(
+        repository: Arc<Mutex<R>>,
+        config: &Config,
+        id: u32,
+        token: String,
+    ) -> Result<CoinMetadata, FetchError> {
+        let query = [("id", id.to_string())];
+        let data = get(repository, config, API_INFO, &query, token).await?;
+        let coin = data.get(&id.to_string()).ok_or(FetchError::NotFound)?;
+
+        let links = LinkKind::ALL
+            .into_iter()
+            .flat_map(|kind| {
+                coin["urls"][kind.as_str()]
+                    .as_array()
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|url| url.as_str())
+                    .filter(|url| !url.trim().is_empty())
+                    .map(move |url| CoinLink {
+                        kind,
+                        url: url.trim().to_string(),
+                    })
+            })
+            .collect();
+
+        // Tags are slugs, `tag-names` holds their display names when present
+        let tags = coin["tag-names"]
+            .as_array()
+            .or_else(|| coin["tags"].as_array())
+            .into_iter()
+            .flatten()
+            .filter_map(|tag| tag.as_str().map(|tag| tag.to_string()))
+            .collect();
+
+        let contracts = coin["contract_address"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|contract| {
+                Some(TokenContract {
+                    platform: contract["platform"]["name"].as_str()?.to_string(),
+                    address: contract["contract_address"].as_str()?.to_string(),
+                })
+            })
+            .collect();
+
+        Ok(CoinMetadata {
+            coin_id: id,
+            description: coin["description"].as_str().unwrap_or_default().to_string(),
+            logo_url: coin["logo"]
+                .as_str()
+                .filter(|url| !url.is_empty())
+                .map(|url| url.to_string()),
+            logo: None,
+            links,
+            tags,
+            contracts,
+            fetched_at: chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+        })
+    }
+
+    /// Fetches the plan limits of `token` and the credits used against them. The provider
+    /// does not charge for this, so it always bypasses the cache.
+    pub async fn fetch_key_info<R: ProviderRepository>(
+        repository: Arc<Mutex<R>>,
+        config: &Config,
+        token: String,
+    ) -> Result<PlanInfo, FetchError> {
+        validate_key(repository, config, token)
+            .await
+            .map_err(|(e, _)| e)
+    }
+
+    /// Like `fetch_key_info`, but tells why the provider refused `token` when it did.
+    pub async fn validate_key<R: ProviderRepository>(
+        repository: Arc<Mutex<R>>,
+        config: &Config,
+        token: String,
+    ) -> Result<PlanInfo, (FetchError, Option<KeyFailure>)> {
+        // The plan is the one of `token`, another key must not answer for it
+        let data = request_with_key(repository, config, API_KEY_INFO, &[], token).await?;
+        let plan = &data["plan"];
+        let usage = &data["usage"];
+        let number =
+            |value: &serde_json::Value| value.as_u64().and_then(|value| u32::try_from(value).ok());
+
+        Ok(PlanInfo {
+            credit_limit_daily: number(&plan["credit_limit_daily"]),
+            credit_limit_monthly: number(&plan["credit_limit_monthly"]),
+            rate_limit_minute: number(&plan["rate_limit_minute"]),
+            credits_used_day: number(&usage["current_day"]["credits_used"]).unwrap_or_default(),
+            credits_used_month: number(&usage["current_month"]["credits_used"]).unwrap_or_default(),
+            monthly_reset: plan["credit_limit_monthly_reset_timestamp"]
+                .as_str()
+                .map(|time| time.to_string()),
+            fetched_at: chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+        })
+    }
+
+    /// Downloads the image at `url`; the provider serves logos without authentication.
+    pub async fn fetch_logo(config: &Config, url: &str) -> Result<Logo, FetchError> {
+        let mut res = client(config)?
+            .get(url)
+            .send()
+            .await
+            .map_err(|e| FetchError::Upstream(e.to_string()))?;
+
+        if !res.status().is_success() {
+            return Err(FetchError::Upstream(res.status().to_string()));
+        }
+
+        let content_type = res
+            .headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .filter(|value| value.starts_with("image/"))
+            .map(|value| value.to_string())
+            .ok_or_else(|| FetchError::Upstream("Logo is not an image".to_string()))?;
+
+        let too_large = || FetchError::Upstream("Logo is too large".to_string());
+
+        if res
+            .content_length()
+            .is_some_and(|length| length > MAX_LOGO_BYTES as u64)
+        {
+            return Err(too_large());
+        }
+
+        // The length may be missing or wrong, so the body is read no further than the limit
+        let mut bytes = vec![];
+
+        while let Some(chunk) = res
+            .chunk()
+            .await
+            .map_err(|e| FetchError::Upstream(e.to_string()))?
+        {
+            if bytes.len() + chunk.len() > MAX_LOGO_BYTES {
+                return Err(too_large());
+            }
+
+            bytes.extend_from_slice(&chunk);
+        }
+
+        Ok(Logo {
+            content_type,
+            bytes,
+        })
+    }
+}
+
+/// CoinGecko's public API, a secondary price source.
+pub(crate) mod coingecko {
+    use std::collections::HashMap;
+
+    use crate::{
+        config::Config,
+        domain::provider::aggregation::{QuoteSource, SourceQuote},
+    };
+
+    use super::{client, send_error, FetchError};
+
+    const BASE_URL: &str = "https://api.coingecko.com";
+    const API_SIMPLE_PRICE: &str = "/api/v3/simple/price";
+
+    /// USD prices and 24h volumes keyed by upper-case symbol. A symbol several coins share
+    /// resolves to the largest of them.
+    pub async fn fetch_quotes(
+        config: &Config,
+        symbols: &[String],
+    ) -> Result<HashMap<String, SourceQuote>, FetchError> {
+        let symbols = symbols
+            .iter()
+            .map(|symbol| symbol.to_lowercase())
+            .collect::<Vec<String>>()
+            .join(",");
+
+        let res = client(config)?
+            .get(format!("{}{}", BASE_URL, API_SIMPLE_PRICE))
+            .query(&[
+                ("symbols", symbols.as_str()),
+                ("vs_currencies", "usd"),
+                ("include_24hr_vol", "true"),
+            ])
+            .send()
+            .await
+            .map_err(send_error)?;
+
+        if !res.status().is_success() {
+            return Err(FetchError::Upstream(res.status().to_string()));
+        }
+
+        let body = res
+            .json::<serde_json::Map<String, serde_json::Value>>()
+            .await
+            .map_err(|e| FetchError::Upstream(e.to_string()))?;
+
+        Ok(body
+            .into_iter()
+            .filter_map(|(symbol, quote)| {
+                Some((
+                    symbol.to_uppercase(),
+                    SourceQuote {
+                        source: QuoteSource::CoinGecko,
+                        price: quote["usd"].as_f64()?,
+                        volume_24h: quote["usd_24h_vol"].as_f64(),
+                    },
+                ))
+            })
+            .collect())
+    }
+}
+
+/// Binance's public spot tickers, a secondary price source. USDT pairs stand in for USD.
+pub(crate) mod binance {
+    use std::collections::HashMap;
+
+    use crate::{
+        config::Config,
+        domain::provider::aggregation::{QuoteSource, SourceQuote},
+    };
+
+    use super::{client, send_error, FetchError};
+
+    const BASE_URL: &str = "https://api.binance.com";
+    const API_TICKER_24HR: &str = "/api/v3/ticker/24hr";
+    const QUOTE_ASSET: &str = "USDT";
+
+    /// Last prices and 24h quote volumes keyed by upper-case symbol; symbols without a USDT
+    /// pair are missing.
+    pub async fn fetch_quotes(
+        config: &Config,
+        symbols: &[String],
+    ) -> Result<HashMap<String, SourceQuote>, FetchError> {
+        // Asking for specific pairs fails as a whole on any unlisted one, so all tickers
+        // are requested and filtered
+        let res = client(config)?
+            .get(format!("{}{}", BASE_URL, API_TICKER_24HR))
+            .query(&[("type", "MINI")])
+            .send()
+            .await
+            .map_err(send_error)?;
+
+        if !res.status().is_success() {
+            return Err(FetchError::Upstream(res.status().to_string()));
+        }
 
-                Ok(CoinsResponse { coins: results })
+        let tickers = res
+            .json::<Vec<serde_json::Value>>()
+            .await
+            .map_err(|e| FetchError::Upstream(e.to_string()))?;
+
+        let pairs = symbols
+            .iter()
+            .map(|symbol| (format!("{}{}", symbol.to_uppercase(), QUOTE_ASSET), symbol))
+            .collect::<HashMap<String, &String>>();
+
+        let number = |value: &serde_json::Value| value.as_str()?.parse::<f64>().ok();
+
+        Ok(tickers
+            .iter()
+            .filter_map(|ticker| {
+                let symbol = pairs.get(ticker["symbol"].as_str()?)?;
+
+                Some((
+                    symbol.to_uppercase(),
+                    SourceQuote {
+                        source: QuoteSource::Binance,
+                        price: number(&ticker["lastPrice"])?,
+                        volume_24h: number(&ticker["quoteVolume"]),
+                    },
+                ))
+            })
+            .collect())
+    }
+}
+
+/// The provider's quotes combined with the configured secondary sources.
+pub(crate) mod aggregated {
+    use std::{collections::HashMap, sync::Arc};
+
+    use tokio::sync::Mutex;
+
+    use crate::{
+        config::Config,
+        domain::provider::aggregation::{aggregate, join_sources, QuoteSource, SourceQuote},
+        driven::repository::ProviderRepository,
+        driving::tauri::coins::CoinsResponse,
+    };
+
+    use super::{binance, coingecko, coinmarketcap, FetchError};
+
+    /// Fetches the latest quotes for `ids` from the provider and, when secondary sources
+    /// are configured, queries them for `symbols` at the same time and replaces each price
+    /// with the aggregate. The sources that contributed are recorded on every quote. A
+    /// failing secondary source is skipped; only the provider is required.
+    pub async fn fetch_ids<R: ProviderRepository>(
+        repository: Arc<Mutex<R>>,
+        config: &Config,
+        ids: Vec<u32>,
+        symbols: Vec<String>,
+        token: String,
+    ) -> Result<CoinsResponse, FetchError> {
+        if config.aggregation.sources.is_empty() || symbols.is_empty() {
+            return coinmarketcap::fetch_ids(repository, config, ids, token).await;
+        }
+
+        let (fetched, secondary) = tokio::join!(
+            coinmarketcap::fetch_ids(repository, config, ids, token),
+            secondary_quotes(config, &symbols),
+        );
+        let mut fetched = fetched?;
+
+        for coin in fetched.coins.iter_mut() {
+            let mut quotes = vec![SourceQuote {
+                source: QuoteSource::CoinMarketCap,
+                price: coin.price,
+                volume_24h: Some(coin.volume_24h),
+            }];
+            quotes.extend(
+                secondary
+                    .iter()
+                    .filter_map(|quotes| quotes.get(&coin.symbol.to_uppercase()))
+                    .cloned(),
+            );
+
+            let Some(aggregated) = aggregate(
+                &quotes,
+                config.aggregation.method,
+                config.aggregation.max_deviation,
+            ) else {
+                continue;
+            };
+
+            if !aggregated.rejected.is_empty() {
+                log::warn!(
+                    "Dropped outlying {} prices of {}",
+                    join_sources(&aggregated.rejected),
+                    coin.symbol
+                );
             }
-            Err(e) => Err(format!("Error: {}", e)),
+
+            coin.price = aggregated.price;
+            coin.sources = join_sources(&aggregated.sources);
         }
+
+        Ok(fetched)
+    }
+
+    /// Quotes of every configured secondary source, queried concurrently.
+    async fn secondary_quotes(
+        config: &Config,
+        symbols: &[String],
+    ) -> Vec<HashMap<String, SourceQuote>> {
+        let enabled = |source: QuoteSource| config.aggregation.sources.contains(&source);
+
+        let (coingecko, binance) = tokio::join!(
+            async {
+                if enabled(QuoteSource::CoinGecko) {
+                    Some(coingecko::fetch_quotes(config, symbols).await)
+                } else {
+                    None
+                }
+            },
+            async {
+                if enabled(QuoteSource::Binance) {
+                    Some(binance::fetch_quotes(config, symbols).await)
+                } else {
+                    None
+                }
+            },
+        );
+
+        [
+            (QuoteSource::CoinGecko, coingecko),
+            (QuoteSource::Binance, binance),
+        ]
+        .into_iter()
+        .filter_map(|(source, result)| match result? {
+            Ok(quotes) => Some(quotes),
+            Err(e) => {
+                log::warn!("Could not fetch {} quotes: {}", source, e);
+                None
+            }
+        })
+        .collect()
     }
 }
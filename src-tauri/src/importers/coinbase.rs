@@ -0,0 +1,233 @@
+//! Coinbase exports: Advanced Trade fills (`portfolio,trade id,product,side,created at,...`)
+//! and the retail transaction history, which starts with a few lines of preamble before
+//! its `ID,Timestamp,Transaction Type,...` header.
+use crate::domain::transaction::transaction::{Exchange, Trade, TradeSide};
+
+use super::{parse_number, parse_timestamp, reader, split_pair, Columns, ParsedTrades};
+
+pub fn parse(content: &str) -> Result<ParsedTrades, String> {
+    let start = content
+        .lines()
+        .position(|line| {
+            let line = line.to_lowercase();
+            line.contains("trade id") || line.contains("timestamp")
+        })
+        .ok_or_else(|| "Missing header row".to_string())?;
+    let content = content
+        .lines()
+        .skip(start)
+        .collect::<Vec<&str>>()
+        .join("\n");
+
+    let mut reader = reader(&content);
+    let columns = Columns::new(reader.headers().map_err(|e| e.to_string())?);
+    let fills = columns.has("trade id");
+
+    if fills {
+        columns.require(&[
+            "trade id",
+            "product",
+            "side",
+            "created at",
+            "size",
+            "price",
+            "fee",
+        ])?;
+    } else {
+        columns.require(&[
+            "timestamp",
+            "transaction type",
+            "asset",
+            "quantity transacted",
+            "price currency",
+            "price at transaction",
+        ])?;
+    }
+
+    let mut parsed = ParsedTrades::default();
+
+    for (index, record) in reader.records().enumerate() {
+        let row = index + 1;
+
+        match record.map_err(|e| e.to_string()) {
+            Ok(record) if fills => parsed.push(row, fill(&columns, &record)),
+            Ok(record) => {
+                if let Some(trade) = transaction(&columns, &record).transpose() {
+                    parsed.push(row, trade);
+                }
+            }
+            Err(e) => parsed.push(row, Err(e)),
+        }
+    }
+
+    Ok(parsed)
+}
+
+fn fill(columns: &Columns, record: &csv::StringRecord) -> Result<Trade, String> {
+    let product = columns.get(record, "product")?;
+    let (base, quote) =
+        split_pair(product).ok_or_else(|| format!("Unknown product {}", product))?;
+    let price = parse_number(columns.get(record, "price")?)?;
+    let size = parse_number(columns.get(record, "size")?)?;
+
+    // Orders placed in quote currency report their size in it too
+    let quantity = match columns.get(record, "size unit") {
+        Ok(unit) if unit.eq_ignore_ascii_case(&quote) && price > 0.0 => size / price,
+        _ => size,
+    };
+    let fee_asset = columns
+        .get(record, "price/fee/total unit")
+        .map(|unit| unit.to_uppercase())
+        .unwrap_or_else(|_| quote.clone());
+
+    Ok(Trade {
+        exchange: Exchange::Coinbase,
+        trade_id: columns.get(record, "trade id")?.to_string(),
+        executed_at: parse_timestamp(columns.get(record, "created at")?)?,
+        side: columns.get(record, "side")?.parse()?,
+        base,
+        quote,
+        quantity,
+        price,
+        fee: parse_number(columns.get(record, "fee")?)?,
+        fee_asset,
+    })
+}
+
+/// Returns `None` for rows that are not trades, such as sends, receives or rewards.
+fn transaction(columns: &Columns, record: &csv::StringRecord) -> Result<Option<Trade>, String> {
+    let kind = columns.get(record, "transaction type")?.to_lowercase();
+    let side = match kind.trim_start_matches("advanced trade ") {
+        "buy" => TradeSide::Buy,
+        "sell" => TradeSide::Sell,
+        _ => return Ok(None),
+    };
+
+    let executed_at = columns.get(record, "timestamp")?;
+    let base = columns.get(record, "asset")?.to_uppercase();
+    let quote = columns.get(record, "price currency")?.to_uppercase();
+    let quantity = columns.get(record, "quantity transacted")?;
+
+    // Older exports have no ID column; derive one from the row like for Binance
+    let trade_id = match columns.get(record, "id") {
+        Ok(id) if !id.is_empty() => id.to_string(),
+        _ => [executed_at, kind.as_str(), base.as_str(), quantity].join("|"),
+    };
+    let fee = match columns.get(record, "fees and/or spread") {
+        Ok(fee) if !fee.is_empty() => parse_number(fee)?,
+        _ => 0.0,
+    };
+
+    Ok(Some(Trade {
+        exchange: Exchange::Coinbase,
+        trade_id,
+        executed_at: parse_timestamp(executed_at)?,
+        side,
+        quantity: parse_number(quantity)?,
+        price: parse_number(columns.get(record, "price at transaction")?)?,
+        fee,
+        fee_asset: quote.clone(),
+        base,
+        quote,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn advanced_trade_fills_are_parsed() {
+        let content = "\
+portfolio,trade id,product,side,created at,size,size unit,price,fee,total,price/fee/total unit
+default,1001,BTC-USD,BUY,2024-03-09T16:00:00.000Z,0.5,BTC,68000,1.5,-34001.5,USD
+default,1002,ETH-USD,SELL,2024-03-09T17:00:00.000Z,700,USD,3500,0.7,699.3,USD
+";
+        let parsed = parse(content).unwrap();
+
+        assert!(parsed.errors.is_empty());
+        assert_eq!(
+            parsed.trades[0].1,
+            Trade {
+                exchange: Exchange::Coinbase,
+                trade_id: "1001".to_string(),
+                executed_at: "2024-03-09T16:00:00Z".to_string(),
+                side: TradeSide::Buy,
+                base: "BTC".to_string(),
+                quote: "USD".to_string(),
+                quantity: 0.5,
+                price: 68000.0,
+                fee: 1.5,
+                fee_asset: "USD".to_string(),
+            }
+        );
+        // Sized in USD, so the quantity is converted to ETH
+        assert_eq!(parsed.trades[1].1.quantity, 0.2);
+    }
+
+    #[test]
+    fn transaction_history_skips_its_preamble_and_other_rows() {
+        let content = "\
+You can use this transaction report to inform your likely tax obligations.
+
+User,example,5f1c2d
+ID,Timestamp,Transaction Type,Asset,Quantity Transacted,Price Currency,Price at Transaction,Subtotal,Total (inclusive of fees and/or spread),Fees and/or spread,Notes
+abc1,2024-03-09 16:00:00 UTC,Buy,BTC,0.001,USD,$68000.00,$68.00,$69.00,$1.00,Bought BTC
+abc2,2024-03-09 17:00:00 UTC,Send,BTC,0.0005,USD,$68100.00,,,,Sent BTC
+abc3,2024-03-09 18:00:00 UTC,Advanced Trade Sell,ETH,1,EUR,\"€3,200.00\",€3200,€3190,€10,Sold ETH
+";
+        let parsed = parse(content).unwrap();
+
+        assert!(parsed.errors.is_empty());
+        assert_eq!(parsed.trades.len(), 2);
+        assert_eq!(
+            parsed.trades[0],
+            (
+                1,
+                Trade {
+                    exchange: Exchange::Coinbase,
+                    trade_id: "abc1".to_string(),
+                    executed_at: "2024-03-09T16:00:00Z".to_string(),
+                    side: TradeSide::Buy,
+                    base: "BTC".to_string(),
+                    quote: "USD".to_string(),
+                    quantity: 0.001,
+                    price: 68000.0,
+                    fee: 1.0,
+                    fee_asset: "USD".to_string(),
+                }
+            )
+        );
+
+        let (row, eth) = &parsed.trades[1];
+        assert_eq!(*row, 3);
+        assert_eq!(
+            (eth.side, eth.price, eth.fee),
+            (TradeSide::Sell, 3200.0, 10.0)
+        );
+        assert_eq!(eth.fee_asset, "EUR");
+    }
+
+    #[test]
+    fn older_transaction_history_derives_trade_ids() {
+        let content = "\
+Timestamp,Transaction Type,Asset,Quantity Transacted,Price Currency,Price at Transaction,Subtotal,Total (inclusive of fees and/or spread),Fees and/or spread,Notes
+2021-01-05T10:00:00Z,Buy,ETH,2,USD,1000,2000,2010,10,Bought ETH
+";
+        let parsed = parse(content).unwrap();
+
+        assert_eq!(
+            parsed.trades[0].1.trade_id,
+            "2021-01-05T10:00:00Z|buy|ETH|2"
+        );
+        assert_eq!(parsed.trades[0].1.quantity, 2.0);
+    }
+
+    #[test]
+    fn other_files_are_rejected() {
+        assert_eq!(
+            parse("nothing,here\n1,2\n").unwrap_err(),
+            "Missing header row"
+        );
+    }
+}
@@ -0,0 +1,153 @@
+//! Kraken `trades.csv` exports (`txid,ordertxid,pair,time,type,ordertype,price,cost,fee,vol,...`).
+use crate::domain::transaction::transaction::{Exchange, Trade};
+
+use super::{parse_number, parse_timestamp, reader, split_pair, Columns, ParsedTrades};
+
+/// Kraken's legacy asset codes, prefixed with X for crypto and Z for fiat.
+const ASSETS: [(&str, &str); 20] = [
+    ("XXBT", "BTC"),
+    ("XBT", "BTC"),
+    ("XETH", "ETH"),
+    ("XXDG", "DOGE"),
+    ("XDG", "DOGE"),
+    ("XXRP", "XRP"),
+    ("XLTC", "LTC"),
+    ("XXLM", "XLM"),
+    ("XXMR", "XMR"),
+    ("XETC", "ETC"),
+    ("XZEC", "ZEC"),
+    ("XREP", "REP"),
+    ("XMLN", "MLN"),
+    ("ZUSD", "USD"),
+    ("ZEUR", "EUR"),
+    ("ZGBP", "GBP"),
+    ("ZJPY", "JPY"),
+    ("ZCAD", "CAD"),
+    ("ZAUD", "AUD"),
+    ("ZCHF", "CHF"),
+];
+
+pub fn parse(content: &str) -> Result<ParsedTrades, String> {
+    let mut reader = reader(content);
+    let columns = Columns::new(reader.headers().map_err(|e| e.to_string())?);
+
+    columns.require(&["txid", "pair", "time", "type", "price", "fee", "vol"])?;
+
+    let mut parsed = ParsedTrades::default();
+
+    for (index, record) in reader.records().enumerate() {
+        let trade = record
+            .map_err(|e| e.to_string())
+            .and_then(|record| trade(&columns, &record));
+
+        parsed.push(index + 1, trade);
+    }
+
+    Ok(parsed)
+}
+
+fn trade(columns: &Columns, record: &csv::StringRecord) -> Result<Trade, String> {
+    let pair = columns.get(record, "pair")?;
+    let (base, quote) = split_kraken_pair(pair).ok_or_else(|| format!("Unknown pair {}", pair))?;
+
+    Ok(Trade {
+        exchange: Exchange::Kraken,
+        trade_id: columns.get(record, "txid")?.to_string(),
+        executed_at: parse_timestamp(columns.get(record, "time")?)?,
+        side: columns.get(record, "type")?.parse()?,
+        quantity: parse_number(columns.get(record, "vol")?)?,
+        price: parse_number(columns.get(record, "price")?)?,
+        fee: parse_number(columns.get(record, "fee")?)?,
+        fee_asset: quote.clone(),
+        base,
+        quote,
+    })
+}
+
+/// Splits pairs such as `XXBTZUSD`, `XBTUSDT` or `DOT/EUR` and maps Kraken's asset codes
+/// to common symbols.
+fn split_kraken_pair(pair: &str) -> Option<(String, String)> {
+    let pair = pair.trim().to_uppercase();
+
+    let (base, quote) = match ASSETS
+        .iter()
+        .filter(|(code, _)| code.len() == 4)
+        .find_map(|(code, _)| pair.strip_suffix(code).map(|base| (base, *code)))
+    {
+        Some((base, quote)) if !base.is_empty() && !pair.contains(['/', '-']) => {
+            (base.to_string(), quote.to_string())
+        }
+        _ => split_pair(&pair)?,
+    };
+
+    Some((asset(&base), asset(&quote)))
+}
+
+fn asset(code: &str) -> String {
+    ASSETS
+        .iter()
+        .find(|(kraken, _)| *kraken == code)
+        .map(|(_, symbol)| symbol.to_string())
+        .unwrap_or_else(|| code.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::transaction::transaction::TradeSide;
+
+    use super::*;
+
+    #[test]
+    fn trades_are_parsed() {
+        let content = "\
+txid,ordertxid,pair,time,type,ordertype,price,cost,fee,vol,margin,misc,ledgers
+TQ2AAA-BBBBB-CCCCCC,OQ2AAA-BBBBB-CCCCCC,XXBTZUSD,2024-03-09 16:00:00.1234,buy,limit,68000.0,68.0,0.1,0.001,0,,
+TQ2DDD-EEEEE-FFFFFF,OQ2DDD-EEEEE-FFFFFF,XETHXXBT,2024-03-09 17:00:00.5678,sell,market,0.05,0.1,0.0002,2,0,,
+TQ2GGG-HHHHH-IIIIII,OQ2GGG-HHHHH-IIIIII,FOO,2024-03-09 18:00:00,buy,market,1,1,0,1,0,,
+";
+        let parsed = parse(content).unwrap();
+
+        assert_eq!(
+            parsed.trades[0].1,
+            Trade {
+                exchange: Exchange::Kraken,
+                trade_id: "TQ2AAA-BBBBB-CCCCCC".to_string(),
+                executed_at: "2024-03-09T16:00:00Z".to_string(),
+                side: TradeSide::Buy,
+                base: "BTC".to_string(),
+                quote: "USD".to_string(),
+                quantity: 0.001,
+                price: 68000.0,
+                fee: 0.1,
+                fee_asset: "USD".to_string(),
+            }
+        );
+
+        let eth = &parsed.trades[1].1;
+        assert_eq!((eth.base.as_str(), eth.quote.as_str()), ("ETH", "BTC"));
+        assert_eq!((eth.side, eth.fee_asset.as_str()), (TradeSide::Sell, "BTC"));
+
+        assert_eq!(parsed.errors.len(), 1);
+        assert_eq!(parsed.errors[0].row, 3);
+        assert_eq!(parsed.errors[0].message, "Unknown pair FOO");
+    }
+
+    #[test]
+    fn asset_codes_are_mapped() {
+        let pair = |base: &str, quote: &str| Some((base.to_string(), quote.to_string()));
+
+        assert_eq!(split_kraken_pair("XXBTZEUR"), pair("BTC", "EUR"));
+        assert_eq!(split_kraken_pair("XBTUSDT"), pair("BTC", "USDT"));
+        assert_eq!(split_kraken_pair("XDG/USD"), pair("DOGE", "USD"));
+        assert_eq!(split_kraken_pair("DOT/EUR"), pair("DOT", "EUR"));
+        assert_eq!(split_kraken_pair("SOLUSD"), pair("SOL", "USD"));
+    }
+
+    #[test]
+    fn other_files_are_rejected() {
+        assert_eq!(
+            parse("txid,pair,time\n").unwrap_err(),
+            "Missing column type"
+        );
+    }
+}
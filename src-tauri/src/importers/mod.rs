@@ -0,0 +1,219 @@
+//! Parsers for the trade-history CSV exports of supported exchanges. They only normalize
+//! rows into `Trade`s; resolving symbols to coins is up to `domain::transaction`.
+pub(crate) mod binance;
+pub(crate) mod coinbase;
+pub(crate) mod kraken;
+
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, NaiveDateTime, SecondsFormat, Utc};
+
+use crate::domain::{
+    transaction::transaction::{Exchange, Trade},
+    transfer::transfer::RowError,
+};
+
+/// Fiat currencies; trades quoted in them have no quote coin.
+pub(crate) const FIAT: [&str; 16] = [
+    "USD", "EUR", "GBP", "JPY", "AUD", "CAD", "CHF", "TRY", "BRL", "RUB", "UAH", "PLN", "NGN",
+    "ZAR", "KRW", "INR",
+];
+
+/// Quote assets used to split pairs written without a separator, e.g. `BTCUSDT`. Longer
+/// symbols are tried first.
+const QUOTE_ASSETS: [&str; 24] = [
+    "FDUSD", "USDT", "USDC", "BUSD", "TUSD", "BIDR", "IDRT", "DOGE", "DAI", "BTC", "ETH", "BNB",
+    "EUR", "USD", "GBP", "TRY", "BRL", "AUD", "JPY", "UAH", "PLN", "RUB", "ZAR", "SOL",
+];
+
+#[derive(Debug, Default)]
+pub struct ParsedTrades {
+    /// Trades with their 1-based row number
+    pub trades: Vec<(usize, Trade)>,
+    pub errors: Vec<RowError>,
+}
+
+impl ParsedTrades {
+    fn push(&mut self, row: usize, trade: Result<Trade, String>) {
+        match trade {
+            Ok(trade) => self.trades.push((row, trade)),
+            Err(message) => self.errors.push(RowError {
+                row,
+                message,
+                details: BTreeMap::new(),
+            }),
+        }
+    }
+}
+
+/// Parses a trade-history export. Fails only when the file is not a CSV export of the given
+/// exchange; bad rows are reported in `ParsedTrades::errors`.
+pub fn parse(exchange: Exchange, content: &str) -> Result<ParsedTrades, String> {
+    match exchange {
+        Exchange::Binance => binance::parse(content),
+        Exchange::Kraken => kraken::parse(content),
+        Exchange::Coinbase => coinbase::parse(content),
+    }
+}
+
+/// Column lookup by header name, ignoring case and surrounding whitespace.
+struct Columns(BTreeMap<String, usize>);
+
+impl Columns {
+    fn new(headers: &csv::StringRecord) -> Self {
+        Self(
+            headers
+                .iter()
+                .enumerate()
+                .map(|(index, header)| (header.trim().to_lowercase(), index))
+                .collect(),
+        )
+    }
+
+    fn has(&self, name: &str) -> bool {
+        self.0.contains_key(name)
+    }
+
+    fn require(&self, names: &[&str]) -> Result<(), String> {
+        match names.iter().find(|name| !self.has(name)) {
+            Some(name) => Err(format!("Missing column {}", name)),
+            None => Ok(()),
+        }
+    }
+
+    fn get<'a>(&self, record: &'a csv::StringRecord, name: &str) -> Result<&'a str, String> {
+        self.0
+            .get(name)
+            .and_then(|index| record.get(*index))
+            .map(|value| value.trim())
+            .ok_or_else(|| format!("Missing value for {}", name))
+    }
+}
+
+fn reader(content: &str) -> csv::Reader<&[u8]> {
+    csv::ReaderBuilder::new()
+        .flexible(true)
+        .trim(csv::Trim::All)
+        .from_reader(content.as_bytes())
+}
+
+/// Splits `BTC-USD`, `BTC/USD` or `BTCUSDT` into base and quote symbols.
+fn split_pair(pair: &str) -> Option<(String, String)> {
+    let pair = pair.trim().to_uppercase();
+
+    if let Some((base, quote)) = pair.split_once(['-', '/']) {
+        return Some((base.to_string(), quote.to_string()));
+    }
+
+    let mut quotes = QUOTE_ASSETS.to_vec();
+    quotes.sort_by_key(|quote| std::cmp::Reverse(quote.len()));
+
+    let splits = quotes
+        .into_iter()
+        .filter_map(|quote| {
+            pair.strip_suffix(quote)
+                .filter(|base| !base.is_empty())
+                .map(|base| (base.to_string(), quote.to_string()))
+        })
+        .collect::<Vec<(String, String)>>();
+
+    // `DOTUSD` could also be DO/TUSD; prefer bases of at least three letters
+    splits
+        .iter()
+        .find(|(base, _)| base.len() >= 3)
+        .or(splits.first())
+        .cloned()
+}
+
+/// Parses a number, ignoring thousands separators and currency signs.
+fn parse_number(value: &str) -> Result<f64, String> {
+    value
+        .trim()
+        .trim_start_matches(['$', '€', '£'])
+        .replace(',', "")
+        .parse::<f64>()
+        .map_err(|_| format!("Invalid number {}", value))
+}
+
+/// Splits an amount with its asset appended, e.g. `0.00100000BTC`.
+fn parse_amount(value: &str) -> Result<(f64, String), String> {
+    let value = value.trim();
+    let split = value
+        .find(|c: char| c.is_ascii_alphabetic())
+        .ok_or_else(|| format!("Missing asset in {}", value))?;
+    let (number, asset) = value.split_at(split);
+
+    Ok((parse_number(number)?, asset.to_uppercase()))
+}
+
+/// Normalizes a UTC timestamp in one of the usual export formats to RFC 3339.
+fn parse_timestamp(value: &str) -> Result<String, String> {
+    let value = value.trim();
+
+    if let Ok(date) = DateTime::parse_from_rfc3339(value) {
+        return Ok(date
+            .with_timezone(&Utc)
+            .to_rfc3339_opts(SecondsFormat::Secs, true));
+    }
+
+    let value = value.trim_end_matches(" UTC");
+
+    [
+        "%Y-%m-%d %H:%M:%S%.f",
+        "%Y-%m-%d %H:%M:%S",
+        "%Y-%m-%dT%H:%M:%S%.f",
+    ]
+    .iter()
+    .find_map(|format| NaiveDateTime::parse_from_str(value, format).ok())
+    .map(|date| date.and_utc().to_rfc3339_opts(SecondsFormat::Secs, true))
+    .ok_or_else(|| format!("Invalid timestamp {}", value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pairs_are_split() {
+        let pair = |base: &str, quote: &str| Some((base.to_string(), quote.to_string()));
+
+        assert_eq!(split_pair("eth-usd"), pair("ETH", "USD"));
+        assert_eq!(split_pair("SOL/EUR"), pair("SOL", "EUR"));
+        assert_eq!(split_pair("BTCUSDT"), pair("BTC", "USDT"));
+        assert_eq!(split_pair("DOTUSD"), pair("DOT", "USD"));
+        assert_eq!(split_pair("OPUSDT"), pair("OP", "USDT"));
+        assert_eq!(split_pair("USDT"), None);
+        assert_eq!(split_pair("XYZ"), None);
+    }
+
+    #[test]
+    fn numbers_and_amounts_are_parsed() {
+        assert_eq!(parse_number("$1,234.50"), Ok(1234.5));
+        assert!(parse_number("abc").is_err());
+        assert_eq!(
+            parse_amount("0.00100000BTC"),
+            Ok((0.001, "BTC".to_string()))
+        );
+        assert_eq!(parse_amount("12.5usdt"), Ok((12.5, "USDT".to_string())));
+        assert!(parse_amount("12.5").is_err());
+    }
+
+    #[test]
+    fn timestamps_are_normalized_to_utc() {
+        for value in [
+            "2024-03-09 16:00:00",
+            "2024-03-09 16:00:00.1234",
+            "2024-03-09 16:00:00 UTC",
+            "2024-03-09T16:00:00",
+            "2024-03-09T18:00:00+02:00",
+        ] {
+            assert_eq!(
+                parse_timestamp(value),
+                Ok("2024-03-09T16:00:00Z".to_string()),
+                "{}",
+                value
+            );
+        }
+        assert!(parse_timestamp("yesterday").is_err());
+    }
+}
@@ -1,7 +1,8 @@
 #![allow(dead_code)]
 use std::sync::Arc;
 
-use config::Config;
+use config::{profile::ActiveProfile, Config};
+use domain::{events::EventBus, provider::connectivity::Connectivity, stream::ticker::LivePrices};
 use driven::repository::sqlite::SqliteRepository;
 use tauri::{async_runtime::Mutex, Manager};
 
@@ -10,10 +11,22 @@ mod domain;
 mod driven;
 mod driving;
 mod fetch;
+mod importers;
+mod stream;
 
 struct AppState {
     config: config::Config,
     sqlite_repo: Arc<Mutex<SqliteRepository>>,
+    events: EventBus,
+    connectivity: Connectivity,
+    live_prices: LivePrices,
+    /// The profile whose database `sqlite_repo` is open on
+    profile: ActiveProfile,
+}
+
+/// Entry point of the headless `cryptoscan-cli` binary; returns the process exit code.
+pub fn run_cli() -> i32 {
+    driving::cli::run()
 }
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
@@ -21,15 +34,46 @@ pub fn run() {
     tauri::Builder::default()
         .plugin(tauri_plugin_shell::init())
         .setup(|app| {
-            let config = Config::from(String::from(app.path().app_data_dir()?.to_str().unwrap()));
+            let config = Config::load(
+                String::from(app.path().app_data_dir()?.to_str().unwrap()),
+                String::from(app.path().app_config_dir()?.to_str().unwrap()),
+            )?;
+
+            app.handle().plugin(
+                tauri_plugin_log::Builder::default()
+                    .level(config.log_level)
+                    .build(),
+            )?;
+
             let sqlite_repo = Arc::new(Mutex::new(SqliteRepository::new(&config.sqlite)));
 
-            let state = AppState {
+            let events = EventBus::new();
+            driving::tauri::events::forward(app.handle().clone(), &events);
+
+            let profile = ActiveProfile::new(config.profile.clone());
+
+            let state = Arc::new(AppState {
                 config,
                 sqlite_repo,
-            };
+                events,
+                connectivity: Connectivity::new(),
+                live_prices: LivePrices::new(),
+                profile,
+            });
+
+            driving::tauri::coins::refresh_periodically(state.clone());
+            driving::tauri::connectivity::watch(state.clone());
+            driving::tauri::stream::start(state.clone());
+            driving::tauri::trash::purge_periodically(state.clone());
+
+            let server_state = state.clone();
+            tauri::async_runtime::spawn(async move {
+                if let Err(e) = driving::http::serve(server_state).await {
+                    log::error!("{}", e);
+                }
+            });
 
-            app.manage(Arc::new(state));
+            app.manage(state);
 
             Ok(())
         })
@@ -41,11 +85,53 @@ pub fn run() {
             driving::tauri::coins::find_coins,
             driving::tauri::coins::delete_coin,
             driving::tauri::coins::delete_all_coins,
+            driving::tauri::trash::get_deleted_coins,
+            driving::tauri::trash::restore_coin,
+            driving::tauri::trash::undo_delete,
             driving::tauri::coins::get_all_coins,
             driving::tauri::coins::fetch_coins_by_id,
             driving::tauri::coins::fetch_coins_by_symbol,
+            driving::tauri::coins::get_coin_details,
+            driving::tauri::catalog::refresh_catalog,
+            driving::tauri::catalog::search_catalog,
+            driving::tauri::catalog::add_top_coins,
+            driving::tauri::candles::get_candles,
+            driving::tauri::candles::backfill_candles,
+            driving::tauri::indicators::get_indicators,
+            driving::tauri::analytics::get_correlations,
+            driving::tauri::analytics::get_portfolio_risk,
             driving::tauri::settings::create_setting,
             driving::tauri::settings::find_setting,
+            driving::tauri::config::get_config,
+            driving::tauri::connectivity::get_connectivity,
+            driving::tauri::stream::get_live_prices,
+            driving::tauri::profiles::get_profiles,
+            driving::tauri::profiles::create_profile,
+            driving::tauri::profiles::switch_profile,
+            driving::tauri::profiles::delete_profile,
+            driving::tauri::usage::get_api_usage,
+            driving::tauri::usage::get_plan_info,
+            driving::tauri::api_keys::add_api_key,
+            driving::tauri::api_keys::get_api_keys,
+            driving::tauri::api_keys::set_api_key_enabled,
+            driving::tauri::api_keys::test_api_key,
+            driving::tauri::api_keys::delete_api_key,
+            driving::tauri::api_keys::set_api_key,
+            driving::tauri::transfer::export_data,
+            driving::tauri::transfer::import_data,
+            driving::tauri::audit::get_audit_log,
+            driving::tauri::transactions::import_transactions,
+            driving::tauri::transactions::get_transactions,
+            driving::tauri::transactions::delete_transaction,
+            driving::tauri::watchlists::create_watchlist,
+            driving::tauri::watchlists::rename_watchlist,
+            driving::tauri::watchlists::delete_watchlist,
+            driving::tauri::watchlists::find_watchlist,
+            driving::tauri::watchlists::get_all_watchlists,
+            driving::tauri::watchlists::add_watchlist_coin,
+            driving::tauri::watchlists::remove_watchlist_coin,
+            driving::tauri::watchlists::reorder_watchlist,
+            driving::tauri::watchlists::refresh_watchlist,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
@@ -0,0 +1,320 @@
+use std::time::{Duration, Instant};
+
+use futures_util::{SinkExt, StreamExt};
+use serde_json::json;
+use tokio::sync::mpsc::UnboundedSender;
+use tokio_tungstenite::{connect_async, tungstenite::Message};
+
+use crate::{config::StreamExchange, domain::stream::ticker::Ticker};
+
+/// How often the connection is checked and a ping sent
+const PING_INTERVAL: Duration = Duration::from_secs(20);
+/// A connection without any message for this long, pongs included, is considered dead
+const IDLE_TIMEOUT: Duration = Duration::from_secs(60);
+const MIN_BACKOFF: Duration = Duration::from_secs(1);
+const MAX_BACKOFF: Duration = Duration::from_secs(60);
+
+/// Binance pairs are quoted in USDT, which stands in for USD
+const BINANCE_QUOTE: &str = "USDT";
+const KRAKEN_QUOTE: &str = "USD";
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum StreamEvent {
+    Connected,
+    Disconnected(String),
+    Ticker(Ticker),
+}
+
+/// Streams the tickers of `symbols` from `exchange` at `url` into `events`, reconnecting
+/// with exponential backoff whenever the connection fails or goes silent. Returns once
+/// `events` is closed.
+pub async fn stream_tickers(
+    exchange: StreamExchange,
+    url: String,
+    symbols: Vec<String>,
+    events: UnboundedSender<StreamEvent>,
+) {
+    let mut backoff = MIN_BACKOFF;
+
+    loop {
+        let reason = match read_tickers(exchange, &url, &symbols, &events, &mut backoff).await {
+            Ok(()) => "Connection closed".to_string(),
+            Err(e) => e,
+        };
+
+        if events.send(StreamEvent::Disconnected(reason)).is_err() {
+            return;
+        }
+
+        tokio::time::sleep(backoff).await;
+        backoff = (backoff * 2).min(MAX_BACKOFF);
+    }
+}
+
+/// Connects, subscribes and forwards tickers until the connection ends. Resets `backoff`
+/// once connected.
+async fn read_tickers(
+    exchange: StreamExchange,
+    url: &str,
+    symbols: &[String],
+    events: &UnboundedSender<StreamEvent>,
+    backoff: &mut Duration,
+) -> Result<(), String> {
+    let (mut socket, _) = connect_async(url).await.map_err(|e| e.to_string())?;
+
+    socket
+        .send(Message::Text(subscription(exchange, symbols)))
+        .await
+        .map_err(|e| e.to_string())?;
+
+    *backoff = MIN_BACKOFF;
+
+    if events.send(StreamEvent::Connected).is_err() {
+        return Ok(());
+    }
+
+    let mut heartbeat = tokio::time::interval(PING_INTERVAL);
+    heartbeat.tick().await;
+    let mut last_message = Instant::now();
+
+    loop {
+        tokio::select! {
+            message = socket.next() => {
+                let message = match message {
+                    Some(Ok(message)) => message,
+                    Some(Err(e)) => return Err(e.to_string()),
+                    None => return Ok(()),
+                };
+                last_message = Instant::now();
+
+                match message {
+                    Message::Text(text) => {
+                        for ticker in parse_tickers(exchange, &text) {
+                            if events.send(StreamEvent::Ticker(ticker)).is_err() {
+                                return Ok(());
+                            }
+                        }
+                    }
+                    Message::Close(_) => return Ok(()),
+                    // Pings are answered by the library, pongs only show the connection is alive
+                    _ => {}
+                }
+            }
+            _ = heartbeat.tick() => {
+                if last_message.elapsed() >= IDLE_TIMEOUT {
+                    return Err("Heartbeat timed out".to_string());
+                }
+
+                socket
+                    .send(Message::Ping(vec![]))
+                    .await
+                    .map_err(|e| e.to_string())?;
+            }
+        }
+    }
+}
+
+/// The message subscribing to the tickers of `symbols`.
+fn subscription(exchange: StreamExchange, symbols: &[String]) -> String {
+    match exchange {
+        StreamExchange::Binance => json!({
+            "method": "SUBSCRIBE",
+            "params": symbols
+                .iter()
+                .map(|symbol| format!("{}{}@miniTicker", symbol, BINANCE_QUOTE).to_lowercase())
+                .collect::<Vec<String>>(),
+            "id": 1,
+        }),
+        StreamExchange::Kraken => json!({
+            "method": "subscribe",
+            "params": {
+                "channel": "ticker",
+                "symbol": symbols
+                    .iter()
+                    .map(|symbol| format!("{}/{}", symbol.to_uppercase(), KRAKEN_QUOTE))
+                    .collect::<Vec<String>>(),
+            },
+        }),
+    }
+    .to_string()
+}
+
+/// The tickers in a text message; subscription acknowledgements, heartbeats and anything
+/// unexpected yield none.
+fn parse_tickers(exchange: StreamExchange, text: &str) -> Vec<Ticker> {
+    let Ok(message) = serde_json::from_str::<serde_json::Value>(text) else {
+        return vec![];
+    };
+    let number = |value: &serde_json::Value| {
+        value
+            .as_f64()
+            .or_else(|| value.as_str().and_then(|value| value.parse().ok()))
+    };
+
+    match exchange {
+        StreamExchange::Binance => {
+            if message["e"] != "24hrMiniTicker" {
+                return vec![];
+            }
+
+            let ticker = || {
+                Some(Ticker {
+                    symbol: message["s"]
+                        .as_str()?
+                        .strip_suffix(BINANCE_QUOTE)?
+                        .to_string(),
+                    price: number(&message["c"])?,
+                    volume_24h: number(&message["q"]),
+                    time: message["E"].as_i64()? / 1000,
+                })
+            };
+
+            ticker().into_iter().collect()
+        }
+        StreamExchange::Kraken => {
+            if message["channel"] != "ticker" {
+                return vec![];
+            }
+
+            let now = chrono::Utc::now().timestamp();
+
+            message["data"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .filter_map(|data| {
+                    let volume = number(&data["volume"])
+                        .zip(number(&data["vwap"]))
+                        .map(|(volume, vwap)| volume * vwap);
+
+                    Some(Ticker {
+                        symbol: data["symbol"]
+                            .as_str()?
+                            .strip_suffix(&format!("/{}", KRAKEN_QUOTE))?
+                            .to_string(),
+                        price: number(&data["last"])?,
+                        volume_24h: volume,
+                        time: data["timestamp"]
+                            .as_str()
+                            .and_then(|time| chrono::DateTime::parse_from_rfc3339(time).ok())
+                            .map(|time| time.timestamp())
+                            .unwrap_or(now),
+                    })
+                })
+                .collect()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::{net::TcpListener, sync::mpsc, time::timeout};
+    use tokio_tungstenite::accept_async;
+
+    use super::*;
+
+    const WAIT: Duration = Duration::from_secs(5);
+
+    #[test]
+    fn binance_mini_tickers_are_parsed() {
+        let text = r#"{"e":"24hrMiniTicker","E":1710000000123,"s":"BTCUSDT","c":"68000.50","o":"67000","h":"69000","l":"66000","v":"100","q":"6800000"}"#;
+
+        assert_eq!(
+            parse_tickers(StreamExchange::Binance, text),
+            vec![Ticker {
+                symbol: "BTC".to_string(),
+                price: 68000.5,
+                volume_24h: Some(6800000.0),
+                time: 1710000000,
+            }]
+        );
+        assert!(parse_tickers(StreamExchange::Binance, r#"{"result":null,"id":1}"#).is_empty());
+    }
+
+    #[test]
+    fn kraken_tickers_are_parsed() {
+        let text = r#"{"channel":"ticker","type":"update","data":[{"symbol":"ETH/USD","last":3500.25,"volume":10.0,"vwap":3400.0,"timestamp":"2024-03-09T16:00:00.000000Z"}]}"#;
+
+        assert_eq!(
+            parse_tickers(StreamExchange::Kraken, text),
+            vec![Ticker {
+                symbol: "ETH".to_string(),
+                price: 3500.25,
+                volume_24h: Some(34000.0),
+                time: 1710000000,
+            }]
+        );
+        assert!(parse_tickers(StreamExchange::Kraken, r#"{"channel":"heartbeat"}"#).is_empty());
+    }
+
+    #[test]
+    fn subscriptions_name_the_pairs() {
+        let symbols = vec!["BTC".to_string(), "ETH".to_string()];
+
+        assert_eq!(
+            subscription(StreamExchange::Binance, &symbols),
+            r#"{"id":1,"method":"SUBSCRIBE","params":["btcusdt@miniTicker","ethusdt@miniTicker"]}"#
+        );
+        assert!(subscription(StreamExchange::Kraken, &symbols).contains(r#"["BTC/USD","ETH/USD"]"#));
+    }
+
+    /// A local stand-in for the exchange: it expects the subscription, sends one ticker and
+    /// hangs up, which the client must survive by reconnecting.
+    #[tokio::test]
+    async fn tickers_keep_flowing_across_reconnects() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}", listener.local_addr().unwrap());
+        let (sender, mut receiver) = mpsc::unbounded_channel();
+
+        let client = tokio::spawn(stream_tickers(
+            StreamExchange::Binance,
+            url,
+            vec!["BTC".to_string()],
+            sender,
+        ));
+
+        for (price, time) in [("68000.5", 1710000000000_i64), ("68100.25", 1710000001000)] {
+            let (tcp, _) = timeout(WAIT, listener.accept()).await.unwrap().unwrap();
+            let mut socket = accept_async(tcp).await.unwrap();
+
+            let subscription = timeout(WAIT, socket.next())
+                .await
+                .unwrap()
+                .unwrap()
+                .unwrap();
+            assert!(subscription
+                .to_text()
+                .unwrap()
+                .contains("btcusdt@miniTicker"));
+
+            socket
+                .send(Message::Text(format!(
+                    r#"{{"e":"24hrMiniTicker","E":{},"s":"BTCUSDT","c":"{}","q":"1000"}}"#,
+                    time, price
+                )))
+                .await
+                .unwrap();
+            socket.close(None).await.unwrap();
+
+            assert_eq!(
+                timeout(WAIT, receiver.recv()).await.unwrap(),
+                Some(StreamEvent::Connected)
+            );
+            assert_eq!(
+                timeout(WAIT, receiver.recv()).await.unwrap(),
+                Some(StreamEvent::Ticker(Ticker {
+                    symbol: "BTC".to_string(),
+                    price: price.parse().unwrap(),
+                    volume_24h: Some(1000.0),
+                    time: time / 1000,
+                }))
+            );
+            assert!(matches!(
+                timeout(WAIT, receiver.recv()).await.unwrap(),
+                Some(StreamEvent::Disconnected(_))
+            ));
+        }
+
+        client.abort();
+    }
+}
@@ -0,0 +1,3 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export interface AddApiKeyRequest { label: string, key: string, plan: string | null, monthly_credits: number | null, priority: number | null, }
\ No newline at end of file
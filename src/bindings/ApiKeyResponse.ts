@@ -0,0 +1,3 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export interface ApiKeyResponse { id: number, label: string, key: string, plan: string, monthly_credits: number | null, priority: number, enabled: boolean, status: string, status_message: string | null, available_at: string | null, credits_used: number, last_used_at: string | null, }
\ No newline at end of file
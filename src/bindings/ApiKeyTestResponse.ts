@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+import type { ApiKeyResponse } from "./ApiKeyResponse";
+import type { PlanInfoResponse } from "./PlanInfoResponse";
+
+export interface ApiKeyTestResponse { key: ApiKeyResponse, valid: boolean, message: string | null, plan: PlanInfoResponse | null, }
\ No newline at end of file
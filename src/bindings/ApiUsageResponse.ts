@@ -0,0 +1,5 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+import type { EndpointUsageResponse } from "./EndpointUsageResponse";
+
+export interface ApiUsageResponse { usage: Array<EndpointUsageResponse>, calls: number, credits: number, daily_credits: number | null, }
\ No newline at end of file
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+import type { AuditEntity } from "./AuditEntity";
+import type { Origin } from "./Origin";
+
+export interface AuditRecordResponse { id: number, recorded_at: number, operation: string, entity: AuditEntity, entity_id: string, before: string | null, after: string | null, origin: Origin, }
\ No newline at end of file
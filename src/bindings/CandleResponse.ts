@@ -0,0 +1,3 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export interface CandleResponse { open_time: number, open: number, high: number, low: number, close: number, volume: number, }
\ No newline at end of file
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+import type { CandleInterval } from "./CandleInterval";
+import type { CandleRange } from "./CandleRange";
+import type { CandleResponse } from "./CandleResponse";
+
+export interface CandlesResponse { coin_id: number, interval: CandleInterval, range: CandleRange, candles: Array<CandleResponse>, gaps: Array<CandleRange>, }
\ No newline at end of file
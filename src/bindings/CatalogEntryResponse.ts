@@ -0,0 +1,3 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export interface CatalogEntryResponse { id: number, slug: string, symbol: string, name: string, rank: number | null, tracked: boolean, }
\ No newline at end of file
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+import type { CoinLink } from "./CoinLink";
+import type { CoinResponse } from "./CoinResponse";
+import type { TokenContract } from "./TokenContract";
+
+export interface CoinDetailsResponse { coin: CoinResponse, description: string, logo_url: string | null, logo: string | null, links: Array<CoinLink>, tags: Array<string>, contracts: Array<TokenContract>, fetched_at: string, }
\ No newline at end of file
@@ -0,0 +1,5 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+import type { LinkKind } from "./LinkKind";
+
+export interface CoinLink { kind: LinkKind, url: string, }
\ No newline at end of file
@@ -0,0 +1,3 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export interface CoinResponse { id: number, name: string, symbol: string, price: number, volume_24h: number, percent_change_1h: number, percent_change_24h: number, percent_change_7d: number, market_cap: number, last_updated: string, age_seconds: number | null, is_stale: boolean, sources: string, }
\ No newline at end of file
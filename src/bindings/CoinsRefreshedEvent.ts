@@ -0,0 +1,5 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+import type { CoinResponse } from "./CoinResponse";
+
+export interface CoinsRefreshedEvent { coins: Array<CoinResponse>, }
\ No newline at end of file
@@ -0,0 +1,5 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+import type { ConfigSource } from "./ConfigSource";
+
+export interface ConfigEntry { key: string, value: string, source: ConfigSource, }
\ No newline at end of file
@@ -0,0 +1,5 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+import type { ConfigEntry } from "./ConfigEntry";
+
+export interface ConfigResponse { config_file: string, entries: Array<ConfigEntry>, }
\ No newline at end of file
@@ -0,0 +1,5 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+import type { CandleRange } from "./CandleRange";
+
+export interface CorrelationsResponse { coin_ids: Array<number>, range: CandleRange, observations: number, matrix: Array<Array<number | null>>, }
\ No newline at end of file
@@ -0,0 +1,3 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export interface CreateCoinRequest { id: number, name: string, symbol: string, price: number | null, volume_24h: number | null, percent_change_1h: number | null, percent_change_24h: number | null, percent_change_7d: number | null, market_cap: number | null, last_updated: string, }
\ No newline at end of file
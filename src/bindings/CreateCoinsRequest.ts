@@ -0,0 +1,5 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+import type { CreateCoinRequest } from "./CreateCoinRequest";
+
+export interface CreateCoinsRequest { coins: Array<CreateCoinRequest>, }
\ No newline at end of file
@@ -0,0 +1,5 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+import type { CoinResponse } from "./CoinResponse";
+
+export interface DeletedCoinResponse { coin: CoinResponse, deleted_at: number, deletion_id: number, }
\ No newline at end of file
@@ -0,0 +1,3 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export interface EndpointUsageResponse { day: string, endpoint: string, calls: number, errors: number, credits: number, avg_latency_ms: number, max_latency_ms: number, }
\ No newline at end of file
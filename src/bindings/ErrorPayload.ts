@@ -0,0 +1,5 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+import type { ErrorCategory } from "./ErrorCategory";
+
+export interface ErrorPayload { code: string, category: ErrorCategory, message: string, details: Record<string, Array<string>> | null, }
\ No newline at end of file
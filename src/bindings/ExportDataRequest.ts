@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+import type { DataFormat } from "./DataFormat";
+import type { Dataset } from "./Dataset";
+
+export interface ExportDataRequest { dataset: Dataset, format: DataFormat, include_secrets: boolean, }
\ No newline at end of file
@@ -0,0 +1,5 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+import type { AuditEntity } from "./AuditEntity";
+
+export interface GetAuditLogRequest { entity: AuditEntity | null, entity_id: string | null, start: number | null, end: number | null, limit: number | null, }
\ No newline at end of file
@@ -0,0 +1,5 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+import type { CandleInterval } from "./CandleInterval";
+
+export interface GetCandlesRequest { coin_id: number, interval: CandleInterval, start: number | null, end: number | null, }
\ No newline at end of file
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+import type { CandleInterval } from "./CandleInterval";
+import type { IndicatorSpec } from "./IndicatorSpec";
+
+export interface GetIndicatorsRequest { coin_id: number, interval: CandleInterval, start: number | null, end: number | null, indicators: Array<IndicatorSpec>, }
\ No newline at end of file
@@ -0,0 +1,5 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+import type { HoldingRequest } from "./HoldingRequest";
+
+export interface GetPortfolioRiskRequest { holdings: Array<HoldingRequest>, start: number | null, end: number | null, confidence: number | null, risk_free_rate: number | null, }
\ No newline at end of file
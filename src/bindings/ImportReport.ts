@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+import type { Dataset } from "./Dataset";
+import type { RowError } from "./RowError";
+
+export interface ImportReport { dataset: Dataset, dry_run: boolean, total: number, valid: number, imported: number, duplicates: number, errors: Array<RowError>, }
\ No newline at end of file
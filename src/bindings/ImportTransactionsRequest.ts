@@ -0,0 +1,5 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+import type { Exchange } from "./Exchange";
+
+export interface ImportTransactionsRequest { exchange: Exchange, content: string, dry_run: boolean, }
\ No newline at end of file
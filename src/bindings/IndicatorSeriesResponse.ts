@@ -0,0 +1,5 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+import type { IndicatorSpec } from "./IndicatorSpec";
+
+export interface IndicatorSeriesResponse { indicator: IndicatorSpec, lines: Record<string, Array<number | null>>, }
\ No newline at end of file
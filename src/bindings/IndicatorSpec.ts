@@ -0,0 +1,3 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type IndicatorSpec = { "kind": "sma", period: number, } | { "kind": "ema", period: number, } | { "kind": "rsi", period: number, } | { "kind": "macd", fast: number, slow: number, signal: number, } | { "kind": "bollinger", period: number, k: number, } | { "kind": "realized_volatility", period: number, } | { "kind": "max_drawdown" };
\ No newline at end of file
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+import type { CandleInterval } from "./CandleInterval";
+import type { CandleRange } from "./CandleRange";
+import type { IndicatorSeriesResponse } from "./IndicatorSeriesResponse";
+
+export interface IndicatorsResponse { coin_id: number, interval: CandleInterval, range: CandleRange, timestamps: Array<number>, close: Array<number>, indicators: Array<IndicatorSeriesResponse>, gaps: Array<CandleRange>, }
\ No newline at end of file
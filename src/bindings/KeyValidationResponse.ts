@@ -0,0 +1,5 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+import type { PlanInfoResponse } from "./PlanInfoResponse";
+
+export interface KeyValidationResponse { validity: string, message: string | null, available_at: string | null, plan: PlanInfoResponse | null, stored: boolean, }
\ No newline at end of file
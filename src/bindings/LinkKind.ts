@@ -0,0 +1,3 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type LinkKind = "website" | "explorer" | "source_code" | "technical_doc" | "announcement" | "message_board" | "chat" | "twitter" | "reddit" | "facebook";
\ No newline at end of file
@@ -0,0 +1,3 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export interface LivePriceResponse { symbol: string, price: number, volume_24h: number | null, updated_at: string, }
\ No newline at end of file
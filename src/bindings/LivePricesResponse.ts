@@ -0,0 +1,5 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+import type { LivePriceResponse } from "./LivePriceResponse";
+
+export interface LivePricesResponse { exchange: string, connected: boolean, prices: Array<LivePriceResponse>, }
\ No newline at end of file
@@ -0,0 +1,3 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export interface PlanInfoResponse { credit_limit_daily: number | null, credit_limit_monthly: number | null, rate_limit_minute: number | null, credits_used_day: number, credits_used_month: number, monthly_reset: string | null, fetched_at: string, }
\ No newline at end of file
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+import type { CandleRange } from "./CandleRange";
+import type { CoinRiskResponse } from "./CoinRiskResponse";
+
+export interface PortfolioRiskResponse { range: CandleRange, observations: number, confidence: number, risk_free_rate: number, volatility: number | null, beta: number | null, historical_var: number | null, parametric_var: number | null, sharpe_ratio: number | null, coins: Array<CoinRiskResponse>, }
\ No newline at end of file
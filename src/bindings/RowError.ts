@@ -0,0 +1,3 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export interface RowError { row: number, message: string, details: Record<string, Array<string>>, }
\ No newline at end of file
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+import type { Exchange } from "./Exchange";
+import type { RowError } from "./RowError";
+
+export interface TransactionImportReport { exchange: Exchange, dry_run: boolean, total: number, imported: number, duplicates: number, errors: Array<RowError>, unmapped_assets: Array<string>, }
\ No newline at end of file
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+import type { Exchange } from "./Exchange";
+import type { TradeSide } from "./TradeSide";
+
+export interface TransactionResponse { id: number, exchange: Exchange, trade_id: string, executed_at: string, side: TradeSide, base_coin_id: number, base_symbol: string, quote_coin_id: number | null, quote_symbol: string, quantity: number, price: number, fee: number, fee_asset: string, }
\ No newline at end of file
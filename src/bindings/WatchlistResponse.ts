@@ -0,0 +1,3 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export interface WatchlistResponse { id: number, name: string, coin_ids: Array<number>, }
\ No newline at end of file